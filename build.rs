@@ -0,0 +1,22 @@
+use std::env;
+use std::path::PathBuf;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    // protoc и google/protobuf/*.proto берем из protoc-bin-vendored, чтобы
+    // сборка не зависела от установленного в системе protoc
+    let mut config = tonic_prost_build::Config::new();
+    config.protoc_executable(protoc_bin_vendored::protoc_bin_path()?);
+    let include = protoc_bin_vendored::include_path()?;
+
+    // Дескриптор нужен сервису reflection (grpcurl, Postman)
+    let out_dir = PathBuf::from(env::var("OUT_DIR")?);
+    tonic_prost_build::configure()
+        .file_descriptor_set_path(out_dir.join("user_service_descriptor.bin"))
+        .compile_with_config(
+            config,
+            &[PathBuf::from("proto/user/v1/user_service.proto")],
+            &[PathBuf::from("proto"), include],
+        )?;
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+#!/bin/sh
+# Скачивает закрепленную версию Redoc в assets/ для страницы /docs.
+# Архив пакета сверяется с хешем целостности (SHA-512) из реестра npm.
+set -eu
+
+REDOC_VERSION="2.1.5"
+REGISTRY="https://registry.npmjs.org/redoc"
+TARGET="$(dirname "$0")/../assets/redoc.standalone.js"
+
+workdir="$(mktemp -d)"
+trap 'rm -rf "$workdir"' EXIT
+
+curl -fsSL "$REGISTRY/$REDOC_VERSION" -o "$workdir/package.json"
+expected="$(sed -n 's/.*"integrity":"sha512-\([^"]*\)".*/\1/p' "$workdir/package.json")"
+if [ -z "$expected" ]; then
+    echo "No integrity hash for redoc $REDOC_VERSION in the npm registry" >&2
+    exit 1
+fi
+
+curl -fsSL "$REGISTRY/-/redoc-$REDOC_VERSION.tgz" -o "$workdir/redoc.tgz"
+actual="$(openssl dgst -sha512 -binary "$workdir/redoc.tgz" | openssl base64 -A)"
+if [ "$actual" != "$expected" ]; then
+    echo "redoc-$REDOC_VERSION.tgz does not match the registry integrity hash" >&2
+    exit 1
+fi
+
+tar -xzf "$workdir/redoc.tgz" -C "$workdir" package/bundles/redoc.standalone.js
+mkdir -p "$(dirname "$TARGET")"
+cp "$workdir/package/bundles/redoc.standalone.js" "$TARGET"
+echo "Installed redoc $REDOC_VERSION to $TARGET"
@@ -0,0 +1,45 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub expires_in_days: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ApiKeyResponse {
+    pub id: String,
+    pub name: String,
+    pub prefix: String,
+    pub scopes: Vec<String>,
+    pub expires_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<crate::domain::ApiKey> for ApiKeyResponse {
+    fn from(api_key: crate::domain::ApiKey) -> Self {
+        Self {
+            id: api_key.id().to_string(),
+            name: api_key.name().to_string(),
+            prefix: api_key.prefix().to_string(),
+            scopes: api_key.scopes().iter().map(|scope| scope.as_str().to_string()).collect(),
+            expires_at: *api_key.expires_at(),
+            last_used_at: api_key.last_used_at().copied(),
+            created_at: *api_key.created_at(),
+            revoked_at: api_key.revoked_at().copied(),
+        }
+    }
+}
+
+// Возвращается только при создании: полный ключ больше нигде не показывается
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreatedApiKeyResponse {
+    #[serde(flatten)]
+    pub api_key: ApiKeyResponse,
+    pub key: String,
+}
\ No newline at end of file
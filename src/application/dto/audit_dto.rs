@@ -0,0 +1,86 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use crate::domain::{AuditChainStatus, AuditEvent, FieldChange};
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct AuditListQuery {
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct FieldChangeResponse {
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+}
+
+impl From<FieldChange> for FieldChangeResponse {
+    fn from(change: FieldChange) -> Self {
+        Self {
+            field: change.field,
+            before: change.before,
+            after: change.after,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditEventResponse {
+    pub sequence: u64,
+    pub user_id: String,
+    pub resource: String,
+    pub action: String,
+    pub actor: String,
+    pub request_id: Option<String>,
+    pub client_ip: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+    pub changes: Vec<FieldChangeResponse>,
+    pub previous_hash: String,
+    pub hash: String,
+}
+
+impl From<AuditEvent> for AuditEventResponse {
+    fn from(event: AuditEvent) -> Self {
+        Self {
+            sequence: event.sequence,
+            user_id: event.record.user_id.to_string(),
+            resource: event.record.resource,
+            action: event.record.action.as_str().to_string(),
+            actor: event.record.actor,
+            request_id: event.record.request_id,
+            client_ip: event.record.client_ip,
+            occurred_at: event.record.occurred_at,
+            changes: event.record.changes.into_iter().map(Into::into).collect(),
+            previous_hash: event.previous_hash,
+            hash: event.hash,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditPageResponse {
+    pub events: Vec<AuditEventResponse>,
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditChainResponse {
+    pub valid: bool,
+    pub events: usize,
+    pub broken_at: Option<u64>,
+}
+
+impl From<AuditChainStatus> for AuditChainResponse {
+    fn from(status: AuditChainStatus) -> Self {
+        Self {
+            valid: status.is_valid(),
+            events: status.events,
+            broken_at: status.broken_at,
+        }
+    }
+}
\ No newline at end of file
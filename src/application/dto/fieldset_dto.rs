@@ -0,0 +1,157 @@
+use std::collections::BTreeSet;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use thiserror::Error;
+use crate::application::dto::{ApiKeyResponse, UserResponse};
+
+pub const USER_FIELDS: [&str; 7] = ["id", "email", "name", "roles", "created_at", "updated_at", "erased_at"];
+pub const USER_INCLUDES: [&str; 2] = ["roles", "api_keys"];
+
+// `fields` и `include` - имена через запятую, например `?fields=id,email&include=api_keys`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FieldsetQuery {
+    pub fields: Option<String>,
+    pub include: Option<String>,
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum FieldsetError {
+    #[error("Unknown {names} in `{parameter}`, allowed: {allowed}")]
+    Unknown { parameter: &'static str, names: String, allowed: String },
+    #[error("Parameter `{0}` must not be empty")]
+    Empty(&'static str),
+}
+
+/// Пользователь после `fields` и `include`. Имя нужно XML: у произвольного
+/// объекта нет корневого элемента, а здесь он тот же, что у UserResponse.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename = "UserResponse")]
+pub struct SparseUser(pub Value);
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename = "UserListResponse")]
+pub struct SparseUserList {
+    pub users: Vec<SparseUser>,
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+/// Какие поля пользователя отдавать и что встроить в ответ.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserFieldset {
+    // None - все поля UserResponse
+    fields: Option<BTreeSet<&'static str>>,
+    includes: BTreeSet<&'static str>,
+}
+
+impl UserFieldset {
+    pub fn parse(query: &FieldsetQuery) -> Result<Self, FieldsetError> {
+        let fields = query
+            .fields
+            .as_deref()
+            .map(|fields| Self::parse_names("fields", fields, &USER_FIELDS))
+            .transpose()?;
+        let includes = query
+            .include
+            .as_deref()
+            .map(|include| Self::parse_names("include", include, &USER_INCLUDES))
+            .transpose()?
+            .unwrap_or_default();
+        Ok(Self { fields, includes })
+    }
+
+    // Все неизвестные имена перечисляются сразу, а не по одному за запрос
+    fn parse_names(parameter: &'static str, value: &str, allowed: &[&'static str]) -> Result<BTreeSet<&'static str>, FieldsetError> {
+        let names: Vec<&str> = value.split(',').map(str::trim).filter(|name| !name.is_empty()).collect();
+        if names.is_empty() {
+            return Err(FieldsetError::Empty(parameter));
+        }
+
+        let unknown: Vec<String> = names
+            .iter()
+            .filter(|name| !allowed.contains(name))
+            .map(|name| format!("`{}`", name))
+            .collect();
+        if !unknown.is_empty() {
+            return Err(FieldsetError::Unknown {
+                parameter,
+                names: unknown.join(", "),
+                allowed: allowed.join(", "),
+            });
+        }
+        Ok(allowed.iter().copied().filter(|name| names.contains(name)).collect())
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.fields.is_none() && self.includes.is_empty()
+    }
+
+    pub fn includes_api_keys(&self) -> bool {
+        self.includes.contains("api_keys")
+    }
+
+    // `id` остается всегда: без него клиент не сопоставит ресурс
+    pub fn project(&self, user: &UserResponse, api_keys: Option<Vec<ApiKeyResponse>>) -> Value {
+        let Ok(Value::Object(mut user)) = serde_json::to_value(user) else {
+            return Value::Null;
+        };
+        if let Some(fields) = &self.fields {
+            user.retain(|name, _| {
+                name == "id" || fields.contains(name.as_str()) || (name == "roles" && self.includes.contains("roles"))
+            });
+        }
+        if self.includes_api_keys() {
+            user.insert("api_keys".to_string(), serde_json::to_value(api_keys.unwrap_or_default()).unwrap_or_default());
+        }
+        Value::Object(user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn query(fields: Option<&str>, include: Option<&str>) -> FieldsetQuery {
+        FieldsetQuery { fields: fields.map(str::to_string), include: include.map(str::to_string) }
+    }
+
+    fn user() -> UserResponse {
+        let now = chrono::Utc::now();
+        UserResponse {
+            id: "42".to_string(),
+            email: "alice@example.com".to_string(),
+            name: "Alice".to_string(),
+            roles: vec!["member".to_string()],
+            created_at: now,
+            updated_at: now,
+            erased_at: None,
+        }
+    }
+
+    #[test]
+    fn test_fields_trim_user_and_include_embeds_relations() {
+        let fieldset = UserFieldset::parse(&query(Some("email"), None)).unwrap();
+        assert_eq!(fieldset.project(&user(), None), serde_json::json!({ "id": "42", "email": "alice@example.com" }));
+
+        let fieldset = UserFieldset::parse(&query(Some("id, name"), Some("roles,api_keys"))).unwrap();
+        let projected = fieldset.project(&user(), Some(Vec::new()));
+        assert_eq!(projected, serde_json::json!({ "id": "42", "name": "Alice", "roles": ["member"], "api_keys": [] }));
+
+        assert!(UserFieldset::parse(&query(None, None)).unwrap().is_default());
+    }
+
+    #[test]
+    fn test_unknown_and_empty_names_are_rejected() {
+        let error = UserFieldset::parse(&query(Some("id,password,ssn"), None)).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Unknown `password`, `ssn` in `fields`, allowed: id, email, name, roles, created_at, updated_at, erased_at"
+        );
+
+        let error = UserFieldset::parse(&query(None, Some("sessions"))).unwrap_err();
+        assert_eq!(error.to_string(), "Unknown `sessions` in `include`, allowed: roles, api_keys");
+
+        assert_eq!(UserFieldset::parse(&query(Some(" , "), None)), Err(FieldsetError::Empty("fields")));
+    }
+}
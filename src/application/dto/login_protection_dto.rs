@@ -0,0 +1,8 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AccountUnlockResponse {
+    pub user_id: String,
+    pub was_locked: bool,
+}
\ No newline at end of file
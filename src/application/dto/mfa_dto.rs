@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MfaStatusResponse {
+    pub enabled: bool,
+    pub status: Option<String>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub recovery_codes_remaining: usize,
+}
+
+impl From<Option<crate::domain::MfaEnrollment>> for MfaStatusResponse {
+    fn from(enrollment: Option<crate::domain::MfaEnrollment>) -> Self {
+        Self {
+            enabled: enrollment.as_ref().is_some_and(|enrollment| enrollment.is_active()),
+            status: enrollment.as_ref().map(|enrollment| enrollment.status().as_str().to_string()),
+            confirmed_at: enrollment.as_ref().and_then(|enrollment| enrollment.confirmed_at().copied()),
+            recovery_codes_remaining: enrollment.map_or(0, |enrollment| enrollment.recovery_code_hashes().len()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MfaEnrollmentResponse {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MfaConfirmRequest {
+    pub code: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MfaRecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+// Второй фактор: код из приложения или один из кодов восстановления
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MfaVerifyRequest {
+    pub code: Option<String>,
+    pub recovery_code: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MfaChallengeResponse {
+    pub token: String,
+    pub recovery_codes_remaining: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct MfaQrQuery {
+    pub format: Option<String>,
+}
\ No newline at end of file
@@ -1,3 +1,27 @@
 pub mod user_dto;
+pub mod outbox_dto;
+pub mod api_key_dto;
+pub mod mfa_dto;
+pub mod login_protection_dto;
+pub mod session_dto;
+pub mod oidc_dto;
+pub mod scim_dto;
+pub mod user_data_dto;
+pub mod audit_dto;
+pub mod webhook_dto;
+pub mod websocket_dto;
+pub mod fieldset_dto;
 
-pub use user_dto::*;
\ No newline at end of file
+pub use user_dto::*;
+pub use outbox_dto::*;
+pub use api_key_dto::*;
+pub use mfa_dto::*;
+pub use login_protection_dto::*;
+pub use session_dto::*;
+pub use oidc_dto::*;
+pub use scim_dto::*;
+pub use user_data_dto::*;
+pub use audit_dto::*;
+pub use webhook_dto::*;
+pub use websocket_dto::*;
+pub use fieldset_dto::*;
\ No newline at end of file
@@ -0,0 +1,24 @@
+use serde::{Deserialize, Serialize};
+use crate::application::dto::UserResponse;
+
+// Параметры возврата от провайдера: `code` и `state` или `error`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OidcCallbackQuery {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OidcAuthorizationResponse {
+    pub authorization_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OidcLoginResponse {
+    pub token: String,
+    pub user: UserResponse,
+    // Пользователь создан при этом входе
+    pub created: bool,
+}
\ No newline at end of file
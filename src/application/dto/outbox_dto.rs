@@ -0,0 +1,37 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxListQuery {
+    pub status: Option<String>,
+    pub limit: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutboxMessageResponse {
+    pub id: String,
+    pub topic: String,
+    pub dedup_key: String,
+    pub status: String,
+    pub attempts: u32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl From<crate::domain::OutboxMessage> for OutboxMessageResponse {
+    fn from(message: crate::domain::OutboxMessage) -> Self {
+        Self {
+            id: message.id().to_string(),
+            topic: message.topic().to_string(),
+            dedup_key: message.dedup_key().to_string(),
+            status: message.status().as_str().to_string(),
+            attempts: message.attempts(),
+            next_attempt_at: *message.next_attempt_at(),
+            last_error: message.last_error().map(str::to_string),
+            created_at: *message.created_at(),
+            delivered_at: message.delivered_at().copied(),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,50 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::domain::{Session, SessionPolicy};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SessionResponse {
+    pub id: String,
+    pub auth_level: String,
+    pub user_agent: Option<String>,
+    pub ip_address: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_seen_at: DateTime<Utc>,
+    pub idle_expires_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    // Сессия, из которой сделан запрос
+    pub current: bool,
+}
+
+impl SessionResponse {
+    pub fn new(session: &Session, policy: &SessionPolicy, current: bool) -> Self {
+        Self {
+            id: session.id().to_string(),
+            auth_level: session.auth_level().as_str().to_string(),
+            user_agent: session.metadata().user_agent.clone(),
+            ip_address: session.metadata().ip_address.map(|ip| ip.to_string()),
+            created_at: *session.created_at(),
+            last_seen_at: *session.last_seen_at(),
+            idle_expires_at: session.idle_expires_at(policy),
+            expires_at: *session.expires_at(),
+            current,
+        }
+    }
+}
+
+// Значение cookie сессии не попадает в тело ответа: обработчик
+// выставляет его в HttpOnly cookie
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreatedSessionResponse {
+    #[serde(flatten)]
+    pub session: SessionResponse,
+    pub csrf_token: String,
+    #[serde(skip)]
+    pub session_token: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct SessionsRevokedResponse {
+    pub revoked: usize,
+}
\ No newline at end of file
@@ -0,0 +1,109 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use crate::application::dto::{
+    ApiKeyResponse, AuditEventResponse, MfaStatusResponse, OutboxMessageResponse, SessionResponse, UserFieldset, UserResponse,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalIdentityResponse {
+    pub provider: String,
+    pub subject: String,
+    pub linked_at: DateTime<Utc>,
+    pub last_login_at: DateTime<Utc>,
+}
+
+impl From<crate::domain::ExternalIdentity> for ExternalIdentityResponse {
+    fn from(identity: crate::domain::ExternalIdentity) -> Self {
+        Self {
+            provider: identity.provider().to_string(),
+            subject: identity.subject().to_string(),
+            linked_at: *identity.linked_at(),
+            last_login_at: *identity.last_login_at(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ErasureReceiptResponse {
+    pub receipt_id: String,
+    pub user_id: String,
+    pub requested_by: String,
+    pub erased_at: DateTime<Utc>,
+    pub erased_fields: Vec<String>,
+    pub revoked_api_keys: usize,
+    pub revoked_sessions: usize,
+    pub unlinked_identities: usize,
+    pub mfa_removed: bool,
+    pub cancelled_outbox_emails: usize,
+    pub redacted_audit_events: usize,
+}
+
+impl From<crate::domain::ErasureReceipt> for ErasureReceiptResponse {
+    fn from(receipt: crate::domain::ErasureReceipt) -> Self {
+        Self {
+            receipt_id: receipt.id.to_string(),
+            user_id: receipt.user_id.to_string(),
+            requested_by: receipt.requested_by.to_string(),
+            erased_at: receipt.erased_at,
+            erased_fields: receipt.erased_fields,
+            revoked_api_keys: receipt.revoked_api_keys,
+            revoked_sessions: receipt.revoked_sessions,
+            unlinked_identities: receipt.unlinked_identities,
+            mfa_removed: receipt.mfa_removed,
+            cancelled_outbox_emails: receipt.cancelled_outbox_emails,
+            redacted_audit_events: receipt.redacted_audit_events,
+        }
+    }
+}
+
+// Все, что хранится о пользователе; секреты (хеши ключей, TOTP) не выгружаются
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserDataExport {
+    pub generated_at: DateTime<Utc>,
+    pub profile: UserResponse,
+    // Журнал аудита изменений пользователя, новые события первыми
+    pub audit: Vec<AuditEventResponse>,
+    // Письма и события пользователя из outbox
+    pub notifications: Vec<OutboxMessageResponse>,
+    pub sessions: Vec<SessionResponse>,
+    pub api_keys: Vec<ApiKeyResponse>,
+    pub mfa: MfaStatusResponse,
+    pub identities: Vec<ExternalIdentityResponse>,
+    pub erasure_receipt: Option<ErasureReceiptResponse>,
+}
+
+impl UserDataExport {
+    // Содержимое архива выгрузки: по JSON-файлу на каждый раздел
+    pub fn files(&self) -> Result<Vec<(String, Vec<u8>)>, serde_json::Error> {
+        self.files_with(&UserFieldset::default())
+    }
+
+    // profile.json с выбранными полями; остальные разделы выгружаются полностью
+    pub fn files_with(&self, fieldset: &UserFieldset) -> Result<Vec<(String, Vec<u8>)>, serde_json::Error> {
+        let profile = match fieldset.is_default() {
+            true => serde_json::to_vec_pretty(&self.profile)?,
+            false => serde_json::to_vec_pretty(&fieldset.project(&self.profile, Some(self.api_keys.clone())))?,
+        };
+        let mut files = vec![
+            ("profile.json".to_string(), profile),
+            ("audit.json".to_string(), serde_json::to_vec_pretty(&self.audit)?),
+            ("notifications.json".to_string(), serde_json::to_vec_pretty(&self.notifications)?),
+            ("sessions.json".to_string(), serde_json::to_vec_pretty(&self.sessions)?),
+            ("api_keys.json".to_string(), serde_json::to_vec_pretty(&self.api_keys)?),
+            ("mfa.json".to_string(), serde_json::to_vec_pretty(&self.mfa)?),
+            ("identities.json".to_string(), serde_json::to_vec_pretty(&self.identities)?),
+        ];
+        if let Some(receipt) = &self.erasure_receipt {
+            files.push(("erasure_receipt.json".to_string(), serde_json::to_vec_pretty(receipt)?));
+        }
+
+        let manifest = serde_json::json!({
+            "user_id": self.profile.id,
+            "generated_at": self.generated_at,
+            "files": files.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        });
+        files.insert(0, ("manifest.json".to_string(), serde_json::to_vec_pretty(&manifest)?));
+        Ok(files)
+    }
+}
\ No newline at end of file
@@ -1,25 +1,42 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct CreateUserRequest {
     pub email: String,
     pub name: String,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UpdateUserRequest {
     pub email: Option<String>,
     pub name: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UpdateUserRolesRequest {
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct UserByEmailRequest {
+    // Пустой email отклоняется обработчиком с понятной ошибкой
+    #[serde(default)]
+    #[schema(example = "alice@example.com")]
+    pub email: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct UserResponse {
     pub id: String,
     pub email: String,
     pub name: String,
+    pub roles: Vec<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub erased_at: Option<DateTime<Utc>>,
 }
 
 impl From<crate::domain::User> for UserResponse {
@@ -28,17 +45,48 @@ impl From<crate::domain::User> for UserResponse {
             id: user.id().to_string(),
             email: user.email().as_str().to_string(),
             name: user.name().to_string(),
+            roles: user.roles().iter().map(|role| role.as_str().to_string()).collect(),
             created_at: *user.created_at(),
             updated_at: *user.updated_at(),
+            erased_at: user.erased_at().copied(),
         }
     }
 }
 
+// Фильтры списка: `role` - точное совпадение роли, `search` - подстрока
+// email или имени без учета регистра
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ListUsersQuery {
+    pub offset: Option<usize>,
+    pub limit: Option<usize>,
+    pub role: Option<String>,
+    pub search: Option<String>,
+    #[serde(default)]
+    pub include_erased: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserListResponse {
+    pub users: Vec<UserResponse>,
+    pub total: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+// `events` - типы событий через запятую, например `user.created,user.deleted`
 #[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserChangesQuery {
+    pub events: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
 pub struct ApiResponse<T> {
     pub success: bool,
     pub data: Option<T>,
     pub error: Option<String>,
+    // В тело ответа не попадает: REST отдает только текст ошибки
+    #[serde(skip)]
+    pub cause: Option<crate::domain::DomainError>,
 }
 
 impl<T> ApiResponse<T> {
@@ -47,6 +95,7 @@ impl<T> ApiResponse<T> {
             success: true,
             data: Some(data),
             error: None,
+            cause: None,
         }
     }
 
@@ -55,6 +104,12 @@ impl<T> ApiResponse<T> {
             success: false,
             data: None,
             error: Some(error),
+            cause: None,
         }
     }
+
+    pub fn with_cause(mut self, cause: Option<crate::domain::DomainError>) -> Self {
+        self.cause = cause;
+        self
+    }
 }
\ No newline at end of file
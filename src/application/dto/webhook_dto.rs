@@ -0,0 +1,86 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub secret: String,
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct WebhookDeliveryListQuery {
+    pub limit: Option<usize>,
+}
+
+// Секрет подписи в ответах не возвращается
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookResponse {
+    pub id: String,
+    pub url: String,
+    pub events: Vec<String>,
+    pub status: String,
+    pub consecutive_failures: u32,
+    pub failing_since: Option<DateTime<Utc>>,
+    pub disabled_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<crate::domain::WebhookSubscription> for WebhookResponse {
+    fn from(webhook: crate::domain::WebhookSubscription) -> Self {
+        Self {
+            id: webhook.id().to_string(),
+            url: webhook.url().to_string(),
+            events: webhook.events().to_vec(),
+            status: webhook.status().as_str().to_string(),
+            consecutive_failures: webhook.consecutive_failures(),
+            failing_since: webhook.failing_since().copied(),
+            disabled_at: webhook.disabled_at().copied(),
+            created_at: *webhook.created_at(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookAttemptResponse {
+    pub attempted_at: DateTime<Utc>,
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct WebhookDeliveryResponse {
+    pub id: String,
+    pub webhook_id: String,
+    pub event: String,
+    pub status: String,
+    pub attempts: Vec<WebhookAttemptResponse>,
+    pub next_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl From<crate::domain::WebhookDelivery> for WebhookDeliveryResponse {
+    fn from(delivery: crate::domain::WebhookDelivery) -> Self {
+        Self {
+            id: delivery.id().to_string(),
+            webhook_id: delivery.webhook_id().to_string(),
+            event: delivery.event().to_string(),
+            status: delivery.status().as_str().to_string(),
+            attempts: delivery.attempts()
+                .iter()
+                .map(|attempt| WebhookAttemptResponse {
+                    attempted_at: attempt.attempted_at,
+                    status_code: attempt.status_code,
+                    error: attempt.error.clone(),
+                })
+                .collect(),
+            next_attempt_at: *delivery.next_attempt_at(),
+            created_at: *delivery.created_at(),
+            delivered_at: delivery.delivered_at().copied(),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,41 @@
+use serde::{Deserialize, Serialize};
+
+// Сообщения клиента в `/ws`: `{"type": "subscribe", "user_ids": ["..."]}`,
+// `{"type": "subscribe", "all": true}`, `unsubscribe` с теми же полями и `ping`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebSocketClientMessage {
+    Subscribe {
+        #[serde(default)]
+        user_ids: Vec<String>,
+        #[serde(default)]
+        all: bool,
+    },
+    Unsubscribe {
+        #[serde(default)]
+        user_ids: Vec<String>,
+        #[serde(default)]
+        all: bool,
+    },
+    Ping,
+}
+
+// Ответ на subscribe/unsubscribe содержит итоговый набор подписок соединения
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebSocketServerMessage {
+    Subscriptions {
+        all: bool,
+        user_ids: Vec<String>,
+    },
+    Event {
+        id: u64,
+        event: String,
+        user_id: String,
+        data: serde_json::Value,
+    },
+    Pong,
+    Error {
+        message: String,
+    },
+}
\ No newline at end of file
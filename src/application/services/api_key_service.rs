@@ -0,0 +1,37 @@
+use crate::domain::{Actor, ApiKeyRepository, SecretHasher, UserRepository};
+use crate::application::ManageApiKeysUseCase;
+use crate::application::dto::{ApiKeyResponse, CreateApiKeyRequest, CreatedApiKeyResponse, ApiResponse};
+
+#[derive(Clone)]
+pub struct ApiKeyApplicationService<R: UserRepository, K: ApiKeyRepository, H: SecretHasher> {
+    manage_api_keys_use_case: ManageApiKeysUseCase<R, K, H>,
+}
+
+impl<R: UserRepository, K: ApiKeyRepository, H: SecretHasher> ApiKeyApplicationService<R, K, H> {
+    pub fn new(user_repository: R, api_key_repository: K, secret_hasher: H) -> Self {
+        Self {
+            manage_api_keys_use_case: ManageApiKeysUseCase::new(user_repository, api_key_repository, secret_hasher),
+        }
+    }
+
+    pub async fn create_api_key(&self, actor: &Actor, user_id: String, request: CreateApiKeyRequest) -> ApiResponse<CreatedApiKeyResponse> {
+        match self.manage_api_keys_use_case.create(actor, user_id, request).await {
+            Ok(api_key) => ApiResponse::success(api_key),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn list_api_keys(&self, user_id: String) -> ApiResponse<Vec<ApiKeyResponse>> {
+        match self.manage_api_keys_use_case.list(user_id).await {
+            Ok(api_keys) => ApiResponse::success(api_keys),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn revoke_api_key(&self, user_id: String, key_id: String) -> ApiResponse<ApiKeyResponse> {
+        match self.manage_api_keys_use_case.revoke(user_id, key_id).await {
+            Ok(api_key) => ApiResponse::success(api_key),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,30 @@
+use crate::domain::AuditStore;
+use crate::application::ReadAuditTrailUseCase;
+use crate::application::dto::{ApiResponse, AuditChainResponse, AuditListQuery, AuditPageResponse};
+
+#[derive(Clone)]
+pub struct AuditApplicationService<A: AuditStore> {
+    read_audit_trail_use_case: ReadAuditTrailUseCase<A>,
+}
+
+impl<A: AuditStore> AuditApplicationService<A> {
+    pub fn new(audit_store: A) -> Self {
+        Self {
+            read_audit_trail_use_case: ReadAuditTrailUseCase::new(audit_store),
+        }
+    }
+
+    pub async fn list_user_events(&self, user_id: String, query: AuditListQuery) -> ApiResponse<AuditPageResponse> {
+        match self.read_audit_trail_use_case.list(user_id, query).await {
+            Ok(page) => ApiResponse::success(page),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn verify_chain(&self) -> ApiResponse<AuditChainResponse> {
+        match self.read_audit_trail_use_case.verify().await {
+            Ok(status) => ApiResponse::success(status),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+}
\ No newline at end of file
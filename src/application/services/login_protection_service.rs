@@ -0,0 +1,23 @@
+use crate::domain::{LockoutPolicy, LoginAttemptStore, UserRepository};
+use crate::application::LoginProtectionUseCase;
+use crate::application::dto::{AccountUnlockResponse, ApiResponse};
+
+#[derive(Clone)]
+pub struct LoginProtectionApplicationService<R: UserRepository, S: LoginAttemptStore> {
+    login_protection_use_case: LoginProtectionUseCase<R, S>,
+}
+
+impl<R: UserRepository, S: LoginAttemptStore> LoginProtectionApplicationService<R, S> {
+    pub fn new(user_repository: R, attempt_store: S, policy: LockoutPolicy) -> Self {
+        Self {
+            login_protection_use_case: LoginProtectionUseCase::new(user_repository, attempt_store, policy),
+        }
+    }
+
+    pub async fn unlock_account(&self, user_id: String) -> ApiResponse<AccountUnlockResponse> {
+        match self.login_protection_use_case.unlock(user_id).await {
+            Ok(response) => ApiResponse::success(response),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,77 @@
+use crate::domain::{MfaDomainService, MfaRepository, SecretCipher, SecretHasher, TokenService, TotpService, UserRepository};
+use crate::application::ManageMfaUseCase;
+use crate::application::dto::{
+    MfaChallengeResponse, MfaConfirmRequest, MfaEnrollmentResponse, MfaRecoveryCodesResponse, MfaStatusResponse,
+    MfaVerifyRequest, ApiResponse,
+};
+
+#[derive(Clone)]
+pub struct MfaApplicationService<R, M, C, O, H, T>
+where
+    R: UserRepository,
+    M: MfaRepository,
+    C: SecretCipher,
+    O: TotpService,
+    H: SecretHasher,
+    T: TokenService,
+{
+    manage_mfa_use_case: ManageMfaUseCase<R, M, C, O, H, T>,
+}
+
+impl<R, M, C, O, H, T> MfaApplicationService<R, M, C, O, H, T>
+where
+    R: UserRepository,
+    M: MfaRepository,
+    C: SecretCipher,
+    O: TotpService,
+    H: SecretHasher,
+    T: TokenService,
+{
+    pub fn new(user_repository: R, mfa_domain_service: MfaDomainService<M, C, O, H>, token_service: T) -> Self {
+        Self {
+            manage_mfa_use_case: ManageMfaUseCase::new(user_repository, mfa_domain_service, token_service),
+        }
+    }
+
+    pub async fn get_status(&self, user_id: String) -> ApiResponse<MfaStatusResponse> {
+        match self.manage_mfa_use_case.status(user_id).await {
+            Ok(status) => ApiResponse::success(status),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn enroll(&self, user_id: String) -> ApiResponse<MfaEnrollmentResponse> {
+        match self.manage_mfa_use_case.enroll(user_id).await {
+            Ok(enrollment) => ApiResponse::success(enrollment),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn get_provisioning_uri(&self, user_id: String) -> ApiResponse<String> {
+        match self.manage_mfa_use_case.provisioning_uri(user_id).await {
+            Ok(uri) => ApiResponse::success(uri),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn confirm(&self, user_id: String, request: MfaConfirmRequest) -> ApiResponse<MfaRecoveryCodesResponse> {
+        match self.manage_mfa_use_case.confirm(user_id, request.code).await {
+            Ok(recovery_codes) => ApiResponse::success(recovery_codes),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn challenge(&self, user_id: String, request: MfaVerifyRequest) -> ApiResponse<MfaChallengeResponse> {
+        match self.manage_mfa_use_case.challenge(user_id, request).await {
+            Ok(challenge) => ApiResponse::success(challenge),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn disable(&self, user_id: String, request: MfaVerifyRequest) -> ApiResponse<()> {
+        match self.manage_mfa_use_case.disable(user_id, request).await {
+            Ok(()) => ApiResponse::success(()),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+}
\ No newline at end of file
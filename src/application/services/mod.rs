@@ -1,3 +1,23 @@
 pub mod user_service;
+pub mod outbox_service;
+pub mod api_key_service;
+pub mod mfa_service;
+pub mod login_protection_service;
+pub mod session_service;
+pub mod oidc_service;
+pub mod scim_service;
+pub mod user_data_service;
+pub mod audit_service;
+pub mod webhook_service;
 
-pub use user_service::*;
\ No newline at end of file
+pub use user_service::*;
+pub use outbox_service::*;
+pub use api_key_service::*;
+pub use mfa_service::*;
+pub use login_protection_service::*;
+pub use session_service::*;
+pub use oidc_service::*;
+pub use scim_service::*;
+pub use user_data_service::*;
+pub use audit_service::*;
+pub use webhook_service::*;
\ No newline at end of file
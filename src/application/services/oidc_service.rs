@@ -0,0 +1,56 @@
+use crate::domain::{ExternalIdentityRepository, IdentityProvider, OidcLoginStore, TokenService, UserRepository};
+use crate::application::OidcLoginUseCase;
+use crate::application::dto::{ApiResponse, OidcAuthorizationResponse, OidcCallbackQuery, OidcLoginResponse};
+
+#[derive(Clone)]
+pub struct OidcApplicationService<R, I, L, T, P>
+where
+    R: UserRepository,
+    I: ExternalIdentityRepository,
+    L: OidcLoginStore,
+    T: TokenService,
+    P: IdentityProvider,
+{
+    oidc_login_use_case: OidcLoginUseCase<R, I, L, T, P>,
+}
+
+impl<R, I, L, T, P> OidcApplicationService<R, I, L, T, P>
+where
+    R: UserRepository,
+    I: ExternalIdentityRepository,
+    L: OidcLoginStore,
+    T: TokenService,
+    P: IdentityProvider,
+{
+    pub fn new(
+        user_repository: R,
+        identity_repository: I,
+        login_store: L,
+        token_service: T,
+        providers: impl IntoIterator<Item = (String, P)>,
+    ) -> Self {
+        Self {
+            oidc_login_use_case: OidcLoginUseCase::new(
+                user_repository,
+                identity_repository,
+                login_store,
+                token_service,
+                providers,
+            ),
+        }
+    }
+
+    pub async fn begin_login(&self, provider: String) -> ApiResponse<OidcAuthorizationResponse> {
+        match self.oidc_login_use_case.begin(provider).await {
+            Ok(authorization) => ApiResponse::success(authorization),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn complete_login(&self, provider: String, query: OidcCallbackQuery) -> ApiResponse<OidcLoginResponse> {
+        match self.oidc_login_use_case.complete(provider, query).await {
+            Ok(login) => ApiResponse::success(login),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,37 @@
+use crate::domain::OutboxRepository;
+use crate::application::ManageOutboxUseCase;
+use crate::application::dto::{OutboxListQuery, OutboxMessageResponse, ApiResponse};
+
+#[derive(Clone)]
+pub struct OutboxAdminService<R: OutboxRepository> {
+    manage_outbox_use_case: ManageOutboxUseCase<R>,
+}
+
+impl<R: OutboxRepository> OutboxAdminService<R> {
+    pub fn new(outbox_repository: R) -> Self {
+        Self {
+            manage_outbox_use_case: ManageOutboxUseCase::new(outbox_repository),
+        }
+    }
+
+    pub async fn list_messages(&self, query: OutboxListQuery) -> ApiResponse<Vec<OutboxMessageResponse>> {
+        match self.manage_outbox_use_case.list(query.status, query.limit).await {
+            Ok(messages) => ApiResponse::success(messages),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn get_message(&self, message_id: String) -> ApiResponse<OutboxMessageResponse> {
+        match self.manage_outbox_use_case.get(message_id).await {
+            Ok(message) => ApiResponse::success(message),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn requeue_message(&self, message_id: String) -> ApiResponse<OutboxMessageResponse> {
+        match self.manage_outbox_use_case.requeue(message_id).await {
+            Ok(message) => ApiResponse::success(message),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+}
\ No newline at end of file
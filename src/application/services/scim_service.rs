@@ -0,0 +1,192 @@
+use std::sync::Arc;
+use serde_json::{json, Value};
+use crate::domain::UserRepository;
+use crate::application::{ScimProvisioningUseCase, SCIM_MAX_RESULTS};
+use crate::application::use_cases::scim_provisioning::ApplicationError;
+use crate::application::dto::{
+    ScimError, ScimListQuery, ScimListResponse, ScimPatchRequest, ScimUser, SCIM_LIST_RESPONSE_SCHEMA,
+    SCIM_USER_SCHEMA,
+};
+
+const SERVICE_PROVIDER_CONFIG_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:ServiceProviderConfig";
+const RESOURCE_TYPE_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:ResourceType";
+const SCHEMA_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:Schema";
+
+impl From<ApplicationError> for ScimError {
+    fn from(error: ApplicationError) -> Self {
+        let detail = error.to_string();
+        match error {
+            ApplicationError::InvalidValue(_) => ScimError::new(400, Some("invalidValue"), detail),
+            ApplicationError::InvalidFilter(_) => ScimError::new(400, Some("invalidFilter"), detail),
+            ApplicationError::InvalidPath(_) => ScimError::new(400, Some("invalidPath"), detail),
+            ApplicationError::InvalidSyntax(_) => ScimError::new(400, Some("invalidSyntax"), detail),
+            ApplicationError::Mutability(_) => ScimError::new(400, Some("mutability"), detail),
+            ApplicationError::UserNotFound => ScimError::new(404, None, detail),
+            ApplicationError::UserAlreadyExists => ScimError::new(409, Some("uniqueness"), detail),
+            ApplicationError::DomainError(_) => ScimError::new(500, None, detail),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ScimApplicationService<R: UserRepository + Clone> {
+    scim_provisioning_use_case: Arc<ScimProvisioningUseCase<R>>,
+}
+
+impl<R: UserRepository + Clone> ScimApplicationService<R> {
+    pub fn new(user_repository: R) -> Self {
+        Self {
+            scim_provisioning_use_case: Arc::new(ScimProvisioningUseCase::new(user_repository)),
+        }
+    }
+
+    pub async fn create_user(&self, resource: ScimUser) -> Result<ScimUser, ScimError> {
+        Ok(self.scim_provisioning_use_case.create(resource).await?)
+    }
+
+    pub async fn get_user(&self, user_id: String) -> Result<ScimUser, ScimError> {
+        Ok(self.scim_provisioning_use_case.get(user_id).await?)
+    }
+
+    pub async fn replace_user(&self, user_id: String, resource: ScimUser) -> Result<ScimUser, ScimError> {
+        Ok(self.scim_provisioning_use_case.replace(user_id, resource).await?)
+    }
+
+    pub async fn patch_user(&self, user_id: String, request: ScimPatchRequest) -> Result<ScimUser, ScimError> {
+        Ok(self.scim_provisioning_use_case.patch(user_id, request).await?)
+    }
+
+    pub async fn delete_user(&self, user_id: String) -> Result<(), ScimError> {
+        Ok(self.scim_provisioning_use_case.delete(user_id).await?)
+    }
+
+    pub async fn list_users(&self, query: ScimListQuery) -> Result<ScimListResponse<ScimUser>, ScimError> {
+        Ok(self.scim_provisioning_use_case.list(query).await?)
+    }
+
+    // Возможности сервиса (RFC 7644, раздел 5)
+    pub fn service_provider_config(&self) -> Value {
+        json!({
+            "schemas": [SERVICE_PROVIDER_CONFIG_SCHEMA],
+            "patch": { "supported": true },
+            "bulk": { "supported": false, "maxOperations": 0, "maxPayloadSize": 0 },
+            "filter": { "supported": true, "maxResults": SCIM_MAX_RESULTS },
+            "changePassword": { "supported": false },
+            "sort": { "supported": false },
+            "etag": { "supported": false },
+            "authenticationSchemes": [{
+                "type": "oauthbearertoken",
+                "name": "Bearer token",
+                "description": "Static bearer token from SCIM_BEARER_TOKEN",
+                "primary": true,
+            }],
+            "meta": { "resourceType": "ServiceProviderConfig", "location": "/scim/v2/ServiceProviderConfig" },
+        })
+    }
+
+    pub fn resource_types(&self) -> Value {
+        Self::list_of(vec![Self::user_resource_type()])
+    }
+
+    pub fn resource_type(&self, name: &str) -> Option<Value> {
+        (name == "User").then(Self::user_resource_type)
+    }
+
+    pub fn schemas(&self) -> Value {
+        Self::list_of(vec![Self::user_schema()])
+    }
+
+    pub fn schema(&self, id: &str) -> Option<Value> {
+        (id == SCIM_USER_SCHEMA).then(Self::user_schema)
+    }
+
+    fn list_of(resources: Vec<Value>) -> Value {
+        json!({
+            "schemas": [SCIM_LIST_RESPONSE_SCHEMA],
+            "totalResults": resources.len(),
+            "startIndex": 1,
+            "itemsPerPage": resources.len(),
+            "Resources": resources,
+        })
+    }
+
+    fn user_resource_type() -> Value {
+        json!({
+            "schemas": [RESOURCE_TYPE_SCHEMA],
+            "id": "User",
+            "name": "User",
+            "endpoint": "/Users",
+            "schema": SCIM_USER_SCHEMA,
+            "meta": { "resourceType": "ResourceType", "location": "/scim/v2/ResourceTypes/User" },
+        })
+    }
+
+    // Описаны только атрибуты, которые отображаются на сущность User
+    fn user_schema() -> Value {
+        let string = |name: &str, required: bool, uniqueness: &str| json!({
+            "name": name,
+            "type": "string",
+            "multiValued": false,
+            "required": required,
+            "caseExact": false,
+            "mutability": "readWrite",
+            "returned": "default",
+            "uniqueness": uniqueness,
+        });
+        json!({
+            "schemas": [SCHEMA_SCHEMA],
+            "id": SCIM_USER_SCHEMA,
+            "name": "User",
+            "description": "User Account",
+            "attributes": [
+                string("userName", true, "server"),
+                {
+                    "name": "name",
+                    "type": "complex",
+                    "multiValued": false,
+                    "required": false,
+                    "mutability": "readWrite",
+                    "returned": "default",
+                    "subAttributes": [
+                        string("formatted", false, "none"),
+                        string("givenName", false, "none"),
+                        string("familyName", false, "none"),
+                    ],
+                },
+                string("displayName", false, "none"),
+                {
+                    "name": "emails",
+                    "type": "complex",
+                    "multiValued": true,
+                    "required": false,
+                    "mutability": "readWrite",
+                    "returned": "default",
+                    "subAttributes": [
+                        string("value", true, "server"),
+                        string("type", false, "none"),
+                        {
+                            "name": "primary",
+                            "type": "boolean",
+                            "multiValued": false,
+                            "required": false,
+                            "mutability": "readWrite",
+                            "returned": "default",
+                        },
+                    ],
+                },
+                {
+                    "name": "active",
+                    "type": "boolean",
+                    "multiValued": false,
+                    "required": false,
+                    "mutability": "readWrite",
+                    "returned": "default",
+                },
+            ],
+            "meta": {
+                "resourceType": "Schema",
+                "location": format!("/scim/v2/Schemas/{}", SCIM_USER_SCHEMA),
+            },
+        })
+    }
+}
\ No newline at end of file
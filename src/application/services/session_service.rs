@@ -0,0 +1,55 @@
+use crate::domain::{Actor, SecretHasher, SessionMetadata, SessionPolicy, SessionStore, UserRepository};
+use crate::application::ManageSessionsUseCase;
+use crate::application::dto::{ApiResponse, CreatedSessionResponse, SessionResponse, SessionsRevokedResponse};
+
+#[derive(Clone)]
+pub struct SessionApplicationService<R: UserRepository, S: SessionStore, H: SecretHasher> {
+    manage_sessions_use_case: ManageSessionsUseCase<R, S, H>,
+}
+
+impl<R: UserRepository, S: SessionStore, H: SecretHasher> SessionApplicationService<R, S, H> {
+    pub fn new(user_repository: R, session_store: S, secret_hasher: H, policy: SessionPolicy) -> Self {
+        Self {
+            manage_sessions_use_case: ManageSessionsUseCase::new(user_repository, session_store, secret_hasher, policy),
+        }
+    }
+
+    pub fn policy(&self) -> &SessionPolicy {
+        self.manage_sessions_use_case.policy()
+    }
+
+    pub async fn create_session(&self, actor: &Actor, metadata: SessionMetadata) -> ApiResponse<CreatedSessionResponse> {
+        match self.manage_sessions_use_case.start(actor, metadata).await {
+            Ok(session) => ApiResponse::success(session),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn list_sessions(&self, actor: &Actor, user_id: String) -> ApiResponse<Vec<SessionResponse>> {
+        match self.manage_sessions_use_case.list(actor, user_id).await {
+            Ok(sessions) => ApiResponse::success(sessions),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn revoke_session(&self, user_id: String, session_id: String) -> ApiResponse<SessionsRevokedResponse> {
+        match self.manage_sessions_use_case.revoke(user_id, session_id).await {
+            Ok(revoked) => ApiResponse::success(revoked),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn revoke_all_sessions(&self, user_id: String) -> ApiResponse<SessionsRevokedResponse> {
+        match self.manage_sessions_use_case.revoke_all(user_id).await {
+            Ok(revoked) => ApiResponse::success(revoked),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn end_current_session(&self, actor: &Actor) -> ApiResponse<SessionsRevokedResponse> {
+        match self.manage_sessions_use_case.end_current(actor).await {
+            Ok(revoked) => ApiResponse::success(revoked),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+}
\ No newline at end of file
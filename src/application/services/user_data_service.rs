@@ -0,0 +1,70 @@
+use crate::domain::{
+    Actor, ApiKeyRepository, AuditStore, ErasureReceiptRepository, ExternalIdentityRepository, MfaRepository, OutboxRepository,
+    SessionPolicy, SessionStore, UserRepository,
+};
+use crate::application::ManageUserDataUseCase;
+use crate::application::dto::{ApiResponse, ErasureReceiptResponse, UserDataExport};
+
+#[derive(Clone)]
+pub struct UserDataApplicationService<R, K, S, M, I, E, A>
+where
+    R: UserRepository + OutboxRepository,
+    K: ApiKeyRepository,
+    S: SessionStore,
+    M: MfaRepository,
+    I: ExternalIdentityRepository,
+    E: ErasureReceiptRepository,
+    A: AuditStore,
+{
+    manage_user_data_use_case: ManageUserDataUseCase<R, K, S, M, I, E, A>,
+}
+
+impl<R, K, S, M, I, E, A> UserDataApplicationService<R, K, S, M, I, E, A>
+where
+    R: UserRepository + OutboxRepository,
+    K: ApiKeyRepository,
+    S: SessionStore,
+    M: MfaRepository,
+    I: ExternalIdentityRepository,
+    E: ErasureReceiptRepository,
+    A: AuditStore,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        user_repository: R,
+        api_key_repository: K,
+        session_store: S,
+        mfa_repository: M,
+        identity_repository: I,
+        receipt_repository: E,
+        audit_store: A,
+        session_policy: SessionPolicy,
+    ) -> Self {
+        Self {
+            manage_user_data_use_case: ManageUserDataUseCase::new(
+                user_repository,
+                api_key_repository,
+                session_store,
+                mfa_repository,
+                identity_repository,
+                receipt_repository,
+                audit_store,
+                session_policy,
+            ),
+        }
+    }
+
+    pub async fn export_user_data(&self, user_id: String) -> ApiResponse<UserDataExport> {
+        match self.manage_user_data_use_case.export(user_id).await {
+            Ok(export) => ApiResponse::success(export),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn erase_user(&self, actor: &Actor, user_id: String) -> ApiResponse<ErasureReceiptResponse> {
+        match self.manage_user_data_use_case.erase(actor, user_id).await {
+            Ok(receipt) => ApiResponse::success(receipt),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+}
\ No newline at end of file
@@ -1,56 +1,84 @@
-use crate::domain::{UserRepository, DomainError};
-use crate::application::{CreateUserUseCase, GetUserUseCase, UpdateUserUseCase, DeleteUserUseCase};
-use crate::application::dto::{CreateUserRequest, UpdateUserRequest, UserResponse, ApiResponse};
+use crate::domain::{UserRepository, UserEventBus};
+use crate::application::{CreateUserUseCase, GetUserUseCase, UpdateUserUseCase, DeleteUserUseCase, ChangeUserRolesUseCase, ListUsersUseCase};
+use crate::application::dto::{CreateUserRequest, UpdateUserRequest, UpdateUserRolesRequest, UserResponse, ListUsersQuery, UserListResponse, ApiResponse};
 
+#[derive(Clone)]
 pub struct UserApplicationService<R: UserRepository> {
     create_user_use_case: CreateUserUseCase<R>,
     get_user_use_case: GetUserUseCase<R>,
     update_user_use_case: UpdateUserUseCase<R>,
     delete_user_use_case: DeleteUserUseCase<R>,
+    change_user_roles_use_case: ChangeUserRolesUseCase<R>,
+    list_users_use_case: ListUsersUseCase<R>,
 }
 
-impl<R: UserRepository> UserApplicationService<R> {
+impl<R: UserRepository + Clone> UserApplicationService<R> {
     pub fn new(user_repository: R) -> Self {
         Self {
             create_user_use_case: CreateUserUseCase::new(user_repository.clone()),
             get_user_use_case: GetUserUseCase::new(user_repository.clone()),
             update_user_use_case: UpdateUserUseCase::new(user_repository.clone()),
-            delete_user_use_case: DeleteUserUseCase::new(user_repository),
+            delete_user_use_case: DeleteUserUseCase::new(user_repository.clone()),
+            change_user_roles_use_case: ChangeUserRolesUseCase::new(user_repository.clone()),
+            list_users_use_case: ListUsersUseCase::new(user_repository),
         }
     }
 
+    // Один и тот же экземпляр шины разделяется всеми сценариями изменения пользователя
+    pub fn with_event_bus(mut self, event_bus: UserEventBus) -> Self {
+        self.create_user_use_case = self.create_user_use_case.with_event_bus(event_bus.clone());
+        self.update_user_use_case = self.update_user_use_case.with_event_bus(event_bus.clone());
+        self.delete_user_use_case = self.delete_user_use_case.with_event_bus(event_bus.clone());
+        self.change_user_roles_use_case = self.change_user_roles_use_case.with_event_bus(event_bus);
+        self
+    }
+
     pub async fn create_user(&self, request: CreateUserRequest) -> ApiResponse<UserResponse> {
         match self.create_user_use_case.execute(request.email, request.name).await {
             Ok(user) => ApiResponse::success(UserResponse::from(user)),
-            Err(error) => ApiResponse::error(error.to_string()),
+            Err(error) => ApiResponse::error(error.to_string()).with_cause(error.cause()),
         }
     }
 
     pub async fn get_user(&self, user_id: String) -> ApiResponse<UserResponse> {
         match self.get_user_use_case.execute(user_id).await {
             Ok(user) => ApiResponse::success(user),
-            Err(error) => ApiResponse::error(error.to_string()),
+            Err(error) => ApiResponse::error(error.to_string()).with_cause(error.cause()),
         }
     }
 
     pub async fn get_user_by_email(&self, email: String) -> ApiResponse<UserResponse> {
         match self.get_user_use_case.get_by_email(email).await {
             Ok(user) => ApiResponse::success(user),
-            Err(error) => ApiResponse::error(error.to_string()),
+            Err(error) => ApiResponse::error(error.to_string()).with_cause(error.cause()),
+        }
+    }
+
+    pub async fn list_users(&self, query: ListUsersQuery) -> ApiResponse<UserListResponse> {
+        match self.list_users_use_case.execute(query).await {
+            Ok(page) => ApiResponse::success(page),
+            Err(error) => ApiResponse::error(error.to_string()).with_cause(error.cause()),
         }
     }
 
     pub async fn update_user(&self, user_id: String, request: UpdateUserRequest) -> ApiResponse<UserResponse> {
         match self.update_user_use_case.execute(user_id, request.email, request.name).await {
             Ok(user) => ApiResponse::success(user),
-            Err(error) => ApiResponse::error(error.to_string()),
+            Err(error) => ApiResponse::error(error.to_string()).with_cause(error.cause()),
+        }
+    }
+
+    pub async fn change_user_roles(&self, user_id: String, request: UpdateUserRolesRequest) -> ApiResponse<UserResponse> {
+        match self.change_user_roles_use_case.execute(user_id, request.roles).await {
+            Ok(user) => ApiResponse::success(user),
+            Err(error) => ApiResponse::error(error.to_string()).with_cause(error.cause()),
         }
     }
 
     pub async fn delete_user(&self, user_id: String) -> ApiResponse<()> {
         match self.delete_user_use_case.execute(user_id).await {
             Ok(_) => ApiResponse::success(()),
-            Err(error) => ApiResponse::error(error.to_string()),
+            Err(error) => ApiResponse::error(error.to_string()).with_cause(error.cause()),
         }
     }
 }
@@ -58,16 +86,16 @@ impl<R: UserRepository> UserApplicationService<R> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::{User, Email};
+    use crate::domain::{User, Email, DomainError, OutboxMessage};
 
     struct MockUserRepository {
-        users: std::collections::HashMap<String, User>,
+        users: std::sync::Mutex<std::collections::HashMap<String, User>>,
     }
 
     impl MockUserRepository {
         fn new() -> Self {
             Self {
-                users: std::collections::HashMap::new(),
+                users: std::sync::Mutex::new(std::collections::HashMap::new()),
             }
         }
     }
@@ -75,31 +103,43 @@ mod tests {
     impl Clone for MockUserRepository {
         fn clone(&self) -> Self {
             Self {
-                users: self.users.clone(),
+                users: std::sync::Mutex::new(self.users.lock().unwrap().clone()),
             }
         }
     }
 
     impl UserRepository for MockUserRepository {
-        async fn find_by_id(&self, id: &domain::UserId) -> Result<Option<User>, DomainError> {
-            Ok(self.users.get(&id.to_string()).cloned())
+        async fn find_by_id(&self, id: &crate::domain::UserId) -> Result<Option<User>, DomainError> {
+            Ok(self.users.lock().unwrap().get(&id.to_string()).cloned())
         }
 
         async fn find_by_email(&self, email: &Email) -> Result<Option<User>, DomainError> {
-            Ok(self.users.values()
+            Ok(self.users.lock().unwrap().values()
                 .find(|user| user.email() == email)
                 .cloned())
         }
 
+        async fn list_users(&self) -> Result<Vec<User>, DomainError> {
+            Ok(self.users.lock().unwrap().values().cloned().collect())
+        }
+
         async fn save(&self, user: &User) -> Result<(), DomainError> {
-            self.users.insert(user.id().to_string(), user.clone());
+            self.users.lock().unwrap().insert(user.id().to_string(), user.clone());
             Ok(())
         }
 
-        async fn delete(&self, id: &domain::UserId) -> Result<(), DomainError> {
-            self.users.remove(&id.to_string());
+        async fn delete(&self, id: &crate::domain::UserId) -> Result<(), DomainError> {
+            self.users.lock().unwrap().remove(&id.to_string());
             Ok(())
         }
+
+        async fn save_with_outbox(&self, user: &User, _messages: Vec<OutboxMessage>) -> Result<(), DomainError> {
+            self.save(user).await
+        }
+
+        async fn delete_with_outbox(&self, id: &crate::domain::UserId, _messages: Vec<OutboxMessage>) -> Result<(), DomainError> {
+            self.delete(id).await
+        }
     }
 
     #[tokio::test]
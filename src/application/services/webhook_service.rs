@@ -0,0 +1,67 @@
+use crate::domain::{SecretCipher, WebhookRepository};
+use crate::application::ManageWebhooksUseCase;
+use crate::application::dto::{
+    ApiResponse, CreateWebhookRequest, WebhookDeliveryListQuery, WebhookDeliveryResponse, WebhookResponse,
+};
+
+#[derive(Clone)]
+pub struct WebhookApplicationService<R: WebhookRepository, C: SecretCipher> {
+    manage_webhooks_use_case: ManageWebhooksUseCase<R, C>,
+}
+
+impl<R: WebhookRepository, C: SecretCipher> WebhookApplicationService<R, C> {
+    pub fn new(webhook_repository: R, secret_cipher: C) -> Self {
+        Self {
+            manage_webhooks_use_case: ManageWebhooksUseCase::new(webhook_repository, secret_cipher),
+        }
+    }
+
+    pub async fn create_webhook(&self, request: CreateWebhookRequest) -> ApiResponse<WebhookResponse> {
+        match self.manage_webhooks_use_case.create(request).await {
+            Ok(webhook) => ApiResponse::success(webhook),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn list_webhooks(&self) -> ApiResponse<Vec<WebhookResponse>> {
+        match self.manage_webhooks_use_case.list().await {
+            Ok(webhooks) => ApiResponse::success(webhooks),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn get_webhook(&self, webhook_id: String) -> ApiResponse<WebhookResponse> {
+        match self.manage_webhooks_use_case.get(webhook_id).await {
+            Ok(webhook) => ApiResponse::success(webhook),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn delete_webhook(&self, webhook_id: String) -> ApiResponse<()> {
+        match self.manage_webhooks_use_case.delete(webhook_id).await {
+            Ok(()) => ApiResponse::success(()),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn enable_webhook(&self, webhook_id: String) -> ApiResponse<WebhookResponse> {
+        match self.manage_webhooks_use_case.enable(webhook_id).await {
+            Ok(webhook) => ApiResponse::success(webhook),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn list_deliveries(&self, webhook_id: String, query: WebhookDeliveryListQuery) -> ApiResponse<Vec<WebhookDeliveryResponse>> {
+        match self.manage_webhooks_use_case.list_deliveries(webhook_id, query.limit).await {
+            Ok(deliveries) => ApiResponse::success(deliveries),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+
+    pub async fn redeliver(&self, webhook_id: String, delivery_id: String) -> ApiResponse<WebhookDeliveryResponse> {
+        match self.manage_webhooks_use_case.redeliver(webhook_id, delivery_id).await {
+            Ok(delivery) => ApiResponse::success(delivery),
+            Err(error) => ApiResponse::error(error.to_string()),
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,128 @@
+use chrono::Utc;
+use crate::domain::{
+    UserRepository, TokenService, ApiKeyDomainService, ApiKeyRepository, SecretHasher, SessionDomainService,
+    SessionPolicy, SessionStore, Actor, UserId, DomainError,
+};
+
+#[derive(Debug, Clone)]
+pub enum Credentials {
+    Bearer(String),
+    ApiKey(String),
+    // CSRF-токен передается только для запросов, изменяющих состояние
+    Session { token: String, csrf_token: Option<String> },
+}
+
+pub struct AuthenticateUseCase<R: UserRepository, T: TokenService, K: ApiKeyRepository, S: SessionStore, H: SecretHasher> {
+    user_repository: R,
+    token_service: T,
+    api_key_domain_service: ApiKeyDomainService<K, H>,
+    session_domain_service: SessionDomainService<S, H>,
+}
+
+impl<R, T, K, S, H> AuthenticateUseCase<R, T, K, S, H>
+where
+    R: UserRepository,
+    T: TokenService,
+    K: ApiKeyRepository,
+    S: SessionStore,
+    H: SecretHasher + Clone,
+{
+    pub fn new(
+        user_repository: R,
+        token_service: T,
+        api_key_repository: K,
+        session_store: S,
+        secret_hasher: H,
+        session_policy: SessionPolicy,
+    ) -> Self {
+        Self {
+            user_repository,
+            token_service,
+            api_key_domain_service: ApiKeyDomainService::new(api_key_repository, secret_hasher.clone()),
+            session_domain_service: SessionDomainService::new(session_store, secret_hasher, session_policy),
+        }
+    }
+
+    pub async fn execute(&self, credentials: &Credentials) -> Result<Actor, ApplicationError> {
+        match credentials {
+            Credentials::Bearer(token) => {
+                let verified = self.token_service
+                    .verify(token)
+                    .map_err(|err| ApplicationError::InvalidToken(err.to_string()))?;
+                
+                let actor = self.load_actor(&verified.user_id).await?;
+                Ok(actor.with_auth_level(verified.auth_level))
+            }
+            Credentials::ApiKey(key) => {
+                let api_key = self.api_key_domain_service
+                    .authenticate(key)
+                    .await
+                    .map_err(|err| match err {
+                        DomainError::Unauthorized(message) => ApplicationError::InvalidApiKey(message),
+                        other => ApplicationError::DomainError(other),
+                    })?;
+                
+                // Ключ - один фактор: права администратора по нему недоступны
+                let actor = self.load_actor(api_key.user_id()).await?;
+                Ok(actor.with_scopes(api_key.scopes().to_vec()).with_api_key(*api_key.id()))
+            }
+            Credentials::Session { token, csrf_token } => {
+                let session = self.session_domain_service
+                    .authenticate(token, Utc::now())
+                    .await
+                    .map_err(|err| match err {
+                        DomainError::Unauthorized(message) => ApplicationError::InvalidSession(message),
+                        other => ApplicationError::DomainError(other),
+                    })?;
+                
+                if let Some(csrf_token) = csrf_token {
+                    self.session_domain_service
+                        .verify_csrf(&session, csrf_token)
+                        .map_err(|_| ApplicationError::InvalidCsrfToken)?;
+                }
+                
+                let actor = self.load_actor(session.user_id()).await?;
+                Ok(actor.with_auth_level(session.auth_level()).with_session(*session.id()))
+            }
+        }
+    }
+
+    async fn load_actor(&self, user_id: &UserId) -> Result<Actor, ApplicationError> {
+        // Роли берем из хранилища, а не из токена: их смена действует сразу
+        let user = self.user_repository
+            .find_by_id(user_id)
+            .await
+            .map_err(ApplicationError::DomainError)?
+            .filter(|user| !user.is_erased())
+            .ok_or(ApplicationError::UnknownUser)?;
+            
+        Ok(Actor::from_user(&user))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApplicationError {
+    #[error("Invalid token: {0}")]
+    InvalidToken(String),
+    
+    #[error("Invalid API key: {0}")]
+    InvalidApiKey(String),
+    
+    #[error("Invalid session: {0}")]
+    InvalidSession(String),
+    
+    #[error("CSRF token missing or invalid")]
+    InvalidCsrfToken,
+    
+    #[error("Credentials refer to an unknown user")]
+    UnknownUser,
+    
+    #[error("Domain error: {0}")]
+    DomainError(#[from] DomainError),
+}
+
+impl From<ApplicationError> for String {
+    fn from(error: ApplicationError) -> Self {
+        error.to_string()
+    }
+}
\ No newline at end of file
@@ -0,0 +1,66 @@
+use crate::domain::{UserDomainService, UserEventBus, UserRepository, UserId, Role, DomainError};
+use crate::application::dto::UserResponse;
+
+#[derive(Clone)]
+pub struct ChangeUserRolesUseCase<R: UserRepository> {
+    user_domain_service: UserDomainService<R>,
+}
+
+impl<R: UserRepository> ChangeUserRolesUseCase<R> {
+    pub fn new(user_repository: R) -> Self {
+        Self {
+            user_domain_service: UserDomainService::new(user_repository),
+        }
+    }
+
+    pub fn with_event_bus(mut self, event_bus: UserEventBus) -> Self {
+        self.user_domain_service = self.user_domain_service.with_event_bus(event_bus);
+        self
+    }
+
+    pub async fn execute(&self, user_id: String, roles: Vec<String>) -> Result<UserResponse, ApplicationError> {
+        let user_id = UserId::from_string(user_id)
+            .map_err(|err| ApplicationError::InvalidUserId(err.to_string()))?;
+        
+        let roles = roles
+            .iter()
+            .map(|role| Role::parse(role))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| ApplicationError::InvalidRole(err.to_string()))?;
+        
+        let user = self.user_domain_service
+            .change_roles(user_id, roles)
+            .await
+            .map_err(ApplicationError::DomainError)?;
+            
+        Ok(UserResponse::from(user))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApplicationError {
+    #[error("Invalid user ID: {0}")]
+    InvalidUserId(String),
+    
+    #[error("Invalid role: {0}")]
+    InvalidRole(String),
+    
+    #[error("Domain error: {0}")]
+    DomainError(#[from] DomainError),
+}
+
+impl From<ApplicationError> for String {
+    fn from(error: ApplicationError) -> Self {
+        error.to_string()
+    }
+}
+
+impl ApplicationError {
+    pub fn cause(&self) -> Option<DomainError> {
+        match self {
+            ApplicationError::InvalidUserId(message) => Some(DomainError::InvalidUserData(message.clone())),
+            ApplicationError::InvalidRole(message) => Some(DomainError::InvalidUserData(message.clone())),
+            ApplicationError::DomainError(error) => Some(error.clone()),
+        }
+    }
+}
\ No newline at end of file
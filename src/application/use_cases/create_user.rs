@@ -1,5 +1,6 @@
-use crate::domain::{UserDomainService, UserRepository, Email, DomainError};
+use crate::domain::{UserDomainService, UserEventBus, UserRepository, Email, DomainError};
 
+#[derive(Clone)]
 pub struct CreateUserUseCase<R: UserRepository> {
     user_domain_service: UserDomainService<R>,
 }
@@ -11,7 +12,12 @@ impl<R: UserRepository> CreateUserUseCase<R> {
         }
     }
 
-    pub async fn execute(&self, email: String, name: String) -> Result<domain::User, ApplicationError> {
+    pub fn with_event_bus(mut self, event_bus: UserEventBus) -> Self {
+        self.user_domain_service = self.user_domain_service.with_event_bus(event_bus);
+        self
+    }
+
+    pub async fn execute(&self, email: String, name: String) -> Result<crate::domain::User, ApplicationError> {
         let email = Email::new(email)
             .map_err(|err| ApplicationError::InvalidEmail(err.to_string()))?;
         
@@ -42,43 +48,65 @@ impl From<ApplicationError> for String {
     }
 }
 
+impl ApplicationError {
+    pub fn cause(&self) -> Option<DomainError> {
+        match self {
+            ApplicationError::InvalidEmail(message) => Some(DomainError::InvalidEmail(message.clone())),
+            ApplicationError::DomainError(error) => Some(error.clone()),
+            ApplicationError::Unexpected(_) => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::domain::Email;
 
     struct MockUserRepository {
-        users: std::collections::HashMap<String, domain::User>,
+        users: std::sync::Mutex<std::collections::HashMap<String, crate::domain::User>>,
     }
 
     impl MockUserRepository {
         fn new() -> Self {
             Self {
-                users: std::collections::HashMap::new(),
+                users: std::sync::Mutex::new(std::collections::HashMap::new()),
             }
         }
     }
 
     impl UserRepository for MockUserRepository {
-        async fn find_by_id(&self, id: &domain::UserId) -> Result<Option<domain::User>, DomainError> {
-            Ok(self.users.get(&id.to_string()).cloned())
+        async fn find_by_id(&self, id: &crate::domain::UserId) -> Result<Option<crate::domain::User>, DomainError> {
+            Ok(self.users.lock().unwrap().get(&id.to_string()).cloned())
         }
 
-        async fn find_by_email(&self, email: &Email) -> Result<Option<domain::User>, DomainError> {
-            Ok(self.users.values()
+        async fn find_by_email(&self, email: &Email) -> Result<Option<crate::domain::User>, DomainError> {
+            Ok(self.users.lock().unwrap().values()
                 .find(|user| user.email() == email)
                 .cloned())
         }
 
-        async fn save(&self, user: &domain::User) -> Result<(), DomainError> {
-            self.users.insert(user.id().to_string(), user.clone());
+        async fn list_users(&self) -> Result<Vec<crate::domain::User>, DomainError> {
+            Ok(self.users.lock().unwrap().values().cloned().collect())
+        }
+
+        async fn save(&self, user: &crate::domain::User) -> Result<(), DomainError> {
+            self.users.lock().unwrap().insert(user.id().to_string(), user.clone());
             Ok(())
         }
 
-        async fn delete(&self, id: &domain::UserId) -> Result<(), DomainError> {
-            self.users.remove(&id.to_string());
+        async fn delete(&self, id: &crate::domain::UserId) -> Result<(), DomainError> {
+            self.users.lock().unwrap().remove(&id.to_string());
             Ok(())
         }
+
+        async fn save_with_outbox(&self, user: &crate::domain::User, _messages: Vec<crate::domain::OutboxMessage>) -> Result<(), DomainError> {
+            self.save(user).await
+        }
+
+        async fn delete_with_outbox(&self, id: &crate::domain::UserId, _messages: Vec<crate::domain::OutboxMessage>) -> Result<(), DomainError> {
+            self.delete(id).await
+        }
     }
 
     #[tokio::test]
@@ -1,5 +1,6 @@
-use crate::domain::{UserDomainService, UserRepository, UserId, DomainError};
+use crate::domain::{UserDomainService, UserEventBus, UserRepository, UserId, DomainError};
 
+#[derive(Clone)]
 pub struct DeleteUserUseCase<R: UserRepository> {
     user_domain_service: UserDomainService<R>,
 }
@@ -11,6 +12,11 @@ impl<R: UserRepository> DeleteUserUseCase<R> {
         }
     }
 
+    pub fn with_event_bus(mut self, event_bus: UserEventBus) -> Self {
+        self.user_domain_service = self.user_domain_service.with_event_bus(event_bus);
+        self
+    }
+
     pub async fn execute(&self, user_id: String) -> Result<(), ApplicationError> {
         let user_id = UserId::from_string(user_id)
             .map_err(|err| ApplicationError::InvalidUserId(err.to_string()))?;
@@ -40,4 +46,14 @@ impl From<ApplicationError> for String {
     fn from(error: ApplicationError) -> Self {
         error.to_string()
     }
+}
+
+impl ApplicationError {
+    pub fn cause(&self) -> Option<DomainError> {
+        match self {
+            ApplicationError::InvalidUserId(message) => Some(DomainError::InvalidUserData(message.clone())),
+            ApplicationError::UserNotFound => Some(DomainError::UserNotFound),
+            ApplicationError::DomainError(error) => Some(error.clone()),
+        }
+    }
 }
\ No newline at end of file
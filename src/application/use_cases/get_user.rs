@@ -1,6 +1,7 @@
-use crate::domain::{UserDomainService, UserRepository, UserId, DomainError};
+use crate::domain::{UserRepository, UserId, DomainError};
 use crate::application::dto::UserResponse;
 
+#[derive(Clone)]
 pub struct GetUserUseCase<R: UserRepository> {
     user_repository: R,
 }
@@ -58,4 +59,16 @@ impl From<ApplicationError> for String {
     fn from(error: ApplicationError) -> Self {
         error.to_string()
     }
+}
+
+impl ApplicationError {
+    // Доменная причина ошибки: по ней адаптеры (gRPC) выбирают код ответа
+    pub fn cause(&self) -> Option<DomainError> {
+        match self {
+            ApplicationError::InvalidUserId(message) => Some(DomainError::InvalidUserData(message.clone())),
+            ApplicationError::InvalidEmail(message) => Some(DomainError::InvalidEmail(message.clone())),
+            ApplicationError::UserNotFound => Some(DomainError::UserNotFound),
+            ApplicationError::DomainError(error) => Some(error.clone()),
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,122 @@
+use crate::domain::{Role, User, UserRepository, DomainError};
+use crate::application::dto::{ListUsersQuery, UserListResponse, UserResponse};
+
+const DEFAULT_PAGE_SIZE: usize = 20;
+pub const MAX_PAGE_SIZE: usize = 100;
+
+#[derive(Clone)]
+pub struct ListUsersUseCase<R: UserRepository> {
+    user_repository: R,
+}
+
+impl<R: UserRepository> ListUsersUseCase<R> {
+    pub fn new(user_repository: R) -> Self {
+        Self { user_repository }
+    }
+
+    pub async fn execute(&self, query: ListUsersQuery) -> Result<UserListResponse, ApplicationError> {
+        let role = query.role
+            .as_deref()
+            .map(Role::parse)
+            .transpose()
+            .map_err(|err| ApplicationError::InvalidFilter(err.to_string()))?;
+        let search = query.search
+            .map(|search| search.trim().to_lowercase())
+            .filter(|search| !search.is_empty());
+        let offset = query.offset.unwrap_or(0);
+        let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE).clamp(1, MAX_PAGE_SIZE);
+
+        let matches = |user: &User| {
+            (query.include_erased || !user.is_erased())
+                && role.is_none_or(|role| user.has_role(role))
+                && search.as_ref().is_none_or(|search| {
+                    user.email().as_str().to_lowercase().contains(search.as_str())
+                        || user.name().to_lowercase().contains(search.as_str())
+                })
+        };
+
+        let users: Vec<User> = self.user_repository
+            .list_users()
+            .await
+            .map_err(ApplicationError::DomainError)?
+            .into_iter()
+            .filter(matches)
+            .collect();
+
+        Ok(UserListResponse {
+            total: users.len(),
+            users: users.into_iter().skip(offset).take(limit).map(UserResponse::from).collect(),
+            offset,
+            limit,
+        })
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApplicationError {
+    #[error("Invalid filter: {0}")]
+    InvalidFilter(String),
+    
+    #[error("Domain error: {0}")]
+    DomainError(#[from] DomainError),
+}
+
+impl From<ApplicationError> for String {
+    fn from(error: ApplicationError) -> Self {
+        error.to_string()
+    }
+}
+
+impl ApplicationError {
+    pub fn cause(&self) -> Option<DomainError> {
+        match self {
+            ApplicationError::InvalidFilter(message) => Some(DomainError::InvalidUserData(message.clone())),
+            ApplicationError::DomainError(error) => Some(error.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::domain::Email;
+    use crate::infrastructure::InMemoryUserRepository;
+
+    async fn repository() -> InMemoryUserRepository {
+        let repository = InMemoryUserRepository::new();
+        for (email, name) in [("alice@example.com", "Alice"), ("bob@example.com", "Bob"), ("carol@corp.example", "Carol")] {
+            let mut user = User::new(Email::new(email.to_string()).unwrap(), name.to_string()).unwrap();
+            if name == "Carol" {
+                user.change_roles(vec![Role::Support]).unwrap();
+            }
+            repository.save(&user).await.unwrap();
+        }
+        let mut erased = User::new(Email::new("dave@example.com".to_string()).unwrap(), "Dave".to_string()).unwrap();
+        erased.erase(Utc::now()).unwrap();
+        repository.save(&erased).await.unwrap();
+        repository
+    }
+
+    #[tokio::test]
+    async fn test_filters_and_pagination() {
+        let use_case = ListUsersUseCase::new(repository().await);
+
+        let page = use_case.execute(ListUsersQuery { limit: Some(2), ..ListUsersQuery::default() }).await.unwrap();
+        assert_eq!((page.total, page.users.len()), (3, 2));
+        let next = use_case.execute(ListUsersQuery { offset: Some(2), limit: Some(2), ..ListUsersQuery::default() }).await.unwrap();
+        let mut names: Vec<_> = page.users.iter().chain(&next.users).map(|user| user.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, vec!["Alice", "Bob", "Carol"]);
+
+        let search = use_case.execute(ListUsersQuery { search: Some(" EXAMPLE.COM".to_string()), ..ListUsersQuery::default() }).await.unwrap();
+        assert_eq!(search.total, 2);
+        let support = use_case.execute(ListUsersQuery { role: Some("support".to_string()), ..ListUsersQuery::default() }).await.unwrap();
+        assert_eq!(support.users.iter().map(|user| user.name.as_str()).collect::<Vec<_>>(), vec!["Carol"]);
+        let all = use_case.execute(ListUsersQuery { include_erased: true, ..ListUsersQuery::default() }).await.unwrap();
+        assert_eq!(all.total, 4);
+
+        let invalid = use_case.execute(ListUsersQuery { role: Some("owner".to_string()), ..ListUsersQuery::default() }).await;
+        assert!(matches!(invalid, Err(ApplicationError::InvalidFilter(_))));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,204 @@
+use std::net::IpAddr;
+use chrono::{DateTime, Utc};
+use crate::domain::{
+    AttemptSubject, LockoutPolicy, LoginAttemptStore, LoginProtectionDomainService, OutboxMessage, OutboxPayload,
+    UserRepository, UserId, DomainError,
+};
+use crate::application::dto::AccountUnlockResponse;
+
+#[derive(Clone)]
+pub struct LoginProtectionUseCase<R: UserRepository, S: LoginAttemptStore> {
+    user_repository: R,
+    login_protection_domain_service: LoginProtectionDomainService<S>,
+}
+
+impl<R: UserRepository, S: LoginAttemptStore> LoginProtectionUseCase<R, S> {
+    pub fn new(user_repository: R, attempt_store: S, policy: LockoutPolicy) -> Self {
+        Self {
+            user_repository,
+            login_protection_domain_service: LoginProtectionDomainService::new(attempt_store, policy),
+        }
+    }
+
+    pub async fn check(&self, user_id: Option<&str>, client_ip: Option<IpAddr>) -> Result<(), ApplicationError> {
+        let subjects = Self::subjects(user_id, client_ip);
+        
+        self.login_protection_domain_service
+            .check(&subjects, Utc::now())
+            .await
+            .map_err(|err| match err {
+                DomainError::AccountLocked(until) => ApplicationError::AccountLocked(until),
+                DomainError::TooManyAttempts(seconds) => ApplicationError::TooManyAttempts(seconds),
+                other => ApplicationError::DomainError(other),
+            })
+    }
+
+    pub async fn check_account(&self, user_id: &UserId) -> Result<(), ApplicationError> {
+        self.login_protection_domain_service
+            .ensure_not_locked(user_id, Utc::now())
+            .await
+            .map_err(|err| match err {
+                DomainError::AccountLocked(until) => ApplicationError::AccountLocked(until),
+                other => ApplicationError::DomainError(other),
+            })
+    }
+
+    pub async fn record_failure(&self, user_id: Option<&str>, client_ip: Option<IpAddr>) -> Result<(), ApplicationError> {
+        let now = Utc::now();
+        
+        for subject in Self::subjects(user_id, client_ip) {
+            let locked_until = self.login_protection_domain_service
+                .record_failure(&subject, now)
+                .await
+                .map_err(ApplicationError::DomainError)?;
+            
+            if let (AttemptSubject::Account(user_id), Some(locked_until)) = (&subject, locked_until) {
+                self.notify_locked(user_id, locked_until).await?;
+            }
+        }
+        
+        Ok(())
+    }
+
+    pub async fn record_success(&self, user_id: &str) -> Result<(), ApplicationError> {
+        let Some(subject) = Self::subjects(Some(user_id), None).pop() else {
+            return Ok(());
+        };
+        
+        self.login_protection_domain_service
+            .record_success(&subject)
+            .await
+            .map_err(ApplicationError::DomainError)
+    }
+
+    pub async fn unlock(&self, user_id: String) -> Result<AccountUnlockResponse, ApplicationError> {
+        let user_id = UserId::from_string(user_id)
+            .map_err(|err| ApplicationError::InvalidUserId(err.to_string()))?;
+        
+        self.user_repository
+            .find_by_id(&user_id)
+            .await
+            .map_err(ApplicationError::DomainError)?
+            .ok_or(ApplicationError::UserNotFound)?;
+        
+        let was_locked = self.login_protection_domain_service
+            .unlock(&user_id, Utc::now())
+            .await
+            .map_err(ApplicationError::DomainError)?;
+            
+        Ok(AccountUnlockResponse {
+            user_id: user_id.to_string(),
+            was_locked,
+        })
+    }
+
+    // Письмо уходит через outbox, как и остальные уведомления пользователю
+    async fn notify_locked(&self, user_id: &UserId, locked_until: DateTime<Utc>) -> Result<(), ApplicationError> {
+        let Some(user) = self.user_repository
+            .find_by_id(user_id)
+            .await
+            .map_err(ApplicationError::DomainError)?
+        else {
+            return Ok(());
+        };
+        
+        let messages = vec![OutboxMessage::new(OutboxPayload::AccountLockedEmail { user_id: user.id().clone(), locked_until })];
+        self.user_repository
+            .save_with_outbox(&user, messages)
+            .await
+            .map_err(ApplicationError::DomainError)
+    }
+
+    // Некорректный ID в пути не блокирует несуществующий аккаунт, учитывается только адрес
+    fn subjects(user_id: Option<&str>, client_ip: Option<IpAddr>) -> Vec<AttemptSubject> {
+        let account = user_id
+            .and_then(|user_id| UserId::from_string(user_id.to_string()).ok())
+            .map(AttemptSubject::Account);
+        
+        client_ip.map(AttemptSubject::ClientIp).into_iter().chain(account).collect()
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApplicationError {
+    #[error("Invalid user ID: {0}")]
+    InvalidUserId(String),
+    
+    #[error("User not found")]
+    UserNotFound,
+    
+    #[error("Account is locked until {0}")]
+    AccountLocked(DateTime<Utc>),
+    
+    #[error("Too many failed attempts, retry in {0} seconds")]
+    TooManyAttempts(u64),
+    
+    #[error("Domain error: {0}")]
+    DomainError(#[from] DomainError),
+}
+
+impl ApplicationError {
+    // Через сколько секунд имеет смысл повторить попытку (для `Retry-After`)
+    pub fn retry_after(&self) -> Option<u64> {
+        match self {
+            ApplicationError::AccountLocked(until) => Some((*until - Utc::now()).num_seconds().max(1) as u64),
+            ApplicationError::TooManyAttempts(seconds) => Some(*seconds),
+            _ => None,
+        }
+    }
+}
+
+impl From<ApplicationError> for String {
+    fn from(error: ApplicationError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Email, OutboxRepository, OutboxStatus, User};
+    use crate::infrastructure::{InMemoryLoginAttemptStore, InMemoryUserRepository};
+
+    #[tokio::test]
+    async fn test_lock_sends_email_and_admin_unlocks() {
+        let repository = InMemoryUserRepository::new();
+        let email = Email::new("locked@example.com".to_string()).unwrap();
+        let user = User::new(email, "Locked User".to_string()).unwrap();
+        repository.save(&user).await.unwrap();
+
+        let policy = LockoutPolicy {
+            account_threshold: 1,
+            ..LockoutPolicy::default()
+        };
+        let use_case = LoginProtectionUseCase::new(repository.clone(), InMemoryLoginAttemptStore::new(), policy);
+        let user_id = user.id().to_string();
+
+        use_case.record_failure(Some(&user_id), None).await.unwrap();
+
+        let result = use_case.check(Some(&user_id), None).await;
+        assert!(matches!(result, Err(ApplicationError::AccountLocked(_))));
+        assert!(result.unwrap_err().retry_after().unwrap() > 0);
+
+        let outbox = repository.list_outbox_messages(Some(OutboxStatus::Pending), 10).await.unwrap();
+        assert_eq!(outbox.len(), 1);
+        assert_eq!(outbox[0].topic(), "email.account_locked");
+
+        let unlocked = use_case.unlock(user_id.clone()).await.unwrap();
+        assert!(unlocked.was_locked);
+        assert!(use_case.check(Some(&user_id), None).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_unlock_unknown_user() {
+        let use_case = LoginProtectionUseCase::new(
+            InMemoryUserRepository::new(),
+            InMemoryLoginAttemptStore::new(),
+            LockoutPolicy::default(),
+        );
+
+        let result = use_case.unlock(UserId::new().to_string()).await;
+
+        assert!(matches!(result, Err(ApplicationError::UserNotFound)));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,192 @@
+use chrono::{Duration, Utc};
+use uuid::Uuid;
+use crate::domain::{
+    Actor, ApiKeyDomainService, ApiKeyRepository, SecretHasher, Scope, UserRepository, UserId, DomainError,
+};
+use crate::application::dto::{ApiKeyResponse, CreateApiKeyRequest, CreatedApiKeyResponse};
+
+const DEFAULT_EXPIRY_DAYS: u32 = 90;
+const MAX_EXPIRY_DAYS: u32 = 365;
+
+#[derive(Clone)]
+pub struct ManageApiKeysUseCase<R: UserRepository, K: ApiKeyRepository, H: SecretHasher> {
+    user_repository: R,
+    api_key_domain_service: ApiKeyDomainService<K, H>,
+}
+
+impl<R: UserRepository, K: ApiKeyRepository, H: SecretHasher> ManageApiKeysUseCase<R, K, H> {
+    pub fn new(user_repository: R, api_key_repository: K, secret_hasher: H) -> Self {
+        Self {
+            user_repository,
+            api_key_domain_service: ApiKeyDomainService::new(api_key_repository, secret_hasher),
+        }
+    }
+
+    pub async fn create(&self, actor: &Actor, user_id: String, request: CreateApiKeyRequest) -> Result<CreatedApiKeyResponse, ApplicationError> {
+        let user_id = Self::parse_user_id(user_id)?;
+        
+        let scopes = request.scopes
+            .iter()
+            .map(|scope| Scope::parse(scope))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| ApplicationError::InvalidScope(err.to_string()))?;
+        
+        // Ключ, созданный по API-ключу, не может получить больше прав, чем исходный
+        if let Some(scope) = scopes.iter().find(|scope| !actor.has_scope(**scope)) {
+            return Err(ApplicationError::InvalidScope(format!("{} exceeds the caller's scopes", scope)));
+        }
+        
+        let expires_in_days = request.expires_in_days.unwrap_or(DEFAULT_EXPIRY_DAYS);
+        if expires_in_days == 0 || expires_in_days > MAX_EXPIRY_DAYS {
+            return Err(ApplicationError::InvalidExpiry(format!(
+                "expires_in_days must be between 1 and {}",
+                MAX_EXPIRY_DAYS
+            )));
+        }
+        
+        self.user_repository
+            .find_by_id(&user_id)
+            .await
+            .map_err(ApplicationError::DomainError)?
+            .ok_or(ApplicationError::UserNotFound)?;
+        
+        let expires_at = Utc::now() + Duration::days(i64::from(expires_in_days));
+        let (api_key, key) = self.api_key_domain_service
+            .issue_key(user_id, request.name, scopes, expires_at)
+            .await
+            .map_err(ApplicationError::DomainError)?;
+            
+        Ok(CreatedApiKeyResponse {
+            api_key: ApiKeyResponse::from(api_key),
+            key,
+        })
+    }
+
+    pub async fn list(&self, user_id: String) -> Result<Vec<ApiKeyResponse>, ApplicationError> {
+        let user_id = Self::parse_user_id(user_id)?;
+        
+        let api_keys = self.api_key_domain_service
+            .list_keys(&user_id)
+            .await
+            .map_err(ApplicationError::DomainError)?;
+            
+        Ok(api_keys.into_iter().map(ApiKeyResponse::from).collect())
+    }
+
+    pub async fn revoke(&self, user_id: String, key_id: String) -> Result<ApiKeyResponse, ApplicationError> {
+        let user_id = Self::parse_user_id(user_id)?;
+        let key_id = Uuid::parse_str(&key_id)
+            .map_err(|_| ApplicationError::InvalidKeyId("Invalid UUID format".to_string()))?;
+        
+        let api_key = self.api_key_domain_service
+            .revoke_key(&user_id, &key_id)
+            .await
+            .map_err(|err| match err {
+                DomainError::ApiKeyNotFound => ApplicationError::KeyNotFound,
+                other => ApplicationError::DomainError(other),
+            })?;
+            
+        Ok(ApiKeyResponse::from(api_key))
+    }
+
+    fn parse_user_id(user_id: String) -> Result<UserId, ApplicationError> {
+        UserId::from_string(user_id).map_err(|err| ApplicationError::InvalidUserId(err.to_string()))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApplicationError {
+    #[error("Invalid user ID: {0}")]
+    InvalidUserId(String),
+    
+    #[error("Invalid API key ID: {0}")]
+    InvalidKeyId(String),
+    
+    #[error("Invalid scope: {0}")]
+    InvalidScope(String),
+    
+    #[error("Invalid expiry: {0}")]
+    InvalidExpiry(String),
+    
+    #[error("User not found")]
+    UserNotFound,
+    
+    #[error("API key not found")]
+    KeyNotFound,
+    
+    #[error("Domain error: {0}")]
+    DomainError(#[from] DomainError),
+}
+
+impl From<ApplicationError> for String {
+    fn from(error: ApplicationError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Email, Role, User};
+    use crate::infrastructure::{InMemoryApiKeyRepository, InMemoryUserRepository, Sha256SecretHasher};
+
+    async fn setup() -> (ManageApiKeysUseCase<InMemoryUserRepository, InMemoryApiKeyRepository, Sha256SecretHasher>, User) {
+        let user_repository = InMemoryUserRepository::new();
+        let email = Email::new("owner@example.com".to_string()).unwrap();
+        let user = User::new(email, "Owner".to_string()).unwrap();
+        user_repository.save(&user).await.unwrap();
+
+        let use_case = ManageApiKeysUseCase::new(user_repository, InMemoryApiKeyRepository::new(), Sha256SecretHasher::new());
+        (use_case, user)
+    }
+
+    fn request(scopes: &[&str], expires_in_days: Option<u32>) -> CreateApiKeyRequest {
+        CreateApiKeyRequest {
+            name: "deploy".to_string(),
+            scopes: scopes.iter().map(|scope| scope.to_string()).collect(),
+            expires_in_days,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_list_and_revoke() {
+        let (use_case, user) = setup().await;
+        let actor = Actor::from_user(&user);
+
+        let created = use_case.create(&actor, user.id().to_string(), request(&["users:read"], None)).await.unwrap();
+        assert!(created.key.starts_with(&created.api_key.prefix));
+
+        let listed = use_case.list(user.id().to_string()).await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].prefix, created.api_key.prefix);
+
+        let revoked = use_case.revoke(user.id().to_string(), created.api_key.id.clone()).await.unwrap();
+        assert!(revoked.revoked_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_requests() {
+        let (use_case, user) = setup().await;
+        let actor = Actor::from_user(&user);
+        let user_id = user.id().to_string();
+
+        let unknown_scope = use_case.create(&actor, user_id.clone(), request(&["users:*"], None)).await;
+        assert!(matches!(unknown_scope, Err(ApplicationError::InvalidScope(_))));
+
+        let too_long = use_case.create(&actor, user_id.clone(), request(&["users:read"], Some(1000))).await;
+        assert!(matches!(too_long, Err(ApplicationError::InvalidExpiry(_))));
+
+        let missing_user = use_case.create(&actor, UserId::new().to_string(), request(&["users:read"], None)).await;
+        assert!(matches!(missing_user, Err(ApplicationError::UserNotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_api_key_cannot_escalate_scopes() {
+        let (use_case, user) = setup().await;
+        let actor = Actor::new(user.id().clone(), Role::Member).with_scopes(vec![Scope::ApiKeysWrite, Scope::UsersRead]);
+
+        let result = use_case.create(&actor, user.id().to_string(), request(&["users:delete"], None)).await;
+
+        assert!(matches!(result, Err(ApplicationError::InvalidScope(_))));
+    }
+}
\ No newline at end of file
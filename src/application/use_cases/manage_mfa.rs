@@ -0,0 +1,199 @@
+use crate::domain::{
+    AuthLevel, MfaCode, MfaDomainService, MfaRepository, SecretCipher, SecretHasher, TokenService, TotpService,
+    User, UserRepository, UserId, DomainError,
+};
+use crate::application::dto::{
+    MfaChallengeResponse, MfaEnrollmentResponse, MfaRecoveryCodesResponse, MfaStatusResponse, MfaVerifyRequest,
+};
+
+#[derive(Clone)]
+pub struct ManageMfaUseCase<R, M, C, O, H, T>
+where
+    R: UserRepository,
+    M: MfaRepository,
+    C: SecretCipher,
+    O: TotpService,
+    H: SecretHasher,
+    T: TokenService,
+{
+    user_repository: R,
+    mfa_domain_service: MfaDomainService<M, C, O, H>,
+    token_service: T,
+}
+
+impl<R, M, C, O, H, T> ManageMfaUseCase<R, M, C, O, H, T>
+where
+    R: UserRepository,
+    M: MfaRepository,
+    C: SecretCipher,
+    O: TotpService,
+    H: SecretHasher,
+    T: TokenService,
+{
+    pub fn new(user_repository: R, mfa_domain_service: MfaDomainService<M, C, O, H>, token_service: T) -> Self {
+        Self { user_repository, mfa_domain_service, token_service }
+    }
+
+    pub async fn status(&self, user_id: String) -> Result<MfaStatusResponse, ApplicationError> {
+        let user_id = Self::parse_user_id(user_id)?;
+        
+        let enrollment = self.mfa_domain_service
+            .status(&user_id)
+            .await
+            .map_err(ApplicationError::DomainError)?;
+            
+        Ok(MfaStatusResponse::from(enrollment))
+    }
+
+    pub async fn enroll(&self, user_id: String) -> Result<MfaEnrollmentResponse, ApplicationError> {
+        let user = self.find_user(user_id).await?;
+        
+        let setup = self.mfa_domain_service
+            .enroll(&user)
+            .await
+            .map_err(ApplicationError::DomainError)?;
+            
+        Ok(MfaEnrollmentResponse {
+            secret: setup.secret,
+            otpauth_uri: setup.otpauth_uri,
+        })
+    }
+
+    pub async fn provisioning_uri(&self, user_id: String) -> Result<String, ApplicationError> {
+        let user = self.find_user(user_id).await?;
+        
+        self.mfa_domain_service
+            .provisioning_uri(&user)
+            .await
+            .map_err(ApplicationError::DomainError)
+    }
+
+    pub async fn confirm(&self, user_id: String, code: String) -> Result<MfaRecoveryCodesResponse, ApplicationError> {
+        let user_id = Self::parse_user_id(user_id)?;
+        
+        let recovery_codes = self.mfa_domain_service
+            .confirm(&user_id, &code)
+            .await
+            .map_err(ApplicationError::DomainError)?;
+            
+        Ok(MfaRecoveryCodesResponse { recovery_codes })
+    }
+
+    /// Шаг входа: обменивает токен первого фактора на токен с подтвержденной MFA.
+    pub async fn challenge(&self, user_id: String, request: MfaVerifyRequest) -> Result<MfaChallengeResponse, ApplicationError> {
+        let user_id = Self::parse_user_id(user_id)?;
+        let code = Self::parse_code(request)?;
+        
+        let enrollment = self.mfa_domain_service
+            .verify(&user_id, &code)
+            .await
+            .map_err(ApplicationError::DomainError)?;
+        let token = self.token_service
+            .issue(&user_id, AuthLevel::MultiFactor)
+            .map_err(ApplicationError::DomainError)?;
+            
+        Ok(MfaChallengeResponse {
+            token,
+            recovery_codes_remaining: enrollment.recovery_code_hashes().len(),
+        })
+    }
+
+    pub async fn disable(&self, user_id: String, request: MfaVerifyRequest) -> Result<(), ApplicationError> {
+        let user_id = Self::parse_user_id(user_id)?;
+        let code = Self::parse_code(request)?;
+        
+        self.mfa_domain_service
+            .disable(&user_id, &code)
+            .await
+            .map_err(ApplicationError::DomainError)
+    }
+
+    async fn find_user(&self, user_id: String) -> Result<User, ApplicationError> {
+        let user_id = Self::parse_user_id(user_id)?;
+        
+        self.user_repository
+            .find_by_id(&user_id)
+            .await
+            .map_err(ApplicationError::DomainError)?
+            .ok_or(ApplicationError::UserNotFound)
+    }
+
+    fn parse_user_id(user_id: String) -> Result<UserId, ApplicationError> {
+        UserId::from_string(user_id).map_err(|err| ApplicationError::InvalidUserId(err.to_string()))
+    }
+
+    fn parse_code(request: MfaVerifyRequest) -> Result<MfaCode, ApplicationError> {
+        match (request.code, request.recovery_code) {
+            (Some(code), None) => Ok(MfaCode::Totp(code)),
+            (None, Some(recovery_code)) => Ok(MfaCode::Recovery(recovery_code)),
+            _ => Err(ApplicationError::InvalidRequest(
+                "Exactly one of code or recovery_code is required".to_string(),
+            )),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApplicationError {
+    #[error("Invalid user ID: {0}")]
+    InvalidUserId(String),
+    
+    #[error("Invalid request: {0}")]
+    InvalidRequest(String),
+    
+    #[error("User not found")]
+    UserNotFound,
+    
+    #[error("Domain error: {0}")]
+    DomainError(#[from] DomainError),
+}
+
+impl From<ApplicationError> for String {
+    fn from(error: ApplicationError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use data_encoding::BASE32_NOPAD;
+    use crate::domain::Email;
+    use crate::infrastructure::{
+        AesGcmSecretCipher, InMemoryMfaRepository, InMemoryUserRepository, JwtTokenService, Rfc6238TotpService,
+        Sha256SecretHasher,
+    };
+
+    #[tokio::test]
+    async fn test_challenge_issues_mfa_token() {
+        let user_repository = InMemoryUserRepository::new();
+        let email = Email::new("admin@example.com".to_string()).unwrap();
+        let user = User::new(email, "Admin".to_string()).unwrap();
+        user_repository.save(&user).await.unwrap();
+
+        let tokens = JwtTokenService::new("secret", Duration::minutes(5));
+        let mfa = MfaDomainService::new(
+            InMemoryMfaRepository::new(),
+            AesGcmSecretCipher::new(&[2u8; 32]),
+            Rfc6238TotpService::new("Test"),
+            Sha256SecretHasher::new(),
+        );
+        let use_case = ManageMfaUseCase::new(user_repository, mfa, tokens.clone());
+        let user_id = user.id().to_string();
+
+        let enrollment = use_case.enroll(user_id.clone()).await.unwrap();
+        let secret = BASE32_NOPAD.decode(enrollment.secret.as_bytes()).unwrap();
+        let code = Rfc6238TotpService::code_at(&secret, (Utc::now().timestamp() / 30) as u64);
+        let recovery = use_case.confirm(user_id.clone(), code).await.unwrap();
+        assert!(use_case.status(user_id.clone()).await.unwrap().enabled);
+
+        let both = MfaVerifyRequest { code: Some("123456".to_string()), recovery_code: Some("x".to_string()) };
+        assert!(matches!(use_case.challenge(user_id.clone(), both).await, Err(ApplicationError::InvalidRequest(_))));
+
+        let request = MfaVerifyRequest { code: None, recovery_code: Some(recovery.recovery_codes[0].clone()) };
+        let challenge = use_case.challenge(user_id, request).await.unwrap();
+        assert_eq!(challenge.recovery_codes_remaining, 9);
+        assert_eq!(tokens.verify(&challenge.token).unwrap().auth_level, AuthLevel::MultiFactor);
+    }
+}
\ No newline at end of file
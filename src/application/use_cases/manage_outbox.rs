@@ -0,0 +1,123 @@
+use chrono::Utc;
+use uuid::Uuid;
+use crate::domain::{OutboxRepository, OutboxStatus, DomainError};
+use crate::application::dto::OutboxMessageResponse;
+
+const DEFAULT_LIST_LIMIT: usize = 100;
+
+#[derive(Clone)]
+pub struct ManageOutboxUseCase<R: OutboxRepository> {
+    outbox_repository: R,
+}
+
+impl<R: OutboxRepository> ManageOutboxUseCase<R> {
+    pub fn new(outbox_repository: R) -> Self {
+        Self { outbox_repository }
+    }
+
+    pub async fn list(&self, status: Option<String>, limit: Option<usize>) -> Result<Vec<OutboxMessageResponse>, ApplicationError> {
+        let status = status
+            .map(|status| OutboxStatus::parse(&status))
+            .transpose()
+            .map_err(|err| ApplicationError::InvalidStatus(err.to_string()))?;
+        
+        let messages = self.outbox_repository
+            .list_outbox_messages(status, limit.unwrap_or(DEFAULT_LIST_LIMIT))
+            .await
+            .map_err(ApplicationError::DomainError)?;
+            
+        Ok(messages.into_iter().map(OutboxMessageResponse::from).collect())
+    }
+
+    pub async fn get(&self, message_id: String) -> Result<OutboxMessageResponse, ApplicationError> {
+        let message_id = Self::parse_id(&message_id)?;
+        
+        let message = self.outbox_repository
+            .find_outbox_message(&message_id)
+            .await
+            .map_err(ApplicationError::DomainError)?
+            .ok_or(ApplicationError::MessageNotFound)?;
+            
+        Ok(OutboxMessageResponse::from(message))
+    }
+
+    pub async fn requeue(&self, message_id: String) -> Result<OutboxMessageResponse, ApplicationError> {
+        let message_id = Self::parse_id(&message_id)?;
+        
+        let mut message = self.outbox_repository
+            .find_outbox_message(&message_id)
+            .await
+            .map_err(ApplicationError::DomainError)?
+            .ok_or(ApplicationError::MessageNotFound)?;
+        
+        message.requeue(Utc::now()).map_err(ApplicationError::DomainError)?;
+        self.outbox_repository
+            .update_outbox_message(&message)
+            .await
+            .map_err(ApplicationError::DomainError)?;
+            
+        Ok(OutboxMessageResponse::from(message))
+    }
+
+    fn parse_id(message_id: &str) -> Result<Uuid, ApplicationError> {
+        Uuid::parse_str(message_id)
+            .map_err(|_| ApplicationError::InvalidMessageId("Invalid UUID format".to_string()))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApplicationError {
+    #[error("Invalid outbox message ID: {0}")]
+    InvalidMessageId(String),
+    
+    #[error("Invalid status: {0}")]
+    InvalidStatus(String),
+    
+    #[error("Outbox message not found")]
+    MessageNotFound,
+    
+    #[error("Domain error: {0}")]
+    DomainError(#[from] DomainError),
+}
+
+impl From<ApplicationError> for String {
+    fn from(error: ApplicationError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{OutboxMessage, OutboxPayload, UserId, UserRepository};
+    use crate::infrastructure::InMemoryUserRepository;
+
+    #[tokio::test]
+    async fn test_requeue_dead_lettered_message() {
+        let repository = InMemoryUserRepository::new();
+        let user_id = UserId::new();
+        let mut message = OutboxMessage::new(OutboxPayload::UserDeleted { user_id: user_id.clone() });
+        let message_id = message.id().to_string();
+        repository.delete_with_outbox(&user_id, vec![message.clone()]).await.unwrap();
+        message.dead_letter("provider is down".to_string());
+        repository.update_outbox_message(&message).await.unwrap();
+
+        let use_case = ManageOutboxUseCase::new(repository);
+        let dead = use_case.list(Some("dead_lettered".to_string()), None).await.unwrap();
+        assert_eq!(dead.len(), 1);
+
+        let requeued = use_case.requeue(message_id).await.unwrap();
+        assert_eq!(requeued.status, "pending");
+        assert_eq!(requeued.attempts, 0);
+        assert!(use_case.list(Some("dead_lettered".to_string()), None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_status_filter() {
+        let use_case = ManageOutboxUseCase::new(InMemoryUserRepository::new());
+
+        let result = use_case.list(Some("unknown".to_string()), None).await;
+
+        assert!(matches!(result, Err(ApplicationError::InvalidStatus(_))));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,199 @@
+use chrono::Utc;
+use uuid::Uuid;
+use crate::domain::{
+    Actor, SecretHasher, SessionDomainService, SessionMetadata, SessionPolicy, SessionStore, UserRepository, UserId,
+    DomainError,
+};
+use crate::application::dto::{CreatedSessionResponse, SessionResponse, SessionsRevokedResponse};
+
+#[derive(Clone)]
+pub struct ManageSessionsUseCase<R: UserRepository, S: SessionStore, H: SecretHasher> {
+    user_repository: R,
+    session_domain_service: SessionDomainService<S, H>,
+}
+
+impl<R: UserRepository, S: SessionStore, H: SecretHasher> ManageSessionsUseCase<R, S, H> {
+    pub fn new(user_repository: R, session_store: S, secret_hasher: H, policy: SessionPolicy) -> Self {
+        Self {
+            user_repository,
+            session_domain_service: SessionDomainService::new(session_store, secret_hasher, policy),
+        }
+    }
+
+    pub fn policy(&self) -> &SessionPolicy {
+        self.session_domain_service.policy()
+    }
+
+    /// Обменивает bearer-токен на cookie-сессию с тем же уровнем аутентификации.
+    pub async fn start(&self, actor: &Actor, metadata: SessionMetadata) -> Result<CreatedSessionResponse, ApplicationError> {
+        // API-ключ и сама сессия не должны порождать новые сессии
+        if actor.scopes().is_some() || actor.session_id().is_some() {
+            return Err(ApplicationError::BearerTokenRequired);
+        }
+        
+        let (session, tokens) = self.session_domain_service
+            .start(actor.user_id().clone(), actor.auth_level(), metadata, Utc::now())
+            .await
+            .map_err(ApplicationError::DomainError)?;
+            
+        Ok(CreatedSessionResponse {
+            session: SessionResponse::new(&session, self.policy(), true),
+            csrf_token: tokens.csrf_token,
+            session_token: tokens.session_token,
+        })
+    }
+
+    pub async fn list(&self, actor: &Actor, user_id: String) -> Result<Vec<SessionResponse>, ApplicationError> {
+        let user_id = self.find_user_id(user_id).await?;
+        
+        let sessions = self.session_domain_service
+            .list_sessions(&user_id, Utc::now())
+            .await
+            .map_err(ApplicationError::DomainError)?;
+            
+        Ok(sessions
+            .iter()
+            .map(|session| SessionResponse::new(session, self.policy(), actor.session_id() == Some(session.id())))
+            .collect())
+    }
+
+    pub async fn revoke(&self, user_id: String, session_id: String) -> Result<SessionsRevokedResponse, ApplicationError> {
+        let user_id = self.find_user_id(user_id).await?;
+        let session_id = Uuid::parse_str(&session_id)
+            .map_err(|_| ApplicationError::InvalidSessionId("Invalid UUID format".to_string()))?;
+        
+        self.session_domain_service
+            .revoke(&user_id, &session_id)
+            .await
+            .map_err(|err| match err {
+                DomainError::SessionNotFound => ApplicationError::SessionNotFound,
+                other => ApplicationError::DomainError(other),
+            })?;
+            
+        Ok(SessionsRevokedResponse { revoked: 1 })
+    }
+
+    pub async fn revoke_all(&self, user_id: String) -> Result<SessionsRevokedResponse, ApplicationError> {
+        let user_id = self.find_user_id(user_id).await?;
+        
+        let sessions = self.session_domain_service
+            .list_sessions(&user_id, Utc::now())
+            .await
+            .map_err(ApplicationError::DomainError)?;
+        self.session_domain_service
+            .revoke_all(&user_id)
+            .await
+            .map_err(ApplicationError::DomainError)?;
+            
+        Ok(SessionsRevokedResponse { revoked: sessions.len() })
+    }
+
+    pub async fn end_current(&self, actor: &Actor) -> Result<SessionsRevokedResponse, ApplicationError> {
+        let session_id = actor.session_id().ok_or(ApplicationError::NoCurrentSession)?;
+        
+        self.session_domain_service
+            .revoke(actor.user_id(), session_id)
+            .await
+            .map_err(|err| match err {
+                DomainError::SessionNotFound => ApplicationError::SessionNotFound,
+                other => ApplicationError::DomainError(other),
+            })?;
+            
+        Ok(SessionsRevokedResponse { revoked: 1 })
+    }
+
+    async fn find_user_id(&self, user_id: String) -> Result<UserId, ApplicationError> {
+        let user_id = UserId::from_string(user_id)
+            .map_err(|err| ApplicationError::InvalidUserId(err.to_string()))?;
+        
+        self.user_repository
+            .find_by_id(&user_id)
+            .await
+            .map_err(ApplicationError::DomainError)?
+            .ok_or(ApplicationError::UserNotFound)?;
+            
+        Ok(user_id)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApplicationError {
+    #[error("Invalid user ID: {0}")]
+    InvalidUserId(String),
+    
+    #[error("Invalid session ID: {0}")]
+    InvalidSessionId(String),
+    
+    #[error("Sessions can only be started with a bearer token")]
+    BearerTokenRequired,
+    
+    #[error("Request is not authenticated with a session")]
+    NoCurrentSession,
+    
+    #[error("User not found")]
+    UserNotFound,
+    
+    #[error("Session not found")]
+    SessionNotFound,
+    
+    #[error("Domain error: {0}")]
+    DomainError(#[from] DomainError),
+}
+
+impl From<ApplicationError> for String {
+    fn from(error: ApplicationError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{AuthLevel, Email, Scope, User};
+    use crate::infrastructure::{InMemorySessionStore, InMemoryUserRepository, Sha256SecretHasher};
+
+    async fn setup() -> (ManageSessionsUseCase<InMemoryUserRepository, InMemorySessionStore, Sha256SecretHasher>, User) {
+        let user_repository = InMemoryUserRepository::new();
+        let email = Email::new("owner@example.com".to_string()).unwrap();
+        let user = User::new(email, "Owner".to_string()).unwrap();
+        user_repository.save(&user).await.unwrap();
+
+        let use_case = ManageSessionsUseCase::new(
+            user_repository,
+            InMemorySessionStore::new(),
+            Sha256SecretHasher::new(),
+            SessionPolicy::default(),
+        );
+        (use_case, user)
+    }
+
+    #[tokio::test]
+    async fn test_start_list_and_revoke_all() {
+        let (use_case, user) = setup().await;
+        let actor = Actor::from_user(&user).with_auth_level(AuthLevel::MultiFactor);
+
+        let created = use_case.start(&actor, SessionMetadata::default()).await.unwrap();
+        use_case.start(&actor, SessionMetadata::default()).await.unwrap();
+        assert_eq!(created.session.auth_level, "multi_factor");
+        assert!(!created.session_token.is_empty());
+
+        let session_actor = Actor::from_user(&user).with_session(Uuid::parse_str(&created.session.id).unwrap());
+        let listed = use_case.list(&session_actor, user.id().to_string()).await.unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed.iter().filter(|session| session.current).count(), 1);
+
+        let revoked = use_case.revoke_all(user.id().to_string()).await.unwrap();
+        assert_eq!(revoked.revoked, 2);
+        assert!(use_case.list(&actor, user.id().to_string()).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_only_bearer_token_starts_session() {
+        let (use_case, user) = setup().await;
+
+        let api_key_actor = Actor::from_user(&user).with_scopes(vec![Scope::UsersRead]);
+        let result = use_case.start(&api_key_actor, SessionMetadata::default()).await;
+
+        assert!(matches!(result, Err(ApplicationError::BearerTokenRequired)));
+    }
+}
\ No newline at end of file
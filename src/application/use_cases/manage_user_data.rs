@@ -0,0 +1,303 @@
+use chrono::Utc;
+use uuid::Uuid;
+use crate::domain::{
+    Actor, ApiKeyRepository, AuditStore, ErasureReceipt, ErasureReceiptRepository, ExternalIdentityRepository, MfaRepository,
+    OutboxMessage, OutboxPayload, OutboxRepository, SessionPolicy, SessionStore, User, UserRepository, UserId,
+    DomainError,
+};
+use crate::application::dto::{ErasureReceiptResponse, SessionResponse, UserDataExport, UserResponse};
+
+// Права субъекта данных: выгрузка всего, что о нем хранится, и стирание
+#[derive(Clone)]
+pub struct ManageUserDataUseCase<R, K, S, M, I, E, A>
+where
+    R: UserRepository + OutboxRepository,
+    K: ApiKeyRepository,
+    S: SessionStore,
+    M: MfaRepository,
+    I: ExternalIdentityRepository,
+    E: ErasureReceiptRepository,
+    A: AuditStore,
+{
+    user_repository: R,
+    api_key_repository: K,
+    session_store: S,
+    mfa_repository: M,
+    identity_repository: I,
+    receipt_repository: E,
+    audit_store: A,
+    session_policy: SessionPolicy,
+}
+
+impl<R, K, S, M, I, E, A> ManageUserDataUseCase<R, K, S, M, I, E, A>
+where
+    R: UserRepository + OutboxRepository,
+    K: ApiKeyRepository,
+    S: SessionStore,
+    M: MfaRepository,
+    I: ExternalIdentityRepository,
+    E: ErasureReceiptRepository,
+    A: AuditStore,
+{
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        user_repository: R,
+        api_key_repository: K,
+        session_store: S,
+        mfa_repository: M,
+        identity_repository: I,
+        receipt_repository: E,
+        audit_store: A,
+        session_policy: SessionPolicy,
+    ) -> Self {
+        Self {
+            user_repository,
+            api_key_repository,
+            session_store,
+            mfa_repository,
+            identity_repository,
+            receipt_repository,
+            audit_store,
+            session_policy,
+        }
+    }
+
+    pub async fn export(&self, user_id: String) -> Result<UserDataExport, ApplicationError> {
+        let user = self.find_user(user_id).await?;
+        let user_id = user.id();
+
+        let audit = self.audit_store.list_user_audit_events(user_id, 0, usize::MAX).await?;
+        let notifications = self.user_repository.list_user_outbox_messages(user_id).await?;
+        let sessions = self.session_store.list_sessions(user_id).await?;
+        let api_keys = self.api_key_repository.list_api_keys(user_id).await?;
+        let mfa = self.mfa_repository.find_mfa_enrollment(user_id).await?;
+        let identities = self.identity_repository.list_identities(user_id).await?;
+        let erasure_receipt = self.receipt_repository.find_erasure_receipt(user_id).await?;
+
+        Ok(UserDataExport {
+            generated_at: Utc::now(),
+            profile: UserResponse::from(user),
+            audit: audit.events.into_iter().map(Into::into).collect(),
+            notifications: notifications.into_iter().map(Into::into).collect(),
+            sessions: sessions
+                .iter()
+                .map(|session| SessionResponse::new(session, &self.session_policy, false))
+                .collect(),
+            api_keys: api_keys.into_iter().map(Into::into).collect(),
+            mfa: mfa.into(),
+            identities: identities.into_iter().map(Into::into).collect(),
+            erasure_receipt: erasure_receipt.map(Into::into),
+        })
+    }
+
+    /// Необратимо обезличивает пользователя. Запись и ее идентификатор
+    /// остаются, поэтому ссылки из ключей, сессий и outbox не ломаются;
+    /// все способы входа от имени пользователя отзываются.
+    pub async fn erase(&self, actor: &Actor, user_id: String) -> Result<ErasureReceiptResponse, ApplicationError> {
+        let mut user = self.find_user(user_id).await?;
+        let now = Utc::now();
+
+        user.erase(now).map_err(|_| ApplicationError::AlreadyErased)?;
+        let messages = vec![OutboxMessage::new(OutboxPayload::UserErased { user_id: user.id().clone() })];
+        self.user_repository.save_with_outbox(&user, messages).await?;
+        let user_id = user.id();
+
+        let cancelled_outbox_emails = self.user_repository.cancel_user_emails(user_id).await?;
+
+        let mut revoked_api_keys = 0;
+        for mut api_key in self.api_key_repository.list_api_keys(user_id).await? {
+            if api_key.revoked_at().is_none() {
+                api_key.revoke(now)?;
+                self.api_key_repository.save_api_key(&api_key).await?;
+                revoked_api_keys += 1;
+            }
+        }
+
+        let revoked_sessions = self.session_store.list_sessions(user_id).await?.len();
+        self.session_store.delete_user_sessions(user_id).await?;
+
+        let mfa_removed = self.mfa_repository.find_mfa_enrollment(user_id).await?.is_some();
+        self.mfa_repository.delete_mfa_enrollment(user_id).await?;
+
+        let unlinked_identities = self.identity_repository.list_identities(user_id).await?.len();
+        self.identity_repository.delete_user_identities(user_id).await?;
+
+        // Последним шагом, чтобы стереть и события, записанные самим стиранием
+        let redacted_audit_events = self.audit_store.erase_user_audit_data(user_id).await?;
+
+        let receipt = ErasureReceipt {
+            id: Uuid::new_v4(),
+            user_id: user_id.clone(),
+            requested_by: actor.user_id().clone(),
+            erased_at: now,
+            erased_fields: vec!["email".to_string(), "name".to_string()],
+            revoked_api_keys,
+            revoked_sessions,
+            unlinked_identities,
+            mfa_removed,
+            cancelled_outbox_emails,
+            redacted_audit_events,
+        };
+        self.receipt_repository.save_erasure_receipt(&receipt).await?;
+
+        Ok(receipt.into())
+    }
+
+    async fn find_user(&self, user_id: String) -> Result<User, ApplicationError> {
+        let user_id = UserId::from_string(user_id)
+            .map_err(|err| ApplicationError::InvalidUserId(err.to_string()))?;
+
+        self.user_repository
+            .find_by_id(&user_id)
+            .await?
+            .ok_or(ApplicationError::UserNotFound)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApplicationError {
+    #[error("Invalid user ID: {0}")]
+    InvalidUserId(String),
+
+    #[error("User not found")]
+    UserNotFound,
+
+    #[error("User is already erased")]
+    AlreadyErased,
+
+    #[error("Domain error: {0}")]
+    DomainError(#[from] DomainError),
+}
+
+impl From<ApplicationError> for String {
+    fn from(error: ApplicationError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{
+        ApiKey, Email, ExternalIdentity, MfaEnrollment, Role, Scope, Session, SessionMetadata, AuthLevel,
+    };
+    use crate::infrastructure::{
+        AuditStoreBackend, AuditTrail, InMemoryApiKeyRepository, InMemoryAuditStore, InMemoryErasureReceiptRepository,
+        InMemoryExternalIdentityRepository, InMemoryMfaRepository, InMemorySessionStore, InMemoryUserRepository,
+    };
+
+    type TestUseCase = ManageUserDataUseCase<
+        InMemoryUserRepository,
+        InMemoryApiKeyRepository,
+        InMemorySessionStore,
+        InMemoryMfaRepository,
+        InMemoryExternalIdentityRepository,
+        InMemoryErasureReceiptRepository,
+        AuditStoreBackend,
+    >;
+
+    async fn setup() -> (TestUseCase, InMemoryUserRepository, User) {
+        let audit_store = AuditStoreBackend::InMemory(InMemoryAuditStore::new());
+        let user_repository = InMemoryUserRepository::new().with_audit_trail(AuditTrail::new(audit_store.clone()));
+        let api_key_repository = InMemoryApiKeyRepository::new();
+        let session_store = InMemorySessionStore::new();
+        let mfa_repository = InMemoryMfaRepository::new();
+        let identity_repository = InMemoryExternalIdentityRepository::new();
+
+        let user = User::new(Email::new("jane@example.com".to_string()).unwrap(), "Jane Doe".to_string()).unwrap();
+        let messages = vec![OutboxMessage::new(OutboxPayload::WelcomeEmail { user_id: user.id().clone() })];
+        user_repository.save_with_outbox(&user, messages).await.unwrap();
+
+        let now = Utc::now();
+        let api_key = ApiKey::new(
+            user.id().clone(),
+            "ci".to_string(),
+            "rk_test".to_string(),
+            "hash".to_string(),
+            vec![Scope::UsersRead],
+            now + chrono::Duration::days(30),
+        )
+        .unwrap();
+        api_key_repository.save_api_key(&api_key).await.unwrap();
+        let session = Session::new(
+            user.id().clone(),
+            "session-hash".to_string(),
+            "csrf-hash".to_string(),
+            AuthLevel::SingleFactor,
+            SessionMetadata::default(),
+            now,
+            &SessionPolicy::default(),
+        );
+        session_store.save_session(&session).await.unwrap();
+        mfa_repository.save_mfa_enrollment(&MfaEnrollment::new(user.id().clone(), "sealed".to_string())).await.unwrap();
+        identity_repository
+            .save_identity(&ExternalIdentity::new("corp".to_string(), "sub-1".to_string(), user.id().clone(), now))
+            .await
+            .unwrap();
+
+        let use_case = ManageUserDataUseCase::new(
+            user_repository.clone(),
+            api_key_repository,
+            session_store,
+            mfa_repository,
+            identity_repository,
+            InMemoryErasureReceiptRepository::new(),
+            audit_store,
+            SessionPolicy::default(),
+        );
+        (use_case, user_repository, user)
+    }
+
+    #[tokio::test]
+    async fn test_export_collects_user_data() {
+        let (use_case, _, user) = setup().await;
+
+        let export = use_case.export(user.id().to_string()).await.unwrap();
+
+        assert_eq!(export.profile.email, "jane@example.com");
+        assert_eq!(export.audit.len(), 1);
+        assert_eq!(export.notifications.len(), 1);
+        assert_eq!(export.sessions.len(), 1);
+        assert_eq!(export.api_keys.len(), 1);
+        assert_eq!(export.identities.len(), 1);
+        assert!(export.erasure_receipt.is_none());
+        let names: Vec<String> = export.files().unwrap().into_iter().map(|(name, _)| name).collect();
+        assert!(names.contains(&"manifest.json".to_string()));
+        assert!(names.contains(&"api_keys.json".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_erase_anonymizes_and_revokes_access() {
+        let (use_case, user_repository, user) = setup().await;
+        let admin = Actor::new(UserId::new(), Role::Admin);
+
+        let receipt = use_case.erase(&admin, user.id().to_string()).await.unwrap();
+
+        assert_eq!(receipt.user_id, user.id().to_string());
+        assert_eq!(receipt.requested_by, admin.user_id().to_string());
+        assert_eq!(receipt.revoked_api_keys, 1);
+        assert_eq!(receipt.revoked_sessions, 1);
+        assert_eq!(receipt.unlinked_identities, 1);
+        assert!(receipt.mfa_removed);
+        assert_eq!(receipt.cancelled_outbox_emails, 1);
+        assert_eq!(receipt.redacted_audit_events, 2);
+
+        let erased = user_repository.find_by_id(user.id()).await.unwrap().unwrap();
+        assert!(erased.is_erased());
+        assert!(user_repository.find_by_email(user.email()).await.unwrap().is_none());
+
+        // Выгрузка после стирания содержит только надгробные значения и квитанцию
+        let export = use_case.export(user.id().to_string()).await.unwrap();
+        let archive = serde_json::to_string(&export).unwrap();
+        assert!(!archive.contains("jane@example.com"));
+        assert!(!archive.contains("Jane Doe"));
+        assert!(export.erasure_receipt.is_some());
+        assert_eq!(export.audit[0].action, "user.erased");
+        assert!(export.api_keys.iter().all(|api_key| api_key.revoked_at.is_some()));
+
+        assert!(matches!(
+            use_case.erase(&admin, user.id().to_string()).await,
+            Err(ApplicationError::AlreadyErased)
+        ));
+    }
+}
\ No newline at end of file
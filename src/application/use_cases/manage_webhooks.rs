@@ -0,0 +1,211 @@
+use chrono::Utc;
+use uuid::Uuid;
+use crate::domain::{SecretCipher, WebhookRepository, WebhookSubscription, DomainError};
+use crate::application::dto::{CreateWebhookRequest, WebhookDeliveryResponse, WebhookResponse};
+
+const MIN_SECRET_LENGTH: usize = 16;
+const DEFAULT_DELIVERY_LIMIT: usize = 50;
+const MAX_DELIVERY_LIMIT: usize = 200;
+
+#[derive(Clone)]
+pub struct ManageWebhooksUseCase<R: WebhookRepository, C: SecretCipher> {
+    webhook_repository: R,
+    secret_cipher: C,
+}
+
+impl<R: WebhookRepository, C: SecretCipher> ManageWebhooksUseCase<R, C> {
+    pub fn new(webhook_repository: R, secret_cipher: C) -> Self {
+        Self { webhook_repository, secret_cipher }
+    }
+
+    pub async fn create(&self, request: CreateWebhookRequest) -> Result<WebhookResponse, ApplicationError> {
+        if request.secret.chars().count() < MIN_SECRET_LENGTH {
+            return Err(ApplicationError::InvalidWebhook(format!(
+                "Webhook secret must be at least {} characters",
+                MIN_SECRET_LENGTH
+            )));
+        }
+
+        // Секрет привязан к подписке: шифротекст нельзя перенести на другую
+        let id = Uuid::new_v4();
+        let secret = self.secret_cipher
+            .encrypt(request.secret.as_bytes(), &id.to_string())
+            .map_err(ApplicationError::DomainError)?;
+        let webhook = WebhookSubscription::new(id, request.url, secret, request.events)
+            .map_err(|err| ApplicationError::InvalidWebhook(err.to_string()))?;
+
+        self.webhook_repository
+            .save_webhook(&webhook)
+            .await
+            .map_err(ApplicationError::DomainError)?;
+
+        Ok(WebhookResponse::from(webhook))
+    }
+
+    pub async fn list(&self) -> Result<Vec<WebhookResponse>, ApplicationError> {
+        let webhooks = self.webhook_repository
+            .list_webhooks()
+            .await
+            .map_err(ApplicationError::DomainError)?;
+
+        Ok(webhooks.into_iter().map(WebhookResponse::from).collect())
+    }
+
+    pub async fn get(&self, webhook_id: String) -> Result<WebhookResponse, ApplicationError> {
+        let webhook = self.find_webhook(&webhook_id).await?;
+        Ok(WebhookResponse::from(webhook))
+    }
+
+    pub async fn delete(&self, webhook_id: String) -> Result<(), ApplicationError> {
+        let webhook = self.find_webhook(&webhook_id).await?;
+        self.webhook_repository
+            .delete_webhook(webhook.id())
+            .await
+            .map_err(ApplicationError::DomainError)
+    }
+
+    pub async fn enable(&self, webhook_id: String) -> Result<WebhookResponse, ApplicationError> {
+        let mut webhook = self.find_webhook(&webhook_id).await?;
+        webhook.enable();
+        self.webhook_repository
+            .save_webhook(&webhook)
+            .await
+            .map_err(ApplicationError::DomainError)?;
+
+        Ok(WebhookResponse::from(webhook))
+    }
+
+    pub async fn list_deliveries(&self, webhook_id: String, limit: Option<usize>) -> Result<Vec<WebhookDeliveryResponse>, ApplicationError> {
+        let webhook = self.find_webhook(&webhook_id).await?;
+        let limit = limit.unwrap_or(DEFAULT_DELIVERY_LIMIT).clamp(1, MAX_DELIVERY_LIMIT);
+
+        let deliveries = self.webhook_repository
+            .list_deliveries(webhook.id(), limit)
+            .await
+            .map_err(ApplicationError::DomainError)?;
+
+        Ok(deliveries.into_iter().map(WebhookDeliveryResponse::from).collect())
+    }
+
+    // Ставит доставку в очередь заново; отправит ее фоновый диспетчер
+    pub async fn redeliver(&self, webhook_id: String, delivery_id: String) -> Result<WebhookDeliveryResponse, ApplicationError> {
+        let webhook = self.find_webhook(&webhook_id).await?;
+        if !webhook.is_active() {
+            return Err(ApplicationError::WebhookDisabled);
+        }
+
+        let delivery_id = Self::parse_id(&delivery_id)?;
+        let mut delivery = self.webhook_repository
+            .find_delivery(&delivery_id)
+            .await
+            .map_err(ApplicationError::DomainError)?
+            .filter(|delivery| delivery.webhook_id() == webhook.id())
+            .ok_or(ApplicationError::DeliveryNotFound)?;
+
+        delivery.redeliver(Utc::now());
+        self.webhook_repository
+            .save_delivery(&delivery)
+            .await
+            .map_err(ApplicationError::DomainError)?;
+
+        Ok(WebhookDeliveryResponse::from(delivery))
+    }
+
+    async fn find_webhook(&self, webhook_id: &str) -> Result<WebhookSubscription, ApplicationError> {
+        let webhook_id = Self::parse_id(webhook_id)?;
+        self.webhook_repository
+            .find_webhook(&webhook_id)
+            .await
+            .map_err(ApplicationError::DomainError)?
+            .ok_or(ApplicationError::WebhookNotFound)
+    }
+
+    fn parse_id(id: &str) -> Result<Uuid, ApplicationError> {
+        Uuid::parse_str(id)
+            .map_err(|_| ApplicationError::InvalidId("Invalid UUID format".to_string()))
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApplicationError {
+    #[error("Invalid ID: {0}")]
+    InvalidId(String),
+
+    #[error("Invalid webhook: {0}")]
+    InvalidWebhook(String),
+
+    #[error("Webhook not found")]
+    WebhookNotFound,
+
+    #[error("Webhook delivery not found")]
+    DeliveryNotFound,
+
+    #[error("Webhook is disabled, enable it before redelivering")]
+    WebhookDisabled,
+
+    #[error("Domain error: {0}")]
+    DomainError(#[from] DomainError),
+}
+
+impl From<ApplicationError> for String {
+    fn from(error: ApplicationError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{WebhookDelivery, WebhookDeliveryStatus};
+    use crate::infrastructure::{AesGcmSecretCipher, InMemoryWebhookRepository};
+
+    fn use_case() -> (ManageWebhooksUseCase<InMemoryWebhookRepository, AesGcmSecretCipher>, InMemoryWebhookRepository) {
+        let repository = InMemoryWebhookRepository::new();
+        (ManageWebhooksUseCase::new(repository.clone(), AesGcmSecretCipher::new(&[7u8; 32])), repository)
+    }
+
+    fn request(secret: &str, events: Vec<&str>) -> CreateWebhookRequest {
+        CreateWebhookRequest {
+            url: "https://hooks.example.com/users".to_string(),
+            secret: secret.to_string(),
+            events: events.into_iter().map(str::to_string).collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_encrypts_secret() {
+        let (use_case, repository) = use_case();
+
+        let created = use_case.create(request("0123456789abcdef-secret", vec!["user.created"])).await.unwrap();
+
+        let stored = repository.find_webhook(&Uuid::parse_str(&created.id).unwrap()).await.unwrap().unwrap();
+        assert_ne!(stored.secret(), "0123456789abcdef-secret");
+        let secret = use_case.secret_cipher.decrypt(stored.secret(), &created.id).unwrap();
+        assert_eq!(secret, b"0123456789abcdef-secret");
+        assert_eq!(created.events, vec!["user.created".to_string()]);
+        assert!(matches!(use_case.create(request("short", Vec::new())).await, Err(ApplicationError::InvalidWebhook(_))));
+        assert!(matches!(
+            use_case.create(request("0123456789abcdef-secret", vec!["user.unknown"])).await,
+            Err(ApplicationError::InvalidWebhook(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_redeliver_requeues_delivery_of_the_webhook() {
+        let (use_case, repository) = use_case();
+        let webhook = use_case.create(request("0123456789abcdef-secret", Vec::new())).await.unwrap();
+        let webhook_id = Uuid::parse_str(&webhook.id).unwrap();
+        let mut delivery = WebhookDelivery::new(webhook_id, "user.created".to_string(), "{}".to_string());
+        delivery.fail(Utc::now(), "connection refused".to_string());
+        repository.save_delivery(&delivery).await.unwrap();
+
+        let redelivered = use_case.redeliver(webhook.id.clone(), delivery.id().to_string()).await.unwrap();
+        assert_eq!(redelivered.status, WebhookDeliveryStatus::Pending.as_str());
+        assert_eq!(redelivered.attempts.len(), 1);
+
+        let other = use_case.create(request("0123456789abcdef-secret", Vec::new())).await.unwrap();
+        let result = use_case.redeliver(other.id, delivery.id().to_string()).await;
+        assert!(matches!(result, Err(ApplicationError::DeliveryNotFound)));
+        assert_eq!(use_case.list_deliveries(webhook.id, None).await.unwrap().len(), 1);
+    }
+}
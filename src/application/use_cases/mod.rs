@@ -1,9 +1,42 @@
+// У каждого сценария свой ApplicationError, к нему обращаются по пути модуля
+#![allow(ambiguous_glob_reexports)]
+
 pub mod create_user;
 pub mod get_user;
 pub mod update_user;
 pub mod delete_user;
+pub mod manage_outbox;
+pub mod change_user_roles;
+pub mod authenticate;
+pub mod manage_api_keys;
+pub mod manage_mfa;
+pub mod login_protection;
+pub mod manage_sessions;
+pub mod oidc_login;
+pub mod scim_provisioning;
+pub mod rate_limit;
+pub mod rotate_pii_keys;
+pub mod manage_user_data;
+pub mod read_audit_trail;
+pub mod manage_webhooks;
+pub mod list_users;
 
 pub use create_user::*;
 pub use get_user::*;
 pub use update_user::*;
-pub use delete_user::*;
\ No newline at end of file
+pub use delete_user::*;
+pub use manage_outbox::*;
+pub use change_user_roles::*;
+pub use authenticate::*;
+pub use manage_api_keys::*;
+pub use manage_mfa::*;
+pub use login_protection::*;
+pub use manage_sessions::*;
+pub use oidc_login::*;
+pub use scim_provisioning::*;
+pub use rate_limit::*;
+pub use rotate_pii_keys::*;
+pub use manage_user_data::*;
+pub use read_audit_trail::*;
+pub use manage_webhooks::*;
+pub use list_users::*;
\ No newline at end of file
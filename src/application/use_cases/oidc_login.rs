@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+use chrono::Utc;
+use crate::domain::{
+    AuthLevel, ExternalIdentityRepository, IdentityProvider, OidcLoginDomainService, OidcLoginStore, TokenService,
+    UserRepository, DomainError,
+};
+use crate::application::dto::{OidcAuthorizationResponse, OidcCallbackQuery, OidcLoginResponse, UserResponse};
+
+#[derive(Clone)]
+pub struct OidcLoginUseCase<R, I, L, T, P>
+where
+    R: UserRepository,
+    I: ExternalIdentityRepository,
+    L: OidcLoginStore,
+    T: TokenService,
+    P: IdentityProvider,
+{
+    oidc_login_domain_service: OidcLoginDomainService<R, I, L>,
+    token_service: T,
+    providers: HashMap<String, P>,
+}
+
+impl<R, I, L, T, P> OidcLoginUseCase<R, I, L, T, P>
+where
+    R: UserRepository,
+    I: ExternalIdentityRepository,
+    L: OidcLoginStore,
+    T: TokenService,
+    P: IdentityProvider,
+{
+    pub fn new(
+        user_repository: R,
+        identity_repository: I,
+        login_store: L,
+        token_service: T,
+        providers: impl IntoIterator<Item = (String, P)>,
+    ) -> Self {
+        Self {
+            oidc_login_domain_service: OidcLoginDomainService::new(user_repository, identity_repository, login_store),
+            token_service,
+            providers: providers.into_iter().collect(),
+        }
+    }
+
+    pub async fn begin(&self, provider_name: String) -> Result<OidcAuthorizationResponse, ApplicationError> {
+        let provider = self.provider(&provider_name)?;
+        
+        let request = self.oidc_login_domain_service
+            .start_login(&provider_name, Utc::now())
+            .await
+            .map_err(ApplicationError::DomainError)?;
+        let authorization_url = provider
+            .authorization_url(&request)
+            .await
+            .map_err(ApplicationError::DomainError)?;
+            
+        Ok(OidcAuthorizationResponse { authorization_url })
+    }
+
+    pub async fn complete(&self, provider_name: String, query: OidcCallbackQuery) -> Result<OidcLoginResponse, ApplicationError> {
+        let provider = self.provider(&provider_name)?;
+        
+        if let Some(error) = query.error {
+            let description = query.error_description.map(|description| format!(": {}", description)).unwrap_or_default();
+            return Err(ApplicationError::ProviderRejected(format!("{}{}", error, description)));
+        }
+        let (Some(code), Some(state)) = (query.code, query.state) else {
+            return Err(ApplicationError::InvalidCallback("code and state are required".to_string()));
+        };
+        
+        let now = Utc::now();
+        let request = self.oidc_login_domain_service
+            .resume_login(&provider_name, &state, now)
+            .await
+            .map_err(Self::login_error)?;
+        let claims = provider
+            .exchange_code(&code, &request)
+            .await
+            .map_err(Self::login_error)?;
+        let (user, created) = self.oidc_login_domain_service
+            .link_identity(&provider_name, &claims, now)
+            .await
+            .map_err(Self::login_error)?;
+        
+        // Внешний вход - один фактор; MFA подтверждается отдельно через mfa/challenge
+        let token = self.token_service
+            .issue(user.id(), AuthLevel::SingleFactor)
+            .map_err(ApplicationError::DomainError)?;
+            
+        Ok(OidcLoginResponse {
+            token,
+            user: UserResponse::from(user),
+            created,
+        })
+    }
+
+    fn provider(&self, provider_name: &str) -> Result<&P, ApplicationError> {
+        self.providers
+            .get(provider_name)
+            .ok_or_else(|| ApplicationError::UnknownProvider(provider_name.to_string()))
+    }
+
+    fn login_error(error: DomainError) -> ApplicationError {
+        match error {
+            DomainError::Unauthorized(message) => ApplicationError::LoginRejected(message),
+            other => ApplicationError::DomainError(other),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApplicationError {
+    #[error("Unknown identity provider: {0}")]
+    UnknownProvider(String),
+    
+    #[error("Identity provider rejected the login: {0}")]
+    ProviderRejected(String),
+    
+    #[error("Invalid callback: {0}")]
+    InvalidCallback(String),
+    
+    #[error("Login rejected: {0}")]
+    LoginRejected(String),
+    
+    #[error("Domain error: {0}")]
+    DomainError(#[from] DomainError),
+}
+
+impl From<ApplicationError> for String {
+    fn from(error: ApplicationError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::infrastructure::{
+        InMemoryExternalIdentityRepository, InMemoryOidcLoginStore, InMemoryUserRepository, JwtTokenService, OidcClient,
+    };
+    use crate::infrastructure::mock_oidc_issuer::{MockIdentity, MockOidcIssuer};
+
+    type TestUseCase = OidcLoginUseCase<
+        InMemoryUserRepository,
+        InMemoryExternalIdentityRepository,
+        InMemoryOidcLoginStore,
+        JwtTokenService,
+        OidcClient,
+    >;
+
+    fn use_case(issuer: &MockOidcIssuer) -> (TestUseCase, JwtTokenService) {
+        let token_service = JwtTokenService::new("test-secret", Duration::hours(1));
+        let use_case = OidcLoginUseCase::new(
+            InMemoryUserRepository::new(),
+            InMemoryExternalIdentityRepository::new(),
+            InMemoryOidcLoginStore::new(),
+            token_service.clone(),
+            [("mock".to_string(), OidcClient::new(issuer.provider_config("mock")))],
+        );
+        (use_case, token_service)
+    }
+
+    fn callback(code: String, state: &str) -> OidcCallbackQuery {
+        OidcCallbackQuery {
+            code: Some(code),
+            state: Some(state.to_string()),
+            error: None,
+            error_description: None,
+        }
+    }
+
+    fn state_of(authorization_url: &str) -> String {
+        reqwest::Url::parse(authorization_url)
+            .unwrap()
+            .query_pairs()
+            .find(|(name, _)| name == "state")
+            .map(|(_, value)| value.into_owned())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_login_provisions_user_and_issues_token() {
+        let issuer = MockOidcIssuer::start(MockIdentity::verified("sub-1", "oidc@example.com")).await;
+        let (use_case, token_service) = use_case(&issuer);
+
+        let authorization = use_case.begin("mock".to_string()).await.unwrap();
+        let code = issuer.authorize(&authorization.authorization_url).await;
+        let state = state_of(&authorization.authorization_url);
+        let login = use_case.complete("mock".to_string(), callback(code, &state)).await.unwrap();
+
+        assert!(login.created);
+        assert_eq!(login.user.email, "oidc@example.com");
+        let verified = token_service.verify(&login.token).unwrap();
+        assert_eq!(verified.user_id.to_string(), login.user.id);
+        assert_eq!(verified.auth_level, AuthLevel::SingleFactor);
+
+        // Повторный вход находит связанного пользователя
+        let authorization = use_case.begin("mock".to_string()).await.unwrap();
+        let code = issuer.authorize(&authorization.authorization_url).await;
+        let state = state_of(&authorization.authorization_url);
+        let second = use_case.complete("mock".to_string(), callback(code, &state)).await.unwrap();
+        assert!(!second.created);
+        assert_eq!(second.user.id, login.user.id);
+    }
+
+    #[tokio::test]
+    async fn test_state_cannot_be_replayed() {
+        let issuer = MockOidcIssuer::start(MockIdentity::verified("sub-1", "oidc@example.com")).await;
+        let (use_case, _) = use_case(&issuer);
+
+        let authorization = use_case.begin("mock".to_string()).await.unwrap();
+        let code = issuer.authorize(&authorization.authorization_url).await;
+        let state = state_of(&authorization.authorization_url);
+        use_case.complete("mock".to_string(), callback(code.clone(), &state)).await.unwrap();
+
+        let result = use_case.complete("mock".to_string(), callback(code, &state)).await;
+        assert!(matches!(result, Err(ApplicationError::LoginRejected(_))));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_provider_and_provider_error() {
+        let issuer = MockOidcIssuer::start(MockIdentity::verified("sub-1", "oidc@example.com")).await;
+        let (use_case, _) = use_case(&issuer);
+
+        assert!(matches!(
+            use_case.begin("other".to_string()).await,
+            Err(ApplicationError::UnknownProvider(_))
+        ));
+
+        let query = OidcCallbackQuery {
+            code: None,
+            state: None,
+            error: Some("access_denied".to_string()),
+            error_description: Some("user cancelled".to_string()),
+        };
+        let result = use_case.complete("mock".to_string(), query).await;
+        assert!(matches!(result, Err(ApplicationError::ProviderRejected(message)) if message.contains("access_denied")));
+    }
+}
\ No newline at end of file
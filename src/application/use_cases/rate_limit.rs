@@ -0,0 +1,53 @@
+use chrono::Utc;
+use crate::domain::{RateLimitDecision, RateLimitKey, RateLimitQuota, RateLimitStore, DomainError};
+
+pub struct RateLimitUseCase<S: RateLimitStore> {
+    rate_limit_store: S,
+}
+
+impl<S: RateLimitStore> RateLimitUseCase<S> {
+    pub fn new(rate_limit_store: S) -> Self {
+        Self { rate_limit_store }
+    }
+
+    // У каждой группы маршрутов свой бюджет для одного и того же ключа
+    pub async fn check(&self, group: &str, key: &RateLimitKey, quota: &RateLimitQuota) -> Result<RateLimitDecision, ApplicationError> {
+        let decision = self.rate_limit_store
+            .acquire(&format!("{}:{}", group, key.key()), quota, Utc::now())
+            .await?;
+            
+        Ok(decision)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApplicationError {
+    #[error("Domain error: {0}")]
+    DomainError(#[from] DomainError),
+}
+
+impl From<ApplicationError> for String {
+    fn from(error: ApplicationError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::domain::UserId;
+    use crate::infrastructure::InMemoryRateLimitStore;
+
+    #[tokio::test]
+    async fn test_groups_and_keys_have_separate_budgets() {
+        let use_case = RateLimitUseCase::new(InMemoryRateLimitStore::new());
+        let quota = RateLimitQuota::new(1, Duration::minutes(1)).unwrap();
+        let user = RateLimitKey::User(UserId::new());
+
+        assert!(use_case.check("users", &user, &quota).await.unwrap().allowed);
+        assert!(!use_case.check("users", &user, &quota).await.unwrap().allowed);
+        assert!(use_case.check("admin", &user, &quota).await.unwrap().allowed);
+        assert!(use_case.check("users", &RateLimitKey::User(UserId::new()), &quota).await.unwrap().allowed);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,58 @@
+use crate::domain::{AuditStore, UserId, DomainError};
+use crate::application::dto::{AuditChainResponse, AuditListQuery, AuditPageResponse};
+
+const DEFAULT_PAGE_SIZE: usize = 50;
+const MAX_PAGE_SIZE: usize = 200;
+
+#[derive(Clone)]
+pub struct ReadAuditTrailUseCase<A: AuditStore> {
+    audit_store: A,
+}
+
+impl<A: AuditStore> ReadAuditTrailUseCase<A> {
+    pub fn new(audit_store: A) -> Self {
+        Self { audit_store }
+    }
+
+    // Журнал остается доступным и после удаления пользователя, поэтому
+    // существование пользователя не проверяется
+    pub async fn list(&self, user_id: String, query: AuditListQuery) -> Result<AuditPageResponse, ApplicationError> {
+        let user_id = UserId::from_string(user_id).map_err(ApplicationError::InvalidUserId)?;
+        let offset = query.offset.unwrap_or(0);
+        let limit = query.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+        if limit == 0 || limit > MAX_PAGE_SIZE {
+            return Err(ApplicationError::InvalidLimit(MAX_PAGE_SIZE));
+        }
+
+        let page = self.audit_store.list_user_audit_events(&user_id, offset, limit).await?;
+
+        Ok(AuditPageResponse {
+            events: page.events.into_iter().map(Into::into).collect(),
+            total: page.total,
+            offset,
+            limit,
+        })
+    }
+
+    pub async fn verify(&self) -> Result<AuditChainResponse, ApplicationError> {
+        Ok(self.audit_store.verify_audit_chain().await?.into())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApplicationError {
+    #[error("Invalid user ID: {0}")]
+    InvalidUserId(String),
+
+    #[error("Limit must be between 1 and {0}")]
+    InvalidLimit(usize),
+
+    #[error("Domain error: {0}")]
+    DomainError(#[from] DomainError),
+}
+
+impl From<ApplicationError> for String {
+    fn from(error: ApplicationError) -> Self {
+        error.to_string()
+    }
+}
\ No newline at end of file
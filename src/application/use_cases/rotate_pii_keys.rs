@@ -0,0 +1,82 @@
+use crate::domain::{PiiKeyRotation, DomainError};
+
+const DEFAULT_BATCH_SIZE: usize = 100;
+
+// Перевод зашифрованных персональных данных на активный мастер-ключ.
+// Работает пачками, чтобы не держать блокировку хранилища надолго
+pub struct RotatePiiKeysUseCase<R: PiiKeyRotation> {
+    repository: R,
+    batch_size: usize,
+}
+
+impl<R: PiiKeyRotation> RotatePiiKeysUseCase<R> {
+    pub fn new(repository: R) -> Self {
+        Self {
+            repository,
+            batch_size: DEFAULT_BATCH_SIZE,
+        }
+    }
+
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    // Возвращает общее число переобернутых записей
+    pub async fn execute(&self) -> Result<usize, ApplicationError> {
+        let mut total = 0;
+        loop {
+            let rotated = self.repository.rotate_pii_keys(self.batch_size).await?;
+            if rotated == 0 {
+                return Ok(total);
+            }
+            total += rotated;
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApplicationError {
+    #[error("Domain error: {0}")]
+    DomainError(#[from] DomainError),
+}
+
+impl From<ApplicationError> for String {
+    fn from(error: ApplicationError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct MockRotation {
+        pending: AtomicUsize,
+        calls: AtomicUsize,
+    }
+
+    impl PiiKeyRotation for MockRotation {
+        async fn rotate_pii_keys(&self, limit: usize) -> Result<usize, DomainError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let pending = self.pending.load(Ordering::SeqCst);
+            let rotated = pending.min(limit);
+            self.pending.store(pending - rotated, Ordering::SeqCst);
+            Ok(rotated)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rotates_in_batches_until_done() {
+        let use_case = RotatePiiKeysUseCase::new(MockRotation {
+            pending: AtomicUsize::new(5),
+            calls: AtomicUsize::new(0),
+        })
+        .with_batch_size(2);
+
+        assert_eq!(use_case.execute().await.unwrap(), 5);
+        assert_eq!(use_case.repository.calls.load(Ordering::SeqCst), 4);
+        assert_eq!(use_case.execute().await.unwrap(), 0);
+    }
+}
\ No newline at end of file
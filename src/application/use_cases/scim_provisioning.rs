@@ -0,0 +1,474 @@
+use serde_json::Value;
+use crate::domain::{UserRepository, DomainError};
+use crate::application::{CreateUserUseCase, GetUserUseCase, UpdateUserUseCase, DeleteUserUseCase};
+use crate::application::use_cases::{create_user, get_user, update_user, delete_user};
+use crate::application::dto::{
+    ScimEmail, ScimListQuery, ScimListResponse, ScimName, ScimPatchOperation, ScimPatchRequest, ScimUser,
+    UserResponse, SCIM_PATCH_OP_SCHEMA,
+};
+
+// Ограничение размера страницы, объявленное в ServiceProviderConfig
+pub const SCIM_MAX_RESULTS: usize = 200;
+
+// Изменения, накопленные операциями PATCH и применяемые одним обновлением
+#[derive(Default)]
+struct ScimChanges {
+    email: Option<String>,
+    name: Option<String>,
+    given_name: Option<String>,
+    family_name: Option<String>,
+}
+
+impl ScimChanges {
+    fn full_name(&self) -> Option<String> {
+        self.name.clone().or_else(|| {
+            ScimName {
+                formatted: None,
+                given_name: self.given_name.clone(),
+                family_name: self.family_name.clone(),
+            }
+            .full_name()
+        })
+    }
+}
+
+/// SCIM 2.0 поверх существующих сценариев: `userName` и основной email -
+/// это email пользователя, `displayName`/`name` - его имя.
+pub struct ScimProvisioningUseCase<R: UserRepository> {
+    create_user_use_case: CreateUserUseCase<R>,
+    get_user_use_case: GetUserUseCase<R>,
+    update_user_use_case: UpdateUserUseCase<R>,
+    delete_user_use_case: DeleteUserUseCase<R>,
+    user_repository: R,
+}
+
+impl<R: UserRepository + Clone> ScimProvisioningUseCase<R> {
+    pub fn new(user_repository: R) -> Self {
+        Self {
+            create_user_use_case: CreateUserUseCase::new(user_repository.clone()),
+            get_user_use_case: GetUserUseCase::new(user_repository.clone()),
+            update_user_use_case: UpdateUserUseCase::new(user_repository.clone()),
+            delete_user_use_case: DeleteUserUseCase::new(user_repository.clone()),
+            user_repository,
+        }
+    }
+
+    pub async fn create(&self, resource: ScimUser) -> Result<ScimUser, ApplicationError> {
+        let (email, name) = Self::attributes_of(resource)?;
+        
+        let user = self.create_user_use_case
+            .execute(email, name)
+            .await
+            .map_err(ApplicationError::from)?;
+            
+        Ok(ScimUser::from(UserResponse::from(user)))
+    }
+
+    pub async fn get(&self, user_id: String) -> Result<ScimUser, ApplicationError> {
+        let user = self.get_user_use_case
+            .execute(user_id)
+            .await
+            .map_err(ApplicationError::from)?;
+            
+        Ok(ScimUser::from(user))
+    }
+
+    pub async fn replace(&self, user_id: String, resource: ScimUser) -> Result<ScimUser, ApplicationError> {
+        let (email, name) = Self::attributes_of(resource)?;
+        
+        let user = self.update_user_use_case
+            .execute(user_id, Some(email), Some(name))
+            .await
+            .map_err(ApplicationError::from)?;
+            
+        Ok(ScimUser::from(user))
+    }
+
+    pub async fn patch(&self, user_id: String, request: ScimPatchRequest) -> Result<ScimUser, ApplicationError> {
+        if !request.schemas.iter().any(|schema| schema == SCIM_PATCH_OP_SCHEMA) {
+            return Err(ApplicationError::InvalidSyntax(format!("PATCH request must use schema {}", SCIM_PATCH_OP_SCHEMA)));
+        }
+        
+        let mut changes = ScimChanges::default();
+        for operation in request.operations {
+            Self::apply_operation(&mut changes, operation)?;
+        }
+        
+        let name = changes.full_name();
+        if changes.email.is_none() && name.is_none() {
+            return self.get(user_id).await;
+        }
+        
+        let user = self.update_user_use_case
+            .execute(user_id, changes.email, name)
+            .await
+            .map_err(ApplicationError::from)?;
+            
+        Ok(ScimUser::from(user))
+    }
+
+    pub async fn delete(&self, user_id: String) -> Result<(), ApplicationError> {
+        self.delete_user_use_case
+            .execute(user_id)
+            .await
+            .map_err(ApplicationError::from)
+    }
+
+    pub async fn list(&self, query: ScimListQuery) -> Result<ScimListResponse<ScimUser>, ApplicationError> {
+        let users = match query.filter.as_deref() {
+            Some(filter) => {
+                let user_name = Self::parse_filter(filter)?;
+                match self.get_user_use_case.get_by_email(user_name).await {
+                    Ok(user) => vec![user],
+                    // Некорректный email не может совпасть ни с одним userName
+                    Err(get_user::ApplicationError::UserNotFound | get_user::ApplicationError::InvalidEmail(_)) => Vec::new(),
+                    Err(error) => return Err(ApplicationError::from(error)),
+                }
+            }
+            None => self.user_repository
+                .list_users()
+                .await
+                .map_err(ApplicationError::DomainError)?
+                .into_iter()
+                .map(UserResponse::from)
+                .collect(),
+        };
+        
+        // startIndex считается с единицы
+        let start_index = query.start_index.unwrap_or(1).max(1);
+        let count = query.count.unwrap_or(SCIM_MAX_RESULTS).min(SCIM_MAX_RESULTS);
+        let total_results = users.len();
+        let resources = users
+            .into_iter()
+            .skip(start_index - 1)
+            .take(count)
+            .map(ScimUser::from)
+            .collect();
+            
+        Ok(ScimListResponse::new(total_results, start_index, resources))
+    }
+
+    fn attributes_of(resource: ScimUser) -> Result<(String, String), ApplicationError> {
+        if !resource.active {
+            return Err(Self::deactivation_error());
+        }
+        
+        let name = resource.full_name().unwrap_or_else(|| resource.user_name.clone());
+        Ok((resource.user_name, name))
+    }
+
+    // Поддерживается только фильтр `userName eq "..."`
+    fn parse_filter(filter: &str) -> Result<String, ApplicationError> {
+        let invalid = || ApplicationError::InvalidFilter(format!("Unsupported filter: {}", filter));
+        
+        let (attribute, rest) = filter.trim().split_once(char::is_whitespace).ok_or_else(invalid)?;
+        let (operator, value) = rest.trim_start().split_once(char::is_whitespace).ok_or_else(invalid)?;
+        if !attribute.eq_ignore_ascii_case("userName") || !operator.eq_ignore_ascii_case("eq") {
+            return Err(invalid());
+        }
+        
+        // Строковый литерал фильтра совпадает с JSON-строкой, включая экранирование
+        serde_json::from_str::<String>(value.trim()).map_err(|_| invalid())
+    }
+
+    fn apply_operation(changes: &mut ScimChanges, operation: ScimPatchOperation) -> Result<(), ApplicationError> {
+        match operation.op.to_ascii_lowercase().as_str() {
+            "add" | "replace" => {}
+            "remove" => {
+                return Err(ApplicationError::Mutability(format!(
+                    "Attribute {} is required and cannot be removed",
+                    operation.path.as_deref().unwrap_or("")
+                )));
+            }
+            other => return Err(ApplicationError::InvalidSyntax(format!("Unknown PATCH operation: {}", other))),
+        }
+        
+        let value = operation
+            .value
+            .ok_or_else(|| ApplicationError::InvalidSyntax("PATCH operation requires a value".to_string()))?;
+        
+        match operation.path {
+            Some(path) => Self::apply_attribute(changes, &path, value),
+            // Без path значение - объект с атрибутами ресурса
+            None => {
+                let Value::Object(attributes) = value else {
+                    return Err(ApplicationError::InvalidValue("PATCH value without path must be an object".to_string()));
+                };
+                for (attribute, value) in attributes {
+                    Self::apply_attribute(changes, &attribute, value)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn apply_attribute(changes: &mut ScimChanges, path: &str, value: Value) -> Result<(), ApplicationError> {
+        let path = path.to_ascii_lowercase();
+        match path.as_str() {
+            "username" | "emails.value" => changes.email = Some(Self::string_value(&path, value)?),
+            "displayname" | "name.formatted" => changes.name = Some(Self::string_value(&path, value)?),
+            "name.givenname" => changes.given_name = Some(Self::string_value(&path, value)?),
+            "name.familyname" => changes.family_name = Some(Self::string_value(&path, value)?),
+            "name" => {
+                let name: ScimName = serde_json::from_value(value)
+                    .map_err(|err| ApplicationError::InvalidValue(format!("name: {}", err)))?;
+                changes.name = name.full_name();
+            }
+            "emails" => {
+                let emails: Vec<ScimEmail> = serde_json::from_value(value)
+                    .map_err(|err| ApplicationError::InvalidValue(format!("emails: {}", err)))?;
+                let email = emails
+                    .iter()
+                    .find(|email| email.primary)
+                    .or(emails.first())
+                    .ok_or_else(|| ApplicationError::Mutability("At least one email is required".to_string()))?;
+                changes.email = Some(email.value.clone());
+            }
+            // У пользователя один email, поэтому любой фильтр по emails относится к нему
+            path if path.starts_with("emails[") && path.ends_with("].value") => {
+                changes.email = Some(Self::string_value(path, value)?);
+            }
+            "active" => {
+                // Azure AD передает булевы значения строкой "False"
+                let active = match &value {
+                    Value::Bool(active) => *active,
+                    Value::String(active) if active.eq_ignore_ascii_case("true") => true,
+                    Value::String(active) if active.eq_ignore_ascii_case("false") => false,
+                    _ => return Err(ApplicationError::InvalidValue("active must be a boolean".to_string())),
+                };
+                if !active {
+                    return Err(Self::deactivation_error());
+                }
+            }
+            "schemas" => {}
+            _ => return Err(ApplicationError::InvalidPath(format!("Unsupported attribute path: {}", path))),
+        }
+        Ok(())
+    }
+
+    fn string_value(path: &str, value: Value) -> Result<String, ApplicationError> {
+        match value {
+            Value::String(value) => Ok(value),
+            _ => Err(ApplicationError::InvalidValue(format!("{} must be a string", path))),
+        }
+    }
+
+    // У сущности User нет статуса активности: отключение выполняется удалением
+    fn deactivation_error() -> ApplicationError {
+        ApplicationError::Mutability("Deactivation is not supported, delete the user instead".to_string())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ApplicationError {
+    #[error("Invalid value: {0}")]
+    InvalidValue(String),
+    
+    #[error("Invalid filter: {0}")]
+    InvalidFilter(String),
+    
+    #[error("Invalid path: {0}")]
+    InvalidPath(String),
+    
+    #[error("Invalid syntax: {0}")]
+    InvalidSyntax(String),
+    
+    #[error("Mutability violation: {0}")]
+    Mutability(String),
+    
+    #[error("User not found")]
+    UserNotFound,
+    
+    #[error("User already exists")]
+    UserAlreadyExists,
+    
+    #[error("Domain error: {0}")]
+    DomainError(DomainError),
+}
+
+impl From<DomainError> for ApplicationError {
+    fn from(error: DomainError) -> Self {
+        match error {
+            DomainError::UserNotFound => ApplicationError::UserNotFound,
+            DomainError::UserAlreadyExists => ApplicationError::UserAlreadyExists,
+            DomainError::InvalidEmail(message) | DomainError::InvalidUserData(message) => {
+                ApplicationError::InvalidValue(message)
+            }
+            other => ApplicationError::DomainError(other),
+        }
+    }
+}
+
+impl From<create_user::ApplicationError> for ApplicationError {
+    fn from(error: create_user::ApplicationError) -> Self {
+        match error {
+            create_user::ApplicationError::InvalidEmail(message) => ApplicationError::InvalidValue(message),
+            create_user::ApplicationError::DomainError(error) => ApplicationError::from(error),
+            create_user::ApplicationError::Unexpected(message) => {
+                ApplicationError::DomainError(DomainError::InvalidOperation(message))
+            }
+        }
+    }
+}
+
+impl From<get_user::ApplicationError> for ApplicationError {
+    fn from(error: get_user::ApplicationError) -> Self {
+        match error {
+            // Неизвестный формат id для SCIM-клиента равнозначен отсутствующему ресурсу
+            get_user::ApplicationError::InvalidUserId(_) | get_user::ApplicationError::UserNotFound => {
+                ApplicationError::UserNotFound
+            }
+            get_user::ApplicationError::InvalidEmail(message) => ApplicationError::InvalidValue(message),
+            get_user::ApplicationError::DomainError(error) => ApplicationError::from(error),
+        }
+    }
+}
+
+impl From<update_user::ApplicationError> for ApplicationError {
+    fn from(error: update_user::ApplicationError) -> Self {
+        match error {
+            update_user::ApplicationError::InvalidUserId(_) | update_user::ApplicationError::UserNotFound => {
+                ApplicationError::UserNotFound
+            }
+            update_user::ApplicationError::InvalidEmail(message) => ApplicationError::InvalidValue(message),
+            update_user::ApplicationError::DomainError(error) => ApplicationError::from(error),
+        }
+    }
+}
+
+impl From<delete_user::ApplicationError> for ApplicationError {
+    fn from(error: delete_user::ApplicationError) -> Self {
+        match error {
+            delete_user::ApplicationError::InvalidUserId(_) | delete_user::ApplicationError::UserNotFound => {
+                ApplicationError::UserNotFound
+            }
+            delete_user::ApplicationError::DomainError(error) => ApplicationError::from(error),
+        }
+    }
+}
+
+impl From<ApplicationError> for String {
+    fn from(error: ApplicationError) -> Self {
+        error.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use crate::infrastructure::InMemoryUserRepository;
+
+    fn resource(user_name: &str, display_name: &str) -> ScimUser {
+        serde_json::from_value(json!({
+            "schemas": ["urn:ietf:params:scim:schemas:core:2.0:User"],
+            "userName": user_name,
+            "displayName": display_name,
+        }))
+        .unwrap()
+    }
+
+    fn patch(operations: Value) -> ScimPatchRequest {
+        serde_json::from_value(json!({
+            "schemas": [SCIM_PATCH_OP_SCHEMA],
+            "Operations": operations,
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_create_get_and_filter() {
+        let use_case = ScimProvisioningUseCase::new(InMemoryUserRepository::new());
+
+        let created = use_case.create(resource("Jane@Example.com", "Jane Doe")).await.unwrap();
+        let id = created.id.clone().unwrap();
+        assert_eq!(created.user_name, "jane@example.com");
+        assert_eq!(created.display_name.as_deref(), Some("Jane Doe"));
+        assert_eq!(use_case.get(id.clone()).await.unwrap().user_name, "jane@example.com");
+
+        let duplicate = use_case.create(resource("jane@example.com", "Other")).await;
+        assert!(matches!(duplicate, Err(ApplicationError::UserAlreadyExists)));
+
+        let query = |filter: &str| ScimListQuery { filter: Some(filter.to_string()), start_index: None, count: None };
+        let found = use_case.list(query(r#"userName eq "JANE@example.com""#)).await.unwrap();
+        assert_eq!(found.total_results, 1);
+        assert_eq!(found.resources[0].id.as_deref(), Some(id.as_str()));
+        assert_eq!(use_case.list(query(r#"userName eq "nobody@example.com""#)).await.unwrap().total_results, 0);
+        assert!(matches!(
+            use_case.list(query(r#"displayName co "Jane""#)).await,
+            Err(ApplicationError::InvalidFilter(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_list_is_paginated() {
+        let use_case = ScimProvisioningUseCase::new(InMemoryUserRepository::new());
+        for index in 0..3 {
+            use_case.create(resource(&format!("user{}@example.com", index), "User")).await.unwrap();
+        }
+
+        let page = use_case
+            .list(ScimListQuery { filter: None, start_index: Some(2), count: Some(1) })
+            .await
+            .unwrap();
+
+        assert_eq!(page.total_results, 3);
+        assert_eq!(page.start_index, 2);
+        assert_eq!(page.items_per_page, 1);
+    }
+
+    #[tokio::test]
+    async fn test_replace_and_patch_update_user() {
+        let use_case = ScimProvisioningUseCase::new(InMemoryUserRepository::new());
+        let id = use_case.create(resource("jane@example.com", "Jane Doe")).await.unwrap().id.unwrap();
+
+        let replaced = use_case.replace(id.clone(), resource("jane.doe@example.com", "Jane D.")).await.unwrap();
+        assert_eq!(replaced.user_name, "jane.doe@example.com");
+
+        let patched = use_case
+            .patch(id.clone(), patch(json!([
+                {"op": "Replace", "path": "name.givenName", "value": "Janet"},
+                {"op": "Replace", "path": "name.familyName", "value": "Doe"},
+                {"op": "replace", "path": "emails[type eq \"work\"].value", "value": "janet@example.com"},
+            ])))
+            .await
+            .unwrap();
+        assert_eq!(patched.display_name.as_deref(), Some("Janet Doe"));
+        assert_eq!(patched.user_name, "janet@example.com");
+
+        let patched = use_case
+            .patch(id.clone(), patch(json!([{"op": "replace", "value": {"displayName": "J. Doe"}}])))
+            .await
+            .unwrap();
+        assert_eq!(patched.display_name.as_deref(), Some("J. Doe"));
+    }
+
+    #[tokio::test]
+    async fn test_patch_errors_follow_scim_types() {
+        let use_case = ScimProvisioningUseCase::new(InMemoryUserRepository::new());
+        let id = use_case.create(resource("jane@example.com", "Jane Doe")).await.unwrap().id.unwrap();
+
+        let result = use_case.patch(id.clone(), patch(json!([{"op": "replace", "path": "title", "value": "x"}]))).await;
+        assert!(matches!(result, Err(ApplicationError::InvalidPath(_))));
+
+        let result = use_case.patch(id.clone(), patch(json!([{"op": "replace", "path": "active", "value": "False"}]))).await;
+        assert!(matches!(result, Err(ApplicationError::Mutability(_))));
+
+        let result = use_case.patch(id.clone(), patch(json!([{"op": "remove", "path": "userName"}]))).await;
+        assert!(matches!(result, Err(ApplicationError::Mutability(_))));
+
+        let result = use_case.patch("not-a-uuid".to_string(), patch(json!([]))).await;
+        assert!(matches!(result, Err(ApplicationError::UserNotFound)));
+    }
+
+    #[tokio::test]
+    async fn test_delete_user() {
+        let use_case = ScimProvisioningUseCase::new(InMemoryUserRepository::new());
+        let id = use_case.create(resource("jane@example.com", "Jane Doe")).await.unwrap().id.unwrap();
+
+        use_case.delete(id.clone()).await.unwrap();
+
+        assert!(matches!(use_case.get(id.clone()).await, Err(ApplicationError::UserNotFound)));
+        assert!(matches!(use_case.delete(id).await, Err(ApplicationError::UserNotFound)));
+    }
+}
\ No newline at end of file
@@ -1,6 +1,7 @@
-use crate::domain::{UserDomainService, UserRepository, UserId, DomainError};
+use crate::domain::{UserDomainService, UserEventBus, UserRepository, UserId, Email, DomainError};
 use crate::application::dto::UserResponse;
 
+#[derive(Clone)]
 pub struct UpdateUserUseCase<R: UserRepository> {
     user_domain_service: UserDomainService<R>,
 }
@@ -12,6 +13,11 @@ impl<R: UserRepository> UpdateUserUseCase<R> {
         }
     }
 
+    pub fn with_event_bus(mut self, event_bus: UserEventBus) -> Self {
+        self.user_domain_service = self.user_domain_service.with_event_bus(event_bus);
+        self
+    }
+
     pub async fn execute(&self, user_id: String, email: Option<String>, name: Option<String>) -> Result<UserResponse, ApplicationError> {
         let user_id = UserId::from_string(user_id)
             .map_err(|err| ApplicationError::InvalidUserId(err.to_string()))?;
@@ -50,4 +56,15 @@ impl From<ApplicationError> for String {
     fn from(error: ApplicationError) -> Self {
         error.to_string()
     }
+}
+
+impl ApplicationError {
+    pub fn cause(&self) -> Option<DomainError> {
+        match self {
+            ApplicationError::InvalidUserId(message) => Some(DomainError::InvalidUserData(message.clone())),
+            ApplicationError::InvalidEmail(message) => Some(DomainError::InvalidEmail(message.clone())),
+            ApplicationError::UserNotFound => Some(DomainError::UserNotFound),
+            ApplicationError::DomainError(error) => Some(error.clone()),
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,152 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::domain::{UserId, Scope, DomainError};
+
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    id: Uuid,
+    user_id: UserId,
+    name: String,
+    prefix: String,
+    secret_hash: String,
+    scopes: Vec<Scope>,
+    expires_at: DateTime<Utc>,
+    last_used_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+    revoked_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    pub fn new(
+        user_id: UserId,
+        name: String,
+        prefix: String,
+        secret_hash: String,
+        scopes: Vec<Scope>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Self, DomainError> {
+        if name.trim().is_empty() {
+            return Err(DomainError::InvalidOperation("API key name cannot be empty".to_string()));
+        }
+
+        let mut scopes = scopes;
+        scopes.sort();
+        scopes.dedup();
+        if scopes.is_empty() {
+            return Err(DomainError::InvalidOperation("API key must have at least one scope".to_string()));
+        }
+
+        let now = Utc::now();
+        if expires_at <= now {
+            return Err(DomainError::InvalidOperation("API key expiry must be in the future".to_string()));
+        }
+
+        Ok(Self {
+            id: Uuid::new_v4(),
+            user_id,
+            name: name.trim().to_string(),
+            prefix,
+            secret_hash,
+            scopes,
+            expires_at,
+            last_used_at: None,
+            created_at: now,
+            revoked_at: None,
+        })
+    }
+
+    pub fn id(&self) -> &Uuid {
+        &self.id
+    }
+
+    pub fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn prefix(&self) -> &str {
+        &self.prefix
+    }
+
+    pub fn secret_hash(&self) -> &str {
+        &self.secret_hash
+    }
+
+    pub fn scopes(&self) -> &[Scope] {
+        &self.scopes
+    }
+
+    pub fn expires_at(&self) -> &DateTime<Utc> {
+        &self.expires_at
+    }
+
+    pub fn last_used_at(&self) -> Option<&DateTime<Utc>> {
+        self.last_used_at.as_ref()
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn revoked_at(&self) -> Option<&DateTime<Utc>> {
+        self.revoked_at.as_ref()
+    }
+
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.revoked_at.is_none() && self.expires_at > now
+    }
+
+    pub fn record_usage(&mut self, now: DateTime<Utc>) {
+        self.last_used_at = Some(now);
+    }
+
+    pub fn revoke(&mut self, now: DateTime<Utc>) -> Result<(), DomainError> {
+        if self.revoked_at.is_some() {
+            return Err(DomainError::InvalidOperation("API key is already revoked".to_string()));
+        }
+
+        self.revoked_at = Some(now);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn test_key(expires_at: DateTime<Utc>) -> Result<ApiKey, DomainError> {
+        ApiKey::new(
+            UserId::new(),
+            "ci".to_string(),
+            "uk_abcdefgh".to_string(),
+            "hash".to_string(),
+            vec![Scope::UsersWrite, Scope::UsersRead, Scope::UsersRead],
+            expires_at,
+        )
+    }
+
+    #[test]
+    fn test_new_key_is_active() {
+        let key = test_key(Utc::now() + Duration::days(1)).unwrap();
+
+        assert!(key.is_active(Utc::now()));
+        assert_eq!(key.scopes(), &[Scope::UsersRead, Scope::UsersWrite]);
+        assert!(key.last_used_at().is_none());
+    }
+
+    #[test]
+    fn test_expired_and_revoked_keys_are_inactive() {
+        assert!(test_key(Utc::now() - Duration::seconds(1)).is_err());
+
+        let mut key = test_key(Utc::now() + Duration::days(1)).unwrap();
+        assert!(!key.is_active(Utc::now() + Duration::days(2)));
+
+        key.revoke(Utc::now()).unwrap();
+        assert!(!key.is_active(Utc::now()));
+        assert!(key.revoke(Utc::now()).is_err());
+    }
+}
\ No newline at end of file
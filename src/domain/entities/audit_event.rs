@@ -0,0 +1,385 @@
+use chrono::{DateTime, Utc};
+use crate::domain::{ApiKey, MfaEnrollment, PiiCipher, User, UserId, DomainError};
+
+pub const REDACTED_VALUE: &str = "[REDACTED]";
+pub const ERASED_VALUE: &str = "[ERASED]";
+
+// Хеш, на который ссылается первое событие журнала
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    UserCreated,
+    UserUpdated,
+    UserRolesChanged,
+    UserErased,
+    UserDeleted,
+    ApiKeyCreated,
+    ApiKeyRevoked,
+    MfaEnrolled,
+    MfaActivated,
+    MfaUpdated,
+    MfaRemoved,
+}
+
+impl AuditAction {
+    pub fn parse(value: &str) -> Result<Self, DomainError> {
+        match value {
+            "user.created" => Ok(AuditAction::UserCreated),
+            "user.updated" => Ok(AuditAction::UserUpdated),
+            "user.roles_changed" => Ok(AuditAction::UserRolesChanged),
+            "user.erased" => Ok(AuditAction::UserErased),
+            "user.deleted" => Ok(AuditAction::UserDeleted),
+            "api_key.created" => Ok(AuditAction::ApiKeyCreated),
+            "api_key.revoked" => Ok(AuditAction::ApiKeyRevoked),
+            "mfa.enrolled" => Ok(AuditAction::MfaEnrolled),
+            "mfa.activated" => Ok(AuditAction::MfaActivated),
+            "mfa.updated" => Ok(AuditAction::MfaUpdated),
+            "mfa.removed" => Ok(AuditAction::MfaRemoved),
+            other => Err(DomainError::InvalidOperation(format!("Unknown audit action: {}", other))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditAction::UserCreated => "user.created",
+            AuditAction::UserUpdated => "user.updated",
+            AuditAction::UserRolesChanged => "user.roles_changed",
+            AuditAction::UserErased => "user.erased",
+            AuditAction::UserDeleted => "user.deleted",
+            AuditAction::ApiKeyCreated => "api_key.created",
+            AuditAction::ApiKeyRevoked => "api_key.revoked",
+            AuditAction::MfaEnrolled => "mfa.enrolled",
+            AuditAction::MfaActivated => "mfa.activated",
+            AuditAction::MfaUpdated => "mfa.updated",
+            AuditAction::MfaRemoved => "mfa.removed",
+        }
+    }
+
+    pub fn for_user(before: Option<&User>, after: Option<&User>) -> Option<Self> {
+        match (before, after) {
+            (None, Some(_)) => Some(AuditAction::UserCreated),
+            (Some(_), None) => Some(AuditAction::UserDeleted),
+            (Some(before), Some(after)) if after.is_erased() && !before.is_erased() => Some(AuditAction::UserErased),
+            (Some(before), Some(after)) if before.roles() != after.roles() => Some(AuditAction::UserRolesChanged),
+            (Some(_), Some(_)) => Some(AuditAction::UserUpdated),
+            (None, None) => None,
+        }
+    }
+
+    // Обновление `last_used_at` изменением ключа не считается и в журнал не попадает
+    pub fn for_api_key(before: Option<&ApiKey>, after: Option<&ApiKey>) -> Option<Self> {
+        match (before, after) {
+            (None, Some(_)) => Some(AuditAction::ApiKeyCreated),
+            (Some(before), Some(after)) if before.revoked_at().is_none() && after.revoked_at().is_some() => {
+                Some(AuditAction::ApiKeyRevoked)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn for_mfa(before: Option<&MfaEnrollment>, after: Option<&MfaEnrollment>) -> Option<Self> {
+        match (before, after) {
+            (None, Some(_)) => Some(AuditAction::MfaEnrolled),
+            (Some(_), None) => Some(AuditAction::MfaRemoved),
+            (Some(before), Some(after)) if !before.is_active() && after.is_active() => Some(AuditAction::MfaActivated),
+            (Some(_), Some(_)) => Some(AuditAction::MfaUpdated),
+            (None, None) => None,
+        }
+    }
+}
+
+// Значение поля для журнала. Секреты (хеши, зашифрованные ключи) сравниваются,
+// но не записываются; персональные данные записываются и стираются вместе с пользователем
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuditValue {
+    Plain(String),
+    Personal(String),
+    Secret(String),
+}
+
+pub trait Auditable {
+    fn audit_resource(&self) -> String;
+    fn audit_fields(&self) -> Vec<(&'static str, AuditValue)>;
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub before: Option<String>,
+    pub after: Option<String>,
+    pub personal: bool,
+    // Keyed-хеши персональных значений; переживают стирание самих значений
+    pub before_digest: Option<String>,
+    pub after_digest: Option<String>,
+}
+
+impl FieldChange {
+    pub fn diff<T: Auditable>(before: Option<&T>, after: Option<&T>) -> Vec<FieldChange> {
+        let before = before.map(Auditable::audit_fields).unwrap_or_default();
+        let after = after.map(Auditable::audit_fields).unwrap_or_default();
+        let lookup = |fields: &[(&'static str, AuditValue)], field: &str| {
+            fields.iter().find(|(name, _)| *name == field).map(|(_, value)| value.clone())
+        };
+
+        // У одного типа набор полей всегда одинаковый, отличаются только значения
+        let names: Vec<&'static str> = match after.is_empty() {
+            true => before.iter().map(|(name, _)| *name).collect(),
+            false => after.iter().map(|(name, _)| *name).collect(),
+        };
+        names
+            .into_iter()
+            .filter_map(|name| {
+                let (old, new) = (lookup(&before, name), lookup(&after, name));
+                if old == new {
+                    return None;
+                }
+                let personal = matches!(old, Some(AuditValue::Personal(_))) || matches!(new, Some(AuditValue::Personal(_)));
+                Some(FieldChange {
+                    field: name.to_string(),
+                    before: old.map(render),
+                    after: new.map(render),
+                    personal,
+                    before_digest: None,
+                    after_digest: None,
+                })
+            })
+            .collect()
+    }
+
+    pub fn erase(&mut self) -> bool {
+        if !self.personal {
+            return false;
+        }
+        let erased = |value: &mut Option<String>| {
+            if let Some(value) = value.as_mut().filter(|value| *value != ERASED_VALUE) {
+                *value = ERASED_VALUE.to_string();
+                true
+            } else {
+                false
+            }
+        };
+        let before = erased(&mut self.before);
+        let after = erased(&mut self.after);
+        before || after
+    }
+}
+
+fn render(value: AuditValue) -> String {
+    match value {
+        AuditValue::Plain(value) | AuditValue::Personal(value) => value,
+        AuditValue::Secret(_) => REDACTED_VALUE.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditRecord {
+    pub user_id: UserId,
+    pub resource: String,
+    pub action: AuditAction,
+    // `user:<id>`, `scim`, `anonymous` или `system` для фоновых задач
+    pub actor: String,
+    pub request_id: Option<String>,
+    pub client_ip: Option<String>,
+    pub occurred_at: DateTime<Utc>,
+    pub changes: Vec<FieldChange>,
+}
+
+impl AuditRecord {
+    /// Каноническое представление для хеш-цепочки: каждое значение с длиной,
+    /// чтобы границы полей нельзя было сдвинуть. Вместо персональных значений
+    /// в хеш входят их keyed-хеши, иначе стирание разорвало бы цепочку.
+    pub fn canonical(&self) -> String {
+        let mut parts = vec![
+            self.user_id.to_string(),
+            self.resource.clone(),
+            self.action.as_str().to_string(),
+            self.actor.clone(),
+            self.request_id.clone().unwrap_or_default(),
+            self.client_ip.clone().unwrap_or_default(),
+            self.occurred_at.to_rfc3339(),
+        ];
+        for change in &self.changes {
+            parts.push(change.field.clone());
+            parts.push(change.personal.to_string());
+            let (before, after) = match change.personal {
+                true => (&change.before_digest, &change.after_digest),
+                false => (&change.before, &change.after),
+            };
+            parts.push(before.clone().unwrap_or_default());
+            parts.push(after.clone().unwrap_or_default());
+        }
+
+        parts.iter().map(|part| format!("{}:{};", part.len(), part)).collect()
+    }
+
+    // Контекст keyed-хешей и шифрования персональных значений поля
+    pub fn personal_data_context(&self, field: &str) -> String {
+        format!("audit:{}:{}", self.user_id, field)
+    }
+
+    // Вызывается до вычисления хеша события
+    pub fn digest_personal_data(&mut self, cipher: &impl PiiCipher) {
+        let contexts: Vec<String> = self.changes.iter().map(|change| self.personal_data_context(&change.field)).collect();
+        for (change, context) in self.changes.iter_mut().zip(contexts).filter(|(change, _)| change.personal) {
+            change.before_digest = change.before.as_deref().map(|value| cipher.digest(&context, value));
+            change.after_digest = change.after.as_deref().map(|value| cipher.digest(&context, value));
+        }
+    }
+
+    // Нестертые персональные значения совпадают со своими keyed-хешами
+    pub fn personal_data_intact(&self, cipher: &impl PiiCipher) -> bool {
+        self.changes.iter().filter(|change| change.personal).all(|change| {
+            let context = self.personal_data_context(&change.field);
+            let intact = |value: &Option<String>, digest: &Option<String>| match (value.as_deref(), digest) {
+                (Some(ERASED_VALUE), digest) => digest.is_some(),
+                (Some(value), Some(digest)) => &cipher.digest(&context, value) == digest,
+                (None, None) => true,
+                _ => false,
+            };
+            intact(&change.before, &change.before_digest) && intact(&change.after, &change.after_digest)
+        })
+    }
+
+    pub fn erase_personal_data(&mut self) -> bool {
+        // Без короткого замыкания: стираются все персональные поля записи
+        self.changes.iter_mut().map(FieldChange::erase).filter(|erased| *erased).count() > 0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditEvent {
+    pub sequence: u64,
+    pub record: AuditRecord,
+    pub previous_hash: String,
+    pub hash: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuditPage {
+    pub events: Vec<AuditEvent>,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditChainStatus {
+    pub events: usize,
+    // Первое событие, хеш или ссылка которого не сходится
+    pub broken_at: Option<u64>,
+}
+
+impl AuditChainStatus {
+    pub fn is_valid(&self) -> bool {
+        self.broken_at.is_none()
+    }
+}
+
+impl Auditable for User {
+    fn audit_resource(&self) -> String {
+        "user".to_string()
+    }
+
+    fn audit_fields(&self) -> Vec<(&'static str, AuditValue)> {
+        let roles: Vec<&str> = self.roles().iter().map(|role| role.as_str()).collect();
+        vec![
+            ("email", AuditValue::Personal(self.email().as_str().to_string())),
+            ("name", AuditValue::Personal(self.name().to_string())),
+            ("roles", AuditValue::Plain(roles.join(","))),
+            ("erased_at", AuditValue::Plain(self.erased_at().map(DateTime::to_rfc3339).unwrap_or_default())),
+        ]
+    }
+}
+
+impl Auditable for ApiKey {
+    fn audit_resource(&self) -> String {
+        format!("api_key:{}", self.id())
+    }
+
+    fn audit_fields(&self) -> Vec<(&'static str, AuditValue)> {
+        let scopes: Vec<&str> = self.scopes().iter().map(|scope| scope.as_str()).collect();
+        vec![
+            ("name", AuditValue::Plain(self.name().to_string())),
+            ("prefix", AuditValue::Plain(self.prefix().to_string())),
+            ("secret_hash", AuditValue::Secret(self.secret_hash().to_string())),
+            ("scopes", AuditValue::Plain(scopes.join(","))),
+            ("expires_at", AuditValue::Plain(self.expires_at().to_rfc3339())),
+            ("revoked_at", AuditValue::Plain(self.revoked_at().map(DateTime::to_rfc3339).unwrap_or_default())),
+        ]
+    }
+}
+
+impl Auditable for MfaEnrollment {
+    fn audit_resource(&self) -> String {
+        "mfa".to_string()
+    }
+
+    fn audit_fields(&self) -> Vec<(&'static str, AuditValue)> {
+        vec![
+            ("secret", AuditValue::Secret(self.encrypted_secret().to_string())),
+            ("status", AuditValue::Plain(self.status().as_str().to_string())),
+            ("recovery_codes", AuditValue::Secret(self.recovery_code_hashes().join(","))),
+            ("recovery_codes_left", AuditValue::Plain(self.recovery_code_hashes().len().to_string())),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Email, Role};
+
+    fn user() -> User {
+        User::new(Email::new("alice@example.com".to_string()).unwrap(), "Alice".to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_diff_contains_only_changed_fields() {
+        let before = user();
+        let mut after = before.clone();
+        after.change_roles(vec![Role::Admin]).unwrap();
+
+        let changes = FieldChange::diff(Some(&before), Some(&after));
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "roles");
+        assert_eq!(changes[0].before.as_deref(), Some("member"));
+        assert_eq!(changes[0].after.as_deref(), Some("admin"));
+        assert_eq!(AuditAction::for_user(Some(&before), Some(&after)), Some(AuditAction::UserRolesChanged));
+    }
+
+    #[test]
+    fn test_secrets_are_redacted_but_changes_detected() {
+        let before = MfaEnrollment::new(UserId::new(), "sealed-1".to_string());
+        let after = MfaEnrollment::new(before.user_id().clone(), "sealed-2".to_string());
+
+        let changes = FieldChange::diff(Some(&before), Some(&after));
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].before.as_deref(), Some(REDACTED_VALUE));
+        assert_eq!(changes[0].after.as_deref(), Some(REDACTED_VALUE));
+    }
+
+    #[test]
+    fn test_erasing_personal_data_keeps_canonical_form() {
+        let user = user();
+        let mut record = AuditRecord {
+            user_id: user.id().clone(),
+            resource: user.audit_resource(),
+            action: AuditAction::UserCreated,
+            actor: "anonymous".to_string(),
+            request_id: Some("req-1".to_string()),
+            client_ip: None,
+            occurred_at: Utc::now(),
+            changes: FieldChange::diff(None, Some(&user)),
+        };
+        let canonical = record.canonical();
+
+        assert!(record.erase_personal_data());
+        assert!(!record.erase_personal_data());
+
+        assert_eq!(record.canonical(), canonical);
+        let email = record.changes.iter().find(|change| change.field == "email").unwrap();
+        assert_eq!(email.after.as_deref(), Some(ERASED_VALUE));
+        assert!(record.changes.iter().any(|change| change.field == "roles" && change.after.as_deref() == Some("member")));
+    }
+}
\ No newline at end of file
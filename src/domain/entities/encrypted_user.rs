@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use crate::domain::{UserId, Role};
+
+// Ключ данных записи, зашифрованный мастер-ключом `key_id`
+#[derive(Debug, Clone, PartialEq)]
+pub struct WrappedDataKey {
+    pub key_id: String,
+    pub ciphertext: String,
+}
+
+// Пользователь в виде, в котором он лежит в хранилище: email и имя
+// зашифрованы ключом данных записи, поиск по email - через слепой индекс
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncryptedUser {
+    pub id: UserId,
+    pub email_index: String,
+    pub email: String,
+    pub name: String,
+    pub data_key: WrappedDataKey,
+    pub roles: Vec<Role>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub erased_at: Option<DateTime<Utc>>,
+}
\ No newline at end of file
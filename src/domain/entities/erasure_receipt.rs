@@ -0,0 +1,20 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::domain::UserId;
+
+// Подтверждение стирания персональных данных. Само не содержит персональных
+// данных: только что и когда было удалено и по чьему запросу
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErasureReceipt {
+    pub id: Uuid,
+    pub user_id: UserId,
+    pub requested_by: UserId,
+    pub erased_at: DateTime<Utc>,
+    pub erased_fields: Vec<String>,
+    pub revoked_api_keys: usize,
+    pub revoked_sessions: usize,
+    pub unlinked_identities: usize,
+    pub mfa_removed: bool,
+    pub cancelled_outbox_emails: usize,
+    pub redacted_audit_events: usize,
+}
\ No newline at end of file
@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use crate::domain::UserId;
+
+// Связь учетной записи внешнего провайдера (issuer + sub) с пользователем
+#[derive(Debug, Clone)]
+pub struct ExternalIdentity {
+    provider: String,
+    subject: String,
+    user_id: UserId,
+    linked_at: DateTime<Utc>,
+    last_login_at: DateTime<Utc>,
+}
+
+impl ExternalIdentity {
+    pub fn new(provider: String, subject: String, user_id: UserId, now: DateTime<Utc>) -> Self {
+        Self {
+            provider,
+            subject,
+            user_id,
+            linked_at: now,
+            last_login_at: now,
+        }
+    }
+
+    pub fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    pub fn subject(&self) -> &str {
+        &self.subject
+    }
+
+    pub fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+
+    pub fn linked_at(&self) -> &DateTime<Utc> {
+        &self.linked_at
+    }
+
+    pub fn last_login_at(&self) -> &DateTime<Utc> {
+        &self.last_login_at
+    }
+
+    pub fn record_login(&mut self, now: DateTime<Utc>) {
+        self.last_login_at = now;
+    }
+}
\ No newline at end of file
@@ -0,0 +1,204 @@
+use std::net::IpAddr;
+use chrono::{DateTime, Duration, Utc};
+use crate::domain::{UserId, DomainError};
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AttemptSubject {
+    Account(UserId),
+    ClientIp(IpAddr),
+}
+
+impl AttemptSubject {
+    pub fn key(&self) -> String {
+        match self {
+            AttemptSubject::Account(user_id) => format!("account:{}", user_id),
+            AttemptSubject::ClientIp(ip) => format!("ip:{}", ip),
+        }
+    }
+
+    pub fn parse(key: &str) -> Result<Self, DomainError> {
+        let invalid = || DomainError::InvalidOperation(format!("Invalid attempt subject: {}", key));
+        match key.split_once(':').ok_or_else(invalid)? {
+            ("account", user_id) => UserId::from_string(user_id.to_string())
+                .map(AttemptSubject::Account)
+                .map_err(|_| invalid()),
+            ("ip", ip) => ip.parse().map(AttemptSubject::ClientIp).map_err(|_| invalid()),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LockoutPolicy {
+    pub account_threshold: u32,
+    pub ip_threshold: u32,
+    pub lockout_duration: Duration,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    // Счетчик обнуляется, если неудачных попыток не было дольше этого окна
+    pub failure_window: Duration,
+}
+
+impl Default for LockoutPolicy {
+    fn default() -> Self {
+        Self {
+            account_threshold: 5,
+            ip_threshold: 20,
+            lockout_duration: Duration::minutes(15),
+            base_delay: Duration::seconds(1),
+            max_delay: Duration::seconds(30),
+            failure_window: Duration::minutes(15),
+        }
+    }
+}
+
+impl LockoutPolicy {
+    pub fn threshold_for(&self, subject: &AttemptSubject) -> u32 {
+        match subject {
+            AttemptSubject::Account(_) => self.account_threshold,
+            AttemptSubject::ClientIp(_) => self.ip_threshold,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LoginAttempts {
+    subject: AttemptSubject,
+    failures: u32,
+    last_failure_at: Option<DateTime<Utc>>,
+    locked_until: Option<DateTime<Utc>>,
+}
+
+impl LoginAttempts {
+    pub fn new(subject: AttemptSubject) -> Self {
+        Self {
+            subject,
+            failures: 0,
+            last_failure_at: None,
+            locked_until: None,
+        }
+    }
+
+    pub fn from_existing(
+        subject: AttemptSubject,
+        failures: u32,
+        last_failure_at: Option<DateTime<Utc>>,
+        locked_until: Option<DateTime<Utc>>,
+    ) -> Self {
+        Self { subject, failures, last_failure_at, locked_until }
+    }
+
+    pub fn subject(&self) -> &AttemptSubject {
+        &self.subject
+    }
+
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    pub fn last_failure_at(&self) -> Option<&DateTime<Utc>> {
+        self.last_failure_at.as_ref()
+    }
+
+    pub fn locked_until(&self) -> Option<&DateTime<Utc>> {
+        self.locked_until.as_ref()
+    }
+
+    pub fn is_locked(&self, now: DateTime<Utc>) -> bool {
+        self.locked_until.is_some_and(|until| until > now)
+    }
+
+    /// Момент, раньше которого следующая попытка отклоняется: конец блокировки
+    /// или прогрессивная задержка `base_delay * 2^(failures - 1)`.
+    pub fn retry_at(&self, policy: &LockoutPolicy) -> Option<DateTime<Utc>> {
+        if let Some(until) = self.locked_until {
+            return Some(until);
+        }
+
+        let last_failure_at = self.last_failure_at?;
+        let factor = 2i32.saturating_pow(self.failures.saturating_sub(1));
+        let delay = (policy.base_delay * factor).min(policy.max_delay);
+        Some(last_failure_at + delay)
+    }
+
+    /// Учитывает неудачную попытку; возвращает время окончания блокировки,
+    /// если именно эта попытка привела к блокировке.
+    pub fn record_failure(&mut self, now: DateTime<Utc>, policy: &LockoutPolicy) -> Option<DateTime<Utc>> {
+        let lock_expired = self.locked_until.is_some_and(|until| until <= now);
+        let window_passed = self.last_failure_at.is_some_and(|last| now - last > policy.failure_window);
+        if lock_expired || window_passed {
+            self.reset();
+        }
+
+        self.failures += 1;
+        self.last_failure_at = Some(now);
+
+        if self.locked_until.is_none() && self.failures >= policy.threshold_for(&self.subject) {
+            let until = now + policy.lockout_duration;
+            self.locked_until = Some(until);
+            return Some(until);
+        }
+
+        None
+    }
+
+    pub fn reset(&mut self) {
+        self.failures = 0;
+        self.last_failure_at = None;
+        self.locked_until = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> LockoutPolicy {
+        LockoutPolicy {
+            account_threshold: 3,
+            ..LockoutPolicy::default()
+        }
+    }
+
+    #[test]
+    fn test_subject_key_round_trip() {
+        let account = AttemptSubject::Account(UserId::new());
+        let ip = AttemptSubject::ClientIp("10.0.0.1".parse().unwrap());
+
+        assert_eq!(AttemptSubject::parse(&account.key()).unwrap(), account);
+        assert_eq!(AttemptSubject::parse(&ip.key()).unwrap(), ip);
+        assert!(AttemptSubject::parse("session:1").is_err());
+    }
+
+    #[test]
+    fn test_progressive_delay() {
+        let policy = policy();
+        let now = Utc::now();
+        let mut attempts = LoginAttempts::new(AttemptSubject::Account(UserId::new()));
+
+        attempts.record_failure(now, &policy);
+        assert_eq!(attempts.retry_at(&policy), Some(now + Duration::seconds(1)));
+
+        attempts.record_failure(now, &policy);
+        assert_eq!(attempts.retry_at(&policy), Some(now + Duration::seconds(2)));
+    }
+
+    #[test]
+    fn test_lock_after_threshold_and_auto_unlock() {
+        let policy = policy();
+        let now = Utc::now();
+        let mut attempts = LoginAttempts::new(AttemptSubject::Account(UserId::new()));
+
+        assert!(attempts.record_failure(now, &policy).is_none());
+        assert!(attempts.record_failure(now, &policy).is_none());
+        let until = attempts.record_failure(now, &policy).unwrap();
+
+        assert_eq!(until, now + policy.lockout_duration);
+        assert!(attempts.is_locked(now));
+        assert!(!attempts.is_locked(until));
+
+        // После окончания блокировки счет начинается заново
+        assert!(attempts.record_failure(until + Duration::seconds(1), &policy).is_none());
+        assert_eq!(attempts.failures(), 1);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,141 @@
+use chrono::{DateTime, Utc};
+use crate::domain::{UserId, DomainError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MfaStatus {
+    // Секрет выдан, но пользователь еще не подтвердил его кодом
+    Pending,
+    Active,
+}
+
+impl MfaStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MfaStatus::Pending => "pending",
+            MfaStatus::Active => "active",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct MfaEnrollment {
+    user_id: UserId,
+    encrypted_secret: String,
+    status: MfaStatus,
+    recovery_code_hashes: Vec<String>,
+    last_used_step: Option<u64>,
+    created_at: DateTime<Utc>,
+    confirmed_at: Option<DateTime<Utc>>,
+}
+
+impl MfaEnrollment {
+    pub fn new(user_id: UserId, encrypted_secret: String) -> Self {
+        Self {
+            user_id,
+            encrypted_secret,
+            status: MfaStatus::Pending,
+            recovery_code_hashes: Vec::new(),
+            last_used_step: None,
+            created_at: Utc::now(),
+            confirmed_at: None,
+        }
+    }
+
+    pub fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+
+    pub fn encrypted_secret(&self) -> &str {
+        &self.encrypted_secret
+    }
+
+    pub fn status(&self) -> MfaStatus {
+        self.status
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status == MfaStatus::Active
+    }
+
+    pub fn recovery_code_hashes(&self) -> &[String] {
+        &self.recovery_code_hashes
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn confirmed_at(&self) -> Option<&DateTime<Utc>> {
+        self.confirmed_at.as_ref()
+    }
+
+    pub fn activate(&mut self, now: DateTime<Utc>, recovery_code_hashes: Vec<String>) -> Result<(), DomainError> {
+        if self.is_active() {
+            return Err(DomainError::InvalidOperation("MFA is already enabled".to_string()));
+        }
+
+        self.status = MfaStatus::Active;
+        self.confirmed_at = Some(now);
+        self.recovery_code_hashes = recovery_code_hashes;
+        Ok(())
+    }
+
+    /// Запоминает временной шаг принятого TOTP-кода: повторно тот же
+    /// или более ранний код не принимается.
+    pub fn accept_step(&mut self, step: u64) -> Result<(), DomainError> {
+        if self.last_used_step.is_some_and(|last| step <= last) {
+            return Err(DomainError::InvalidMfaCode);
+        }
+
+        self.last_used_step = Some(step);
+        Ok(())
+    }
+
+    pub fn consume_recovery_code(&mut self, index: usize) -> Result<(), DomainError> {
+        if index >= self.recovery_code_hashes.len() {
+            return Err(DomainError::InvalidMfaCode);
+        }
+
+        self.recovery_code_hashes.remove(index);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activate_once() {
+        let mut enrollment = MfaEnrollment::new(UserId::new(), "encrypted".to_string());
+        assert_eq!(enrollment.status(), MfaStatus::Pending);
+
+        enrollment.activate(Utc::now(), vec!["hash".to_string()]).unwrap();
+
+        assert!(enrollment.is_active());
+        assert_eq!(enrollment.recovery_code_hashes().len(), 1);
+        assert!(enrollment.activate(Utc::now(), Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_totp_step_cannot_be_replayed() {
+        let mut enrollment = MfaEnrollment::new(UserId::new(), "encrypted".to_string());
+
+        enrollment.accept_step(100).unwrap();
+
+        assert!(matches!(enrollment.accept_step(100), Err(DomainError::InvalidMfaCode)));
+        assert!(enrollment.accept_step(99).is_err());
+        assert!(enrollment.accept_step(101).is_ok());
+    }
+
+    #[test]
+    fn test_recovery_code_is_consumed() {
+        let mut enrollment = MfaEnrollment::new(UserId::new(), "encrypted".to_string());
+        enrollment.activate(Utc::now(), vec!["a".to_string(), "b".to_string()]).unwrap();
+
+        enrollment.consume_recovery_code(0).unwrap();
+
+        assert_eq!(enrollment.recovery_code_hashes(), &["b".to_string()]);
+        assert!(enrollment.consume_recovery_code(5).is_err());
+    }
+}
\ No newline at end of file
@@ -1,3 +1,29 @@
 pub mod user;
+pub mod outbox_message;
+pub mod api_key;
+pub mod mfa_enrollment;
+pub mod login_attempts;
+pub mod session;
+pub mod external_identity;
+pub mod oidc_login_request;
+pub mod rate_limit;
+pub mod encrypted_user;
+pub mod erasure_receipt;
+pub mod audit_event;
+pub mod user_event;
+pub mod webhook;
 
-pub use user::*;
\ No newline at end of file
+pub use user::*;
+pub use outbox_message::*;
+pub use api_key::*;
+pub use mfa_enrollment::*;
+pub use login_attempts::*;
+pub use session::*;
+pub use external_identity::*;
+pub use oidc_login_request::*;
+pub use rate_limit::*;
+pub use encrypted_user::*;
+pub use erasure_receipt::*;
+pub use audit_event::*;
+pub use user_event::*;
+pub use webhook::*;
\ No newline at end of file
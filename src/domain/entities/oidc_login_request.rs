@@ -0,0 +1,50 @@
+use chrono::{DateTime, Duration, Utc};
+
+// Незавершенный вход через OIDC: живет между редиректом на провайдера
+// и возвратом на callback
+#[derive(Debug, Clone)]
+pub struct OidcLoginRequest {
+    provider: String,
+    state: String,
+    nonce: String,
+    code_verifier: String,
+    created_at: DateTime<Utc>,
+}
+
+impl OidcLoginRequest {
+    pub const TTL_MINUTES: i64 = 10;
+
+    pub fn new(provider: String, state: String, nonce: String, code_verifier: String, now: DateTime<Utc>) -> Self {
+        Self {
+            provider,
+            state,
+            nonce,
+            code_verifier,
+            created_at: now,
+        }
+    }
+
+    pub fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    pub fn state(&self) -> &str {
+        &self.state
+    }
+
+    pub fn nonce(&self) -> &str {
+        &self.nonce
+    }
+
+    pub fn code_verifier(&self) -> &str {
+        &self.code_verifier
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        now >= self.created_at + Duration::minutes(Self::TTL_MINUTES)
+    }
+}
\ No newline at end of file
@@ -0,0 +1,275 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::domain::{UserId, DomainError};
+
+// Outbox хранит только идентификаторы: email и имя получатель читает из
+// репозитория в момент доставки, копий персональных данных здесь нет
+#[derive(Debug, Clone)]
+pub enum OutboxPayload {
+    WelcomeEmail { user_id: UserId },
+    AccountLockedEmail { user_id: UserId, locked_until: DateTime<Utc> },
+    UserCreated { user_id: UserId },
+    UserUpdated { user_id: UserId, updated_at: DateTime<Utc> },
+    UserDeleted { user_id: UserId },
+    UserErased { user_id: UserId },
+}
+
+impl OutboxPayload {
+    pub fn topic(&self) -> &'static str {
+        match self {
+            OutboxPayload::WelcomeEmail { .. } => "email.welcome",
+            OutboxPayload::AccountLockedEmail { .. } => "email.account_locked",
+            OutboxPayload::UserCreated { .. } => "user.created",
+            OutboxPayload::UserUpdated { .. } => "user.updated",
+            OutboxPayload::UserDeleted { .. } => "user.deleted",
+            OutboxPayload::UserErased { .. } => "user.erased",
+        }
+    }
+
+    // Ключ дедупликации: одно и то же изменение не попадет в outbox дважды,
+    // а получатели могут по нему отбрасывать повторные доставки
+    fn dedup_key(&self) -> String {
+        match self {
+            OutboxPayload::WelcomeEmail { user_id }
+            | OutboxPayload::UserCreated { user_id }
+            | OutboxPayload::UserDeleted { user_id } => format!("{}:{}", self.topic(), user_id),
+            OutboxPayload::UserUpdated { user_id, updated_at } => {
+                format!("{}:{}:{}", self.topic(), user_id, updated_at.timestamp_micros())
+            }
+            OutboxPayload::UserErased { user_id } => format!("{}:{}", self.topic(), user_id),
+            OutboxPayload::AccountLockedEmail { user_id, locked_until } => {
+                format!("{}:{}:{}", self.topic(), user_id, locked_until.timestamp_micros())
+            }
+        }
+    }
+
+    pub fn user_id(&self) -> &UserId {
+        match self {
+            OutboxPayload::WelcomeEmail { user_id }
+            | OutboxPayload::AccountLockedEmail { user_id, .. }
+            | OutboxPayload::UserCreated { user_id }
+            | OutboxPayload::UserUpdated { user_id, .. }
+            | OutboxPayload::UserDeleted { user_id }
+            | OutboxPayload::UserErased { user_id } => user_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OutboxStatus {
+    Pending,
+    Delivered,
+    DeadLettered,
+}
+
+impl OutboxStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutboxStatus::Pending => "pending",
+            OutboxStatus::Delivered => "delivered",
+            OutboxStatus::DeadLettered => "dead_lettered",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self, DomainError> {
+        match value {
+            "pending" => Ok(OutboxStatus::Pending),
+            "delivered" => Ok(OutboxStatus::Delivered),
+            "dead_lettered" => Ok(OutboxStatus::DeadLettered),
+            other => Err(DomainError::InvalidOperation(format!("Unknown outbox status: {}", other))),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OutboxMessage {
+    id: Uuid,
+    dedup_key: String,
+    payload: OutboxPayload,
+    status: OutboxStatus,
+    attempts: u32,
+    next_attempt_at: DateTime<Utc>,
+    last_error: Option<String>,
+    created_at: DateTime<Utc>,
+    delivered_at: Option<DateTime<Utc>>,
+}
+
+impl OutboxMessage {
+    pub fn new(payload: OutboxPayload) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            dedup_key: payload.dedup_key(),
+            payload,
+            status: OutboxStatus::Pending,
+            attempts: 0,
+            next_attempt_at: now,
+            last_error: None,
+            created_at: now,
+            delivered_at: None,
+        }
+    }
+
+    pub fn id(&self) -> &Uuid {
+        &self.id
+    }
+
+    pub fn dedup_key(&self) -> &str {
+        &self.dedup_key
+    }
+
+    pub fn payload(&self) -> &OutboxPayload {
+        &self.payload
+    }
+
+    pub fn topic(&self) -> &'static str {
+        self.payload.topic()
+    }
+
+    pub fn status(&self) -> OutboxStatus {
+        self.status
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn next_attempt_at(&self) -> &DateTime<Utc> {
+        &self.next_attempt_at
+    }
+
+    pub fn last_error(&self) -> Option<&str> {
+        self.last_error.as_deref()
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn delivered_at(&self) -> Option<&DateTime<Utc>> {
+        self.delivered_at.as_ref()
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.status == OutboxStatus::Pending && self.next_attempt_at <= now
+    }
+
+    /// Откладывает сообщение до `until`, чтобы другой диспетчер не взял его,
+    /// пока текущий выполняет доставку.
+    pub fn lease(&mut self, until: DateTime<Utc>) {
+        self.next_attempt_at = until;
+    }
+
+    pub fn mark_delivered(&mut self, now: DateTime<Utc>) {
+        self.status = OutboxStatus::Delivered;
+        self.attempts += 1;
+        self.delivered_at = Some(now);
+        self.last_error = None;
+    }
+
+    pub fn schedule_retry(&mut self, error: String, retry_at: DateTime<Utc>) {
+        self.attempts += 1;
+        self.last_error = Some(error);
+        self.next_attempt_at = retry_at;
+    }
+
+    pub fn dead_letter(&mut self, error: String) {
+        self.status = OutboxStatus::DeadLettered;
+        self.attempts += 1;
+        self.last_error = Some(error);
+    }
+
+    // Недоставленное письмо стертому пользователю больше некому отправлять,
+    // поэтому оно уходит в dead-letter. Возвращает, было ли письмо отменено
+    pub fn cancel_email_to(&mut self, user_id: &UserId) -> bool {
+        if self.status != OutboxStatus::Pending
+            || !self.topic().starts_with("email.")
+            || self.payload.user_id() != user_id
+        {
+            return false;
+        }
+
+        self.status = OutboxStatus::DeadLettered;
+        self.last_error = Some("Recipient was erased".to_string());
+        true
+    }
+
+    pub fn requeue(&mut self, now: DateTime<Utc>) -> Result<(), DomainError> {
+        if self.status == OutboxStatus::Delivered {
+            return Err(DomainError::InvalidOperation(
+                "Delivered outbox message cannot be requeued".to_string(),
+            ));
+        }
+
+        self.status = OutboxStatus::Pending;
+        self.attempts = 0;
+        self.next_attempt_at = now;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_message_is_due() {
+        let message = OutboxMessage::new(OutboxPayload::WelcomeEmail { user_id: UserId::new() });
+
+        assert_eq!(message.status(), OutboxStatus::Pending);
+        assert_eq!(message.topic(), "email.welcome");
+        assert!(message.is_due(Utc::now()));
+    }
+
+    #[test]
+    fn test_dedup_key_is_stable_for_same_change() {
+        let user_id = UserId::new();
+        let first = OutboxMessage::new(OutboxPayload::UserCreated { user_id: user_id.clone() });
+        let second = OutboxMessage::new(OutboxPayload::UserCreated { user_id: user_id.clone() });
+
+        assert_eq!(first.dedup_key(), second.dedup_key());
+        assert_ne!(first.id(), second.id());
+        assert_eq!(first.dedup_key(), format!("user.created:{}", user_id));
+    }
+
+    #[test]
+    fn test_retry_and_dead_letter() {
+        let mut message = OutboxMessage::new(OutboxPayload::UserDeleted { user_id: UserId::new() });
+        let retry_at = Utc::now() + chrono::Duration::seconds(30);
+
+        message.schedule_retry("timeout".to_string(), retry_at);
+        assert_eq!(message.attempts(), 1);
+        assert!(!message.is_due(Utc::now()));
+
+        message.dead_letter("timeout".to_string());
+        assert_eq!(message.status(), OutboxStatus::DeadLettered);
+        assert_eq!(message.last_error(), Some("timeout"));
+
+        message.requeue(Utc::now()).unwrap();
+        assert_eq!(message.status(), OutboxStatus::Pending);
+        assert_eq!(message.attempts(), 0);
+    }
+
+    #[test]
+    fn test_delivered_message_cannot_be_requeued() {
+        let mut message = OutboxMessage::new(OutboxPayload::UserDeleted { user_id: UserId::new() });
+        message.mark_delivered(Utc::now());
+
+        assert!(matches!(message.requeue(Utc::now()), Err(DomainError::InvalidOperation(_))));
+    }
+
+    #[test]
+    fn test_cancel_email_to_erased_user() {
+        let user_id = UserId::new();
+        let mut email = OutboxMessage::new(OutboxPayload::WelcomeEmail { user_id: user_id.clone() });
+        let mut event = OutboxMessage::new(OutboxPayload::UserCreated { user_id: user_id.clone() });
+        let mut other = OutboxMessage::new(OutboxPayload::WelcomeEmail { user_id: UserId::new() });
+
+        assert!(email.cancel_email_to(&user_id));
+        assert!(!event.cancel_email_to(&user_id));
+        assert!(!other.cancel_email_to(&user_id));
+
+        assert_eq!(email.status(), OutboxStatus::DeadLettered);
+        assert_eq!(event.status(), OutboxStatus::Pending);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,141 @@
+use std::net::IpAddr;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+use crate::domain::{UserId, DomainError};
+
+// Чей бюджет запросов расходуется: ключа, пользователя или адреса клиента
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum RateLimitKey {
+    ApiKey(Uuid),
+    User(UserId),
+    ClientIp(IpAddr),
+    // Адрес неизвестен (нет ConnectInfo): такие запросы делят общий бюджет
+    Unknown,
+}
+
+impl RateLimitKey {
+    pub fn key(&self) -> String {
+        match self {
+            RateLimitKey::ApiKey(id) => format!("api_key:{}", id),
+            RateLimitKey::User(user_id) => format!("user:{}", user_id),
+            RateLimitKey::ClientIp(ip) => format!("ip:{}", ip),
+            RateLimitKey::Unknown => "unknown".to_string(),
+        }
+    }
+}
+
+/// Квота `limit` запросов за `period` по алгоритму GCRA: запросы
+/// расходуют бюджет равномерно, допускается всплеск до `limit` подряд.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimitQuota {
+    limit: u32,
+    period: Duration,
+}
+
+impl RateLimitQuota {
+    pub fn new(limit: u32, period: Duration) -> Result<Self, DomainError> {
+        if limit == 0 || period <= Duration::zero() {
+            return Err(DomainError::InvalidOperation("Rate limit must allow at least one request per period".to_string()));
+        }
+        Ok(Self { limit, period })
+    }
+
+    // Формат `<запросы>/<секунды>`, например `100/60`
+    pub fn parse(value: &str) -> Result<Self, DomainError> {
+        let invalid = || DomainError::InvalidOperation(format!("Invalid rate limit: {}", value));
+        let (limit, period) = value.trim().split_once('/').ok_or_else(invalid)?;
+        let limit = limit.trim().parse().map_err(|_| invalid())?;
+        let period: i64 = period.trim().parse().map_err(|_| invalid())?;
+        Self::new(limit, Duration::seconds(period))
+    }
+
+    pub fn limit(&self) -> u32 {
+        self.limit
+    }
+
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    // Интервал, за который восстанавливается один запрос
+    pub fn emission_interval(&self) -> Duration {
+        self.period / self.limit as i32
+    }
+
+    /// Новое теоретическое время прибытия (TAT) после запроса
+    /// или `None`, если запрос превышает квоту.
+    pub fn next_arrival(&self, arrival: Option<DateTime<Utc>>, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        let next = arrival.map_or(now, |arrival| arrival.max(now)) + self.emission_interval();
+        (next - now <= self.period).then_some(next)
+    }
+
+    /// Состояние бюджета по TAT, сохраненному после проверки.
+    pub fn decision(&self, arrival: DateTime<Utc>, allowed: bool, now: DateTime<Utc>) -> RateLimitDecision {
+        let reset_after = (arrival - now).max(Duration::zero());
+        let interval = self.emission_interval();
+        let remaining = match allowed {
+            true => ((self.period - reset_after).num_milliseconds() / interval.num_milliseconds().max(1)) as u32,
+            false => 0,
+        };
+        let retry_after = (!allowed).then(|| (arrival + interval - self.period - now).max(Duration::zero()));
+
+        RateLimitDecision {
+            allowed,
+            limit: self.limit,
+            remaining: remaining.min(self.limit),
+            reset_after,
+            retry_after,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    // Через сколько бюджет полностью восстановится
+    pub reset_after: Duration,
+    // Для отклоненного запроса: когда можно повторить
+    pub retry_after: Option<Duration>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_burst_then_steady_rate() {
+        let quota = RateLimitQuota::new(3, Duration::seconds(3)).unwrap();
+        let now = Utc::now();
+
+        let mut arrival = None;
+        for expected_remaining in [2, 1, 0] {
+            let next = quota.next_arrival(arrival, now).unwrap();
+            assert_eq!(quota.decision(next, true, now).remaining, expected_remaining);
+            arrival = Some(next);
+        }
+
+        let arrival = arrival.unwrap();
+        assert!(quota.next_arrival(Some(arrival), now).is_none());
+        let denied = quota.decision(arrival, false, now);
+        assert_eq!(denied.retry_after, Some(Duration::seconds(1)));
+        assert_eq!(denied.reset_after, Duration::seconds(3));
+
+        // Через интервал восстанавливается ровно один запрос
+        let later = now + Duration::seconds(1);
+        let next = quota.next_arrival(Some(arrival), later).unwrap();
+        assert_eq!(quota.decision(next, true, later).remaining, 0);
+    }
+
+    #[test]
+    fn test_parse_quota() {
+        let quota = RateLimitQuota::parse("100/60").unwrap();
+        assert_eq!(quota.limit(), 100);
+        assert_eq!(quota.period(), Duration::seconds(60));
+
+        assert!(RateLimitQuota::parse("0/60").is_err());
+        assert!(RateLimitQuota::parse("100").is_err());
+        assert!(RateLimitQuota::parse("100/-1").is_err());
+    }
+}
\ No newline at end of file
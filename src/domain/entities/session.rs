@@ -0,0 +1,183 @@
+use std::net::IpAddr;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+use crate::domain::{AuthLevel, UserId};
+
+#[derive(Debug, Clone)]
+pub struct SessionPolicy {
+    // Сессия истекает, если ею не пользовались дольше этого времени
+    pub idle_timeout: Duration,
+    // И в любом случае через это время после входа
+    pub absolute_timeout: Duration,
+}
+
+impl Default for SessionPolicy {
+    fn default() -> Self {
+        Self {
+            idle_timeout: Duration::minutes(30),
+            absolute_timeout: Duration::hours(12),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SessionMetadata {
+    pub user_agent: Option<String>,
+    pub ip_address: Option<IpAddr>,
+}
+
+// Значения cookie сессии и CSRF-токена хранятся только в виде хешей
+#[derive(Debug, Clone)]
+pub struct Session {
+    id: Uuid,
+    user_id: UserId,
+    token_hash: String,
+    csrf_token_hash: String,
+    auth_level: AuthLevel,
+    metadata: SessionMetadata,
+    created_at: DateTime<Utc>,
+    last_seen_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+}
+
+impl Session {
+    pub fn new(
+        user_id: UserId,
+        token_hash: String,
+        csrf_token_hash: String,
+        auth_level: AuthLevel,
+        metadata: SessionMetadata,
+        now: DateTime<Utc>,
+        policy: &SessionPolicy,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            token_hash,
+            csrf_token_hash,
+            auth_level,
+            metadata,
+            created_at: now,
+            last_seen_at: now,
+            expires_at: now + policy.absolute_timeout,
+        }
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn from_existing(
+        id: Uuid,
+        user_id: UserId,
+        token_hash: String,
+        csrf_token_hash: String,
+        auth_level: AuthLevel,
+        metadata: SessionMetadata,
+        created_at: DateTime<Utc>,
+        last_seen_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            user_id,
+            token_hash,
+            csrf_token_hash,
+            auth_level,
+            metadata,
+            created_at,
+            last_seen_at,
+            expires_at,
+        }
+    }
+
+    pub fn id(&self) -> &Uuid {
+        &self.id
+    }
+
+    pub fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+
+    pub fn token_hash(&self) -> &str {
+        &self.token_hash
+    }
+
+    pub fn csrf_token_hash(&self) -> &str {
+        &self.csrf_token_hash
+    }
+
+    pub fn auth_level(&self) -> AuthLevel {
+        self.auth_level
+    }
+
+    pub fn metadata(&self) -> &SessionMetadata {
+        &self.metadata
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn last_seen_at(&self) -> &DateTime<Utc> {
+        &self.last_seen_at
+    }
+
+    pub fn expires_at(&self) -> &DateTime<Utc> {
+        &self.expires_at
+    }
+
+    /// Момент, когда сессия истечет без дальнейших запросов.
+    pub fn idle_expires_at(&self, policy: &SessionPolicy) -> DateTime<Utc> {
+        (self.last_seen_at + policy.idle_timeout).min(self.expires_at)
+    }
+
+    pub fn is_active(&self, now: DateTime<Utc>, policy: &SessionPolicy) -> bool {
+        now < self.idle_expires_at(policy)
+    }
+
+    pub fn touch(&mut self, now: DateTime<Utc>) {
+        self.last_seen_at = now;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(now: DateTime<Utc>, policy: &SessionPolicy) -> Session {
+        Session::new(
+            UserId::new(),
+            "token".to_string(),
+            "csrf".to_string(),
+            AuthLevel::SingleFactor,
+            SessionMetadata::default(),
+            now,
+            policy,
+        )
+    }
+
+    #[test]
+    fn test_idle_timeout_is_extended_by_activity() {
+        let policy = SessionPolicy::default();
+        let now = Utc::now();
+        let mut session = session(now, &policy);
+
+        let later = now + Duration::minutes(25);
+        assert!(session.is_active(later, &policy));
+        session.touch(later);
+
+        assert!(session.is_active(now + Duration::minutes(50), &policy));
+        assert!(!session.is_active(later + Duration::minutes(31), &policy));
+    }
+
+    #[test]
+    fn test_absolute_timeout_cannot_be_extended() {
+        let policy = SessionPolicy::default();
+        let now = Utc::now();
+        let mut session = session(now, &policy);
+
+        let almost_expired = now + Duration::hours(12) - Duration::minutes(1);
+        session.touch(almost_expired);
+
+        assert_eq!(session.idle_expires_at(&policy), now + Duration::hours(12));
+        assert!(!session.is_active(now + Duration::hours(12), &policy));
+    }
+}
\ No newline at end of file
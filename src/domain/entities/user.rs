@@ -1,13 +1,26 @@
 use chrono::{DateTime, Utc};
-use crate::domain::{UserId, Email, DomainError};
+use crate::domain::{
+    UserId, Email, Role, DomainError, UserEvent, UserCreated, UserDeleted, UserEmailChanged, UserErased, UserRenamed,
+    UserRolesChanged,
+};
 
-#[derive(Debug, Clone, PartialEq)]
+// Надгробные значения вместо персональных данных стертого пользователя
+pub const ERASED_USER_NAME: &str = "Erased user";
+const ERASED_EMAIL_DOMAIN: &str = "erased.invalid";
+
+#[derive(Debug, Clone)]
 pub struct User {
     id: UserId,
     email: Email,
     name: String,
+    roles: Vec<Role>,
     created_at: DateTime<Utc>,
     updated_at: DateTime<Utc>,
+    erased_at: Option<DateTime<Utc>>,
+    // Версия потока событий, из которого загружен агрегат (0 - еще не сохранен)
+    version: u64,
+    // События, еще не опубликованные после сохранения
+    events: Vec<UserEvent>,
 }
 
 impl User {
@@ -17,21 +30,35 @@ impl User {
         }
 
         let now = Utc::now();
-        Ok(Self {
+        let mut user = Self {
             id: UserId::new(),
             email,
             name: name.trim().to_string(),
+            roles: vec![Role::Member],
             created_at: now,
             updated_at: now,
-        })
+            erased_at: None,
+            version: 0,
+            events: Vec::new(),
+        };
+        user.record(UserCreated {
+            user_id: user.id.clone(),
+            email: user.email.clone(),
+            name: user.name.clone(),
+            roles: user.roles.clone(),
+            occurred_at: now,
+        });
+        Ok(user)
     }
 
     pub fn from_existing(
         id: UserId,
         email: Email,
         name: String,
+        roles: Vec<Role>,
         created_at: DateTime<Utc>,
         updated_at: DateTime<Utc>,
+        erased_at: Option<DateTime<Utc>>,
     ) -> Result<Self, DomainError> {
         if name.trim().is_empty() {
             return Err(DomainError::InvalidUserData("Name cannot be empty".to_string()));
@@ -41,8 +68,12 @@ impl User {
             id,
             email,
             name: name.trim().to_string(),
+            roles: Self::normalize_roles(roles)?,
             created_at,
             updated_at,
+            erased_at,
+            version: 0,
+            events: Vec::new(),
         })
     }
 
@@ -58,6 +89,18 @@ impl User {
         &self.name
     }
 
+    pub fn roles(&self) -> &[Role] {
+        &self.roles
+    }
+
+    pub fn has_role(&self, role: Role) -> bool {
+        self.roles.contains(&role)
+    }
+
+    pub fn highest_role(&self) -> Role {
+        self.roles.iter().copied().max().unwrap_or(Role::Member)
+    }
+
     pub fn created_at(&self) -> &DateTime<Utc> {
         &self.created_at
     }
@@ -66,21 +109,232 @@ impl User {
         &self.updated_at
     }
 
+    pub fn erased_at(&self) -> Option<&DateTime<Utc>> {
+        self.erased_at.as_ref()
+    }
+
+    pub fn is_erased(&self) -> bool {
+        self.erased_at.is_some()
+    }
+
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    // Хранилища с оптимистичной блокировкой отмечают версию загруженного агрегата
+    pub fn with_version(mut self, version: u64) -> Self {
+        self.version = version;
+        self
+    }
+
+    pub fn pending_events(&self) -> &[UserEvent] {
+        &self.events
+    }
+
+    // Забирает накопленные события; вызывается после сохранения агрегата
+    pub fn take_events(&mut self) -> Vec<UserEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    // Необратимо заменяет email и имя надгробными значениями. Идентификатор
+    // сохраняется, чтобы ссылки из других хранилищ оставались валидными
+    pub fn erase(&mut self, now: DateTime<Utc>) -> Result<(), DomainError> {
+        self.ensure_not_erased()?;
+
+        self.apply_erasure(now)?;
+        self.record(UserErased { user_id: self.id.clone(), occurred_at: now });
+        Ok(())
+    }
+
+    // Удаление выполняет репозиторий, агрегат только фиксирует событие
+    pub fn mark_deleted(&mut self, now: DateTime<Utc>) {
+        self.record(UserDeleted { user_id: self.id.clone(), occurred_at: now });
+    }
+
     pub fn update_name(&mut self, new_name: String) -> Result<(), DomainError> {
+        self.ensure_not_erased()?;
         if new_name.trim().is_empty() {
             return Err(DomainError::InvalidUserData("Name cannot be empty".to_string()));
         }
 
-        self.name = new_name.trim().to_string();
-        self.updated_at = Utc::now();
+        let new_name = new_name.trim().to_string();
+        if new_name == self.name {
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        let previous_name = std::mem::replace(&mut self.name, new_name);
+        self.updated_at = now;
+        self.record(UserRenamed {
+            user_id: self.id.clone(),
+            previous_name,
+            name: self.name.clone(),
+            occurred_at: now,
+        });
         Ok(())
     }
 
     pub fn update_email(&mut self, new_email: Email) -> Result<(), DomainError> {
-        self.email = new_email;
-        self.updated_at = Utc::now();
+        self.ensure_not_erased()?;
+        if new_email == self.email {
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        let previous_email = std::mem::replace(&mut self.email, new_email);
+        self.updated_at = now;
+        self.record(UserEmailChanged {
+            user_id: self.id.clone(),
+            previous_email,
+            email: self.email.clone(),
+            occurred_at: now,
+        });
+        Ok(())
+    }
+
+    pub fn change_roles(&mut self, roles: Vec<Role>) -> Result<(), DomainError> {
+        self.ensure_not_erased()?;
+        let roles = Self::normalize_roles(roles)?;
+        if roles == self.roles {
+            return Ok(());
+        }
+
+        let now = Utc::now();
+        let previous_roles = std::mem::replace(&mut self.roles, roles);
+        self.updated_at = now;
+        self.record(UserRolesChanged {
+            user_id: self.id.clone(),
+            previous_roles,
+            roles: self.roles.clone(),
+            occurred_at: now,
+        });
+        Ok(())
+    }
+
+    // Применяет событие из потока к состоянию. `None` - пользователя еще нет
+    // или он удален; поток можно свернуть через `try_fold` от `None`
+    pub fn apply_event(state: Option<User>, event: &UserEvent) -> Result<Option<User>, DomainError> {
+        let mut user = match (state, event) {
+            (_, UserEvent::Created(event)) => {
+                let user = User::from_existing(
+                    event.user_id.clone(),
+                    event.email.clone(),
+                    event.name.clone(),
+                    event.roles.clone(),
+                    event.occurred_at,
+                    event.occurred_at,
+                    None,
+                )?;
+                return Ok(Some(user));
+            }
+            (_, UserEvent::Deleted(_)) => return Ok(None),
+            (Some(user), _) if user.id == *event.user_id() => user,
+            _ => {
+                return Err(DomainError::InvalidOperation(format!(
+                    "Event {} does not apply to the stream of user {}",
+                    event.name(),
+                    event.user_id(),
+                )));
+            }
+        };
+
+        match event {
+            UserEvent::EmailChanged(event) => user.email = event.email.clone(),
+            UserEvent::Renamed(event) => user.name = event.name.clone(),
+            UserEvent::RolesChanged(event) => user.roles = Self::normalize_roles(event.roles.clone())?,
+            UserEvent::Erased(event) => user.apply_erasure(event.occurred_at)?,
+            UserEvent::Created(_) | UserEvent::Deleted(_) => {}
+        }
+        user.updated_at = *event.occurred_at();
+        Ok(Some(user))
+    }
+
+    // События, переводящие `previous` в текущее состояние. Нужны хранилищам,
+    // которые получают агрегат уже без накопленных событий
+    pub fn events_since(&self, previous: Option<&User>) -> Vec<UserEvent> {
+        let occurred_at = self.updated_at;
+        let previous = match previous {
+            Some(previous) => previous,
+            None => {
+                let mut events = vec![UserEvent::Created(UserCreated {
+                    user_id: self.id.clone(),
+                    email: self.email.clone(),
+                    name: self.name.clone(),
+                    roles: self.roles.clone(),
+                    occurred_at: self.created_at,
+                })];
+                if self.is_erased() {
+                    events.push(UserEvent::Erased(UserErased { user_id: self.id.clone(), occurred_at }));
+                }
+                return events;
+            }
+        };
+
+        // Стирание само определяет надгробные значения полей
+        if self.is_erased() {
+            return match previous.is_erased() {
+                true => Vec::new(),
+                false => vec![UserEvent::Erased(UserErased { user_id: self.id.clone(), occurred_at })],
+            };
+        }
+
+        let mut events = Vec::new();
+        if self.email != previous.email {
+            events.push(UserEvent::EmailChanged(UserEmailChanged {
+                user_id: self.id.clone(),
+                previous_email: previous.email.clone(),
+                email: self.email.clone(),
+                occurred_at,
+            }));
+        }
+        if self.name != previous.name {
+            events.push(UserEvent::Renamed(UserRenamed {
+                user_id: self.id.clone(),
+                previous_name: previous.name.clone(),
+                name: self.name.clone(),
+                occurred_at,
+            }));
+        }
+        if self.roles != previous.roles {
+            events.push(UserEvent::RolesChanged(UserRolesChanged {
+                user_id: self.id.clone(),
+                previous_roles: previous.roles.clone(),
+                roles: self.roles.clone(),
+                occurred_at,
+            }));
+        }
+        events
+    }
+
+    fn apply_erasure(&mut self, now: DateTime<Utc>) -> Result<(), DomainError> {
+        self.email = Email::new(format!("{}@{}", self.id.to_string().replace('-', ""), ERASED_EMAIL_DOMAIN))?;
+        self.name = ERASED_USER_NAME.to_string();
+        self.roles = vec![Role::Member];
+        self.updated_at = now;
+        self.erased_at = Some(now);
         Ok(())
     }
+
+    fn record(&mut self, event: impl Into<UserEvent>) {
+        self.events.push(event.into());
+    }
+
+    fn ensure_not_erased(&self) -> Result<(), DomainError> {
+        if self.is_erased() {
+            return Err(DomainError::InvalidOperation("Erased user cannot be modified".to_string()));
+        }
+        Ok(())
+    }
+
+    fn normalize_roles(mut roles: Vec<Role>) -> Result<Vec<Role>, DomainError> {
+        if roles.is_empty() {
+            return Err(DomainError::InvalidUserData("User must have at least one role".to_string()));
+        }
+
+        roles.sort();
+        roles.dedup();
+        Ok(roles)
+    }
 }
 
 impl PartialEq<User> for User {
@@ -132,4 +386,103 @@ mod tests {
         
         assert_eq!(user.email().as_str(), "jane@example.com");
     }
+
+    #[test]
+    fn test_new_user_is_member() {
+        let email = Email::new("user@example.com".to_string()).unwrap();
+        let user = User::new(email, "John Doe".to_string()).unwrap();
+        
+        assert_eq!(user.roles(), &[Role::Member]);
+        assert_eq!(user.highest_role(), Role::Member);
+    }
+
+    #[test]
+    fn test_change_roles() {
+        let email = Email::new("user@example.com".to_string()).unwrap();
+        let mut user = User::new(email, "John Doe".to_string()).unwrap();
+        
+        user.change_roles(vec![Role::Admin, Role::Member, Role::Admin]).unwrap();
+        
+        assert_eq!(user.roles(), &[Role::Member, Role::Admin]);
+        assert!(user.has_role(Role::Admin));
+        assert!(user.change_roles(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_mutations_record_events() {
+        let email = Email::new("user@example.com".to_string()).unwrap();
+        let mut user = User::new(email, "John Doe".to_string()).unwrap();
+        
+        user.update_name("John Doe".to_string()).unwrap();
+        user.update_name("Jane Doe".to_string()).unwrap();
+        user.update_email(Email::new("jane@example.com".to_string()).unwrap()).unwrap();
+        user.change_roles(vec![Role::Member]).unwrap();
+        user.mark_deleted(Utc::now());
+        
+        let names: Vec<&str> = user.pending_events().iter().map(UserEvent::name).collect();
+        assert_eq!(names, vec!["user.created", "user.renamed", "user.email_changed", "user.deleted"]);
+        let events = user.take_events();
+        assert!(matches!(&events[1], UserEvent::Renamed(event) if event.previous_name == "John Doe" && event.name == "Jane Doe"));
+        assert!(user.pending_events().is_empty());
+    }
+
+    #[test]
+    fn test_events_since_matches_recorded_events() {
+        let email = Email::new("user@example.com".to_string()).unwrap();
+        let mut user = User::new(email, "John Doe".to_string()).unwrap();
+        let created = user.clone();
+        user.take_events();
+        
+        user.update_name("Jane Doe".to_string()).unwrap();
+        user.change_roles(vec![Role::Support]).unwrap();
+        
+        let recorded: Vec<&str> = user.pending_events().iter().map(UserEvent::name).collect();
+        let derived = user.events_since(Some(&created));
+        assert_eq!(derived.iter().map(UserEvent::name).collect::<Vec<_>>(), recorded);
+        assert_eq!(created.events_since(None).len(), 1);
+        
+        let replayed = created.events_since(None)
+            .iter()
+            .chain(&derived)
+            .try_fold(None, User::apply_event)
+            .unwrap()
+            .unwrap();
+        assert_eq!(replayed.name(), "Jane Doe");
+        assert_eq!(replayed.roles(), &[Role::Support]);
+        assert_eq!(replayed.updated_at(), user.updated_at());
+    }
+
+    #[test]
+    fn test_apply_event_folds_erasure_and_deletion() {
+        let email = Email::new("user@example.com".to_string()).unwrap();
+        let mut user = User::new(email, "John Doe".to_string()).unwrap();
+        user.erase(Utc::now()).unwrap();
+        user.mark_deleted(Utc::now());
+        let events = user.take_events();
+        
+        let erased = events[..2].iter().try_fold(None, User::apply_event).unwrap().unwrap();
+        assert!(erased.is_erased());
+        assert_eq!(erased.email(), user.email());
+        assert!(events.iter().try_fold(None, User::apply_event).unwrap().is_none());
+        assert!(User::apply_event(None, &events[1]).is_err());
+    }
+
+    #[test]
+    fn test_erase_replaces_pii_with_tombstones() {
+        let email = Email::new("user@example.com".to_string()).unwrap();
+        let mut user = User::new(email, "John Doe".to_string()).unwrap();
+        user.change_roles(vec![Role::Admin]).unwrap();
+        let id = user.id().clone();
+        
+        user.erase(Utc::now()).unwrap();
+        
+        assert!(user.is_erased());
+        assert_eq!(user.id(), &id);
+        assert_eq!(user.name(), ERASED_USER_NAME);
+        assert!(user.email().as_str().ends_with("@erased.invalid"));
+        assert!(!user.email().as_str().contains("user@example.com"));
+        assert_eq!(user.roles(), &[Role::Member]);
+        assert!(user.erase(Utc::now()).is_err());
+        assert!(user.update_name("John Doe".to_string()).is_err());
+    }
 }
\ No newline at end of file
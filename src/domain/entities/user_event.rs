@@ -0,0 +1,213 @@
+use chrono::{DateTime, Utc};
+use crate::domain::{UserId, Email, Role};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserCreated {
+    pub user_id: UserId,
+    pub email: Email,
+    pub name: String,
+    pub roles: Vec<Role>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserEmailChanged {
+    pub user_id: UserId,
+    pub previous_email: Email,
+    pub email: Email,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserRenamed {
+    pub user_id: UserId,
+    pub previous_name: String,
+    pub name: String,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserRolesChanged {
+    pub user_id: UserId,
+    pub previous_roles: Vec<Role>,
+    pub roles: Vec<Role>,
+    pub occurred_at: DateTime<Utc>,
+}
+
+// Событие не несет персональных данных: надгробные значения выводятся из идентификатора
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserErased {
+    pub user_id: UserId,
+    pub occurred_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserDeleted {
+    pub user_id: UserId,
+    pub occurred_at: DateTime<Utc>,
+}
+
+// Имена всех событий пользователя, как их возвращает `UserEvent::name`
+pub const USER_EVENT_NAMES: [&str; 6] = [
+    "user.created",
+    "user.email_changed",
+    "user.renamed",
+    "user.roles_changed",
+    "user.erased",
+    "user.deleted",
+];
+
+/// Доменные события агрегата `User`. Агрегат накапливает их при изменениях,
+/// а `UserDomainService` забирает и публикует после сохранения.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UserEvent {
+    Created(UserCreated),
+    EmailChanged(UserEmailChanged),
+    Renamed(UserRenamed),
+    RolesChanged(UserRolesChanged),
+    Erased(UserErased),
+    Deleted(UserDeleted),
+}
+
+impl UserEvent {
+    pub fn name(&self) -> &'static str {
+        match self {
+            UserEvent::Created(_) => "user.created",
+            UserEvent::EmailChanged(_) => "user.email_changed",
+            UserEvent::Renamed(_) => "user.renamed",
+            UserEvent::RolesChanged(_) => "user.roles_changed",
+            UserEvent::Erased(_) => "user.erased",
+            UserEvent::Deleted(_) => "user.deleted",
+        }
+    }
+
+    pub fn user_id(&self) -> &UserId {
+        match self {
+            UserEvent::Created(event) => &event.user_id,
+            UserEvent::EmailChanged(event) => &event.user_id,
+            UserEvent::Renamed(event) => &event.user_id,
+            UserEvent::RolesChanged(event) => &event.user_id,
+            UserEvent::Erased(event) => &event.user_id,
+            UserEvent::Deleted(event) => &event.user_id,
+        }
+    }
+
+    pub fn occurred_at(&self) -> &DateTime<Utc> {
+        match self {
+            UserEvent::Created(event) => &event.occurred_at,
+            UserEvent::EmailChanged(event) => &event.occurred_at,
+            UserEvent::Renamed(event) => &event.occurred_at,
+            UserEvent::RolesChanged(event) => &event.occurred_at,
+            UserEvent::Erased(event) => &event.occurred_at,
+            UserEvent::Deleted(event) => &event.occurred_at,
+        }
+    }
+}
+
+/// Тип, на который можно подписаться в `UserEventBus`: конкретное событие
+/// или `UserEvent` целиком.
+pub trait UserEventKind: Clone + Send + 'static {
+    fn from_user_event(event: &UserEvent) -> Option<Self>;
+}
+
+impl UserEventKind for UserEvent {
+    fn from_user_event(event: &UserEvent) -> Option<Self> {
+        Some(event.clone())
+    }
+}
+
+impl UserEventKind for UserCreated {
+    fn from_user_event(event: &UserEvent) -> Option<Self> {
+        match event {
+            UserEvent::Created(event) => Some(event.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl From<UserCreated> for UserEvent {
+    fn from(event: UserCreated) -> Self {
+        UserEvent::Created(event)
+    }
+}
+
+impl UserEventKind for UserEmailChanged {
+    fn from_user_event(event: &UserEvent) -> Option<Self> {
+        match event {
+            UserEvent::EmailChanged(event) => Some(event.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl From<UserEmailChanged> for UserEvent {
+    fn from(event: UserEmailChanged) -> Self {
+        UserEvent::EmailChanged(event)
+    }
+}
+
+impl UserEventKind for UserRenamed {
+    fn from_user_event(event: &UserEvent) -> Option<Self> {
+        match event {
+            UserEvent::Renamed(event) => Some(event.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl From<UserRenamed> for UserEvent {
+    fn from(event: UserRenamed) -> Self {
+        UserEvent::Renamed(event)
+    }
+}
+
+impl UserEventKind for UserRolesChanged {
+    fn from_user_event(event: &UserEvent) -> Option<Self> {
+        match event {
+            UserEvent::RolesChanged(event) => Some(event.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl From<UserRolesChanged> for UserEvent {
+    fn from(event: UserRolesChanged) -> Self {
+        UserEvent::RolesChanged(event)
+    }
+}
+
+impl UserEventKind for UserErased {
+    fn from_user_event(event: &UserEvent) -> Option<Self> {
+        match event {
+            UserEvent::Erased(event) => Some(event.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl From<UserErased> for UserEvent {
+    fn from(event: UserErased) -> Self {
+        UserEvent::Erased(event)
+    }
+}
+
+impl UserEventKind for UserDeleted {
+    fn from_user_event(event: &UserEvent) -> Option<Self> {
+        match event {
+            UserEvent::Deleted(event) => Some(event.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl From<UserDeleted> for UserEvent {
+    fn from(event: UserDeleted) -> Self {
+        UserEvent::Deleted(event)
+    }
+}
+/// Событие вместе с его номером в потоке пользователя (нумерация с 1)
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedUserEvent {
+    pub version: u64,
+    pub event: UserEvent,
+}
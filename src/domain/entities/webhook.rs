@@ -0,0 +1,338 @@
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+use crate::domain::{DomainError, USER_EVENT_NAMES};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WebhookStatus {
+    Active,
+    Disabled,
+}
+
+impl WebhookStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookStatus::Active => "active",
+            WebhookStatus::Disabled => "disabled",
+        }
+    }
+}
+
+// Когда подписка считается неработающей: `failures` неудачных попыток
+// подряд, и первая из них была не раньше чем `window` назад
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WebhookFailurePolicy {
+    pub failures: u32,
+    pub window: Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookSubscription {
+    id: Uuid,
+    url: String,
+    // Секрет подписи зашифрован `SecretCipher`, наружу не отдается
+    secret: String,
+    events: Vec<String>,
+    status: WebhookStatus,
+    consecutive_failures: u32,
+    failing_since: Option<DateTime<Utc>>,
+    disabled_at: Option<DateTime<Utc>>,
+    created_at: DateTime<Utc>,
+}
+
+impl WebhookSubscription {
+    // Пустой фильтр - подписка на все события пользователя
+    pub fn new(id: Uuid, url: String, secret: String, events: Vec<String>) -> Result<Self, DomainError> {
+        let url = url.trim().to_string();
+        if !(url.starts_with("https://") || url.starts_with("http://")) || url.contains(char::is_whitespace) {
+            return Err(DomainError::InvalidOperation("Webhook URL must be an absolute http(s) URL".to_string()));
+        }
+
+        let mut events = events;
+        events.sort();
+        events.dedup();
+        if let Some(unknown) = events.iter().find(|event| !USER_EVENT_NAMES.contains(&event.as_str())) {
+            return Err(DomainError::InvalidOperation(format!("Unknown webhook event: {}", unknown)));
+        }
+
+        Ok(Self {
+            id,
+            url,
+            secret,
+            events,
+            status: WebhookStatus::Active,
+            consecutive_failures: 0,
+            failing_since: None,
+            disabled_at: None,
+            created_at: Utc::now(),
+        })
+    }
+
+    pub fn id(&self) -> &Uuid {
+        &self.id
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn secret(&self) -> &str {
+        &self.secret
+    }
+
+    pub fn events(&self) -> &[String] {
+        &self.events
+    }
+
+    pub fn status(&self) -> WebhookStatus {
+        self.status
+    }
+
+    pub fn consecutive_failures(&self) -> u32 {
+        self.consecutive_failures
+    }
+
+    pub fn failing_since(&self) -> Option<&DateTime<Utc>> {
+        self.failing_since.as_ref()
+    }
+
+    pub fn disabled_at(&self) -> Option<&DateTime<Utc>> {
+        self.disabled_at.as_ref()
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn is_active(&self) -> bool {
+        self.status == WebhookStatus::Active
+    }
+
+    pub fn accepts(&self, event: &str) -> bool {
+        self.is_active() && (self.events.is_empty() || self.events.iter().any(|accepted| accepted == event))
+    }
+
+    pub fn record_success(&mut self) {
+        self.consecutive_failures = 0;
+        self.failing_since = None;
+    }
+
+    // Возвращает true, если подписка только что отключена
+    pub fn record_failure(&mut self, now: DateTime<Utc>, policy: WebhookFailurePolicy) -> bool {
+        self.consecutive_failures += 1;
+        let failing_since = *self.failing_since.get_or_insert(now);
+
+        let sustained = self.consecutive_failures >= policy.failures && now - failing_since >= policy.window;
+        if sustained && self.is_active() {
+            self.status = WebhookStatus::Disabled;
+            self.disabled_at = Some(now);
+            return true;
+        }
+        false
+    }
+
+    pub fn enable(&mut self) {
+        self.status = WebhookStatus::Active;
+        self.disabled_at = None;
+        self.record_success();
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WebhookDeliveryStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+impl WebhookDeliveryStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookDeliveryStatus::Pending => "pending",
+            WebhookDeliveryStatus::Delivered => "delivered",
+            WebhookDeliveryStatus::Failed => "failed",
+        }
+    }
+}
+
+// Одна попытка доставки: код ответа получателя или ошибка соединения
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookAttempt {
+    pub attempted_at: DateTime<Utc>,
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookDelivery {
+    id: Uuid,
+    webhook_id: Uuid,
+    event: String,
+    // Тело запроса в том виде, в котором оно подписывается и отправляется
+    payload: String,
+    status: WebhookDeliveryStatus,
+    attempts: Vec<WebhookAttempt>,
+    // Неудачные попытки с момента постановки в очередь, для расчета паузы
+    failures: u32,
+    next_attempt_at: DateTime<Utc>,
+    created_at: DateTime<Utc>,
+    delivered_at: Option<DateTime<Utc>>,
+}
+
+impl WebhookDelivery {
+    pub fn new(webhook_id: Uuid, event: String, payload: String) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4(),
+            webhook_id,
+            event,
+            payload,
+            status: WebhookDeliveryStatus::Pending,
+            attempts: Vec::new(),
+            failures: 0,
+            next_attempt_at: now,
+            created_at: now,
+            delivered_at: None,
+        }
+    }
+
+    pub fn id(&self) -> &Uuid {
+        &self.id
+    }
+
+    pub fn webhook_id(&self) -> &Uuid {
+        &self.webhook_id
+    }
+
+    pub fn event(&self) -> &str {
+        &self.event
+    }
+
+    pub fn payload(&self) -> &str {
+        &self.payload
+    }
+
+    pub fn status(&self) -> WebhookDeliveryStatus {
+        self.status
+    }
+
+    pub fn attempts(&self) -> &[WebhookAttempt] {
+        &self.attempts
+    }
+
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    pub fn next_attempt_at(&self) -> &DateTime<Utc> {
+        &self.next_attempt_at
+    }
+
+    pub fn created_at(&self) -> &DateTime<Utc> {
+        &self.created_at
+    }
+
+    pub fn delivered_at(&self) -> Option<&DateTime<Utc>> {
+        self.delivered_at.as_ref()
+    }
+
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.status == WebhookDeliveryStatus::Pending && self.next_attempt_at <= now
+    }
+
+    pub fn mark_delivered(&mut self, now: DateTime<Utc>, status_code: u16) {
+        self.attempts.push(WebhookAttempt { attempted_at: now, status_code: Some(status_code), error: None });
+        self.status = WebhookDeliveryStatus::Delivered;
+        self.delivered_at = Some(now);
+    }
+
+    // `retry_at: None` - попытки исчерпаны, доставка считается неудачной
+    pub fn record_failure(&mut self, attempt: WebhookAttempt, retry_at: Option<DateTime<Utc>>) {
+        self.attempts.push(attempt);
+        self.failures += 1;
+        match retry_at {
+            Some(retry_at) => self.next_attempt_at = retry_at,
+            None => self.status = WebhookDeliveryStatus::Failed,
+        }
+    }
+
+    pub fn fail(&mut self, now: DateTime<Utc>, error: String) {
+        self.record_failure(WebhookAttempt { attempted_at: now, status_code: None, error: Some(error) }, None);
+    }
+
+    // Ручная повторная отправка, в том числе уже доставленного события.
+    // Журнал прошлых попыток сохраняется, отсчет повторов начинается заново
+    pub fn redeliver(&mut self, now: DateTime<Utc>) {
+        self.status = WebhookDeliveryStatus::Pending;
+        self.failures = 0;
+        self.next_attempt_at = now;
+        self.delivered_at = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subscription(events: Vec<&str>) -> Result<WebhookSubscription, DomainError> {
+        WebhookSubscription::new(
+            Uuid::new_v4(),
+            "https://hooks.example.com/users".to_string(),
+            "encrypted".to_string(),
+            events.into_iter().map(str::to_string).collect(),
+        )
+    }
+
+    #[test]
+    fn test_event_filter() {
+        let all = subscription(Vec::new()).unwrap();
+        let renames = subscription(vec!["user.renamed", "user.renamed"]).unwrap();
+
+        assert!(all.accepts("user.deleted"));
+        assert_eq!(renames.events(), &["user.renamed".to_string()]);
+        assert!(renames.accepts("user.renamed"));
+        assert!(!renames.accepts("user.deleted"));
+        assert!(subscription(vec!["user.*"]).is_err());
+        assert!(WebhookSubscription::new(Uuid::new_v4(), "ftp://example.com".to_string(), String::new(), Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_disabled_only_after_sustained_failure() {
+        let policy = WebhookFailurePolicy { failures: 3, window: Duration::minutes(10) };
+        let mut webhook = subscription(Vec::new()).unwrap();
+        let start = Utc::now();
+
+        assert!(!webhook.record_failure(start, policy));
+        assert!(!webhook.record_failure(start + Duration::minutes(1), policy));
+        // Порог по числу попыток достигнут, но сбой длится меньше окна
+        assert!(!webhook.record_failure(start + Duration::minutes(2), policy));
+        assert!(webhook.record_failure(start + Duration::minutes(11), policy));
+        assert_eq!(webhook.status(), WebhookStatus::Disabled);
+        assert!(!webhook.accepts("user.created"));
+
+        webhook.enable();
+        assert!(webhook.is_active());
+        assert_eq!(webhook.consecutive_failures(), 0);
+    }
+
+    #[test]
+    fn test_delivery_attempt_log() {
+        let mut delivery = WebhookDelivery::new(Uuid::new_v4(), "user.created".to_string(), "{}".to_string());
+        let now = Utc::now();
+
+        delivery.record_failure(
+            WebhookAttempt { attempted_at: now, status_code: Some(500), error: None },
+            Some(now + Duration::seconds(5)),
+        );
+        assert!(!delivery.is_due(now));
+        assert!(delivery.is_due(now + Duration::seconds(5)));
+
+        delivery.mark_delivered(now + Duration::seconds(5), 204);
+        assert_eq!(delivery.status(), WebhookDeliveryStatus::Delivered);
+        assert_eq!(delivery.attempts().len(), 2);
+        assert_eq!(delivery.failures(), 1);
+
+        delivery.redeliver(now + Duration::seconds(10));
+        assert!(delivery.is_due(now + Duration::seconds(10)));
+        assert_eq!((delivery.attempts().len(), delivery.failures()), (2, 0));
+    }
+}
@@ -19,6 +19,45 @@ pub enum DomainError {
     
     #[error("External service error: {0}")]
     ExternalServiceError(String),
+    
+    #[error("Outbox message not found")]
+    OutboxMessageNotFound,
+    
+    #[error("Webhook not found")]
+    WebhookNotFound,
+    
+    #[error("Webhook delivery not found")]
+    WebhookDeliveryNotFound,
+    
+    #[error("API key not found")]
+    ApiKeyNotFound,
+    
+    #[error("Session not found")]
+    SessionNotFound,
+    
+    #[error("MFA is not enrolled")]
+    MfaNotEnrolled,
+    
+    #[error("Invalid MFA code")]
+    InvalidMfaCode,
+    
+    #[error("Account is locked until {0}")]
+    AccountLocked(chrono::DateTime<chrono::Utc>),
+    
+    #[error("Too many failed attempts, retry in {0} seconds")]
+    TooManyAttempts(u64),
+    
+    #[error("Invalid operation: {0}")]
+    InvalidOperation(String),
+    
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+    
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+    
+    #[error("User was modified concurrently: expected version {0}, stream is at version {1}")]
+    ConcurrentModification(u64, u64),
 }
 
 impl From<DomainError> for String {
@@ -0,0 +1,9 @@
+use uuid::Uuid;
+use crate::domain::{ApiKey, UserId, DomainError};
+
+pub trait ApiKeyRepository: Send + Sync {
+    async fn find_api_key(&self, id: &Uuid) -> Result<Option<ApiKey>, DomainError>;
+    async fn find_api_key_by_prefix(&self, prefix: &str) -> Result<Option<ApiKey>, DomainError>;
+    async fn list_api_keys(&self, user_id: &UserId) -> Result<Vec<ApiKey>, DomainError>;
+    async fn save_api_key(&self, api_key: &ApiKey) -> Result<(), DomainError>;
+}
\ No newline at end of file
@@ -0,0 +1,140 @@
+use chrono::{DateTime, Utc};
+use rand::distr::{Alphanumeric, SampleString};
+use uuid::Uuid;
+use crate::domain::{ApiKey, ApiKeyRepository, SecretHasher, Scope, UserId, DomainError};
+
+const KEY_PREFIX: &str = "uk_";
+const PREFIX_LENGTH: usize = 8;
+const SECRET_LENGTH: usize = 40;
+
+#[derive(Clone)]
+pub struct ApiKeyDomainService<K: ApiKeyRepository, H: SecretHasher> {
+    api_key_repository: K,
+    secret_hasher: H,
+}
+
+impl<K: ApiKeyRepository, H: SecretHasher> ApiKeyDomainService<K, H> {
+    pub fn new(api_key_repository: K, secret_hasher: H) -> Self {
+        Self { api_key_repository, secret_hasher }
+    }
+
+    /// Выпускает ключ и возвращает его вместе с полным значением
+    /// `<prefix>.<secret>`; секрет больше нигде не хранится.
+    pub async fn issue_key(
+        &self,
+        user_id: UserId,
+        name: String,
+        scopes: Vec<Scope>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<(ApiKey, String), DomainError> {
+        let prefix = self.unique_prefix().await?;
+        let secret = Alphanumeric.sample_string(&mut rand::rng(), SECRET_LENGTH);
+
+        let api_key = ApiKey::new(user_id, name, prefix, self.secret_hasher.hash(&secret), scopes, expires_at)?;
+        self.api_key_repository.save_api_key(&api_key).await?;
+
+        let token = format!("{}.{}", api_key.prefix(), secret);
+        Ok((api_key, token))
+    }
+
+    pub async fn list_keys(&self, user_id: &UserId) -> Result<Vec<ApiKey>, DomainError> {
+        self.api_key_repository.list_api_keys(user_id).await
+    }
+
+    pub async fn revoke_key(&self, user_id: &UserId, key_id: &Uuid) -> Result<ApiKey, DomainError> {
+        // Чужой ключ неотличим от несуществующего
+        let mut api_key = self.api_key_repository
+            .find_api_key(key_id)
+            .await?
+            .filter(|api_key| api_key.user_id() == user_id)
+            .ok_or(DomainError::ApiKeyNotFound)?;
+
+        api_key.revoke(Utc::now())?;
+        self.api_key_repository.save_api_key(&api_key).await?;
+
+        Ok(api_key)
+    }
+
+    pub async fn authenticate(&self, token: &str) -> Result<ApiKey, DomainError> {
+        let invalid = || DomainError::Unauthorized("unknown key or wrong secret".to_string());
+
+        let (prefix, secret) = token.split_once('.').ok_or_else(invalid)?;
+        let mut api_key = self.api_key_repository
+            .find_api_key_by_prefix(prefix)
+            .await?
+            .ok_or_else(invalid)?;
+
+        if !self.secret_hasher.verify(secret, api_key.secret_hash()) {
+            return Err(invalid());
+        }
+
+        let now = Utc::now();
+        if !api_key.is_active(now) {
+            return Err(DomainError::Unauthorized("key is expired or revoked".to_string()));
+        }
+
+        api_key.record_usage(now);
+        self.api_key_repository.save_api_key(&api_key).await?;
+
+        Ok(api_key)
+    }
+
+    async fn unique_prefix(&self) -> Result<String, DomainError> {
+        loop {
+            let prefix = format!("{}{}", KEY_PREFIX, Alphanumeric.sample_string(&mut rand::rng(), PREFIX_LENGTH));
+            if self.api_key_repository.find_api_key_by_prefix(&prefix).await?.is_none() {
+                return Ok(prefix);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::infrastructure::{InMemoryApiKeyRepository, Sha256SecretHasher};
+
+    fn service() -> ApiKeyDomainService<InMemoryApiKeyRepository, Sha256SecretHasher> {
+        ApiKeyDomainService::new(InMemoryApiKeyRepository::new(), Sha256SecretHasher::new())
+    }
+
+    #[tokio::test]
+    async fn test_issued_key_authenticates_and_records_usage() {
+        let service = service();
+        let user_id = UserId::new();
+
+        let (api_key, token) = service
+            .issue_key(user_id.clone(), "ci".to_string(), vec![Scope::UsersRead], Utc::now() + Duration::days(1))
+            .await
+            .unwrap();
+
+        assert!(token.starts_with(&format!("{}.", api_key.prefix())));
+        assert!(!api_key.secret_hash().contains(token.split_once('.').unwrap().1));
+
+        let authenticated = service.authenticate(&token).await.unwrap();
+        assert_eq!(authenticated.id(), api_key.id());
+        assert!(service.list_keys(&user_id).await.unwrap()[0].last_used_at().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_wrong_secret_and_revoked_key_are_rejected() {
+        let service = service();
+        let user_id = UserId::new();
+        let (api_key, token) = service
+            .issue_key(user_id.clone(), "ci".to_string(), vec![Scope::UsersRead], Utc::now() + Duration::days(1))
+            .await
+            .unwrap();
+
+        let forged = format!("{}.wrong", api_key.prefix());
+        assert!(matches!(service.authenticate(&forged).await, Err(DomainError::Unauthorized(_))));
+        assert!(service.authenticate("garbage").await.is_err());
+
+        assert!(matches!(
+            service.revoke_key(&UserId::new(), api_key.id()).await,
+            Err(DomainError::ApiKeyNotFound)
+        ));
+        service.revoke_key(&user_id, api_key.id()).await.unwrap();
+        assert!(matches!(service.authenticate(&token).await, Err(DomainError::Unauthorized(_))));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,11 @@
+use crate::domain::{AuditChainStatus, AuditEvent, AuditPage, AuditRecord, UserId, DomainError};
+
+/// Журнал аудита только дописывается: события не изменяются и не удаляются.
+/// Единственное исключение - стирание персональных значений по запросу
+/// пользователя, которое не затрагивает хеш-цепочку.
+pub trait AuditStore: Send + Sync {
+    async fn append_audit_record(&self, record: AuditRecord) -> Result<AuditEvent, DomainError>;
+    async fn list_user_audit_events(&self, user_id: &UserId, offset: usize, limit: usize) -> Result<AuditPage, DomainError>;
+    async fn verify_audit_chain(&self) -> Result<AuditChainStatus, DomainError>;
+    async fn erase_user_audit_data(&self, user_id: &UserId) -> Result<usize, DomainError>;
+}
\ No newline at end of file
@@ -0,0 +1,214 @@
+use uuid::Uuid;
+use crate::domain::{User, UserId, Role, Permission, Scope, AuthLevel, DomainError};
+
+#[derive(Debug, Clone)]
+pub struct Actor {
+    user_id: UserId,
+    role: Role,
+    // None - полный доступ роли (JWT), иначе только перечисленные области API-ключа
+    scopes: Option<Vec<Scope>>,
+    auth_level: AuthLevel,
+    // Сессия, через cookie которой пришел запрос
+    session_id: Option<Uuid>,
+    // API-ключ, которым аутентифицирован запрос
+    api_key_id: Option<Uuid>,
+}
+
+impl Actor {
+    pub fn new(user_id: UserId, role: Role) -> Self {
+        Self { user_id, role, scopes: None, auth_level: AuthLevel::SingleFactor, session_id: None, api_key_id: None }
+    }
+
+    pub fn with_auth_level(mut self, auth_level: AuthLevel) -> Self {
+        self.auth_level = auth_level;
+        self
+    }
+
+    pub fn with_scopes(mut self, scopes: Vec<Scope>) -> Self {
+        self.scopes = Some(scopes);
+        self
+    }
+
+    pub fn with_session(mut self, session_id: Uuid) -> Self {
+        self.session_id = Some(session_id);
+        self
+    }
+
+    pub fn with_api_key(mut self, api_key_id: Uuid) -> Self {
+        self.api_key_id = Some(api_key_id);
+        self
+    }
+
+    pub fn from_user(user: &User) -> Self {
+        Self::new(user.id().clone(), user.highest_role())
+    }
+
+    pub fn user_id(&self) -> &UserId {
+        &self.user_id
+    }
+
+    pub fn role(&self) -> Role {
+        self.role
+    }
+
+    pub fn scopes(&self) -> Option<&[Scope]> {
+        self.scopes.as_deref()
+    }
+
+    pub fn has_scope(&self, scope: Scope) -> bool {
+        self.scopes.as_ref().is_none_or(|scopes| scopes.contains(&scope))
+    }
+
+    pub fn auth_level(&self) -> AuthLevel {
+        self.auth_level
+    }
+
+    pub fn session_id(&self) -> Option<&Uuid> {
+        self.session_id.as_ref()
+    }
+
+    pub fn api_key_id(&self) -> Option<&Uuid> {
+        self.api_key_id.as_ref()
+    }
+}
+
+pub struct AuthorizationService;
+
+impl AuthorizationService {
+    pub fn authorize(actor: &Actor, permission: Permission, target: Option<&UserId>) -> Result<(), DomainError> {
+        let is_self = target.is_some_and(|target| target == actor.user_id());
+
+        if !Self::role_allows(actor.role(), permission, is_self) {
+            return Err(DomainError::Forbidden(format!(
+                "Role {} is not allowed to {}",
+                actor.role(),
+                permission.as_str()
+            )));
+        }
+
+        // Права администратора действуют только в сессии, подтвержденной MFA
+        if actor.role() >= Role::Admin
+            && actor.auth_level() != AuthLevel::MultiFactor
+            && !Self::role_allows(Role::Support, permission, is_self)
+        {
+            return Err(DomainError::Forbidden("MFA is required for admin accounts".to_string()));
+        }
+
+        // API-ключ сужает права роли до выданных ему областей
+        match (Scope::required_for(permission), actor.scopes()) {
+            (_, None) => Ok(()),
+            (Some(scope), Some(_)) if actor.has_scope(scope) => Ok(()),
+            (Some(scope), Some(_)) => Err(DomainError::Forbidden(format!("API key is missing scope {}", scope))),
+            (None, Some(_)) => Err(DomainError::Forbidden(format!(
+                "API keys are not allowed to {}",
+                permission.as_str()
+            ))),
+        }
+    }
+
+    fn role_allows(role: Role, permission: Permission, is_self: bool) -> bool {
+        match permission {
+            // Участник видит и редактирует только себя, поддержка читает всех
+            Permission::ReadUser | Permission::ReadAuditTrail => is_self || role >= Role::Support,
+            Permission::FindUserByEmail | Permission::WatchUserChanges | Permission::ListUsers => role >= Role::Support,
+            Permission::UpdateUser
+            | Permission::ManageApiKeys
+            | Permission::ManageSessions
+            | Permission::ExportUserData => is_self || role >= Role::Admin,
+            // MFA настраивает только сам пользователь: для этого нужен его TOTP-код
+            Permission::ManageMfa => is_self,
+            Permission::DeleteUser
+            | Permission::ChangeRoles
+            | Permission::UnlockUser
+            | Permission::ManageOutbox
+            | Permission::ManageWebhooks
+            | Permission::EraseUser
+            | Permission::VerifyAuditTrail => role >= Role::Admin,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_member_can_only_access_self() {
+        let actor = Actor::new(UserId::new(), Role::Member);
+        let other = UserId::new();
+
+        assert!(AuthorizationService::authorize(&actor, Permission::ReadUser, Some(actor.user_id())).is_ok());
+        assert!(AuthorizationService::authorize(&actor, Permission::UpdateUser, Some(actor.user_id())).is_ok());
+        assert!(AuthorizationService::authorize(&actor, Permission::ReadUser, Some(&other)).is_err());
+        assert!(AuthorizationService::authorize(&actor, Permission::UpdateUser, Some(&other)).is_err());
+        assert!(AuthorizationService::authorize(&actor, Permission::DeleteUser, Some(actor.user_id())).is_err());
+    }
+
+    #[test]
+    fn test_support_can_read_anyone() {
+        let actor = Actor::new(UserId::new(), Role::Support);
+        let other = UserId::new();
+
+        assert!(AuthorizationService::authorize(&actor, Permission::ReadUser, Some(&other)).is_ok());
+        assert!(AuthorizationService::authorize(&actor, Permission::FindUserByEmail, None).is_ok());
+        assert!(AuthorizationService::authorize(&actor, Permission::WatchUserChanges, None).is_ok());
+        assert!(AuthorizationService::authorize(&actor, Permission::ListUsers, None).is_ok());
+        assert!(AuthorizationService::authorize(&actor, Permission::UpdateUser, Some(&other)).is_err());
+        assert!(AuthorizationService::authorize(&actor, Permission::ChangeRoles, Some(&other)).is_err());
+        assert!(AuthorizationService::authorize(&actor, Permission::UnlockUser, Some(&other)).is_err());
+    }
+
+    #[test]
+    fn test_admin_can_do_everything() {
+        let actor = Actor::new(UserId::new(), Role::Admin).with_auth_level(AuthLevel::MultiFactor);
+        let other = UserId::new();
+
+        for permission in [
+            Permission::ReadUser,
+            Permission::UpdateUser,
+            Permission::DeleteUser,
+            Permission::ChangeRoles,
+            Permission::UnlockUser,
+            Permission::ManageApiKeys,
+            Permission::ManageOutbox,
+            Permission::ManageWebhooks,
+        ] {
+            assert!(AuthorizationService::authorize(&actor, permission, Some(&other)).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_admin_without_mfa_keeps_only_support_rights() {
+        let actor = Actor::new(UserId::new(), Role::Admin);
+        let other = UserId::new();
+
+        assert!(AuthorizationService::authorize(&actor, Permission::ReadUser, Some(&other)).is_ok());
+        assert!(AuthorizationService::authorize(&actor, Permission::ManageMfa, Some(actor.user_id())).is_ok());
+        assert!(matches!(
+            AuthorizationService::authorize(&actor, Permission::DeleteUser, Some(&other)),
+            Err(DomainError::Forbidden(message)) if message.contains("MFA")
+        ));
+    }
+
+    #[test]
+    fn test_api_key_scopes_narrow_role() {
+        let actor = Actor::new(UserId::new(), Role::Admin)
+            .with_auth_level(AuthLevel::MultiFactor)
+            .with_scopes(vec![Scope::UsersRead]);
+        let other = UserId::new();
+
+        assert!(AuthorizationService::authorize(&actor, Permission::ReadUser, Some(&other)).is_ok());
+        assert!(AuthorizationService::authorize(&actor, Permission::DeleteUser, Some(&other)).is_err());
+        assert!(AuthorizationService::authorize(&actor, Permission::ManageApiKeys, Some(actor.user_id())).is_err());
+        assert!(AuthorizationService::authorize(&actor, Permission::ManageMfa, Some(actor.user_id())).is_err());
+    }
+
+    #[test]
+    fn test_forbidden_error() {
+        let actor = Actor::new(UserId::new(), Role::Member);
+
+        let result = AuthorizationService::authorize(&actor, Permission::DeleteUser, None);
+
+        assert!(matches!(result, Err(DomainError::Forbidden(_))));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,6 @@
+use crate::domain::{ErasureReceipt, UserId, DomainError};
+
+pub trait ErasureReceiptRepository: Send + Sync {
+    async fn save_erasure_receipt(&self, receipt: &ErasureReceipt) -> Result<(), DomainError>;
+    async fn find_erasure_receipt(&self, user_id: &UserId) -> Result<Option<ErasureReceipt>, DomainError>;
+}
\ No newline at end of file
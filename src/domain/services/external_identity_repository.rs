@@ -0,0 +1,14 @@
+use crate::domain::{ExternalIdentity, OidcLoginRequest, UserId, DomainError};
+
+pub trait ExternalIdentityRepository: Send + Sync {
+    async fn find_identity(&self, provider: &str, subject: &str) -> Result<Option<ExternalIdentity>, DomainError>;
+    async fn list_identities(&self, user_id: &UserId) -> Result<Vec<ExternalIdentity>, DomainError>;
+    async fn save_identity(&self, identity: &ExternalIdentity) -> Result<(), DomainError>;
+    async fn delete_user_identities(&self, user_id: &UserId) -> Result<(), DomainError>;
+}
+
+pub trait OidcLoginStore: Send + Sync {
+    async fn save_login_request(&self, request: &OidcLoginRequest) -> Result<(), DomainError>;
+    // Запрос одноразовый: повторный callback с тем же state не пройдет
+    async fn take_login_request(&self, state: &str) -> Result<Option<OidcLoginRequest>, DomainError>;
+}
\ No newline at end of file
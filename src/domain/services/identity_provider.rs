@@ -0,0 +1,17 @@
+use crate::domain::{OidcLoginRequest, DomainError};
+
+// Утверждения о пользователе из проверенного ID-токена
+#[derive(Debug, Clone)]
+pub struct IdentityClaims {
+    pub subject: String,
+    pub email: Option<String>,
+    pub email_verified: bool,
+    pub name: Option<String>,
+}
+
+pub trait IdentityProvider: Send + Sync {
+    /// Адрес страницы входа провайдера для authorization code flow с PKCE.
+    async fn authorization_url(&self, request: &OidcLoginRequest) -> Result<String, DomainError>;
+    /// Обменивает код на ID-токен и возвращает его проверенные утверждения.
+    async fn exchange_code(&self, code: &str, request: &OidcLoginRequest) -> Result<IdentityClaims, DomainError>;
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+use crate::domain::{AttemptSubject, LoginAttempts, DomainError};
+
+pub trait LoginAttemptStore: Send + Sync {
+    async fn find_attempts(&self, subject: &AttemptSubject) -> Result<Option<LoginAttempts>, DomainError>;
+    async fn save_attempts(&self, attempts: &LoginAttempts) -> Result<(), DomainError>;
+    async fn delete_attempts(&self, subject: &AttemptSubject) -> Result<(), DomainError>;
+}
\ No newline at end of file
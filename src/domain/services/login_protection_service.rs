@@ -0,0 +1,133 @@
+use chrono::{DateTime, Utc};
+use crate::domain::{AttemptSubject, LockoutPolicy, LoginAttemptStore, LoginAttempts, UserId, DomainError};
+
+#[derive(Clone)]
+pub struct LoginProtectionDomainService<S: LoginAttemptStore> {
+    attempt_store: S,
+    policy: LockoutPolicy,
+}
+
+impl<S: LoginAttemptStore> LoginProtectionDomainService<S> {
+    pub fn new(attempt_store: S, policy: LockoutPolicy) -> Self {
+        Self { attempt_store, policy }
+    }
+
+    /// Отклоняет попытку, если аккаунт или адрес заблокирован либо еще не
+    /// истекла прогрессивная задержка после предыдущей ошибки.
+    pub async fn check(&self, subjects: &[AttemptSubject], now: DateTime<Utc>) -> Result<(), DomainError> {
+        for subject in subjects {
+            let Some(attempts) = self.attempt_store.find_attempts(subject).await? else {
+                continue;
+            };
+
+            if let (AttemptSubject::Account(_), Some(until)) = (subject, attempts.locked_until())
+                && attempts.is_locked(now)
+            {
+                return Err(DomainError::AccountLocked(*until));
+            }
+
+            if let Some(retry_at) = attempts.retry_at(&self.policy)
+                && retry_at > now
+            {
+                let seconds = ((retry_at - now).num_milliseconds().max(1) as u64).div_ceil(1000);
+                return Err(DomainError::TooManyAttempts(seconds));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Возвращает время окончания блокировки, если попытка ее вызвала.
+    pub async fn record_failure(&self, subject: &AttemptSubject, now: DateTime<Utc>) -> Result<Option<DateTime<Utc>>, DomainError> {
+        let mut attempts = self.attempt_store
+            .find_attempts(subject)
+            .await?
+            .unwrap_or_else(|| LoginAttempts::new(subject.clone()));
+
+        let locked_until = attempts.record_failure(now, &self.policy);
+        self.attempt_store.save_attempts(&attempts).await?;
+
+        Ok(locked_until)
+    }
+
+    pub async fn record_success(&self, subject: &AttemptSubject) -> Result<(), DomainError> {
+        self.attempt_store.delete_attempts(subject).await
+    }
+
+    /// Проверяет только блокировку аккаунта, без прогрессивной задержки: так
+    /// проверяются уже аутентифицированные запросы по токену или ключу.
+    pub async fn ensure_not_locked(&self, user_id: &UserId, now: DateTime<Utc>) -> Result<(), DomainError> {
+        let subject = AttemptSubject::Account(user_id.clone());
+        match self.attempt_store.find_attempts(&subject).await? {
+            Some(attempts) if attempts.is_locked(now) => match attempts.locked_until() {
+                Some(until) => Err(DomainError::AccountLocked(*until)),
+                None => Ok(()),
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Снимает блокировку аккаунта; возвращает true, если он был заблокирован.
+    pub async fn unlock(&self, user_id: &UserId, now: DateTime<Utc>) -> Result<bool, DomainError> {
+        let subject = AttemptSubject::Account(user_id.clone());
+        let was_locked = self.attempt_store
+            .find_attempts(&subject)
+            .await?
+            .is_some_and(|attempts| attempts.is_locked(now));
+
+        self.attempt_store.delete_attempts(&subject).await?;
+        Ok(was_locked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::infrastructure::InMemoryLoginAttemptStore;
+
+    fn service() -> LoginProtectionDomainService<InMemoryLoginAttemptStore> {
+        let policy = LockoutPolicy {
+            account_threshold: 2,
+            ip_threshold: 3,
+            ..LockoutPolicy::default()
+        };
+        LoginProtectionDomainService::new(InMemoryLoginAttemptStore::new(), policy)
+    }
+
+    #[tokio::test]
+    async fn test_account_is_locked_and_unlocked() {
+        let service = service();
+        let user_id = UserId::new();
+        let account = AttemptSubject::Account(user_id.clone());
+        let now = Utc::now();
+
+        assert!(service.record_failure(&account, now).await.unwrap().is_none());
+        assert!(matches!(service.check(std::slice::from_ref(&account), now).await, Err(DomainError::TooManyAttempts(1))));
+        assert!(service.ensure_not_locked(&user_id, now).await.is_ok());
+
+        let later = now + Duration::seconds(5);
+        assert!(service.check(std::slice::from_ref(&account), later).await.is_ok());
+        let until = service.record_failure(&account, later).await.unwrap().unwrap();
+        assert!(matches!(service.check(std::slice::from_ref(&account), later).await, Err(DomainError::AccountLocked(t)) if t == until));
+        assert!(matches!(service.ensure_not_locked(&user_id, later).await, Err(DomainError::AccountLocked(t)) if t == until));
+
+        assert!(service.unlock(&user_id, later).await.unwrap());
+        assert!(service.check(&[account], later).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_ip_is_throttled_independently() {
+        let service = service();
+        let ip = AttemptSubject::ClientIp("192.0.2.7".parse().unwrap());
+        let account = AttemptSubject::Account(UserId::new());
+        let now = Utc::now();
+
+        for offset in 0..3 {
+            service.record_failure(&ip, now + Duration::minutes(offset)).await.unwrap();
+        }
+
+        let result = service.check(&[account, ip], now + Duration::minutes(3)).await;
+        assert!(matches!(result, Err(DomainError::TooManyAttempts(_))));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+use crate::domain::{MfaEnrollment, UserId, DomainError};
+
+pub trait MfaRepository: Send + Sync {
+    async fn find_mfa_enrollment(&self, user_id: &UserId) -> Result<Option<MfaEnrollment>, DomainError>;
+    async fn save_mfa_enrollment(&self, enrollment: &MfaEnrollment) -> Result<(), DomainError>;
+    async fn delete_mfa_enrollment(&self, user_id: &UserId) -> Result<(), DomainError>;
+}
\ No newline at end of file
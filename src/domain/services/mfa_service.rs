@@ -0,0 +1,219 @@
+use chrono::Utc;
+use rand::distr::{Alphanumeric, SampleString};
+use crate::domain::{
+    MfaEnrollment, MfaRepository, SecretCipher, SecretHasher, TotpService, User, UserId, DomainError,
+};
+
+const RECOVERY_CODE_COUNT: usize = 10;
+const RECOVERY_CODE_LENGTH: usize = 10;
+
+#[derive(Debug, Clone)]
+pub enum MfaCode {
+    Totp(String),
+    Recovery(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct MfaSetup {
+    pub secret: String,
+    pub otpauth_uri: String,
+}
+
+#[derive(Clone)]
+pub struct MfaDomainService<M: MfaRepository, C: SecretCipher, O: TotpService, H: SecretHasher> {
+    mfa_repository: M,
+    secret_cipher: C,
+    totp_service: O,
+    secret_hasher: H,
+}
+
+impl<M: MfaRepository, C: SecretCipher, O: TotpService, H: SecretHasher> MfaDomainService<M, C, O, H> {
+    pub fn new(mfa_repository: M, secret_cipher: C, totp_service: O, secret_hasher: H) -> Self {
+        Self { mfa_repository, secret_cipher, totp_service, secret_hasher }
+    }
+
+    /// Выпускает новый секрет; незавершенная регистрация перезаписывается,
+    /// включенную MFA нужно сначала отключить.
+    pub async fn enroll(&self, user: &User) -> Result<MfaSetup, DomainError> {
+        if let Some(existing) = self.mfa_repository.find_mfa_enrollment(user.id()).await?
+            && existing.is_active()
+        {
+            return Err(DomainError::InvalidOperation("MFA is already enabled".to_string()));
+        }
+
+        let secret = self.totp_service.generate_secret();
+        let encrypted_secret = self.secret_cipher.encrypt(&secret, &user.id().to_string())?;
+        self.mfa_repository
+            .save_mfa_enrollment(&MfaEnrollment::new(user.id().clone(), encrypted_secret))
+            .await?;
+
+        Ok(MfaSetup {
+            secret: self.totp_service.encode_secret(&secret),
+            otpauth_uri: self.totp_service.provisioning_uri(&secret, user.email().as_str()),
+        })
+    }
+
+    // Секрет показывается только до подтверждения регистрации
+    pub async fn provisioning_uri(&self, user: &User) -> Result<String, DomainError> {
+        let enrollment = self.find_enrollment(user.id()).await?;
+        if enrollment.is_active() {
+            return Err(DomainError::InvalidOperation("MFA is already enabled".to_string()));
+        }
+
+        let secret = self.decrypt_secret(&enrollment)?;
+        Ok(self.totp_service.provisioning_uri(&secret, user.email().as_str()))
+    }
+
+    /// Подтверждает регистрацию кодом из приложения и возвращает одноразовые
+    /// коды восстановления; в хранилище остаются только их хеши.
+    pub async fn confirm(&self, user_id: &UserId, code: &str) -> Result<Vec<String>, DomainError> {
+        let mut enrollment = self.find_enrollment(user_id).await?;
+        if enrollment.is_active() {
+            return Err(DomainError::InvalidOperation("MFA is already enabled".to_string()));
+        }
+
+        self.verify_totp(&mut enrollment, code)?;
+
+        let recovery_codes: Vec<String> = (0..RECOVERY_CODE_COUNT)
+            .map(|_| {
+                let raw = Alphanumeric.sample_string(&mut rand::rng(), RECOVERY_CODE_LENGTH).to_lowercase();
+                format!("{}-{}", &raw[..RECOVERY_CODE_LENGTH / 2], &raw[RECOVERY_CODE_LENGTH / 2..])
+            })
+            .collect();
+        let hashes = recovery_codes
+            .iter()
+            .map(|code| self.secret_hasher.hash(&Self::normalize_recovery_code(code)))
+            .collect();
+
+        enrollment.activate(Utc::now(), hashes)?;
+        self.mfa_repository.save_mfa_enrollment(&enrollment).await?;
+
+        Ok(recovery_codes)
+    }
+
+    pub async fn verify(&self, user_id: &UserId, code: &MfaCode) -> Result<MfaEnrollment, DomainError> {
+        let mut enrollment = self.find_enrollment(user_id).await?;
+        if !enrollment.is_active() {
+            return Err(DomainError::MfaNotEnrolled);
+        }
+
+        match code {
+            MfaCode::Totp(code) => self.verify_totp(&mut enrollment, code)?,
+            MfaCode::Recovery(code) => {
+                let code = Self::normalize_recovery_code(code);
+                let index = enrollment
+                    .recovery_code_hashes()
+                    .iter()
+                    .position(|hash| self.secret_hasher.verify(&code, hash))
+                    .ok_or(DomainError::InvalidMfaCode)?;
+                enrollment.consume_recovery_code(index)?;
+            }
+        }
+
+        self.mfa_repository.save_mfa_enrollment(&enrollment).await?;
+        Ok(enrollment)
+    }
+
+    // Отключение требует повторной проверки второго фактора
+    pub async fn disable(&self, user_id: &UserId, code: &MfaCode) -> Result<(), DomainError> {
+        self.verify(user_id, code).await?;
+        self.mfa_repository.delete_mfa_enrollment(user_id).await
+    }
+
+    pub async fn status(&self, user_id: &UserId) -> Result<Option<MfaEnrollment>, DomainError> {
+        self.mfa_repository.find_mfa_enrollment(user_id).await
+    }
+
+    async fn find_enrollment(&self, user_id: &UserId) -> Result<MfaEnrollment, DomainError> {
+        self.mfa_repository
+            .find_mfa_enrollment(user_id)
+            .await?
+            .ok_or(DomainError::MfaNotEnrolled)
+    }
+
+    fn decrypt_secret(&self, enrollment: &MfaEnrollment) -> Result<Vec<u8>, DomainError> {
+        self.secret_cipher.decrypt(enrollment.encrypted_secret(), &enrollment.user_id().to_string())
+    }
+
+    fn verify_totp(&self, enrollment: &mut MfaEnrollment, code: &str) -> Result<(), DomainError> {
+        let secret = self.decrypt_secret(enrollment)?;
+        let step = self.totp_service
+            .verify(&secret, code.trim(), Utc::now())
+            .ok_or(DomainError::InvalidMfaCode)?;
+        enrollment.accept_step(step)
+    }
+
+    fn normalize_recovery_code(code: &str) -> String {
+        code.chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_lowercase())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use data_encoding::BASE32_NOPAD;
+    use crate::domain::Email;
+    use crate::infrastructure::{AesGcmSecretCipher, InMemoryMfaRepository, Rfc6238TotpService, Sha256SecretHasher};
+
+    type TestMfaService = MfaDomainService<InMemoryMfaRepository, AesGcmSecretCipher, Rfc6238TotpService, Sha256SecretHasher>;
+
+    fn service() -> TestMfaService {
+        MfaDomainService::new(
+            InMemoryMfaRepository::new(),
+            AesGcmSecretCipher::new(&[1u8; 32]),
+            Rfc6238TotpService::new("Test"),
+            Sha256SecretHasher::new(),
+        )
+    }
+
+    fn test_user() -> User {
+        let email = Email::new("mfa@example.com".to_string()).unwrap();
+        User::new(email, "Mfa User".to_string()).unwrap()
+    }
+
+    fn current_code(setup: &MfaSetup) -> String {
+        let secret = BASE32_NOPAD.decode(setup.secret.as_bytes()).unwrap();
+        Rfc6238TotpService::code_at(&secret, (Utc::now().timestamp() / 30) as u64)
+    }
+
+    #[tokio::test]
+    async fn test_enroll_confirm_and_verify() {
+        let service = service();
+        let user = test_user();
+
+        let setup = service.enroll(&user).await.unwrap();
+        assert!(setup.otpauth_uri.starts_with("otpauth://totp/Test:mfa%40example.com?secret="));
+        assert!(matches!(service.confirm(user.id(), "000000x").await, Err(DomainError::InvalidMfaCode)));
+
+        let code = current_code(&setup);
+        let recovery_codes = service.confirm(user.id(), &code).await.unwrap();
+        assert_eq!(recovery_codes.len(), RECOVERY_CODE_COUNT);
+        assert!(service.enroll(&user).await.is_err());
+
+        // Тот же TOTP-код второй раз не принимается
+        assert!(service.verify(user.id(), &MfaCode::Totp(code)).await.is_err());
+
+        let recovery = MfaCode::Recovery(recovery_codes[0].to_uppercase());
+        let enrollment = service.verify(user.id(), &recovery).await.unwrap();
+        assert_eq!(enrollment.recovery_code_hashes().len(), RECOVERY_CODE_COUNT - 1);
+        assert!(service.verify(user.id(), &recovery).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_disable_requires_valid_code() {
+        let service = service();
+        let user = test_user();
+        let setup = service.enroll(&user).await.unwrap();
+        let recovery_codes = service.confirm(user.id(), &current_code(&setup)).await.unwrap();
+
+        let wrong = MfaCode::Recovery("not-a-code".to_string());
+        assert!(matches!(service.disable(user.id(), &wrong).await, Err(DomainError::InvalidMfaCode)));
+        assert!(service.status(user.id()).await.unwrap().is_some());
+
+        service.disable(user.id(), &MfaCode::Recovery(recovery_codes[1].clone())).await.unwrap();
+        assert!(service.status(user.id()).await.unwrap().is_none());
+    }
+}
\ No newline at end of file
@@ -1,3 +1,51 @@
 pub mod user_service;
+pub mod outbox_repository;
+pub mod authorization_service;
+pub mod token_service;
+pub mod secret_hasher;
+pub mod api_key_repository;
+pub mod api_key_service;
+pub mod secret_cipher;
+pub mod totp_service;
+pub mod mfa_repository;
+pub mod mfa_service;
+pub mod login_attempt_store;
+pub mod login_protection_service;
+pub mod session_store;
+pub mod session_service;
+pub mod external_identity_repository;
+pub mod identity_provider;
+pub mod oidc_login_service;
+pub mod rate_limit_store;
+pub mod pii_cipher;
+pub mod erasure_receipt_repository;
+pub mod audit_store;
+pub mod user_event_bus;
+pub mod user_history;
+pub mod webhook_repository;
 
-pub use user_service::*;
\ No newline at end of file
+pub use user_service::*;
+pub use outbox_repository::*;
+pub use authorization_service::*;
+pub use token_service::*;
+pub use secret_hasher::*;
+pub use api_key_repository::*;
+pub use api_key_service::*;
+pub use secret_cipher::*;
+pub use totp_service::*;
+pub use mfa_repository::*;
+pub use mfa_service::*;
+pub use login_attempt_store::*;
+pub use login_protection_service::*;
+pub use session_store::*;
+pub use session_service::*;
+pub use external_identity_repository::*;
+pub use identity_provider::*;
+pub use oidc_login_service::*;
+pub use rate_limit_store::*;
+pub use pii_cipher::*;
+pub use erasure_receipt_repository::*;
+pub use audit_store::*;
+pub use user_event_bus::*;
+pub use user_history::*;
+pub use webhook_repository::*;
\ No newline at end of file
@@ -0,0 +1,177 @@
+use chrono::{DateTime, Utc};
+use rand::distr::{Alphanumeric, SampleString};
+use crate::domain::{
+    Email, ExternalIdentity, ExternalIdentityRepository, IdentityClaims, OidcLoginRequest, OidcLoginStore,
+    OutboxMessage, OutboxPayload, User, UserRepository, DomainError,
+};
+
+const STATE_LENGTH: usize = 32;
+const NONCE_LENGTH: usize = 32;
+// RFC 7636: от 43 до 128 символов
+const CODE_VERIFIER_LENGTH: usize = 64;
+
+#[derive(Clone)]
+pub struct OidcLoginDomainService<R: UserRepository, I: ExternalIdentityRepository, L: OidcLoginStore> {
+    user_repository: R,
+    identity_repository: I,
+    login_store: L,
+}
+
+impl<R: UserRepository, I: ExternalIdentityRepository, L: OidcLoginStore> OidcLoginDomainService<R, I, L> {
+    pub fn new(user_repository: R, identity_repository: I, login_store: L) -> Self {
+        Self { user_repository, identity_repository, login_store }
+    }
+
+    pub async fn start_login(&self, provider: &str, now: DateTime<Utc>) -> Result<OidcLoginRequest, DomainError> {
+        // ThreadRng не Send: генератор не должен пережить await
+        let request = {
+            let mut rng = rand::rng();
+            OidcLoginRequest::new(
+                provider.to_string(),
+                Alphanumeric.sample_string(&mut rng, STATE_LENGTH),
+                Alphanumeric.sample_string(&mut rng, NONCE_LENGTH),
+                Alphanumeric.sample_string(&mut rng, CODE_VERIFIER_LENGTH),
+                now,
+            )
+        };
+        self.login_store.save_login_request(&request).await?;
+
+        Ok(request)
+    }
+
+    pub async fn resume_login(&self, provider: &str, state: &str, now: DateTime<Utc>) -> Result<OidcLoginRequest, DomainError> {
+        let request = self.login_store
+            .take_login_request(state)
+            .await?
+            .filter(|request| request.provider() == provider)
+            .ok_or_else(|| DomainError::Unauthorized("unknown or already used login state".to_string()))?;
+
+        if request.is_expired(now) {
+            return Err(DomainError::Unauthorized("login request has expired".to_string()));
+        }
+
+        Ok(request)
+    }
+
+    /// Находит пользователя для внешней учетной записи: по сохраненной связи,
+    /// затем по подтвержденному email; если совпадений нет - создает нового.
+    /// Возвращает пользователя и признак того, что он только что создан.
+    pub async fn link_identity(
+        &self,
+        provider: &str,
+        claims: &IdentityClaims,
+        now: DateTime<Utc>,
+    ) -> Result<(User, bool), DomainError> {
+        if let Some(mut identity) = self.identity_repository.find_identity(provider, &claims.subject).await?
+            && let Some(user) = self.user_repository.find_by_id(identity.user_id()).await?
+        {
+            identity.record_login(now);
+            self.identity_repository.save_identity(&identity).await?;
+            return Ok((user, false));
+        }
+
+        // Иначе любой провайдер смог бы войти в чужой аккаунт, назвав его email
+        let email = match (&claims.email, claims.email_verified) {
+            (Some(email), true) => Email::new(email.clone())?,
+            _ => return Err(DomainError::Unauthorized("identity provider did not return a verified email".to_string())),
+        };
+
+        let (user, created) = match self.user_repository.find_by_email(&email).await? {
+            Some(user) => (user, false),
+            None => (self.provision_user(email, claims.name.as_deref()).await?, true),
+        };
+
+        let identity = ExternalIdentity::new(provider.to_string(), claims.subject.clone(), user.id().clone(), now);
+        self.identity_repository.save_identity(&identity).await?;
+
+        Ok((user, created))
+    }
+
+    async fn provision_user(&self, email: Email, name: Option<&str>) -> Result<User, DomainError> {
+        let name = name
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| email.as_str().split('@').next().unwrap_or_default())
+            .to_string();
+
+        let user = User::new(email, name)?;
+        let messages = vec![
+            OutboxMessage::new(OutboxPayload::WelcomeEmail { user_id: user.id().clone() }),
+            OutboxMessage::new(OutboxPayload::UserCreated { user_id: user.id().clone() }),
+        ];
+        self.user_repository.save_with_outbox(&user, messages).await?;
+
+        Ok(user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::infrastructure::{InMemoryExternalIdentityRepository, InMemoryOidcLoginStore, InMemoryUserRepository};
+
+    fn service() -> OidcLoginDomainService<InMemoryUserRepository, InMemoryExternalIdentityRepository, InMemoryOidcLoginStore> {
+        OidcLoginDomainService::new(
+            InMemoryUserRepository::new(),
+            InMemoryExternalIdentityRepository::new(),
+            InMemoryOidcLoginStore::new(),
+        )
+    }
+
+    fn claims(subject: &str, email: &str, email_verified: bool) -> IdentityClaims {
+        IdentityClaims {
+            subject: subject.to_string(),
+            email: Some(email.to_string()),
+            email_verified,
+            name: Some("Jane Doe".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_login_state_is_single_use_and_bound_to_provider() {
+        let service = service();
+        let now = Utc::now();
+
+        let request = service.start_login("corp", now).await.unwrap();
+        assert!(service.resume_login("other", request.state(), now).await.is_err());
+
+        let request = service.start_login("corp", now).await.unwrap();
+        assert!(service.resume_login("corp", request.state(), now).await.is_ok());
+        assert!(service.resume_login("corp", request.state(), now).await.is_err());
+
+        let request = service.start_login("corp", now).await.unwrap();
+        let late = now + Duration::minutes(OidcLoginRequest::TTL_MINUTES);
+        assert!(service.resume_login("corp", request.state(), late).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_first_login_provisions_and_next_login_reuses_link() {
+        let service = service();
+        let now = Utc::now();
+
+        let (user, created) = service.link_identity("corp", &claims("sub-1", "jane@example.com", true), now).await.unwrap();
+        assert!(created);
+        assert_eq!(user.name(), "Jane Doe");
+
+        // Email у провайдера сменился, но связь по sub сохраняется
+        let (again, created) = service.link_identity("corp", &claims("sub-1", "jane@new.example.com", false), now).await.unwrap();
+        assert!(!created);
+        assert_eq!(again.id(), user.id());
+    }
+
+    #[tokio::test]
+    async fn test_existing_user_is_linked_only_by_verified_email() {
+        let service = service();
+        let now = Utc::now();
+        let existing = User::new(Email::new("jane@example.com".to_string()).unwrap(), "Jane".to_string()).unwrap();
+        service.user_repository.save(&existing).await.unwrap();
+
+        let unverified = service.link_identity("corp", &claims("sub-1", "jane@example.com", false), now).await;
+        assert!(matches!(unverified, Err(DomainError::Unauthorized(_))));
+
+        let (user, created) = service.link_identity("corp", &claims("sub-1", "jane@example.com", true), now).await.unwrap();
+        assert!(!created);
+        assert_eq!(user.id(), existing.id());
+    }
+}
\ No newline at end of file
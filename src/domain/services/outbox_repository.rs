@@ -0,0 +1,17 @@
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+use crate::domain::{OutboxMessage, OutboxStatus, UserId, DomainError};
+
+// Сообщения попадают в outbox только вместе с изменением пользователя
+// (см. `UserRepository::save_with_outbox`), поэтому метода вставки здесь нет.
+pub trait OutboxRepository: Send + Sync {
+    async fn claim_due(&self, now: DateTime<Utc>, lease: Duration, limit: usize) -> Result<Vec<OutboxMessage>, DomainError>;
+    async fn find_outbox_message(&self, id: &Uuid) -> Result<Option<OutboxMessage>, DomainError>;
+    async fn list_outbox_messages(&self, status: Option<OutboxStatus>, limit: usize) -> Result<Vec<OutboxMessage>, DomainError>;
+    async fn update_outbox_message(&self, message: &OutboxMessage) -> Result<(), DomainError>;
+    // Удаляет сообщения, доставленные раньше `before`, возвращает их число
+    async fn prune_delivered_outbox_messages(&self, before: DateTime<Utc>) -> Result<usize, DomainError>;
+    async fn list_user_outbox_messages(&self, user_id: &UserId) -> Result<Vec<OutboxMessage>, DomainError>;
+    // Отменяет недоставленные письма пользователю, возвращает их число
+    async fn cancel_user_emails(&self, user_id: &UserId) -> Result<usize, DomainError>;
+}
\ No newline at end of file
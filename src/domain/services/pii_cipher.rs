@@ -0,0 +1,26 @@
+use crate::domain::{User, Email, EncryptedUser, DomainError};
+
+// Шифрование персональных данных пользователя (envelope encryption):
+// у каждой записи свой ключ данных, обернутый активным мастер-ключом
+pub trait PiiCipher: Send + Sync {
+    fn seal(&self, user: &User) -> Result<EncryptedUser, DomainError>;
+    fn open(&self, record: &EncryptedUser) -> Result<User, DomainError>;
+    // Детерминированный keyed-хеш email для поиска без расшифровки
+    fn blind_index(&self, email: &Email) -> String;
+    // Keyed-хеш значения в контексте (пользователь и поле): журнал аудита
+    // хранит его в хеш-цепочке вместо самого значения
+    fn digest(&self, context: &str, value: &str) -> String;
+    // Отдельное значение вне записи пользователя (например, в файле журнала аудита)
+    fn seal_value(&self, context: &str, value: &str) -> Result<String, DomainError>;
+    fn open_value(&self, context: &str, sealed: &str) -> Result<String, DomainError>;
+    // Ключ данных обернут не активным мастер-ключом
+    fn needs_rotation(&self, record: &EncryptedUser) -> bool;
+    // Переоборачивает ключ данных активным мастер-ключом, поля не меняются
+    fn rotate(&self, record: &EncryptedUser) -> Result<EncryptedUser, DomainError>;
+}
+
+// Хранилище, которое умеет переводить записи на активный мастер-ключ
+pub trait PiiKeyRotation: Send + Sync {
+    // Переоборачивает до `limit` записей и возвращает их число
+    async fn rotate_pii_keys(&self, limit: usize) -> Result<usize, DomainError>;
+}
\ No newline at end of file
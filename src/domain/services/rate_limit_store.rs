@@ -0,0 +1,8 @@
+use chrono::{DateTime, Utc};
+use crate::domain::{RateLimitDecision, RateLimitQuota, DomainError};
+
+pub trait RateLimitStore: Send + Sync {
+    /// Атомарно проверяет квоту для ключа и, если запрос разрешен,
+    /// расходует его из бюджета.
+    async fn acquire(&self, key: &str, quota: &RateLimitQuota, now: DateTime<Utc>) -> Result<RateLimitDecision, DomainError>;
+}
\ No newline at end of file
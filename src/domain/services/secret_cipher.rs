@@ -0,0 +1,8 @@
+use crate::domain::DomainError;
+
+// Обратимое шифрование секретов, которые нужно хранить в открытом виде
+// для проверки (например, TOTP). `context` привязывает шифротекст к владельцу
+pub trait SecretCipher: Send + Sync {
+    fn encrypt(&self, plaintext: &[u8], context: &str) -> Result<String, DomainError>;
+    fn decrypt(&self, ciphertext: &str, context: &str) -> Result<Vec<u8>, DomainError>;
+}
\ No newline at end of file
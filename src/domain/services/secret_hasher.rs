@@ -0,0 +1,6 @@
+// Хеширование выдаваемых пользователю секретов (API-ключи и т.п.):
+// в хранилище попадает только хеш, сам секрет показывается один раз
+pub trait SecretHasher: Send + Sync {
+    fn hash(&self, secret: &str) -> String;
+    fn verify(&self, secret: &str, hash: &str) -> bool;
+}
\ No newline at end of file
@@ -0,0 +1,165 @@
+use chrono::{DateTime, Utc};
+use rand::distr::{Alphanumeric, SampleString};
+use uuid::Uuid;
+use crate::domain::{
+    AuthLevel, SecretHasher, Session, SessionMetadata, SessionPolicy, SessionStore, UserId, DomainError,
+};
+
+const SESSION_TOKEN_LENGTH: usize = 48;
+const CSRF_TOKEN_LENGTH: usize = 32;
+
+// Значения для cookie; показываются клиенту только при создании сессии
+#[derive(Debug, Clone)]
+pub struct SessionTokens {
+    pub session_token: String,
+    pub csrf_token: String,
+}
+
+#[derive(Clone)]
+pub struct SessionDomainService<S: SessionStore, H: SecretHasher> {
+    session_store: S,
+    secret_hasher: H,
+    policy: SessionPolicy,
+}
+
+impl<S: SessionStore, H: SecretHasher> SessionDomainService<S, H> {
+    pub fn new(session_store: S, secret_hasher: H, policy: SessionPolicy) -> Self {
+        Self { session_store, secret_hasher, policy }
+    }
+
+    pub fn policy(&self) -> &SessionPolicy {
+        &self.policy
+    }
+
+    pub async fn start(
+        &self,
+        user_id: UserId,
+        auth_level: AuthLevel,
+        metadata: SessionMetadata,
+        now: DateTime<Utc>,
+    ) -> Result<(Session, SessionTokens), DomainError> {
+        let tokens = SessionTokens {
+            session_token: Alphanumeric.sample_string(&mut rand::rng(), SESSION_TOKEN_LENGTH),
+            csrf_token: Alphanumeric.sample_string(&mut rand::rng(), CSRF_TOKEN_LENGTH),
+        };
+
+        let session = Session::new(
+            user_id,
+            self.secret_hasher.hash(&tokens.session_token),
+            self.secret_hasher.hash(&tokens.csrf_token),
+            auth_level,
+            metadata,
+            now,
+            &self.policy,
+        );
+        self.session_store.save_session(&session).await?;
+
+        Ok((session, tokens))
+    }
+
+    /// Находит сессию по значению cookie и продлевает ее простой.
+    pub async fn authenticate(&self, session_token: &str, now: DateTime<Utc>) -> Result<Session, DomainError> {
+        let mut session = self.session_store
+            .find_session_by_token_hash(&self.secret_hasher.hash(session_token))
+            .await?
+            .ok_or_else(|| DomainError::Unauthorized("unknown session".to_string()))?;
+
+        if !session.is_active(now, &self.policy) {
+            self.session_store.delete_session(session.id()).await?;
+            return Err(DomainError::Unauthorized("session has expired".to_string()));
+        }
+
+        session.touch(now);
+        self.session_store.save_session(&session).await?;
+
+        Ok(session)
+    }
+
+    // CSRF-токен привязан к сессии: чужой или подставленный токен не подойдет
+    pub fn verify_csrf(&self, session: &Session, csrf_token: &str) -> Result<(), DomainError> {
+        match self.secret_hasher.verify(csrf_token, session.csrf_token_hash()) {
+            true => Ok(()),
+            false => Err(DomainError::Forbidden("CSRF token mismatch".to_string())),
+        }
+    }
+
+    pub async fn list_sessions(&self, user_id: &UserId, now: DateTime<Utc>) -> Result<Vec<Session>, DomainError> {
+        let sessions = self.session_store.list_sessions(user_id).await?;
+        Ok(sessions
+            .into_iter()
+            .filter(|session| session.is_active(now, &self.policy))
+            .collect())
+    }
+
+    pub async fn revoke(&self, user_id: &UserId, session_id: &Uuid) -> Result<(), DomainError> {
+        // Чужая сессия неотличима от несуществующей
+        let session = self.session_store
+            .find_session(session_id)
+            .await?
+            .filter(|session| session.user_id() == user_id)
+            .ok_or(DomainError::SessionNotFound)?;
+
+        self.session_store.delete_session(session.id()).await
+    }
+
+    pub async fn revoke_all(&self, user_id: &UserId) -> Result<(), DomainError> {
+        self.session_store.delete_user_sessions(user_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::infrastructure::{InMemorySessionStore, Sha256SecretHasher};
+
+    fn service() -> SessionDomainService<InMemorySessionStore, Sha256SecretHasher> {
+        SessionDomainService::new(InMemorySessionStore::new(), Sha256SecretHasher::new(), SessionPolicy::default())
+    }
+
+    #[tokio::test]
+    async fn test_session_authenticates_until_idle_timeout() {
+        let service = service();
+        let now = Utc::now();
+
+        let (session, tokens) = service
+            .start(UserId::new(), AuthLevel::MultiFactor, SessionMetadata::default(), now)
+            .await
+            .unwrap();
+        assert_ne!(session.token_hash(), tokens.session_token);
+
+        let authenticated = service.authenticate(&tokens.session_token, now + Duration::minutes(10)).await.unwrap();
+        assert_eq!(authenticated.id(), session.id());
+        assert_eq!(authenticated.auth_level(), AuthLevel::MultiFactor);
+
+        let result = service.authenticate(&tokens.session_token, now + Duration::minutes(45)).await;
+        assert!(matches!(result, Err(DomainError::Unauthorized(_))));
+        assert!(service.list_sessions(session.user_id(), now).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_csrf_token_is_bound_to_session() {
+        let service = service();
+        let now = Utc::now();
+        let user_id = UserId::new();
+
+        let (session, tokens) = service.start(user_id.clone(), AuthLevel::SingleFactor, SessionMetadata::default(), now).await.unwrap();
+        let (_, other_tokens) = service.start(user_id, AuthLevel::SingleFactor, SessionMetadata::default(), now).await.unwrap();
+
+        assert!(service.verify_csrf(&session, &tokens.csrf_token).is_ok());
+        assert!(matches!(service.verify_csrf(&session, &other_tokens.csrf_token), Err(DomainError::Forbidden(_))));
+    }
+
+    #[tokio::test]
+    async fn test_revoke_only_own_sessions() {
+        let service = service();
+        let now = Utc::now();
+        let owner = UserId::new();
+
+        let (session, tokens) = service.start(owner.clone(), AuthLevel::SingleFactor, SessionMetadata::default(), now).await.unwrap();
+
+        assert!(matches!(service.revoke(&UserId::new(), session.id()).await, Err(DomainError::SessionNotFound)));
+        service.revoke(&owner, session.id()).await.unwrap();
+        assert!(service.authenticate(&tokens.session_token, now).await.is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,11 @@
+use uuid::Uuid;
+use crate::domain::{Session, UserId, DomainError};
+
+pub trait SessionStore: Send + Sync {
+    async fn find_session(&self, id: &Uuid) -> Result<Option<Session>, DomainError>;
+    async fn find_session_by_token_hash(&self, token_hash: &str) -> Result<Option<Session>, DomainError>;
+    async fn list_sessions(&self, user_id: &UserId) -> Result<Vec<Session>, DomainError>;
+    async fn save_session(&self, session: &Session) -> Result<(), DomainError>;
+    async fn delete_session(&self, id: &Uuid) -> Result<(), DomainError>;
+    async fn delete_user_sessions(&self, user_id: &UserId) -> Result<(), DomainError>;
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+use crate::domain::{UserId, DomainError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuthLevel {
+    SingleFactor,
+    // Токен выдан после прохождения TOTP-проверки
+    MultiFactor,
+}
+
+impl AuthLevel {
+    pub fn parse(value: &str) -> Result<Self, DomainError> {
+        match value {
+            "single_factor" => Ok(AuthLevel::SingleFactor),
+            "multi_factor" => Ok(AuthLevel::MultiFactor),
+            other => Err(DomainError::InvalidOperation(format!("Unknown auth level: {}", other))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuthLevel::SingleFactor => "single_factor",
+            AuthLevel::MultiFactor => "multi_factor",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifiedToken {
+    pub user_id: UserId,
+    pub auth_level: AuthLevel,
+}
+
+pub trait TokenService: Send + Sync {
+    fn issue(&self, user_id: &UserId, auth_level: AuthLevel) -> Result<String, DomainError>;
+    fn verify(&self, token: &str) -> Result<VerifiedToken, DomainError>;
+}
\ No newline at end of file
@@ -0,0 +1,9 @@
+use chrono::{DateTime, Utc};
+
+pub trait TotpService: Send + Sync {
+    fn generate_secret(&self) -> Vec<u8>;
+    fn encode_secret(&self, secret: &[u8]) -> String;
+    fn provisioning_uri(&self, secret: &[u8], account: &str) -> String;
+    // Номер временного шага, которому соответствует код, или None
+    fn verify(&self, secret: &[u8], code: &str, now: DateTime<Utc>) -> Option<u64>;
+}
\ No newline at end of file
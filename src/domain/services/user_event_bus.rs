@@ -0,0 +1,81 @@
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+use crate::domain::{UserEvent, UserEventKind};
+
+type BoxedHandler = Arc<dyn Fn(&UserEvent) -> Option<Pin<Box<dyn Future<Output = ()> + Send>>> + Send + Sync>;
+
+/// Шина доменных событий пользователя внутри процесса. Подписчик получает
+/// только события своего типа (`UserRenamed`, `UserDeleted`...) или все
+/// события, если подписан на `UserEvent`.
+///
+/// События публикуются после сохранения агрегата, поэтому ошибки
+/// подписчиков изменение не откатывают - подписчик обрабатывает их сам.
+#[derive(Clone, Default)]
+pub struct UserEventBus {
+    handlers: Arc<RwLock<Vec<BoxedHandler>>>,
+}
+
+impl UserEventBus {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn subscribe<E, F, Fut>(&self, handler: F)
+    where
+        E: UserEventKind,
+        F: Fn(E) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let handler: BoxedHandler = Arc::new(move |event: &UserEvent| {
+            E::from_user_event(event).map(|event| Box::pin(handler(event)) as Pin<Box<dyn Future<Output = ()> + Send>>)
+        });
+        self.handlers.write().expect("Event bus lock poisoned").push(handler);
+    }
+
+    // Подписчики вызываются по очереди в порядке подписки, каждое событие -
+    // после завершения обработки предыдущего
+    pub async fn publish(&self, events: Vec<UserEvent>) {
+        let handlers = self.handlers.read().expect("Event bus lock poisoned").clone();
+        for event in &events {
+            for handler in &handlers {
+                if let Some(future) = handler(event) {
+                    future.await;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use crate::domain::{Email, User, UserRenamed};
+
+    #[tokio::test]
+    async fn test_typed_subscribers_receive_matching_events() {
+        let bus = UserEventBus::new();
+        let renamed = Arc::new(Mutex::new(Vec::new()));
+        let all = Arc::new(Mutex::new(Vec::new()));
+        {
+            let renamed = renamed.clone();
+            bus.subscribe(move |event: UserRenamed| {
+                let renamed = renamed.clone();
+                async move { renamed.lock().unwrap().push(event.name) }
+            });
+            let all = all.clone();
+            bus.subscribe(move |event: UserEvent| {
+                let all = all.clone();
+                async move { all.lock().unwrap().push(event.name()) }
+            });
+        }
+
+        let mut user = User::new(Email::new("user@example.com".to_string()).unwrap(), "John".to_string()).unwrap();
+        user.update_name("Johnny".to_string()).unwrap();
+        bus.publish(user.take_events()).await;
+
+        assert_eq!(*renamed.lock().unwrap(), vec!["Johnny".to_string()]);
+        assert_eq!(*all.lock().unwrap(), vec!["user.created", "user.renamed"]);
+    }
+}
\ No newline at end of file
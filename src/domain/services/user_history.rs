@@ -0,0 +1,10 @@
+use crate::domain::{User, UserId, RecordedUserEvent, DomainError};
+
+// Хранилище, которое помнит всю историю изменений пользователя
+pub trait UserHistory: Send + Sync {
+    // События в порядке записи, включая удаление пользователя
+    async fn user_history(&self, id: &UserId) -> Result<Vec<RecordedUserEvent>, DomainError>;
+    // Состояние сразу после события с номером `version`; `None`, если
+    // пользователь на тот момент еще не был создан или уже был удален
+    async fn user_at_version(&self, id: &UserId, version: u64) -> Result<Option<User>, DomainError>;
+}
\ No newline at end of file
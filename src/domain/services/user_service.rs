@@ -1,29 +1,52 @@
-use crate::domain::{User, Email, UserId, DomainError};
+use chrono::Utc;
+use crate::domain::{User, Email, UserId, Role, OutboxMessage, OutboxPayload, UserEventBus, DomainError};
 
 pub trait UserRepository: Send + Sync {
     async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, DomainError>;
     async fn find_by_email(&self, email: &Email) -> Result<Option<User>, DomainError>;
+    // Все пользователи в порядке создания
+    async fn list_users(&self) -> Result<Vec<User>, DomainError>;
     async fn save(&self, user: &User) -> Result<(), DomainError>;
     async fn delete(&self, id: &UserId) -> Result<(), DomainError>;
+    async fn save_with_outbox(&self, user: &User, messages: Vec<OutboxMessage>) -> Result<(), DomainError>;
+    async fn delete_with_outbox(&self, id: &UserId, messages: Vec<OutboxMessage>) -> Result<(), DomainError>;
 }
 
+#[derive(Clone)]
 pub struct UserDomainService<R: UserRepository> {
     user_repository: R,
+    event_bus: UserEventBus,
 }
 
 impl<R: UserRepository> UserDomainService<R> {
     pub fn new(user_repository: R) -> Self {
-        Self { user_repository }
+        Self { user_repository, event_bus: UserEventBus::new() }
+    }
+
+    pub fn with_event_bus(mut self, event_bus: UserEventBus) -> Self {
+        self.event_bus = event_bus;
+        self
     }
 
     pub async fn create_user(&self, email: Email, name: String) -> Result<User, DomainError> {
         // Проверяем, что пользователь с таким email не существует
-        if let Some(existing_user) = self.user_repository.find_by_email(&email).await? {
+        if self.user_repository.find_by_email(&email).await?.is_some() {
             return Err(DomainError::UserAlreadyExists);
         }
 
-        // Создаем нового пользователя
-        User::new(email, name)
+        // Создаем нового пользователя и сохраняем его вместе с сообщениями outbox
+        let mut user = User::new(email, name)?;
+        // События забираются до сохранения, чтобы не попасть в репозиторий и outbox
+        let events = user.take_events();
+        let messages = vec![
+            OutboxMessage::new(OutboxPayload::WelcomeEmail { user_id: user.id().clone() }),
+            OutboxMessage::new(OutboxPayload::UserCreated { user_id: user.id().clone() }),
+        ];
+        self.user_repository.save_with_outbox(&user, messages).await?;
+
+        // События публикуются только после успешного сохранения
+        self.event_bus.publish(events).await;
+        Ok(user)
     }
 
     pub async fn update_user(&self, user_id: UserId, email: Option<Email>, name: Option<String>) -> Result<User, DomainError> {
@@ -35,10 +58,10 @@ impl<R: UserRepository> UserDomainService<R> {
         // Обновляем email если нужно
         if let Some(new_email) = email {
             // Проверяем, что новый email не занят другим пользователем
-            if let Some(existing_user) = self.user_repository.find_by_email(&new_email).await? {
-                if existing_user.id() != &user_id {
-                    return Err(DomainError::UserAlreadyExists);
-                }
+            if let Some(existing_user) = self.user_repository.find_by_email(&new_email).await?
+                && existing_user.id() != &user_id
+            {
+                return Err(DomainError::UserAlreadyExists);
             }
             user.update_email(new_email)?;
         }
@@ -49,20 +72,42 @@ impl<R: UserRepository> UserDomainService<R> {
         }
 
         // Сохраняем обновленного пользователя
-        self.user_repository.save(&user).await?;
+        let events = user.take_events();
+        let messages = vec![OutboxMessage::new(OutboxPayload::UserUpdated { user_id: user.id().clone(), updated_at: *user.updated_at() })];
+        self.user_repository.save_with_outbox(&user, messages).await?;
+
+        self.event_bus.publish(events).await;
+        Ok(user)
+    }
+
+    pub async fn change_roles(&self, user_id: UserId, roles: Vec<Role>) -> Result<User, DomainError> {
+        let mut user = self.user_repository.find_by_id(&user_id)
+            .await?
+            .ok_or(DomainError::UserNotFound)?;
 
+        user.change_roles(roles)?;
+
+        let events = user.take_events();
+        let messages = vec![OutboxMessage::new(OutboxPayload::UserUpdated { user_id: user.id().clone(), updated_at: *user.updated_at() })];
+        self.user_repository.save_with_outbox(&user, messages).await?;
+
+        self.event_bus.publish(events).await;
         Ok(user)
     }
 
     pub async fn delete_user(&self, user_id: UserId) -> Result<(), DomainError> {
         // Проверяем, что пользователь существует
-        let user = self.user_repository.find_by_id(&user_id)
+        let mut user = self.user_repository.find_by_id(&user_id)
             .await?
             .ok_or(DomainError::UserNotFound)?;
 
         // Удаляем пользователя
-        self.user_repository.delete(&user.id()).await?;
+        user.mark_deleted(Utc::now());
+        let events = user.take_events();
+        let messages = vec![OutboxMessage::new(OutboxPayload::UserDeleted { user_id: user.id().clone() })];
+        self.user_repository.delete_with_outbox(user.id(), messages).await?;
 
+        self.event_bus.publish(events).await;
         Ok(())
     }
 }
@@ -73,37 +118,49 @@ mod tests {
     use std::sync::Arc;
 
     struct MockUserRepository {
-        users: std::collections::HashMap<String, User>,
+        users: std::sync::Mutex<std::collections::HashMap<String, User>>,
     }
 
     impl MockUserRepository {
         fn new() -> Self {
             Self {
-                users: std::collections::HashMap::new(),
+                users: std::sync::Mutex::new(std::collections::HashMap::new()),
             }
         }
     }
 
     impl UserRepository for MockUserRepository {
         async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, DomainError> {
-            Ok(self.users.get(&id.to_string()).cloned())
+            Ok(self.users.lock().unwrap().get(&id.to_string()).cloned())
         }
 
         async fn find_by_email(&self, email: &Email) -> Result<Option<User>, DomainError> {
-            Ok(self.users.values()
+            Ok(self.users.lock().unwrap().values()
                 .find(|user| user.email() == email)
                 .cloned())
         }
 
+        async fn list_users(&self) -> Result<Vec<User>, DomainError> {
+            Ok(self.users.lock().unwrap().values().cloned().collect())
+        }
+
         async fn save(&self, user: &User) -> Result<(), DomainError> {
-            self.users.insert(user.id().to_string(), user.clone());
+            self.users.lock().unwrap().insert(user.id().to_string(), user.clone());
             Ok(())
         }
 
         async fn delete(&self, id: &UserId) -> Result<(), DomainError> {
-            self.users.remove(&id.to_string());
+            self.users.lock().unwrap().remove(&id.to_string());
             Ok(())
         }
+
+        async fn save_with_outbox(&self, user: &User, _messages: Vec<OutboxMessage>) -> Result<(), DomainError> {
+            self.save(user).await
+        }
+
+        async fn delete_with_outbox(&self, id: &UserId, _messages: Vec<OutboxMessage>) -> Result<(), DomainError> {
+            self.delete(id).await
+        }
     }
 
     #[tokio::test]
@@ -119,14 +176,56 @@ mod tests {
 
     #[tokio::test]
     async fn test_create_user_duplicate_email() {
-        let mut repository = MockUserRepository::new();
+        let repository = MockUserRepository::new();
         let email = Email::new("test@example.com".to_string()).unwrap();
         let existing_user = User::new(email.clone(), "Existing User".to_string()).unwrap();
-        repository.users.insert(existing_user.id().to_string(), existing_user);
+        repository.users.lock().unwrap().insert(existing_user.id().to_string(), existing_user);
 
         let service = UserDomainService::new(repository);
         let result = service.create_user(email, "New User".to_string()).await;
 
         assert!(matches!(result, Err(DomainError::UserAlreadyExists)));
     }
+
+    #[tokio::test]
+    async fn test_change_roles() {
+        let repository = MockUserRepository::new();
+        let email = Email::new("test@example.com".to_string()).unwrap();
+        let existing_user = User::new(email, "Existing User".to_string()).unwrap();
+        let user_id = existing_user.id().clone();
+        repository.users.lock().unwrap().insert(existing_user.id().to_string(), existing_user);
+
+        let service = UserDomainService::new(repository);
+        let user = service.change_roles(user_id, vec![Role::Support]).await.unwrap();
+
+        assert_eq!(user.roles(), &[Role::Support]);
+    }
+
+    #[tokio::test]
+    async fn test_events_are_published_after_save() {
+        let event_bus = UserEventBus::new();
+        let published = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let subscriber = published.clone();
+        event_bus.subscribe(move |event: crate::domain::UserEvent| {
+            let subscriber = subscriber.clone();
+            async move { subscriber.lock().unwrap().push(event.name()) }
+        });
+        let service = UserDomainService::new(MockUserRepository::new()).with_event_bus(event_bus);
+
+        let email = Email::new("test@example.com".to_string()).unwrap();
+        let user = service.create_user(email, "Test User".to_string()).await.unwrap();
+        service.update_user(user.id().clone(), None, Some("Renamed User".to_string())).await.unwrap();
+        service.delete_user(user.id().clone()).await.unwrap();
+
+        assert_eq!(*published.lock().unwrap(), vec!["user.created", "user.renamed", "user.deleted"]);
+    }
+
+    #[tokio::test]
+    async fn test_change_roles_user_not_found() {
+        let service = UserDomainService::new(MockUserRepository::new());
+
+        let result = service.change_roles(UserId::new(), vec![Role::Admin]).await;
+
+        assert!(matches!(result, Err(DomainError::UserNotFound)));
+    }
 }
\ No newline at end of file
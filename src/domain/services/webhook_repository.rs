@@ -0,0 +1,16 @@
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+use crate::domain::{WebhookSubscription, WebhookDelivery, DomainError};
+
+pub trait WebhookRepository: Send + Sync {
+    async fn find_webhook(&self, id: &Uuid) -> Result<Option<WebhookSubscription>, DomainError>;
+    async fn list_webhooks(&self) -> Result<Vec<WebhookSubscription>, DomainError>;
+    async fn save_webhook(&self, webhook: &WebhookSubscription) -> Result<(), DomainError>;
+    // Удаляет подписку вместе с журналом ее доставок
+    async fn delete_webhook(&self, id: &Uuid) -> Result<(), DomainError>;
+    async fn find_delivery(&self, id: &Uuid) -> Result<Option<WebhookDelivery>, DomainError>;
+    // Последние доставки подписки, новые первыми
+    async fn list_deliveries(&self, webhook_id: &Uuid, limit: usize) -> Result<Vec<WebhookDelivery>, DomainError>;
+    async fn due_deliveries(&self, now: DateTime<Utc>, limit: usize) -> Result<Vec<WebhookDelivery>, DomainError>;
+    async fn save_delivery(&self, delivery: &WebhookDelivery) -> Result<(), DomainError>;
+}
\ No newline at end of file
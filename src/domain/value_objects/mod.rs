@@ -1,5 +1,11 @@
 pub mod user_id;
 pub mod email;
+pub mod role;
+pub mod permission;
+pub mod scope;
 
 pub use user_id::*;
-pub use email::*;
\ No newline at end of file
+pub use email::*;
+pub use role::*;
+pub use permission::*;
+pub use scope::*;
\ No newline at end of file
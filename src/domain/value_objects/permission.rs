@@ -0,0 +1,44 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Permission {
+    ReadUser,
+    FindUserByEmail,
+    UpdateUser,
+    DeleteUser,
+    ChangeRoles,
+    UnlockUser,
+    ManageApiKeys,
+    ManageSessions,
+    ManageMfa,
+    ManageOutbox,
+    ExportUserData,
+    EraseUser,
+    ReadAuditTrail,
+    VerifyAuditTrail,
+    ManageWebhooks,
+    WatchUserChanges,
+    ListUsers,
+}
+
+impl Permission {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Permission::ReadUser => "read_user",
+            Permission::FindUserByEmail => "find_user_by_email",
+            Permission::UpdateUser => "update_user",
+            Permission::DeleteUser => "delete_user",
+            Permission::ChangeRoles => "change_roles",
+            Permission::UnlockUser => "unlock_user",
+            Permission::ManageApiKeys => "manage_api_keys",
+            Permission::ManageSessions => "manage_sessions",
+            Permission::ManageMfa => "manage_mfa",
+            Permission::ManageOutbox => "manage_outbox",
+            Permission::ExportUserData => "export_user_data",
+            Permission::EraseUser => "erase_user",
+            Permission::ReadAuditTrail => "read_audit_trail",
+            Permission::VerifyAuditTrail => "verify_audit_trail",
+            Permission::ManageWebhooks => "manage_webhooks",
+            Permission::WatchUserChanges => "watch_user_changes",
+            Permission::ListUsers => "list_users",
+        }
+    }
+}
\ No newline at end of file
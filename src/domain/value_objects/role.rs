@@ -0,0 +1,53 @@
+use std::fmt;
+use crate::domain::errors::DomainError;
+
+// Порядок вариантов задает иерархию: каждая роль включает права предыдущих
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Role {
+    Member,
+    Support,
+    Admin,
+}
+
+impl Role {
+    pub fn parse(value: &str) -> Result<Self, DomainError> {
+        match value.trim().to_lowercase().as_str() {
+            "member" => Ok(Role::Member),
+            "support" => Ok(Role::Support),
+            "admin" => Ok(Role::Admin),
+            other => Err(DomainError::InvalidUserData(format!("Unknown role: {}", other))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Member => "member",
+            Role::Support => "support",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_role() {
+        assert_eq!(Role::parse("Admin").unwrap(), Role::Admin);
+        assert_eq!(Role::parse("support").unwrap(), Role::Support);
+        assert!(Role::parse("root").is_err());
+    }
+
+    #[test]
+    fn test_role_hierarchy() {
+        assert!(Role::Admin > Role::Support);
+        assert!(Role::Support > Role::Member);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,88 @@
+use std::fmt;
+use crate::domain::errors::DomainError;
+use crate::domain::Permission;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Scope {
+    UsersRead,
+    UsersWrite,
+    UsersDelete,
+    RolesWrite,
+    ApiKeysWrite,
+    OutboxManage,
+    WebhooksManage,
+}
+
+impl Scope {
+    pub fn parse(value: &str) -> Result<Self, DomainError> {
+        match value.trim() {
+            "users:read" => Ok(Scope::UsersRead),
+            "users:write" => Ok(Scope::UsersWrite),
+            "users:delete" => Ok(Scope::UsersDelete),
+            "roles:write" => Ok(Scope::RolesWrite),
+            "api_keys:write" => Ok(Scope::ApiKeysWrite),
+            "outbox:manage" => Ok(Scope::OutboxManage),
+            "webhooks:manage" => Ok(Scope::WebhooksManage),
+            other => Err(DomainError::InvalidOperation(format!("Unknown scope: {}", other))),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Scope::UsersRead => "users:read",
+            Scope::UsersWrite => "users:write",
+            Scope::UsersDelete => "users:delete",
+            Scope::RolesWrite => "roles:write",
+            Scope::ApiKeysWrite => "api_keys:write",
+            Scope::OutboxManage => "outbox:manage",
+            Scope::WebhooksManage => "webhooks:manage",
+        }
+    }
+
+    // None - действие недоступно по API-ключу ни с какими областями
+    pub fn required_for(permission: Permission) -> Option<Self> {
+        match permission {
+            Permission::ReadUser
+            | Permission::FindUserByEmail
+            | Permission::ReadAuditTrail
+            | Permission::WatchUserChanges
+            | Permission::ListUsers => Some(Scope::UsersRead),
+            Permission::UpdateUser | Permission::UnlockUser => Some(Scope::UsersWrite),
+            Permission::DeleteUser => Some(Scope::UsersDelete),
+            Permission::ChangeRoles => Some(Scope::RolesWrite),
+            Permission::ManageApiKeys => Some(Scope::ApiKeysWrite),
+            Permission::ManageOutbox => Some(Scope::OutboxManage),
+            Permission::ManageWebhooks => Some(Scope::WebhooksManage),
+            // Управление входом (MFA, браузерные сессии) по API-ключу недоступно
+            Permission::ManageMfa | Permission::ManageSessions => None,
+            // Выгрузка и стирание персональных данных - только интерактивно
+            Permission::ExportUserData | Permission::EraseUser => None,
+            Permission::VerifyAuditTrail => None,
+        }
+    }
+}
+
+impl fmt::Display for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scope() {
+        assert_eq!(Scope::parse("users:read").unwrap(), Scope::UsersRead);
+        assert_eq!(Scope::parse("outbox:manage").unwrap().as_str(), "outbox:manage");
+        assert!(Scope::parse("users:*").is_err());
+    }
+
+    #[test]
+    fn test_required_scope_for_permission() {
+        assert_eq!(Scope::required_for(Permission::FindUserByEmail), Some(Scope::UsersRead));
+        assert_eq!(Scope::required_for(Permission::ChangeRoles), Some(Scope::RolesWrite));
+        assert_eq!(Scope::required_for(Permission::ManageMfa), None);
+    }
+}
\ No newline at end of file
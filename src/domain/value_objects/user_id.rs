@@ -19,10 +19,6 @@ impl UserId {
     pub fn from_uuid(uuid: Uuid) -> Self {
         Self(uuid)
     }
-
-    pub fn to_string(&self) -> String {
-        self.0.to_string()
-    }
 }
 
 impl Default for UserId {
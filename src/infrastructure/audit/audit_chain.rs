@@ -0,0 +1,175 @@
+use data_encoding::HEXLOWER;
+use sha2::{Digest, Sha256};
+use crate::domain::{AuditChainStatus, AuditEvent, AuditPage, AuditRecord, UserId, GENESIS_HASH};
+use crate::infrastructure::auth::EnvelopePiiCipher;
+
+// Хеш события покрывает номер, хеш предыдущего события и каноническую запись:
+// изменение, удаление или перестановка любого события ломает все последующие
+pub fn audit_event_hash(sequence: u64, previous_hash: &str, record: &AuditRecord) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{}:{}:", sequence, previous_hash).as_bytes());
+    hasher.update(record.canonical().as_bytes());
+    HEXLOWER.encode(&hasher.finalize())
+}
+
+/// Общая для хранилищ аудита цепочка событий в порядке записи.
+/// Ключ keyed-хешей персональных значений берется из PII-шифратора.
+#[derive(Clone)]
+pub struct AuditChain {
+    events: Vec<AuditEvent>,
+    cipher: EnvelopePiiCipher,
+}
+
+impl AuditChain {
+    pub fn new(cipher: EnvelopePiiCipher) -> Self {
+        Self::from_events(Vec::new(), cipher)
+    }
+
+    pub fn from_events(events: Vec<AuditEvent>, cipher: EnvelopePiiCipher) -> Self {
+        Self { events, cipher }
+    }
+
+    pub fn events(&self) -> &[AuditEvent] {
+        &self.events
+    }
+
+    pub fn append(&mut self, mut record: AuditRecord) -> AuditEvent {
+        record.digest_personal_data(&self.cipher);
+        let (sequence, previous_hash) = match self.events.last() {
+            Some(last) => (last.sequence + 1, last.hash.clone()),
+            None => (1, GENESIS_HASH.to_string()),
+        };
+        let event = AuditEvent {
+            sequence,
+            hash: audit_event_hash(sequence, &previous_hash, &record),
+            previous_hash,
+            record,
+        };
+        self.events.push(event.clone());
+        event
+    }
+
+    // Новые события первыми
+    pub fn user_page(&self, user_id: &UserId, offset: usize, limit: usize) -> AuditPage {
+        let events: Vec<&AuditEvent> = self.events
+            .iter()
+            .rev()
+            .filter(|event| &event.record.user_id == user_id)
+            .collect();
+
+        AuditPage {
+            total: events.len(),
+            events: events.into_iter().skip(offset).take(limit).cloned().collect(),
+        }
+    }
+
+    pub fn verify(&self) -> AuditChainStatus {
+        let mut previous_hash = GENESIS_HASH;
+        for (index, event) in self.events.iter().enumerate() {
+            let intact = event.sequence == index as u64 + 1
+                && event.previous_hash == previous_hash
+                && event.hash == audit_event_hash(event.sequence, &event.previous_hash, &event.record)
+                && event.record.personal_data_intact(&self.cipher);
+            if !intact {
+                return AuditChainStatus { events: self.events.len(), broken_at: Some(index as u64 + 1) };
+            }
+            previous_hash = &event.hash;
+        }
+
+        AuditChainStatus { events: self.events.len(), broken_at: None }
+    }
+
+    pub fn erase_user(&mut self, user_id: &UserId) -> usize {
+        self.events
+            .iter_mut()
+            .filter(|event| &event.record.user_id == user_id)
+            .map(|event| event.record.erase_personal_data())
+            .filter(|erased| *erased)
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::domain::{AuditAction, Email, FieldChange, User};
+
+    fn record(user: &User) -> AuditRecord {
+        AuditRecord {
+            user_id: user.id().clone(),
+            resource: "user".to_string(),
+            action: AuditAction::UserCreated,
+            actor: "system".to_string(),
+            request_id: None,
+            client_ip: None,
+            occurred_at: Utc::now(),
+            changes: FieldChange::diff(None, Some(user)),
+        }
+    }
+
+    #[test]
+    fn test_chain_detects_tampering() {
+        let user = User::new(Email::new("alice@example.com".to_string()).unwrap(), "Alice".to_string()).unwrap();
+        let mut chain = AuditChain::new(EnvelopePiiCipher::ephemeral());
+        for _ in 0..3 {
+            chain.append(record(&user));
+        }
+        assert!(chain.verify().is_valid());
+
+        // Стирание персональных данных цепочку не ломает
+        assert_eq!(chain.erase_user(user.id()), 3);
+        assert!(chain.verify().is_valid());
+
+        let mut tampered = chain.clone();
+        tampered.events[1].record.actor = "user:someone-else".to_string();
+        assert_eq!(tampered.verify().broken_at, Some(2));
+
+        let mut truncated = chain.clone();
+        truncated.events.remove(0);
+        assert_eq!(truncated.verify().broken_at, Some(1));
+    }
+
+    #[test]
+    fn test_chain_detects_rewritten_personal_data() {
+        let user = User::new(Email::new("alice@example.com".to_string()).unwrap(), "Alice".to_string()).unwrap();
+        let mut chain = AuditChain::new(EnvelopePiiCipher::ephemeral());
+        chain.append(record(&user));
+        chain.append(record(&user));
+
+        let mut tampered = chain.clone();
+        let email = tampered.events[1].record.changes.iter_mut().find(|change| change.field == "email").unwrap();
+        email.after = Some("mallory@example.com".to_string());
+        assert_eq!(tampered.verify().broken_at, Some(2));
+
+        // Подмена keyed-хеша вместе со значением ломает хеш события
+        let mut forged = tampered.clone();
+        let cipher = EnvelopePiiCipher::ephemeral();
+        forged.events[1].record.digest_personal_data(&cipher);
+        assert_eq!(forged.verify().broken_at, Some(2));
+
+        // После стирания значение не восстановить подстановкой
+        assert_eq!(chain.erase_user(user.id()), 2);
+        let mut restored = chain.clone();
+        let name = restored.events[0].record.changes.iter_mut().find(|change| change.field == "name").unwrap();
+        name.after = Some("Mallory".to_string());
+        assert_eq!(restored.verify().broken_at, Some(1));
+    }
+
+    #[test]
+    fn test_user_page_is_newest_first() {
+        let alice = User::new(Email::new("alice@example.com".to_string()).unwrap(), "Alice".to_string()).unwrap();
+        let bob = User::new(Email::new("bob@example.com".to_string()).unwrap(), "Bob".to_string()).unwrap();
+        let mut chain = AuditChain::new(EnvelopePiiCipher::ephemeral());
+        chain.append(record(&alice));
+        chain.append(record(&bob));
+        chain.append(record(&alice));
+
+        let page = chain.user_page(alice.id(), 0, 1);
+
+        assert_eq!(page.total, 2);
+        assert_eq!(page.events.len(), 1);
+        assert_eq!(page.events[0].sequence, 3);
+        assert_eq!(chain.user_page(alice.id(), 1, 10).events[0].sequence, 1);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,56 @@
+use std::future::Future;
+use std::net::IpAddr;
+use std::sync::{Arc, Mutex};
+
+tokio::task_local! {
+    static AUDIT_CONTEXT: AuditContext;
+}
+
+/// Контекст запроса для журнала аудита. Живет в task-local переменной на
+/// время обработки запроса, поэтому репозиториям не нужно передавать
+/// инициатора и адрес через все слои.
+#[derive(Debug, Clone)]
+pub struct AuditContext {
+    request_id: String,
+    client_ip: Option<IpAddr>,
+    // Инициатор становится известен только после аутентификации
+    actor: Arc<Mutex<String>>,
+}
+
+impl AuditContext {
+    pub fn new(request_id: String, client_ip: Option<IpAddr>) -> Self {
+        Self {
+            request_id,
+            client_ip,
+            actor: Arc::new(Mutex::new("anonymous".to_string())),
+        }
+    }
+
+    pub async fn scope<F: Future>(self, future: F) -> F::Output {
+        AUDIT_CONTEXT.scope(self, future).await
+    }
+
+    // Вне запроса (фоновые задачи) вызов ничего не делает
+    pub fn set_actor(actor: impl Into<String>) {
+        let actor = actor.into();
+        let _ = AUDIT_CONTEXT.try_with(|context| {
+            *context.actor.lock().expect("Audit actor lock poisoned") = actor;
+        });
+    }
+
+    pub fn current() -> Option<Self> {
+        AUDIT_CONTEXT.try_with(Clone::clone).ok()
+    }
+
+    pub fn request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    pub fn client_ip(&self) -> Option<IpAddr> {
+        self.client_ip
+    }
+
+    pub fn actor(&self) -> String {
+        self.actor.lock().expect("Audit actor lock poisoned").clone()
+    }
+}
\ No newline at end of file
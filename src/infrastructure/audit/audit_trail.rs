@@ -0,0 +1,91 @@
+use chrono::Utc;
+use crate::domain::{AuditAction, AuditRecord, AuditStore, Auditable, FieldChange, UserId, DomainError};
+use crate::infrastructure::audit::AuditContext;
+use crate::infrastructure::repositories::AuditStoreBackend;
+
+/// Записывает изменения сущностей в журнал аудита. Подключается к
+/// репозиториям через `with_audit_trail` и вызывается до сохранения:
+/// если событие не записалось, изменение тоже не сохраняется.
+#[derive(Clone)]
+pub struct AuditTrail {
+    store: AuditStoreBackend,
+}
+
+impl AuditTrail {
+    pub fn new(store: AuditStoreBackend) -> Self {
+        Self { store }
+    }
+
+    pub fn store(&self) -> &AuditStoreBackend {
+        &self.store
+    }
+
+    pub async fn record<T: Auditable>(
+        &self,
+        user_id: &UserId,
+        action: Option<AuditAction>,
+        before: Option<&T>,
+        after: Option<&T>,
+    ) -> Result<(), DomainError> {
+        let Some(action) = action else {
+            return Ok(());
+        };
+        let Some(resource) = after.or(before).map(Auditable::audit_resource) else {
+            return Ok(());
+        };
+
+        let changes = FieldChange::diff(before, after);
+        if before.is_some() && after.is_some() && changes.is_empty() {
+            return Ok(());
+        }
+
+        let context = AuditContext::current();
+        let record = AuditRecord {
+            user_id: user_id.clone(),
+            resource,
+            action,
+            actor: context.as_ref().map(AuditContext::actor).unwrap_or_else(|| "system".to_string()),
+            request_id: context.as_ref().map(|context| context.request_id().to_string()),
+            client_ip: context.as_ref().and_then(AuditContext::client_ip).map(|ip| ip.to_string()),
+            occurred_at: Utc::now(),
+            changes,
+        };
+        self.store.append_audit_record(record).await.map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Email, User};
+    use crate::infrastructure::repositories::InMemoryAuditStore;
+
+    #[tokio::test]
+    async fn test_record_uses_request_context() {
+        let trail = AuditTrail::new(AuditStoreBackend::InMemory(InMemoryAuditStore::new()));
+        let user = User::new(Email::new("alice@example.com".to_string()).unwrap(), "Alice".to_string()).unwrap();
+
+        let context = AuditContext::new("req-42".to_string(), Some("10.0.0.7".parse().unwrap()));
+        context
+            .scope(async {
+                AuditContext::set_actor("user:admin");
+                trail.record(user.id(), AuditAction::for_user(None, Some(&user)), None, Some(&user)).await
+            })
+            .await
+            .unwrap();
+        // Без изменений полей событие не пишется; вне запроса инициатор - system
+        trail.record(user.id(), Some(AuditAction::UserUpdated), Some(&user), Some(&user)).await.unwrap();
+        let mut renamed = user.clone();
+        renamed.update_name("Alice Smith".to_string()).unwrap();
+        trail.record(user.id(), Some(AuditAction::UserUpdated), Some(&user), Some(&renamed)).await.unwrap();
+
+        let page = trail.store().list_user_audit_events(user.id(), 0, 10).await.unwrap();
+        assert_eq!(page.total, 2);
+        assert_eq!(page.events[0].record.actor, "system");
+        assert_eq!(page.events[0].record.changes.len(), 1);
+        let created = &page.events[1].record;
+        assert_eq!(created.actor, "user:admin");
+        assert_eq!(created.request_id.as_deref(), Some("req-42"));
+        assert_eq!(created.client_ip.as_deref(), Some("10.0.0.7"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+pub mod audit_chain;
+pub mod audit_context;
+pub mod audit_trail;
+
+pub use audit_chain::*;
+pub use audit_context::*;
+pub use audit_trail::*;
\ No newline at end of file
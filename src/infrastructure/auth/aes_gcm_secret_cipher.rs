@@ -0,0 +1,110 @@
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use data_encoding::BASE64;
+use crate::domain::{SecretCipher, DomainError};
+use crate::infrastructure::config::AppConfig;
+
+const NONCE_LENGTH: usize = 12;
+
+/// AES-256-GCM; результат - base64(nonce || шифротекст), `context`
+/// передается как associated data и не хранится.
+#[derive(Clone)]
+pub struct AesGcmSecretCipher {
+    cipher: Aes256Gcm,
+}
+
+impl AesGcmSecretCipher {
+    pub fn new(key: &[u8; 32]) -> Self {
+        Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(key)),
+        }
+    }
+
+    // Ключ TOTP-секретов MFA
+    pub fn from_app_config(config: &AppConfig) -> Result<Self, DomainError> {
+        Self::from_base64_key(&config.mfa_encryption_key, "MFA encryption key")
+    }
+
+    // Отдельный ключ секретов подписок webhook: компрометация одного
+    // из ключей не раскрывает секреты другой подсистемы
+    pub fn for_webhooks(config: &AppConfig) -> Result<Self, DomainError> {
+        Self::from_base64_key(&config.webhook_encryption_key, "Webhook encryption key")
+    }
+
+    fn from_base64_key(encoded: &str, name: &str) -> Result<Self, DomainError> {
+        let key = BASE64
+            .decode(encoded.as_bytes())
+            .map_err(|err| DomainError::InvalidOperation(format!("{} is not valid base64: {}", name, err)))?;
+        let key: [u8; 32] = key
+            .try_into()
+            .map_err(|_| DomainError::InvalidOperation(format!("{} must be 32 bytes", name)))?;
+
+        Ok(Self::new(&key))
+    }
+}
+
+impl SecretCipher for AesGcmSecretCipher {
+    fn encrypt(&self, plaintext: &[u8], context: &str) -> Result<String, DomainError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self.cipher
+            .encrypt(&nonce, Payload { msg: plaintext, aad: context.as_bytes() })
+            .map_err(|_| DomainError::InvalidOperation("Failed to encrypt secret".to_string()))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(BASE64.encode(&sealed))
+    }
+
+    fn decrypt(&self, ciphertext: &str, context: &str) -> Result<Vec<u8>, DomainError> {
+        let sealed = BASE64
+            .decode(ciphertext.as_bytes())
+            .map_err(|_| DomainError::InvalidOperation("Encrypted secret is corrupted".to_string()))?;
+        if sealed.len() <= NONCE_LENGTH {
+            return Err(DomainError::InvalidOperation("Encrypted secret is corrupted".to_string()));
+        }
+
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LENGTH);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: context.as_bytes() })
+            .map_err(|_| DomainError::InvalidOperation("Failed to decrypt secret".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_and_context_binding() {
+        let cipher = AesGcmSecretCipher::from_app_config(&AppConfig::default()).unwrap();
+
+        let sealed = cipher.encrypt(b"totp-secret", "user-1").unwrap();
+
+        assert!(!sealed.contains("totp-secret"));
+        assert_eq!(cipher.decrypt(&sealed, "user-1").unwrap(), b"totp-secret");
+        assert!(cipher.decrypt(&sealed, "user-2").is_err());
+        assert!(AesGcmSecretCipher::new(&[7u8; 32]).decrypt(&sealed, "user-1").is_err());
+    }
+
+    #[test]
+    fn test_invalid_key_is_rejected() {
+        let config = AppConfig {
+            mfa_encryption_key: "c2hvcnQ=".to_string(),
+            ..AppConfig::default()
+        };
+
+        assert!(AesGcmSecretCipher::from_app_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_webhook_key_is_separate_from_mfa_key() {
+        let config = AppConfig::default();
+        let mfa_cipher = AesGcmSecretCipher::from_app_config(&config).unwrap();
+        let webhook_cipher = AesGcmSecretCipher::for_webhooks(&config).unwrap();
+
+        let sealed = webhook_cipher.encrypt(b"whsec", "subscription-1").unwrap();
+
+        assert_eq!(webhook_cipher.decrypt(&sealed, "subscription-1").unwrap(), b"whsec");
+        assert!(mfa_cipher.decrypt(&sealed, "subscription-1").is_err());
+    }
+}
\ No newline at end of file
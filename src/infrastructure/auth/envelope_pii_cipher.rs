@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use aes_gcm::aead::{KeyInit, OsRng};
+use aes_gcm::Aes256Gcm;
+use data_encoding::{BASE64, HEXLOWER};
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use crate::domain::{
+    PiiCipher, SecretCipher, User, Email, EncryptedUser, WrappedDataKey, DomainError,
+};
+use crate::infrastructure::auth::AesGcmSecretCipher;
+use crate::infrastructure::config::AppConfig;
+
+const EPHEMERAL_KEY_ID: &str = "ephemeral";
+
+// Формат файла ключей:
+// {"active_key_id": "2024-06", "master_keys": {"2024-01": "<base64>", "2024-06": "<base64>"}, "index_key": "<base64>"}
+#[derive(Deserialize)]
+struct KeyringFile {
+    active_key_id: String,
+    master_keys: HashMap<String, String>,
+    index_key: String,
+}
+
+struct Keyring {
+    active_key_id: String,
+    master_keys: HashMap<String, AesGcmSecretCipher>,
+    index_key: Vec<u8>,
+}
+
+/// Envelope encryption персональных данных: email и имя шифруются
+/// случайным AES-256-GCM ключом записи, а он - мастер-ключом из файла.
+/// Ротация мастер-ключа переоборачивает только ключи данных. Ключ слепого
+/// индекса от мастер-ключей не зависит, иначе поиск по email сломается.
+#[derive(Clone)]
+pub struct EnvelopePiiCipher {
+    keyring: Arc<Keyring>,
+}
+
+impl EnvelopePiiCipher {
+    pub fn new(
+        active_key_id: impl Into<String>,
+        master_keys: impl IntoIterator<Item = (String, [u8; 32])>,
+        index_key: Vec<u8>,
+    ) -> Result<Self, DomainError> {
+        let active_key_id = active_key_id.into();
+        let master_keys: HashMap<String, AesGcmSecretCipher> = master_keys
+            .into_iter()
+            .map(|(key_id, key)| (key_id, AesGcmSecretCipher::new(&key)))
+            .collect();
+        if !master_keys.contains_key(&active_key_id) {
+            return Err(DomainError::InvalidOperation(format!("Active PII master key {} is missing", active_key_id)));
+        }
+        if index_key.len() < 32 {
+            return Err(DomainError::InvalidOperation("PII index key must be at least 32 bytes".to_string()));
+        }
+
+        Ok(Self {
+            keyring: Arc::new(Keyring { active_key_id, master_keys, index_key }),
+        })
+    }
+
+    // Случайные ключи на время жизни процесса - подходят только для
+    // хранилища в памяти, которое все равно не переживает перезапуск
+    pub fn ephemeral() -> Self {
+        let master_key: [u8; 32] = Aes256Gcm::generate_key(&mut OsRng).into();
+        let index_key: [u8; 32] = Aes256Gcm::generate_key(&mut OsRng).into();
+
+        Self::new(EPHEMERAL_KEY_ID, [(EPHEMERAL_KEY_ID.to_string(), master_key)], index_key.to_vec())
+            .expect("Ephemeral keyring is valid")
+    }
+
+    pub fn from_keyfile(path: impl AsRef<Path>) -> Result<Self, DomainError> {
+        let bytes = std::fs::read(path.as_ref())
+            .map_err(|err| DomainError::InvalidOperation(format!("Failed to read PII keyfile: {}", err)))?;
+        let file: KeyringFile = serde_json::from_slice(&bytes)
+            .map_err(|err| DomainError::InvalidOperation(format!("PII keyfile is malformed: {}", err)))?;
+
+        let master_keys = file.master_keys
+            .into_iter()
+            .map(|(key_id, key)| {
+                let key: [u8; 32] = decode_key(&key)?
+                    .try_into()
+                    .map_err(|_| DomainError::InvalidOperation(format!("PII master key {} must be 32 bytes", key_id)))?;
+                Ok((key_id, key))
+            })
+            .collect::<Result<Vec<_>, DomainError>>()?;
+
+        Self::new(file.active_key_id, master_keys, decode_key(&file.index_key)?)
+    }
+
+    pub fn from_app_config(config: &AppConfig) -> Result<Self, DomainError> {
+        match &config.pii_keyfile {
+            Some(path) => Self::from_keyfile(path),
+            None => {
+                tracing::warn!("PII_KEYFILE is not set, personal data is encrypted with ephemeral keys");
+                Ok(Self::ephemeral())
+            }
+        }
+    }
+
+    pub fn active_key_id(&self) -> &str {
+        &self.keyring.active_key_id
+    }
+
+    fn master_key(&self, key_id: &str) -> Result<&AesGcmSecretCipher, DomainError> {
+        self.keyring.master_keys
+            .get(key_id)
+            .ok_or_else(|| DomainError::InvalidOperation(format!("Unknown PII master key {}", key_id)))
+    }
+
+    fn active_master_key(&self) -> &AesGcmSecretCipher {
+        &self.keyring.master_keys[&self.keyring.active_key_id]
+    }
+
+    fn unwrap_data_key(&self, record: &EncryptedUser) -> Result<AesGcmSecretCipher, DomainError> {
+        let key: [u8; 32] = self.master_key(&record.data_key.key_id)?
+            .decrypt(&record.data_key.ciphertext, &field_context(record, "data_key"))?
+            .try_into()
+            .map_err(|_| DomainError::InvalidOperation("PII data key is corrupted".to_string()))?;
+
+        Ok(AesGcmSecretCipher::new(&key))
+    }
+}
+
+impl PiiCipher for EnvelopePiiCipher {
+    fn seal(&self, user: &User) -> Result<EncryptedUser, DomainError> {
+        let data_key: [u8; 32] = Aes256Gcm::generate_key(&mut OsRng).into();
+        let data_cipher = AesGcmSecretCipher::new(&data_key);
+        let id = user.id().to_string();
+
+        Ok(EncryptedUser {
+            id: user.id().clone(),
+            email_index: self.blind_index(user.email()),
+            email: data_cipher.encrypt(user.email().as_str().as_bytes(), &format!("{}:email", id))?,
+            name: data_cipher.encrypt(user.name().as_bytes(), &format!("{}:name", id))?,
+            data_key: WrappedDataKey {
+                key_id: self.keyring.active_key_id.clone(),
+                ciphertext: self.active_master_key().encrypt(&data_key, &format!("{}:data_key", id))?,
+            },
+            roles: user.roles().to_vec(),
+            created_at: *user.created_at(),
+            updated_at: *user.updated_at(),
+            erased_at: user.erased_at().copied(),
+        })
+    }
+
+    fn open(&self, record: &EncryptedUser) -> Result<User, DomainError> {
+        let data_cipher = self.unwrap_data_key(record)?;
+        let decrypt = |field: &str, ciphertext: &str| {
+            String::from_utf8(data_cipher.decrypt(ciphertext, &field_context(record, field))?)
+                .map_err(|_| DomainError::InvalidOperation(format!("Encrypted {} is corrupted", field)))
+        };
+
+        User::from_existing(
+            record.id.clone(),
+            Email::new(decrypt("email", &record.email)?)?,
+            decrypt("name", &record.name)?,
+            record.roles.clone(),
+            record.created_at,
+            record.updated_at,
+            record.erased_at,
+        )
+    }
+
+    fn blind_index(&self, email: &Email) -> String {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&self.keyring.index_key).expect("HMAC accepts keys of any length");
+        mac.update(email.as_str().as_bytes());
+        HEXLOWER.encode(&mac.finalize().into_bytes())
+    }
+
+    fn digest(&self, context: &str, value: &str) -> String {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&self.keyring.index_key).expect("HMAC accepts keys of any length");
+        mac.update(format!("{}:{};", context.len(), context).as_bytes());
+        mac.update(value.as_bytes());
+        HEXLOWER.encode(&mac.finalize().into_bytes())
+    }
+
+    // `<key_id>:<base64>`: значение шифруется мастер-ключом напрямую, без ключа данных
+    fn seal_value(&self, context: &str, value: &str) -> Result<String, DomainError> {
+        let ciphertext = self.active_master_key().encrypt(value.as_bytes(), context)?;
+        Ok(format!("{}:{}", self.keyring.active_key_id, ciphertext))
+    }
+
+    fn open_value(&self, context: &str, sealed: &str) -> Result<String, DomainError> {
+        let (key_id, ciphertext) = sealed
+            .rsplit_once(':')
+            .ok_or_else(|| DomainError::InvalidOperation("Encrypted value is corrupted".to_string()))?;
+        String::from_utf8(self.master_key(key_id)?.decrypt(ciphertext, context)?)
+            .map_err(|_| DomainError::InvalidOperation("Encrypted value is corrupted".to_string()))
+    }
+
+    fn needs_rotation(&self, record: &EncryptedUser) -> bool {
+        record.data_key.key_id != self.keyring.active_key_id
+    }
+
+    fn rotate(&self, record: &EncryptedUser) -> Result<EncryptedUser, DomainError> {
+        let data_key = self.master_key(&record.data_key.key_id)?
+            .decrypt(&record.data_key.ciphertext, &field_context(record, "data_key"))?;
+
+        Ok(EncryptedUser {
+            data_key: WrappedDataKey {
+                key_id: self.keyring.active_key_id.clone(),
+                ciphertext: self.active_master_key().encrypt(&data_key, &field_context(record, "data_key"))?,
+            },
+            ..record.clone()
+        })
+    }
+}
+
+// Шифротекст привязан к пользователю и полю: подменить одно поле другим нельзя
+fn field_context(record: &EncryptedUser, field: &str) -> String {
+    format!("{}:{}", record.id, field)
+}
+
+fn decode_key(key: &str) -> Result<Vec<u8>, DomainError> {
+    BASE64
+        .decode(key.trim().as_bytes())
+        .map_err(|err| DomainError::InvalidOperation(format!("PII key is not valid base64: {}", err)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cipher(active_key_id: &str) -> EnvelopePiiCipher {
+        EnvelopePiiCipher::new(
+            active_key_id,
+            [("k1".to_string(), [1u8; 32]), ("k2".to_string(), [2u8; 32])],
+            vec![9u8; 32],
+        )
+        .unwrap()
+    }
+
+    fn user() -> User {
+        User::new(Email::new("alice@example.com".to_string()).unwrap(), "Alice Smith".to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_seal_and_open_round_trip() {
+        let cipher = cipher("k1");
+        let user = user();
+
+        let record = cipher.seal(&user).unwrap();
+
+        assert_eq!(record.data_key.key_id, "k1");
+        assert!(!record.email.contains("alice"));
+        assert!(!record.name.contains("Alice"));
+        let opened = cipher.open(&record).unwrap();
+        assert_eq!(opened.email(), user.email());
+        assert_eq!(opened.name(), "Alice Smith");
+
+        // Поля одной записи нельзя переставить местами
+        let swapped = EncryptedUser { email: record.name.clone(), ..record };
+        assert!(cipher.open(&swapped).is_err());
+    }
+
+    #[test]
+    fn test_blind_index_is_keyed_and_stable() {
+        let email = Email::new("alice@example.com".to_string()).unwrap();
+
+        assert_eq!(cipher("k1").blind_index(&email), cipher("k2").blind_index(&email));
+        assert_ne!(cipher("k1").blind_index(&email), EnvelopePiiCipher::ephemeral().blind_index(&email));
+        assert!(!cipher("k1").blind_index(&email).contains("alice"));
+    }
+
+    #[test]
+    fn test_rotation_rewraps_data_key() {
+        let record = cipher("k1").seal(&user()).unwrap();
+        let rotated_cipher = cipher("k2");
+
+        assert!(rotated_cipher.needs_rotation(&record));
+        let rotated = rotated_cipher.rotate(&record).unwrap();
+
+        assert_eq!(rotated.data_key.key_id, "k2");
+        assert_eq!(rotated.email, record.email);
+        assert!(!rotated_cipher.needs_rotation(&rotated));
+        assert_eq!(rotated_cipher.open(&rotated).unwrap().name(), "Alice Smith");
+        // После удаления старого ключа из файла его записи не читаются
+        let retired = EnvelopePiiCipher::new("k2", [("k2".to_string(), [2u8; 32])], vec![9u8; 32]).unwrap();
+        assert!(retired.open(&record).is_err());
+        assert!(retired.open(&rotated).is_ok());
+    }
+
+    #[test]
+    fn test_from_keyfile() {
+        let path = std::env::temp_dir().join(format!("pii-keys-{}.json", uuid::Uuid::new_v4()));
+        let keyfile = serde_json::json!({
+            "active_key_id": "k2",
+            "master_keys": { "k1": BASE64.encode(&[1u8; 32]), "k2": BASE64.encode(&[2u8; 32]) },
+            "index_key": BASE64.encode(&[9u8; 32]),
+        });
+        std::fs::write(&path, keyfile.to_string()).unwrap();
+
+        let loaded = EnvelopePiiCipher::from_keyfile(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.active_key_id(), "k2");
+        let record = cipher("k1").seal(&user()).unwrap();
+        assert_eq!(loaded.open(&record).unwrap().email().as_str(), "alice@example.com");
+        assert!(EnvelopePiiCipher::new("k3", [("k1".to_string(), [1u8; 32])], vec![9u8; 32]).is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,116 @@
+use chrono::{Duration, Utc};
+use jsonwebtoken::{decode, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use crate::domain::{UserId, AuthLevel, TokenService, VerifiedToken, DomainError};
+use crate::infrastructure::config::AppConfig;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    sub: String,
+    iat: i64,
+    exp: i64,
+    // Методы аутентификации (RFC 8176): "mfa" означает пройденную TOTP-проверку
+    #[serde(default)]
+    amr: Vec<String>,
+}
+
+const MFA_METHOD: &str = "mfa";
+
+#[derive(Clone)]
+pub struct JwtTokenService {
+    encoding_key: EncodingKey,
+    decoding_key: DecodingKey,
+    ttl: Duration,
+}
+
+impl JwtTokenService {
+    pub fn new(secret: &str, ttl: Duration) -> Self {
+        Self {
+            encoding_key: EncodingKey::from_secret(secret.as_bytes()),
+            decoding_key: DecodingKey::from_secret(secret.as_bytes()),
+            ttl,
+        }
+    }
+
+    pub fn from_app_config(config: &AppConfig) -> Self {
+        Self::new(&config.jwt_secret, Duration::hours(1))
+    }
+}
+
+impl TokenService for JwtTokenService {
+    fn issue(&self, user_id: &UserId, auth_level: AuthLevel) -> Result<String, DomainError> {
+        let now = Utc::now();
+        let amr = match auth_level {
+            AuthLevel::SingleFactor => Vec::new(),
+            AuthLevel::MultiFactor => vec![MFA_METHOD.to_string()],
+        };
+        let claims = Claims {
+            sub: user_id.to_string(),
+            iat: now.timestamp(),
+            exp: (now + self.ttl).timestamp(),
+            amr,
+        };
+
+        encode(&Header::new(Algorithm::HS256), &claims, &self.encoding_key)
+            .map_err(|err| DomainError::Unauthorized(err.to_string()))
+    }
+
+    fn verify(&self, token: &str) -> Result<VerifiedToken, DomainError> {
+        let data = decode::<Claims>(token, &self.decoding_key, &Validation::new(Algorithm::HS256))
+            .map_err(|err| DomainError::Unauthorized(err.to_string()))?;
+
+        let auth_level = if data.claims.amr.iter().any(|method| method == MFA_METHOD) {
+            AuthLevel::MultiFactor
+        } else {
+            AuthLevel::SingleFactor
+        };
+        let user_id = UserId::from_string(data.claims.sub).map_err(DomainError::Unauthorized)?;
+
+        Ok(VerifiedToken { user_id, auth_level })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_and_verify() {
+        let service = JwtTokenService::new("secret", Duration::minutes(5));
+        let user_id = UserId::new();
+
+        let token = service.issue(&user_id, AuthLevel::SingleFactor).unwrap();
+
+        let verified = service.verify(&token).unwrap();
+        assert_eq!(verified.user_id, user_id);
+        assert_eq!(verified.auth_level, AuthLevel::SingleFactor);
+    }
+
+    #[test]
+    fn test_mfa_level_round_trips() {
+        let service = JwtTokenService::new("secret", Duration::minutes(5));
+
+        let token = service.issue(&UserId::new(), AuthLevel::MultiFactor).unwrap();
+
+        assert_eq!(service.verify(&token).unwrap().auth_level, AuthLevel::MultiFactor);
+    }
+
+    #[test]
+    fn test_token_signed_with_other_secret_is_rejected() {
+        let issuer = JwtTokenService::new("secret", Duration::minutes(5));
+        let verifier = JwtTokenService::new("other-secret", Duration::minutes(5));
+
+        let token = issuer.issue(&UserId::new(), AuthLevel::SingleFactor).unwrap();
+
+        assert!(matches!(verifier.verify(&token), Err(DomainError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn test_expired_token_is_rejected() {
+        let service = JwtTokenService::new("secret", Duration::minutes(-5));
+
+        let token = service.issue(&UserId::new(), AuthLevel::SingleFactor).unwrap();
+
+        assert!(service.verify(&token).is_err());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,197 @@
+//! Локальный OIDC-провайдер для тестов: discovery, JWKS, страница входа,
+//! сразу возвращающая code, и token endpoint с проверкой PKCE.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use axum::{
+    extract::{Form, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect, Response},
+    routing::{get, post},
+    Json, Router,
+};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+use rand::distr::{Alphanumeric, SampleString};
+use reqwest::Url;
+use serde_json::json;
+use crate::infrastructure::auth::OidcClient;
+use crate::infrastructure::config::OidcProviderConfig;
+
+pub const MOCK_CLIENT_ID: &str = "mock-client";
+const KEY_ID: &str = "mock-key";
+const SIGNING_KEY: &[u8] = include_bytes!("testdata/oidc_issuer_key.pem");
+const KEY_MODULUS: &str = "sBw4U8vfVz72ub0FsqpZXdLZRMUo8lRunCF6AoLeqy9s-B0motoPr_LT1PjDlWTQ7ebo18tKQTsytByl8845rSQ-TLTUc_BE9CtCe-lZpaHtGHIwYnpm0hoKLBdQk1oquH_-vYSU1W4ebQ2CmMfw7mkEFsrt04vpv23RrYzKw8hycVO4XEPSPLIX5bcQlJYsraEdVYD-jPODESDwj2sp4PVD1lpOWUsOkV90lEwFwFPJgTIfJj36MrabYT1N5k6SOJz5Yi_uwug-gtZ_iHPlVVGPR2-e3gNuL_KGqvtZrRCTMBAPVTAL0o-h3CnSKSiWVKFDuvEBQBkpe-4qahNqhQ";
+
+#[derive(Debug, Clone)]
+pub struct MockIdentity {
+    pub subject: String,
+    pub email: String,
+    pub email_verified: bool,
+    pub name: Option<String>,
+}
+
+impl MockIdentity {
+    pub fn verified(subject: &str, email: &str) -> Self {
+        Self {
+            subject: subject.to_string(),
+            email: email.to_string(),
+            email_verified: true,
+            name: Some("Jane Doe".to_string()),
+        }
+    }
+}
+
+struct IssuedCode {
+    redirect_uri: String,
+    code_challenge: String,
+    nonce: String,
+    identity: MockIdentity,
+}
+
+struct MockState {
+    issuer: String,
+    identity: Mutex<MockIdentity>,
+    codes: Mutex<HashMap<String, IssuedCode>>,
+}
+
+pub struct MockOidcIssuer {
+    state: Arc<MockState>,
+    http_client: reqwest::Client,
+}
+
+impl MockOidcIssuer {
+    pub async fn start(identity: MockIdentity) -> Self {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let state = Arc::new(MockState {
+            issuer: format!("http://{}", listener.local_addr().unwrap()),
+            identity: Mutex::new(identity),
+            codes: Mutex::new(HashMap::new()),
+        });
+
+        let app = Router::new()
+            .route("/.well-known/openid-configuration", get(discovery_handler))
+            .route("/jwks", get(jwks_handler))
+            .route("/authorize", get(authorize_handler))
+            .route("/token", post(token_handler))
+            .with_state(state.clone());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        Self {
+            state,
+            http_client: reqwest::Client::builder()
+                .redirect(reqwest::redirect::Policy::none())
+                .build()
+                .unwrap(),
+        }
+    }
+
+    pub fn issuer(&self) -> &str {
+        &self.state.issuer
+    }
+
+    pub fn provider_config(&self, name: &str) -> OidcProviderConfig {
+        OidcProviderConfig {
+            name: name.to_string(),
+            issuer: self.state.issuer.clone(),
+            client_id: MOCK_CLIENT_ID.to_string(),
+            client_secret: None,
+            redirect_uri: format!("http://localhost:3000/api/auth/oidc/{}/callback", name),
+        }
+    }
+
+    pub fn set_identity(&self, identity: MockIdentity) {
+        *self.state.identity.lock().unwrap() = identity;
+    }
+
+    /// Открывает страницу входа и возвращает адрес, на который провайдер
+    /// перенаправил браузер (callback с `code` и `state`).
+    pub async fn authorize_redirect(&self, authorization_url: &str) -> Url {
+        let response = self.http_client.get(authorization_url).send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        let location = response.headers()[reqwest::header::LOCATION].to_str().unwrap();
+        Url::parse(location).unwrap()
+    }
+
+    pub async fn authorize(&self, authorization_url: &str) -> String {
+        let redirect = self.authorize_redirect(authorization_url).await;
+        redirect
+            .query_pairs()
+            .find(|(key, _)| key == "code")
+            .map(|(_, code)| code.into_owned())
+            .unwrap()
+    }
+}
+
+async fn discovery_handler(State(state): State<Arc<MockState>>) -> Json<serde_json::Value> {
+    Json(json!({
+        "issuer": state.issuer,
+        "authorization_endpoint": format!("{}/authorize", state.issuer),
+        "token_endpoint": format!("{}/token", state.issuer),
+        "jwks_uri": format!("{}/jwks", state.issuer),
+        "response_types_supported": ["code"],
+        "id_token_signing_alg_values_supported": ["RS256"],
+        "code_challenge_methods_supported": ["S256"],
+    }))
+}
+
+async fn jwks_handler() -> Json<serde_json::Value> {
+    Json(json!({
+        "keys": [{ "kty": "RSA", "kid": KEY_ID, "use": "sig", "alg": "RS256", "n": KEY_MODULUS, "e": "AQAB" }],
+    }))
+}
+
+async fn authorize_handler(State(state): State<Arc<MockState>>, Query(params): Query<HashMap<String, String>>) -> Response {
+    let param = |name: &str| params.get(name).cloned().unwrap_or_default();
+    if param("response_type") != "code" || param("client_id") != MOCK_CLIENT_ID || param("code_challenge_method") != "S256" {
+        return StatusCode::BAD_REQUEST.into_response();
+    }
+
+    let code = Alphanumeric.sample_string(&mut rand::rng(), 24);
+    let identity = state.identity.lock().unwrap().clone();
+    state.codes.lock().unwrap().insert(code.clone(), IssuedCode {
+        redirect_uri: param("redirect_uri"),
+        code_challenge: param("code_challenge"),
+        nonce: param("nonce"),
+        identity,
+    });
+
+    let redirect = Url::parse_with_params(&param("redirect_uri"), &[("code", code), ("state", param("state"))]).unwrap();
+    Redirect::to(redirect.as_str()).into_response()
+}
+
+async fn token_handler(State(state): State<Arc<MockState>>, Form(form): Form<HashMap<String, String>>) -> Response {
+    let invalid_grant = || (StatusCode::BAD_REQUEST, Json(json!({ "error": "invalid_grant" }))).into_response();
+    let field = |name: &str| form.get(name).map(String::as_str).unwrap_or_default();
+
+    let Some(issued) = state.codes.lock().unwrap().remove(field("code")) else {
+        return invalid_grant();
+    };
+    if field("grant_type") != "authorization_code"
+        || field("client_id") != MOCK_CLIENT_ID
+        || field("redirect_uri") != issued.redirect_uri
+        || OidcClient::code_challenge(field("code_verifier")) != issued.code_challenge
+    {
+        return invalid_grant();
+    }
+
+    let now = Utc::now();
+    let claims = json!({
+        "iss": state.issuer,
+        "aud": MOCK_CLIENT_ID,
+        "sub": issued.identity.subject,
+        "iat": now.timestamp(),
+        "exp": (now + Duration::minutes(5)).timestamp(),
+        "nonce": issued.nonce,
+        "email": issued.identity.email,
+        "email_verified": issued.identity.email_verified,
+        "name": issued.identity.name,
+    });
+    let mut header = Header::new(Algorithm::RS256);
+    header.kid = Some(KEY_ID.to_string());
+    let id_token = encode(&header, &claims, &EncodingKey::from_rsa_pem(SIGNING_KEY).unwrap()).unwrap();
+
+    Json(json!({ "access_token": "mock-access-token", "token_type": "Bearer", "id_token": id_token })).into_response()
+}
\ No newline at end of file
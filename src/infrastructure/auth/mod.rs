@@ -0,0 +1,17 @@
+pub mod jwt_token_service;
+pub mod sha256_secret_hasher;
+pub mod totp_service;
+pub mod aes_gcm_secret_cipher;
+pub mod qr_code;
+pub mod oidc_client;
+pub mod envelope_pii_cipher;
+#[cfg(test)]
+pub mod mock_oidc_issuer;
+
+pub use jwt_token_service::*;
+pub use sha256_secret_hasher::*;
+pub use totp_service::*;
+pub use aes_gcm_secret_cipher::*;
+pub use qr_code::*;
+pub use oidc_client::*;
+pub use envelope_pii_cipher::*;
\ No newline at end of file
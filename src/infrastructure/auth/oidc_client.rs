@@ -0,0 +1,311 @@
+use std::sync::Arc;
+use std::time::Duration;
+use data_encoding::BASE64URL_NOPAD;
+use jsonwebtoken::jwk::JwkSet;
+use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+use reqwest::Url;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tokio::sync::{OnceCell, RwLock};
+use crate::domain::{IdentityClaims, IdentityProvider, OidcLoginRequest, DomainError};
+use crate::infrastructure::config::OidcProviderConfig;
+
+const SCOPES: &str = "openid email profile";
+
+// Симметричные алгоритмы не допускаются: ими подписывают секретом клиента,
+// а не ключом из JWKS
+const ALLOWED_ALGORITHMS: [Algorithm; 7] = [
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::EdDSA,
+];
+
+#[derive(Debug, Clone, Deserialize)]
+struct ProviderMetadata {
+    issuer: String,
+    authorization_endpoint: String,
+    token_endpoint: String,
+    jwks_uri: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct IdTokenClaims {
+    sub: String,
+    nonce: Option<String>,
+    email: Option<String>,
+    #[serde(default)]
+    email_verified: bool,
+    name: Option<String>,
+}
+
+fn provider_error(message: impl std::fmt::Display) -> DomainError {
+    DomainError::ExternalServiceError(format!("OIDC provider: {}", message))
+}
+
+/// Клиент OpenID Connect: discovery, authorization code flow с PKCE (S256)
+/// и проверка подписи ID-токена по JWKS провайдера. Метаданные и ключи
+/// загружаются при первом обращении и кэшируются.
+#[derive(Clone)]
+pub struct OidcClient {
+    config: OidcProviderConfig,
+    http_client: reqwest::Client,
+    metadata: Arc<OnceCell<ProviderMetadata>>,
+    jwks: Arc<RwLock<Option<JwkSet>>>,
+}
+
+impl OidcClient {
+    pub fn new(config: OidcProviderConfig) -> Self {
+        let http_client = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(2))
+            .timeout(Duration::from_secs(5))
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("Failed to build OIDC HTTP client");
+
+        Self {
+            config,
+            http_client,
+            metadata: Arc::new(OnceCell::new()),
+            jwks: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    pub fn code_challenge(code_verifier: &str) -> String {
+        BASE64URL_NOPAD.encode(&Sha256::digest(code_verifier.as_bytes()))
+    }
+
+    async fn metadata(&self) -> Result<&ProviderMetadata, DomainError> {
+        self.metadata
+            .get_or_try_init(|| async {
+                let url = format!("{}/.well-known/openid-configuration", self.config.issuer.trim_end_matches('/'));
+                let metadata: ProviderMetadata = self.get_json(&url).await?;
+
+                // Подмена issuer в discovery позволила бы принимать чужие токены
+                if metadata.issuer != self.config.issuer {
+                    return Err(provider_error(format!(
+                        "discovery issuer {} does not match {}",
+                        metadata.issuer, self.config.issuer
+                    )));
+                }
+                Ok(metadata)
+            })
+            .await
+    }
+
+    async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, DomainError> {
+        self.http_client
+            .get(url)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(provider_error)?
+            .json()
+            .await
+            .map_err(provider_error)
+    }
+
+    async fn decoding_key(&self, kid: Option<&str>) -> Result<DecodingKey, DomainError> {
+        if let Some(key) = self.cached_key(kid).await {
+            return key;
+        }
+
+        // Неизвестный kid - вероятно, провайдер сменил ключи: перечитываем JWKS
+        let jwks: JwkSet = self.get_json(&self.metadata().await?.jwks_uri).await?;
+        *self.jwks.write().await = Some(jwks);
+
+        self.cached_key(kid)
+            .await
+            .unwrap_or_else(|| Err(DomainError::Unauthorized("ID token signed with an unknown key".to_string())))
+    }
+
+    async fn cached_key(&self, kid: Option<&str>) -> Option<Result<DecodingKey, DomainError>> {
+        let jwks = self.jwks.read().await;
+        let jwks = jwks.as_ref()?;
+        let jwk = match kid {
+            Some(kid) => jwks.find(kid)?,
+            None if jwks.keys.len() == 1 => &jwks.keys[0],
+            None => return None,
+        };
+        Some(DecodingKey::from_jwk(jwk).map_err(provider_error))
+    }
+
+    async fn verify_id_token(&self, id_token: &str, nonce: &str) -> Result<IdentityClaims, DomainError> {
+        let invalid = |message: String| DomainError::Unauthorized(format!("invalid ID token: {}", message));
+
+        let header = decode_header(id_token).map_err(|err| invalid(err.to_string()))?;
+        if !ALLOWED_ALGORITHMS.contains(&header.alg) {
+            return Err(invalid(format!("algorithm {:?} is not allowed", header.alg)));
+        }
+
+        let key = self.decoding_key(header.kid.as_deref()).await?;
+        let mut validation = Validation::new(header.alg);
+        validation.set_issuer(&[&self.config.issuer]);
+        validation.set_audience(&[&self.config.client_id]);
+        validation.set_required_spec_claims(&["exp", "iat", "iss", "aud", "sub"]);
+
+        let claims = decode::<IdTokenClaims>(id_token, &key, &validation)
+            .map_err(|err| invalid(err.to_string()))?
+            .claims;
+
+        // nonce связывает токен с нашим запросом и защищает от повторного использования
+        if claims.nonce.as_deref() != Some(nonce) {
+            return Err(invalid("nonce mismatch".to_string()));
+        }
+
+        Ok(IdentityClaims {
+            subject: claims.sub,
+            email: claims.email,
+            email_verified: claims.email_verified,
+            name: claims.name,
+        })
+    }
+}
+
+impl IdentityProvider for OidcClient {
+    async fn authorization_url(&self, request: &OidcLoginRequest) -> Result<String, DomainError> {
+        let metadata = self.metadata().await?;
+        let code_challenge = Self::code_challenge(request.code_verifier());
+
+        let url = Url::parse_with_params(
+            &metadata.authorization_endpoint,
+            &[
+                ("response_type", "code"),
+                ("client_id", self.config.client_id.as_str()),
+                ("redirect_uri", self.config.redirect_uri.as_str()),
+                ("scope", SCOPES),
+                ("state", request.state()),
+                ("nonce", request.nonce()),
+                ("code_challenge", code_challenge.as_str()),
+                ("code_challenge_method", "S256"),
+            ],
+        )
+        .map_err(provider_error)?;
+
+        Ok(url.to_string())
+    }
+
+    async fn exchange_code(&self, code: &str, request: &OidcLoginRequest) -> Result<IdentityClaims, DomainError> {
+        let metadata = self.metadata().await?;
+
+        let mut form = vec![
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", self.config.redirect_uri.as_str()),
+            ("client_id", self.config.client_id.as_str()),
+            ("code_verifier", request.code_verifier()),
+        ];
+        if let Some(client_secret) = &self.config.client_secret {
+            form.push(("client_secret", client_secret.as_str()));
+        }
+
+        let response = self.http_client
+            .post(&metadata.token_endpoint)
+            .form(&form)
+            .send()
+            .await
+            .map_err(provider_error)?;
+
+        // 400 означает отклоненный код (invalid_grant), а не сбой провайдера
+        if response.status() == reqwest::StatusCode::BAD_REQUEST {
+            return Err(DomainError::Unauthorized("authorization code was rejected".to_string()));
+        }
+        let tokens: TokenResponse = response
+            .error_for_status()
+            .map_err(provider_error)?
+            .json()
+            .await
+            .map_err(provider_error)?;
+
+        self.verify_id_token(&tokens.id_token, request.nonce()).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use crate::infrastructure::auth::mock_oidc_issuer::{MockIdentity, MockOidcIssuer};
+
+    fn login_request() -> OidcLoginRequest {
+        OidcLoginRequest::new(
+            "mock".to_string(),
+            "state-123".to_string(),
+            "nonce-123".to_string(),
+            "verifier-0123456789-0123456789-0123456789-0123456789".to_string(),
+            Utc::now(),
+        )
+    }
+
+    #[test]
+    fn test_code_challenge_matches_rfc7636_example() {
+        assert_eq!(
+            OidcClient::code_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_code_exchange_returns_verified_claims() {
+        let issuer = MockOidcIssuer::start(MockIdentity::verified("sub-1", "jane@example.com")).await;
+        let client = OidcClient::new(issuer.provider_config("mock"));
+        let request = login_request();
+
+        let authorization_url = client.authorization_url(&request).await.unwrap();
+        let code = issuer.authorize(&authorization_url).await;
+        let claims = client.exchange_code(&code, &request).await.unwrap();
+
+        assert_eq!(claims.subject, "sub-1");
+        assert_eq!(claims.email.as_deref(), Some("jane@example.com"));
+        assert!(claims.email_verified);
+    }
+
+    #[tokio::test]
+    async fn test_wrong_code_verifier_is_rejected() {
+        let issuer = MockOidcIssuer::start(MockIdentity::verified("sub-1", "jane@example.com")).await;
+        let client = OidcClient::new(issuer.provider_config("mock"));
+        let request = login_request();
+
+        let code = issuer.authorize(&client.authorization_url(&request).await.unwrap()).await;
+        let forged = OidcLoginRequest::new(
+            "mock".to_string(),
+            request.state().to_string(),
+            request.nonce().to_string(),
+            "another-verifier-0123456789-0123456789-0123456789".to_string(),
+            Utc::now(),
+        );
+
+        assert!(matches!(client.exchange_code(&code, &forged).await, Err(DomainError::Unauthorized(_))));
+    }
+
+    #[tokio::test]
+    async fn test_token_for_another_nonce_is_rejected() {
+        let issuer = MockOidcIssuer::start(MockIdentity::verified("sub-1", "jane@example.com")).await;
+        let client = OidcClient::new(issuer.provider_config("mock"));
+        let request = login_request();
+
+        let code = issuer.authorize(&client.authorization_url(&request).await.unwrap()).await;
+        let replayed = OidcLoginRequest::new(
+            "mock".to_string(),
+            request.state().to_string(),
+            "other-nonce".to_string(),
+            request.code_verifier().to_string(),
+            Utc::now(),
+        );
+
+        let result = client.exchange_code(&code, &replayed).await;
+        assert!(matches!(result, Err(DomainError::Unauthorized(message)) if message.contains("nonce")));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,70 @@
+use qrcode::render::svg;
+use qrcode::{Color, QrCode};
+use crate::domain::DomainError;
+
+const QUIET_ZONE_MODULES: usize = 4;
+const PNG_MODULE_PIXELS: usize = 8;
+const SVG_MIN_DIMENSION: u32 = 256;
+
+fn encode(data: &str) -> Result<QrCode, DomainError> {
+    QrCode::new(data.as_bytes()).map_err(|err| DomainError::InvalidOperation(format!("Failed to build QR code: {}", err)))
+}
+
+pub fn render_qr_svg(data: &str) -> Result<String, DomainError> {
+    Ok(encode(data)?
+        .render::<svg::Color>()
+        .min_dimensions(SVG_MIN_DIMENSION, SVG_MIN_DIMENSION)
+        .build())
+}
+
+pub fn render_qr_png(data: &str) -> Result<Vec<u8>, DomainError> {
+    let code = encode(data)?;
+    let modules = code.width();
+    let colors = code.to_colors();
+    let size = (modules + 2 * QUIET_ZONE_MODULES) * PNG_MODULE_PIXELS;
+
+    // Оттенки серого: 0 - темный модуль, 255 - фон и свободная зона
+    let mut pixels = vec![255u8; size * size];
+    for (index, color) in colors.iter().enumerate() {
+        if *color != Color::Dark {
+            continue;
+        }
+        let x = (index % modules + QUIET_ZONE_MODULES) * PNG_MODULE_PIXELS;
+        let y = (index / modules + QUIET_ZONE_MODULES) * PNG_MODULE_PIXELS;
+        for row in y..y + PNG_MODULE_PIXELS {
+            pixels[row * size + x..row * size + x + PNG_MODULE_PIXELS].fill(0);
+        }
+    }
+
+    let to_error = |err: png::EncodingError| DomainError::InvalidOperation(format!("Failed to encode PNG: {}", err));
+    let mut png_bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut png_bytes, size as u32, size as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(to_error)?;
+    writer.write_image_data(&pixels).map_err(to_error)?;
+    writer.finish().map_err(to_error)?;
+
+    Ok(png_bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URI: &str = "otpauth://totp/Example:john%40example.com?secret=GEZDGNBVGY3TQOJQ&issuer=Example";
+
+    #[test]
+    fn test_render_svg() {
+        let svg = render_qr_svg(URI).unwrap();
+
+        assert!(svg.contains("<svg"));
+    }
+
+    #[test]
+    fn test_render_png() {
+        let png_bytes = render_qr_png(URI).unwrap();
+
+        assert_eq!(&png_bytes[..8], b"\x89PNG\r\n\x1a\n");
+    }
+}
\ No newline at end of file
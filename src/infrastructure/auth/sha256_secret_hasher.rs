@@ -0,0 +1,45 @@
+use sha2::{Digest, Sha256};
+use crate::domain::SecretHasher;
+
+// Секреты генерируются случайно и достаточно длинные, поэтому медленный
+// парольный хеш не нужен - хватает SHA-256
+#[derive(Debug, Clone, Default)]
+pub struct Sha256SecretHasher;
+
+impl Sha256SecretHasher {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl SecretHasher for Sha256SecretHasher {
+    fn hash(&self, secret: &str) -> String {
+        Sha256::digest(secret.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+
+    fn verify(&self, secret: &str, hash: &str) -> bool {
+        let expected = self.hash(secret);
+        // Сравнение за постоянное время, чтобы не выдавать совпадающий префикс
+        expected.len() == hash.len()
+            && expected.bytes().zip(hash.bytes()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hash_and_verify() {
+        let hasher = Sha256SecretHasher::new();
+        let hash = hasher.hash("secret");
+
+        assert_eq!(hash.len(), 64);
+        assert_ne!(hash, "secret");
+        assert!(hasher.verify("secret", &hash));
+        assert!(!hasher.verify("Secret", &hash));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,133 @@
+use chrono::{DateTime, Utc};
+use data_encoding::BASE32_NOPAD;
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha1::Sha1;
+use crate::domain::TotpService;
+
+const SECRET_LENGTH: usize = 20;
+const DIGITS: u32 = 6;
+const PERIOD_SECONDS: i64 = 30;
+// Допускаем расхождение часов на один шаг в каждую сторону
+const ALLOWED_SKEW: i64 = 1;
+
+/// TOTP по RFC 6238 (HMAC-SHA1, 6 цифр, шаг 30 секунд) - параметры,
+/// которые понимают все распространенные приложения-аутентификаторы.
+#[derive(Debug, Clone)]
+pub struct Rfc6238TotpService {
+    issuer: String,
+}
+
+impl Rfc6238TotpService {
+    pub fn new(issuer: impl Into<String>) -> Self {
+        Self { issuer: issuer.into() }
+    }
+
+    pub fn code_at(secret: &[u8], step: u64) -> String {
+        let mut mac = Hmac::<Sha1>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+        mac.update(&step.to_be_bytes());
+        let digest = mac.finalize().into_bytes();
+
+        // Динамическое усечение (RFC 4226, раздел 5.3)
+        let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+        let binary = u32::from_be_bytes([digest[offset], digest[offset + 1], digest[offset + 2], digest[offset + 3]])
+            & 0x7fff_ffff;
+
+        format!("{:0width$}", binary % 10u32.pow(DIGITS), width = DIGITS as usize)
+    }
+
+    fn step_at(now: DateTime<Utc>) -> i64 {
+        now.timestamp().div_euclid(PERIOD_SECONDS)
+    }
+}
+
+impl TotpService for Rfc6238TotpService {
+    fn generate_secret(&self) -> Vec<u8> {
+        let mut secret = vec![0u8; SECRET_LENGTH];
+        rand::rng().fill_bytes(&mut secret);
+        secret
+    }
+
+    fn encode_secret(&self, secret: &[u8]) -> String {
+        BASE32_NOPAD.encode(secret)
+    }
+
+    fn provisioning_uri(&self, secret: &[u8], account: &str) -> String {
+        format!(
+            "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+            percent_encode(&self.issuer),
+            percent_encode(account),
+            self.encode_secret(secret),
+            percent_encode(&self.issuer),
+            DIGITS,
+            PERIOD_SECONDS,
+        )
+    }
+
+    fn verify(&self, secret: &[u8], code: &str, now: DateTime<Utc>) -> Option<u64> {
+        if code.len() != DIGITS as usize || !code.chars().all(|c| c.is_ascii_digit()) {
+            return None;
+        }
+
+        let current = Self::step_at(now);
+        (current - ALLOWED_SKEW..=current + ALLOWED_SKEW)
+            .filter_map(|step| u64::try_from(step).ok())
+            .find(|step| {
+                let expected = Self::code_at(secret, *step);
+                expected.bytes().zip(code.bytes()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+            })
+    }
+}
+
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn test_rfc6238_vectors() {
+        // Тестовые векторы RFC 6238 (SHA1), усеченные до 6 цифр
+        assert_eq!(Rfc6238TotpService::code_at(RFC_SECRET, 59 / 30), "287082");
+        assert_eq!(Rfc6238TotpService::code_at(RFC_SECRET, 1111111109 / 30), "081804");
+        assert_eq!(Rfc6238TotpService::code_at(RFC_SECRET, 2000000000 / 30), "279037");
+    }
+
+    #[test]
+    fn test_verify_allows_one_step_of_skew() {
+        let service = Rfc6238TotpService::new("Example");
+        let now = Utc.timestamp_opt(1111111109, 0).unwrap();
+        let step = 1111111109 / 30;
+
+        assert_eq!(service.verify(RFC_SECRET, "081804", now), Some(step));
+        let previous = Rfc6238TotpService::code_at(RFC_SECRET, step - 1);
+        assert_eq!(service.verify(RFC_SECRET, &previous, now), Some(step - 1));
+        let stale = Rfc6238TotpService::code_at(RFC_SECRET, step - 2);
+        assert_eq!(service.verify(RFC_SECRET, &stale, now), None);
+        assert_eq!(service.verify(RFC_SECRET, "12345", now), None);
+    }
+
+    #[test]
+    fn test_provisioning_uri() {
+        let service = Rfc6238TotpService::new("Clean API");
+
+        let uri = service.provisioning_uri(RFC_SECRET, "john@example.com");
+
+        assert_eq!(
+            uri,
+            "otpauth://totp/Clean%20API:john%40example.com?secret=GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ\
+             &issuer=Clean%20API&algorithm=SHA1&digits=6&period=30"
+        );
+    }
+}
\ No newline at end of file
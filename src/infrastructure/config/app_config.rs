@@ -1,62 +1,142 @@
-use std::env;
+use chrono::{DateTime, Duration, Utc};
+use crate::domain::{LockoutPolicy, RateLimitQuota, SessionPolicy};
+use super::{ConfigArgs, ConfigError, ConfigLoader};
+
+// Внешний провайдер входа (OpenID Connect); endpoints берутся из discovery
+#[derive(Debug, Clone)]
+pub struct OidcProviderConfig {
+    pub name: String,
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub redirect_uri: String,
+}
+
+// Квоты групп маршрутов; None - ограничение для группы отключено
+#[derive(Debug, Clone)]
+pub struct RateLimitConfig {
+    // Маршруты без аутентификации, бюджет по IP-адресу
+    pub public: Option<RateLimitQuota>,
+    // Маршруты пользователей, бюджет по API-ключу или пользователю
+    pub users: Option<RateLimitQuota>,
+    pub admin: Option<RateLimitQuota>,
+    pub scim: Option<RateLimitQuota>,
+    // Общее хранилище бюджетов (`sqlite:...`); без него - память процесса
+    pub store_url: Option<String>,
+}
+
+impl Default for RateLimitConfig {
+    fn default() -> Self {
+        let per_minute = |limit| RateLimitQuota::new(limit, Duration::minutes(1)).ok();
+        Self {
+            public: per_minute(60),
+            users: per_minute(600),
+            admin: per_minute(120),
+            scim: per_minute(1200),
+            store_url: None,
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct AppConfig {
+    // `development` включает инструменты разработчика (GraphiQL)
+    pub app_env: String,
     pub server_host: String,
     pub server_port: u16,
     pub database_url: String,
     pub jwt_secret: String,
+    // Base64-ключ AES-256 для шифрования TOTP-секретов
+    pub mfa_encryption_key: String,
+    // Base64-ключ AES-256 для шифрования секретов подписок webhook
+    pub webhook_encryption_key: String,
+    // JSON-файл с мастер-ключами и ключом индекса для шифрования email и имен
+    pub pii_keyfile: Option<String>,
+    pub lockout_threshold: u32,
+    pub lockout_duration_secs: u64,
+    // Файл для счетчиков неудачных входов; без него они живут только в памяти
+    pub login_attempts_path: Option<String>,
+    // Файл журнала аудита (JSON Lines); без него журнал живет только в памяти
+    pub audit_log_path: Option<String>,
+    pub session_idle_timeout_secs: u64,
+    pub session_absolute_timeout_secs: u64,
+    // Отключается только для локальной разработки по HTTP
+    pub session_cookie_secure: bool,
+    pub oidc_providers: Vec<OidcProviderConfig>,
+    // Токен SCIM-клиента (IdP); без него /scim/v2 не подключается
+    pub scim_bearer_token: Option<String>,
+    pub rate_limits: RateLimitConfig,
     pub email_service_url: Option<String>,
+    pub email_service_api_key: Option<String>,
     pub log_level: String,
+    pub graphql_max_depth: usize,
+    pub graphql_max_complexity: usize,
+    // Даты вывода v1 из употребления (заголовки Deprecation и Sunset)
+    pub api_v1_deprecated_at: Option<DateTime<Utc>>,
+    pub api_v1_sunset_at: Option<DateTime<Utc>>,
 }
 
 impl Default for AppConfig {
     fn default() -> Self {
         Self {
+            app_env: "production".to_string(),
             server_host: "0.0.0.0".to_string(),
             server_port: 3000,
             database_url: "in-memory".to_string(),
+            // Только для разработки: вне ее загрузчик требует свои значения
             jwt_secret: "your-secret-key".to_string(),
+            mfa_encryption_key: "ZGV2LW9ubHktbWZhLWVuY3J5cHRpb24ta2V5LTAwMDA=".to_string(),
+            webhook_encryption_key: "ZGV2LW9ubHktd2ViaG9vay1zZWNyZXQta2V5LTAwMDA=".to_string(),
+            pii_keyfile: None,
+            lockout_threshold: 5,
+            lockout_duration_secs: 900,
+            login_attempts_path: None,
+            audit_log_path: None,
+            session_idle_timeout_secs: 1800,
+            session_absolute_timeout_secs: 43200,
+            session_cookie_secure: true,
+            oidc_providers: Vec::new(),
+            scim_bearer_token: None,
+            rate_limits: RateLimitConfig::default(),
             email_service_url: None,
+            email_service_api_key: None,
             log_level: "info".to_string(),
+            graphql_max_depth: 8,
+            graphql_max_complexity: 500,
+            api_v1_deprecated_at: None,
+            api_v1_sunset_at: None,
         }
     }
 }
 
 impl AppConfig {
-    pub fn from_env() -> Self {
-        let mut config = Self::default();
-        
-        if let Ok(host) = env::var("SERVER_HOST") {
-            config.server_host = host;
-        }
-        
-        if let Ok(port) = env::var("SERVER_PORT") {
-            config.server_port = port.parse().unwrap_or(3000);
-        }
-        
-        if let Ok(db_url) = env::var("DATABASE_URL") {
-            config.database_url = db_url;
-        }
-        
-        if let Ok(secret) = env::var("JWT_SECRET") {
-            config.jwt_secret = secret;
-        }
-        
-        if let Ok(email_url) = env::var("EMAIL_SERVICE_URL") {
-            config.email_service_url = Some(email_url);
-        }
-        
-        if let Ok(log_level) = env::var("LOG_LEVEL") {
-            config.log_level = log_level;
-        }
-        
-        config
+    // Слои конфигурации без аргументов командной строки
+    pub fn from_env() -> Result<Self, ConfigError> {
+        ConfigLoader::new(ConfigArgs::default()).load().map(|loaded| loaded.config)
+    }
+
+    pub fn is_development(&self) -> bool {
+        self.app_env == "development"
     }
 
     pub fn server_address(&self) -> String {
         format!("{}:{}", self.server_host, self.server_port)
     }
+
+    pub fn lockout_policy(&self) -> LockoutPolicy {
+        LockoutPolicy {
+            account_threshold: self.lockout_threshold,
+            lockout_duration: chrono::Duration::seconds(self.lockout_duration_secs as i64),
+            ..LockoutPolicy::default()
+        }
+    }
+
+    pub fn session_policy(&self) -> SessionPolicy {
+        SessionPolicy {
+            idle_timeout: chrono::Duration::seconds(self.session_idle_timeout_secs as i64),
+            absolute_timeout: chrono::Duration::seconds(self.session_absolute_timeout_secs as i64),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -73,28 +153,46 @@ mod tests {
 
     #[test]
     fn test_config_from_env() {
-        // Устанавливаем переменные окружения
-        std::env::set_var("SERVER_HOST", "127.0.0.1");
-        std::env::set_var("SERVER_PORT", "8080");
-        
-        let config = AppConfig::from_env();
+        // Окружение передается загрузчику напрямую: менять окружение процесса
+        // в тестах небезопасно, их потоки читают его одновременно
+        let env = [("APP_ENV", "development"), ("SERVER_HOST", "127.0.0.1"), ("SERVER_PORT", "8080")];
+        let config = ConfigLoader::new(ConfigArgs::default())
+            .with_env(env.map(|(name, value)| (name.to_string(), value.to_string())))
+            .load()
+            .unwrap()
+            .config;
         assert_eq!(config.server_host, "127.0.0.1");
         assert_eq!(config.server_port, 8080);
-        
-        // Очищаем переменные
-        std::env::remove_var("SERVER_HOST");
-        std::env::remove_var("SERVER_PORT");
     }
 
     #[test]
     fn test_server_address() {
         let config = AppConfig {
+            app_env: "test".to_string(),
             server_host: "localhost".to_string(),
             server_port: 8080,
             database_url: "test".to_string(),
             jwt_secret: "test".to_string(),
+            mfa_encryption_key: "test".to_string(),
+            webhook_encryption_key: "test".to_string(),
+            pii_keyfile: None,
+            lockout_threshold: 5,
+            lockout_duration_secs: 900,
+            login_attempts_path: None,
+            audit_log_path: None,
+            session_idle_timeout_secs: 1800,
+            session_absolute_timeout_secs: 43200,
+            session_cookie_secure: true,
+            oidc_providers: Vec::new(),
+            scim_bearer_token: None,
+            rate_limits: RateLimitConfig::default(),
             email_service_url: None,
+            email_service_api_key: None,
             log_level: "test".to_string(),
+            graphql_max_depth: 8,
+            graphql_max_complexity: 500,
+            api_v1_deprecated_at: None,
+            api_v1_sunset_at: None,
         };
         
         assert_eq!(config.server_address(), "localhost:8080");
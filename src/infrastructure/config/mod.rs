@@ -1,3 +1,5 @@
 pub mod app_config;
+pub mod config_loader;
 
-pub use app_config::*;
\ No newline at end of file
+pub use app_config::*;
+pub use config_loader::*;
\ No newline at end of file
@@ -0,0 +1,14 @@
+use crate::domain::{UserEvent, UserEventBus};
+
+/// Пишет каждое доменное событие пользователя в лог. Значения полей не
+/// логируются: в событиях есть email и имена.
+pub fn subscribe_event_logging(event_bus: &UserEventBus) {
+    event_bus.subscribe(|event: UserEvent| async move {
+        tracing::info!(
+            event = event.name(),
+            user_id = %event.user_id(),
+            occurred_at = %event.occurred_at(),
+            "User domain event"
+        );
+    });
+}
\ No newline at end of file
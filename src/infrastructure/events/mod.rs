@@ -0,0 +1,5 @@
+pub mod logging_event_subscriber;
+pub mod user_change_feed;
+
+pub use logging_event_subscriber::*;
+pub use user_change_feed::*;
\ No newline at end of file
@@ -0,0 +1,191 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+use serde_json::json;
+use tokio::sync::broadcast;
+use crate::domain::{UserEvent, UserEventBus, UserId};
+
+const DEFAULT_REPLAY_CAPACITY: usize = 1000;
+const LIVE_CHANNEL_CAPACITY: usize = 256;
+
+// Одно изменение в ленте: `id` растет монотонно в пределах процесса
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserChange {
+    pub id: u64,
+    pub event: &'static str,
+    pub user_id: UserId,
+    pub data: String,
+}
+
+pub struct UserChangeSubscription {
+    // Изменения после `Last-Event-ID`, которые клиент пропустил
+    pub replay: Vec<UserChange>,
+    // Часть пропущенных изменений уже вытеснена из буфера (или id из
+    // прошлого запуска) - клиенту нужно перечитать состояние целиком
+    pub truncated: bool,
+    pub receiver: broadcast::Receiver<UserChange>,
+}
+
+struct FeedState {
+    next_id: u64,
+    capacity: usize,
+    buffer: VecDeque<UserChange>,
+}
+
+/// Лента изменений пользователей для SSE. Последние изменения хранятся в
+/// ограниченном буфере, чтобы переподключившийся клиент получил пропущенное.
+#[derive(Clone)]
+pub struct UserChangeFeed {
+    state: Arc<Mutex<FeedState>>,
+    sender: broadcast::Sender<UserChange>,
+}
+
+impl UserChangeFeed {
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_REPLAY_CAPACITY)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(LIVE_CHANNEL_CAPACITY);
+        Self {
+            state: Arc::new(Mutex::new(FeedState {
+                next_id: 1,
+                capacity: capacity.max(1),
+                buffer: VecDeque::new(),
+            })),
+            sender,
+        }
+    }
+
+    pub fn publish(&self, event: &UserEvent) -> u64 {
+        let mut state = self.state.lock().expect("Change feed lock poisoned");
+        let id = state.next_id;
+        let change = UserChange {
+            id,
+            event: event.name(),
+            user_id: event.user_id().clone(),
+            data: user_change_payload(event),
+        };
+        state.next_id += 1;
+        if state.buffer.len() == state.capacity {
+            state.buffer.pop_front();
+        }
+        state.buffer.push_back(change.clone());
+
+        // Отправка под блокировкой: подписчик не получит изменение дважды
+        // (из буфера и из канала) и не потеряет его между ними
+        let _ = self.sender.send(change);
+        id
+    }
+
+    pub fn subscribe(&self, last_event_id: Option<u64>) -> UserChangeSubscription {
+        let state = self.state.lock().expect("Change feed lock poisoned");
+        let receiver = self.sender.subscribe();
+
+        let Some(last_event_id) = last_event_id else {
+            return UserChangeSubscription { replay: Vec::new(), truncated: false, receiver };
+        };
+
+        if last_event_id >= state.next_id {
+            return UserChangeSubscription { replay: Vec::new(), truncated: true, receiver };
+        }
+
+        let oldest = state.buffer.front().map_or(state.next_id, |change| change.id);
+        let replay = state.buffer
+            .iter()
+            .filter(|change| change.id > last_event_id)
+            .cloned()
+            .collect();
+
+        UserChangeSubscription { replay, truncated: last_event_id + 1 < oldest, receiver }
+    }
+}
+
+impl Default for UserChangeFeed {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn subscribe_user_change_feed(event_bus: &UserEventBus, feed: UserChangeFeed) {
+    event_bus.subscribe(move |event: UserEvent| {
+        let feed = feed.clone();
+        async move {
+            feed.publish(&event);
+        }
+    });
+}
+
+/// Сведения о событии без персональных данных: email и имя клиент читает
+/// через API, а в буфере и журналах доставок они не задерживаются.
+pub fn user_event_data(event: &UserEvent) -> serde_json::Value {
+    let mut data = json!({ "user_id": event.user_id().to_string() });
+    match event {
+        UserEvent::Created(event) => data["roles"] = json!(event.roles.iter().map(|role| role.as_str()).collect::<Vec<_>>()),
+        UserEvent::RolesChanged(event) => {
+            data["previous_roles"] = json!(event.previous_roles.iter().map(|role| role.as_str()).collect::<Vec<_>>());
+            data["roles"] = json!(event.roles.iter().map(|role| role.as_str()).collect::<Vec<_>>());
+        }
+        _ => {}
+    }
+    data
+}
+
+fn user_change_payload(event: &UserEvent) -> String {
+    json!({
+        "type": event.name(),
+        "occurred_at": event.occurred_at().to_rfc3339(),
+        "data": user_event_data(event),
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Email, User};
+
+    fn events(count: usize) -> Vec<UserEvent> {
+        let mut user = User::new(Email::new("alice@example.com".to_string()).unwrap(), "Alice".to_string()).unwrap();
+        for index in 1..count {
+            user.update_name(format!("Alice {}", index)).unwrap();
+        }
+        user.take_events()
+    }
+
+    #[tokio::test]
+    async fn test_replay_after_last_event_id() {
+        let feed = UserChangeFeed::with_capacity(3);
+        for event in events(5) {
+            feed.publish(&event);
+        }
+
+        let resumed = feed.subscribe(Some(3));
+        assert_eq!(resumed.replay.iter().map(|change| change.id).collect::<Vec<_>>(), vec![4, 5]);
+        assert!(!resumed.truncated);
+        assert_eq!(resumed.replay[0].event, "user.renamed");
+        assert!(!resumed.replay[0].data.contains("alice@example.com"));
+
+        // Изменение 2 уже вытеснено из буфера
+        let stale = feed.subscribe(Some(1));
+        assert!(stale.truncated);
+        assert_eq!(stale.replay.len(), 3);
+
+        // id из прошлого запуска процесса
+        let unknown = feed.subscribe(Some(42));
+        assert!(unknown.truncated && unknown.replay.is_empty());
+        assert!(!feed.subscribe(None).truncated);
+    }
+
+    #[tokio::test]
+    async fn test_live_changes_follow_replay() {
+        let feed = UserChangeFeed::new();
+        let mut events = events(2).into_iter();
+        feed.publish(&events.next().unwrap());
+
+        let mut subscription = feed.subscribe(Some(0));
+        feed.publish(&events.next().unwrap());
+
+        assert_eq!(subscription.replay.len(), 1);
+        assert_eq!(subscription.receiver.recv().await.unwrap().id, 2);
+    }
+}
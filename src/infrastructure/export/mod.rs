@@ -0,0 +1,3 @@
+pub mod zip_archive;
+
+pub use zip_archive::*;
\ No newline at end of file
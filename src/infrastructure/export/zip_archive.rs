@@ -0,0 +1,41 @@
+use std::io::{Cursor, Write};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+use crate::domain::DomainError;
+
+// ZIP-архив в памяти из пар (имя файла, содержимое)
+pub fn write_zip_archive(files: &[(String, Vec<u8>)]) -> Result<Vec<u8>, DomainError> {
+    let to_error = |err: zip::result::ZipError| DomainError::InvalidOperation(format!("Failed to build archive: {}", err));
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    let mut archive = ZipWriter::new(Cursor::new(Vec::new()));
+    for (name, contents) in files {
+        archive.start_file(name.as_str(), options).map_err(to_error)?;
+        archive.write_all(contents).map_err(|err| to_error(err.into()))?;
+    }
+
+    Ok(archive.finish().map_err(to_error)?.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use zip::ZipArchive;
+
+    #[test]
+    fn test_archive_round_trip() {
+        let files = vec![
+            ("profile.json".to_string(), b"{\"name\":\"Jane\"}".to_vec()),
+            ("sessions.json".to_string(), b"[]".to_vec()),
+        ];
+
+        let bytes = write_zip_archive(&files).unwrap();
+        let mut archive = ZipArchive::new(Cursor::new(bytes)).unwrap();
+
+        assert_eq!(archive.len(), 2);
+        let mut contents = String::new();
+        archive.by_name("profile.json").unwrap().read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, "{\"name\":\"Jane\"}");
+    }
+}
\ No newline at end of file
@@ -1,10 +1,15 @@
+use chrono::{DateTime, Utc};
 use crate::domain::{Email, User, DomainError};
 
+// `idempotency_key` одинаков для всех повторных отправок одного письма,
+// по нему провайдер отбрасывает дубликаты
 pub trait EmailService: Send + Sync {
-    async fn send_welcome_email(&self, user: &User) -> Result<(), DomainError>;
-    async fn send_password_reset_email(&self, email: &Email, reset_token: String) -> Result<(), DomainError>;
+    async fn send_welcome_email(&self, user: &User, idempotency_key: &str) -> Result<(), DomainError>;
+    async fn send_password_reset_email(&self, email: &Email, reset_token: String, idempotency_key: &str) -> Result<(), DomainError>;
+    async fn send_account_locked_email(&self, user: &User, locked_until: DateTime<Utc>, idempotency_key: &str) -> Result<(), DomainError>;
 }
 
+#[derive(Default)]
 pub struct ConsoleEmailService;
 
 impl ConsoleEmailService {
@@ -14,7 +19,7 @@ impl ConsoleEmailService {
 }
 
 impl EmailService for ConsoleEmailService {
-    async fn send_welcome_email(&self, user: &User) -> Result<(), DomainError> {
+    async fn send_welcome_email(&self, user: &User, _idempotency_key: &str) -> Result<(), DomainError> {
         println!(
             "Добро пожаловать, {}! Ваш email: {}",
             user.name(),
@@ -23,7 +28,7 @@ impl EmailService for ConsoleEmailService {
         Ok(())
     }
 
-    async fn send_password_reset_email(&self, email: &Email, reset_token: String) -> Result<(), DomainError> {
+    async fn send_password_reset_email(&self, email: &Email, reset_token: String, _idempotency_key: &str) -> Result<(), DomainError> {
         println!(
             "Отправлен токен сброса пароля {} для email: {}",
             reset_token,
@@ -31,34 +36,56 @@ impl EmailService for ConsoleEmailService {
         );
         Ok(())
     }
+
+    async fn send_account_locked_email(&self, user: &User, locked_until: DateTime<Utc>, _idempotency_key: &str) -> Result<(), DomainError> {
+        println!(
+            "Аккаунт {} заблокирован до {} из-за неудачных попыток входа",
+            user.email(),
+            locked_until
+        );
+        Ok(())
+    }
 }
 
+#[derive(Default)]
 pub struct MockEmailService {
     sent_emails: std::sync::Mutex<Vec<String>>,
+    idempotency_keys: std::sync::Mutex<Vec<String>>,
 }
 
 impl MockEmailService {
     pub fn new() -> Self {
-        Self {
-            sent_emails: std::sync::Mutex::new(Vec::new()),
-        }
+        Self::default()
     }
 
     pub fn get_sent_emails(&self) -> Vec<String> {
         self.sent_emails.lock().unwrap().clone()
     }
+
+    pub fn get_idempotency_keys(&self) -> Vec<String> {
+        self.idempotency_keys.lock().unwrap().clone()
+    }
 }
 
 impl EmailService for MockEmailService {
-    async fn send_welcome_email(&self, user: &User) -> Result<(), DomainError> {
+    async fn send_welcome_email(&self, user: &User, idempotency_key: &str) -> Result<(), DomainError> {
         let email = format!("WELCOME: {} - {}", user.name(), user.email());
         self.sent_emails.lock().unwrap().push(email);
+        self.idempotency_keys.lock().unwrap().push(idempotency_key.to_string());
         Ok(())
     }
 
-    async fn send_password_reset_email(&self, email: &Email, reset_token: String) -> Result<(), DomainError> {
+    async fn send_password_reset_email(&self, email: &Email, reset_token: String, idempotency_key: &str) -> Result<(), DomainError> {
         let email = format!("RESET: {} - {}", email, reset_token);
         self.sent_emails.lock().unwrap().push(email);
+        self.idempotency_keys.lock().unwrap().push(idempotency_key.to_string());
+        Ok(())
+    }
+
+    async fn send_account_locked_email(&self, user: &User, locked_until: DateTime<Utc>, idempotency_key: &str) -> Result<(), DomainError> {
+        let email = format!("LOCKED: {} - {}", user.email(), locked_until.to_rfc3339());
+        self.sent_emails.lock().unwrap().push(email);
+        self.idempotency_keys.lock().unwrap().push(idempotency_key.to_string());
         Ok(())
     }
 }
@@ -73,7 +100,7 @@ mod tests {
         let email = Email::new("test@example.com".to_string()).unwrap();
         let user = User::new(email, "Test User".to_string()).unwrap();
 
-        let result = service.send_welcome_email(&user).await;
+        let result = service.send_welcome_email(&user, "welcome").await;
         assert!(result.is_ok());
     }
 
@@ -83,7 +110,7 @@ mod tests {
         let email = Email::new("test@example.com".to_string()).unwrap();
         let user = User::new(email, "Test User".to_string()).unwrap();
 
-        let result = service.send_welcome_email(&user).await;
+        let result = service.send_welcome_email(&user, "welcome").await;
         assert!(result.is_ok());
 
         let sent_emails = service.get_sent_emails();
@@ -0,0 +1,65 @@
+use chrono::{DateTime, Utc};
+use crate::domain::{Email, User, DomainError};
+use crate::infrastructure::config::AppConfig;
+use crate::infrastructure::external_services::{ConsoleEmailService, EmailService, HttpEmailService, HttpEmailServiceConfig};
+
+// Письма уходят провайдеру, если задан `EMAIL_SERVICE_URL`, иначе печатаются в консоль
+pub enum EmailServiceBackend {
+    Console(ConsoleEmailService),
+    Http(HttpEmailService),
+}
+
+impl EmailServiceBackend {
+    pub fn from_app_config(config: &AppConfig) -> Result<Self, DomainError> {
+        match &config.email_service_url {
+            Some(_) => {
+                // Письма доставляет outbox, и повторы с backoff делает только он
+                let mut http_config = HttpEmailServiceConfig::from_app_config(config)?;
+                http_config.max_retries = 0;
+                HttpEmailService::new(http_config).map(EmailServiceBackend::Http)
+            }
+            None => Ok(EmailServiceBackend::Console(ConsoleEmailService::new())),
+        }
+    }
+}
+
+impl EmailService for EmailServiceBackend {
+    async fn send_welcome_email(&self, user: &User, idempotency_key: &str) -> Result<(), DomainError> {
+        match self {
+            EmailServiceBackend::Console(service) => service.send_welcome_email(user, idempotency_key).await,
+            EmailServiceBackend::Http(service) => service.send_welcome_email(user, idempotency_key).await,
+        }
+    }
+
+    async fn send_password_reset_email(&self, email: &Email, reset_token: String, idempotency_key: &str) -> Result<(), DomainError> {
+        match self {
+            EmailServiceBackend::Console(service) => service.send_password_reset_email(email, reset_token, idempotency_key).await,
+            EmailServiceBackend::Http(service) => service.send_password_reset_email(email, reset_token, idempotency_key).await,
+        }
+    }
+
+    async fn send_account_locked_email(&self, user: &User, locked_until: DateTime<Utc>, idempotency_key: &str) -> Result<(), DomainError> {
+        match self {
+            EmailServiceBackend::Console(service) => service.send_account_locked_email(user, locked_until, idempotency_key).await,
+            EmailServiceBackend::Http(service) => service.send_account_locked_email(user, locked_until, idempotency_key).await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_email_service_url_selects_http_provider() {
+        let config = AppConfig {
+            email_service_url: Some("https://mail.example.com/send".to_string()),
+            ..AppConfig::default()
+        };
+        match EmailServiceBackend::from_app_config(&config) {
+            Ok(EmailServiceBackend::Http(service)) => assert_eq!(service.config().max_retries, 0),
+            _ => panic!("expected HTTP email provider"),
+        }
+        assert!(matches!(EmailServiceBackend::from_app_config(&AppConfig::default()), Ok(EmailServiceBackend::Console(_))));
+    }
+}
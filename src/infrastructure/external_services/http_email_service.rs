@@ -0,0 +1,396 @@
+use std::time::Duration;
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use reqwest::StatusCode;
+use serde::Serialize;
+use crate::domain::{Email, User, DomainError};
+use crate::infrastructure::config::AppConfig;
+use crate::infrastructure::external_services::{CircuitBreaker, EmailService};
+
+#[derive(Debug, Clone)]
+pub struct HttpEmailServiceConfig {
+    pub url: String,
+    pub api_key: Option<String>,
+    pub connect_timeout: Duration,
+    pub request_timeout: Duration,
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub failure_threshold: u32,
+    pub open_duration: Duration,
+}
+
+impl HttpEmailServiceConfig {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            api_key: None,
+            connect_timeout: Duration::from_secs(2),
+            request_timeout: Duration::from_secs(5),
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(200),
+            max_backoff: Duration::from_secs(5),
+            failure_threshold: 5,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+
+    pub fn from_app_config(config: &AppConfig) -> Result<Self, DomainError> {
+        let url = config.email_service_url.clone().ok_or_else(|| {
+            DomainError::ExternalServiceError("EMAIL_SERVICE_URL is not configured".to_string())
+        })?;
+
+        let mut http_config = Self::new(url);
+        http_config.api_key = config.email_service_api_key.clone();
+        Ok(http_config)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EmailMessage {
+    pub to: String,
+    pub template: String,
+    pub subject: String,
+    pub text: String,
+}
+
+enum AttemptError {
+    Retryable(String),
+    Permanent(String),
+}
+
+pub struct HttpEmailService {
+    client: reqwest::Client,
+    config: HttpEmailServiceConfig,
+    circuit_breaker: CircuitBreaker,
+}
+
+impl HttpEmailService {
+    pub fn new(config: HttpEmailServiceConfig) -> Result<Self, DomainError> {
+        let client = reqwest::Client::builder()
+            .connect_timeout(config.connect_timeout)
+            .timeout(config.request_timeout)
+            .build()
+            .map_err(|err| DomainError::ExternalServiceError(err.to_string()))?;
+
+        Ok(Self {
+            client,
+            circuit_breaker: CircuitBreaker::new(config.failure_threshold, config.open_duration),
+            config,
+        })
+    }
+
+    pub fn from_app_config(config: &AppConfig) -> Result<Self, DomainError> {
+        Self::new(HttpEmailServiceConfig::from_app_config(config)?)
+    }
+
+    pub fn config(&self) -> &HttpEmailServiceConfig {
+        &self.config
+    }
+
+    pub fn circuit_breaker(&self) -> &CircuitBreaker {
+        &self.circuit_breaker
+    }
+
+    /// Отправляет письмо; `idempotency_key` уходит в заголовке `Idempotency-Key`
+    /// каждой попытки, чтобы провайдер мог отбросить дубликаты.
+    pub async fn send(&self, message: &EmailMessage, idempotency_key: &str) -> Result<(), DomainError> {
+        let Some(permit) = self.circuit_breaker.try_acquire() else {
+            return Err(DomainError::ExternalServiceError(
+                "Email provider circuit breaker is open".to_string(),
+            ));
+        };
+
+        let mut attempt = 0;
+
+        loop {
+            match self.send_once(message, idempotency_key).await {
+                Ok(()) => {
+                    permit.success();
+                    return Ok(());
+                }
+                Err(AttemptError::Permanent(reason)) => {
+                    // Провайдер доступен, но отклонил запрос - это не повод размыкать цепь
+                    permit.success();
+                    return Err(DomainError::ExternalServiceError(reason));
+                }
+                Err(AttemptError::Retryable(reason)) => {
+                    if attempt >= self.config.max_retries {
+                        permit.failure();
+                        return Err(DomainError::ExternalServiceError(format!(
+                            "{} (after {} attempts)",
+                            reason,
+                            attempt + 1
+                        )));
+                    }
+
+                    let delay = self.backoff_delay(attempt);
+                    tracing::warn!(
+                        attempt = attempt + 1,
+                        delay = ?delay,
+                        error = %reason,
+                        "Email provider request failed, retrying"
+                    );
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
+    async fn send_once(&self, message: &EmailMessage, idempotency_key: &str) -> Result<(), AttemptError> {
+        let mut request = self.client
+            .post(&self.config.url)
+            .header("Idempotency-Key", idempotency_key)
+            .json(message);
+
+        if let Some(api_key) = &self.config.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response = request.send().await.map_err(|err| {
+            if err.is_timeout() {
+                AttemptError::Retryable("Email provider request timed out".to_string())
+            } else {
+                AttemptError::Retryable(format!("Email provider request failed: {}", err))
+            }
+        })?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else if status.is_server_error() || status == StatusCode::TOO_MANY_REQUESTS {
+            Err(AttemptError::Retryable(format!("Email provider responded with {}", status)))
+        } else {
+            Err(AttemptError::Permanent(format!("Email provider rejected message with {}", status)))
+        }
+    }
+
+    // Экспоненциальная задержка с "равным" джиттером: [delay / 2, delay]
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let exponential = self.config.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.config.max_backoff);
+        let half = exponential / 2;
+        let jitter_ms = rand::rng().random_range(0..=half.as_millis() as u64);
+        half + Duration::from_millis(jitter_ms)
+    }
+}
+
+impl EmailService for HttpEmailService {
+    async fn send_welcome_email(&self, user: &User, idempotency_key: &str) -> Result<(), DomainError> {
+        let message = EmailMessage {
+            to: user.email().to_string(),
+            template: "welcome".to_string(),
+            subject: "Добро пожаловать!".to_string(),
+            text: format!("Добро пожаловать, {}! Ваш email: {}", user.name(), user.email()),
+        };
+        self.send(&message, idempotency_key).await
+    }
+
+    async fn send_password_reset_email(&self, email: &Email, reset_token: String, idempotency_key: &str) -> Result<(), DomainError> {
+        let message = EmailMessage {
+            to: email.to_string(),
+            template: "password_reset".to_string(),
+            subject: "Сброс пароля".to_string(),
+            text: format!("Ваш токен сброса пароля: {}", reset_token),
+        };
+        self.send(&message, idempotency_key).await
+    }
+
+    async fn send_account_locked_email(&self, user: &User, locked_until: DateTime<Utc>, idempotency_key: &str) -> Result<(), DomainError> {
+        let message = EmailMessage {
+            to: user.email().to_string(),
+            template: "account_locked".to_string(),
+            subject: "Аккаунт временно заблокирован".to_string(),
+            text: format!(
+                "{}, из-за серии неудачных попыток входа ваш аккаунт заблокирован до {}. \
+                 Если это были не вы, обратитесь в поддержку.",
+                user.name(),
+                locked_until.to_rfc3339()
+            ),
+        };
+        self.send(&message, idempotency_key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::infrastructure::external_services::CircuitState;
+    use axum::{extract::State, http::HeaderMap, routing::post, Json, Router};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Default)]
+    struct StubProvider {
+        fail_first: AtomicUsize,
+        fail_status: Mutex<Option<StatusCode>>,
+        latency: Mutex<Duration>,
+        hits: AtomicUsize,
+        requests: Mutex<Vec<(HeaderMap, serde_json::Value)>>,
+    }
+
+    async fn stub_handler(
+        State(stub): State<Arc<StubProvider>>,
+        headers: HeaderMap,
+        Json(body): Json<serde_json::Value>,
+    ) -> StatusCode {
+        stub.hits.fetch_add(1, Ordering::SeqCst);
+        stub.requests.lock().unwrap().push((headers, body));
+
+        let latency = *stub.latency.lock().unwrap();
+        if !latency.is_zero() {
+            tokio::time::sleep(latency).await;
+        }
+
+        let failing = stub.fail_first
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |left| left.checked_sub(1))
+            .is_ok();
+        if failing {
+            return stub.fail_status.lock().unwrap().unwrap_or(StatusCode::SERVICE_UNAVAILABLE);
+        }
+
+        StatusCode::ACCEPTED
+    }
+
+    async fn spawn_stub(stub: Arc<StubProvider>) -> String {
+        let app = Router::new()
+            .route("/send", post(stub_handler))
+            .with_state(stub);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        format!("http://{}/send", address)
+    }
+
+    fn fast_config(url: String) -> HttpEmailServiceConfig {
+        let mut config = HttpEmailServiceConfig::new(url);
+        config.api_key = Some("test-key".to_string());
+        config.request_timeout = Duration::from_millis(200);
+        config.initial_backoff = Duration::from_millis(1);
+        config.max_backoff = Duration::from_millis(5);
+        config
+    }
+
+    fn test_user() -> User {
+        let email = Email::new("test@example.com".to_string()).unwrap();
+        User::new(email, "Test User".to_string()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_sends_json_with_auth_header() {
+        let stub = Arc::new(StubProvider::default());
+        let url = spawn_stub(stub.clone()).await;
+        let service = HttpEmailService::new(fast_config(url)).unwrap();
+
+        service.send_welcome_email(&test_user(), "email.welcome:test").await.unwrap();
+
+        let requests = stub.requests.lock().unwrap();
+        assert_eq!(requests.len(), 1);
+        let (headers, body) = &requests[0];
+        assert_eq!(headers["authorization"], "Bearer test-key");
+        assert_eq!(headers["idempotency-key"], "email.welcome:test");
+        assert_eq!(body["to"], "test@example.com");
+        assert_eq!(body["template"], "welcome");
+    }
+
+    #[tokio::test]
+    async fn test_retries_server_errors_with_same_idempotency_key() {
+        let stub = Arc::new(StubProvider::default());
+        stub.fail_first.store(2, Ordering::SeqCst);
+        let url = spawn_stub(stub.clone()).await;
+        let service = HttpEmailService::new(fast_config(url)).unwrap();
+
+        service.send_welcome_email(&test_user(), "email.welcome:test").await.unwrap();
+
+        assert_eq!(stub.hits.load(Ordering::SeqCst), 3);
+        let requests = stub.requests.lock().unwrap();
+        assert!(requests.iter().all(|(headers, _)| headers["idempotency-key"] == "email.welcome:test"));
+    }
+
+    #[tokio::test]
+    async fn test_gives_up_after_max_retries() {
+        let stub = Arc::new(StubProvider::default());
+        stub.fail_first.store(usize::MAX, Ordering::SeqCst);
+        let url = spawn_stub(stub.clone()).await;
+        let mut config = fast_config(url);
+        config.max_retries = 2;
+        let service = HttpEmailService::new(config).unwrap();
+
+        let result = service.send_welcome_email(&test_user(), "email.welcome:test").await;
+
+        assert!(matches!(result, Err(DomainError::ExternalServiceError(_))));
+        assert_eq!(stub.hits.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_client_errors_are_not_retried() {
+        let stub = Arc::new(StubProvider::default());
+        stub.fail_first.store(usize::MAX, Ordering::SeqCst);
+        *stub.fail_status.lock().unwrap() = Some(StatusCode::UNPROCESSABLE_ENTITY);
+        let url = spawn_stub(stub.clone()).await;
+        let service = HttpEmailService::new(fast_config(url)).unwrap();
+
+        let result = service.send_welcome_email(&test_user(), "email.welcome:test").await;
+
+        assert!(matches!(result, Err(DomainError::ExternalServiceError(_))));
+        assert_eq!(stub.hits.load(Ordering::SeqCst), 1);
+        assert_eq!(service.circuit_breaker().state(), CircuitState::Closed);
+    }
+
+    #[tokio::test]
+    async fn test_timeout_is_mapped_to_external_service_error() {
+        let stub = Arc::new(StubProvider::default());
+        *stub.latency.lock().unwrap() = Duration::from_millis(500);
+        let url = spawn_stub(stub.clone()).await;
+        let mut config = fast_config(url);
+        config.request_timeout = Duration::from_millis(50);
+        config.max_retries = 1;
+        let service = HttpEmailService::new(config).unwrap();
+
+        let result = service.send_welcome_email(&test_user(), "email.welcome:test").await;
+
+        match result {
+            Err(DomainError::ExternalServiceError(message)) => assert!(message.contains("timed out")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(stub.hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_open_circuit_short_circuits_requests() {
+        let stub = Arc::new(StubProvider::default());
+        stub.fail_first.store(usize::MAX, Ordering::SeqCst);
+        let url = spawn_stub(stub.clone()).await;
+        let mut config = fast_config(url);
+        config.max_retries = 0;
+        config.failure_threshold = 2;
+        let service = HttpEmailService::new(config).unwrap();
+        let email = Email::new("test@example.com".to_string()).unwrap();
+
+        for _ in 0..2 {
+            let _ = service.send_password_reset_email(&email, "token".to_string(), "reset:test").await;
+        }
+        assert_eq!(service.circuit_breaker().state(), CircuitState::Open);
+
+        let result = service.send_password_reset_email(&email, "token".to_string(), "reset:test").await;
+
+        assert!(matches!(result, Err(DomainError::ExternalServiceError(_))));
+        assert_eq!(stub.hits.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_config_requires_email_service_url() {
+        let mut app_config = AppConfig::default();
+        assert!(HttpEmailServiceConfig::from_app_config(&app_config).is_err());
+
+        app_config.email_service_url = Some("http://localhost:9000/send".to_string());
+        app_config.email_service_api_key = Some("secret".to_string());
+        let config = HttpEmailServiceConfig::from_app_config(&app_config).unwrap();
+        assert_eq!(config.url, "http://localhost:9000/send");
+        assert_eq!(config.api_key.as_deref(), Some("secret"));
+    }
+}
@@ -1,3 +1,9 @@
 pub mod email_service;
+pub mod circuit_breaker;
+pub mod http_email_service;
+pub mod email_service_backend;
 
-pub use email_service::*;
\ No newline at end of file
+pub use email_service::*;
+pub use circuit_breaker::*;
+pub use http_email_service::*;
+pub use email_service_backend::*;
\ No newline at end of file
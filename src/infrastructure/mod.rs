@@ -1,7 +1,19 @@
 pub mod repositories;
 pub mod external_services;
 pub mod config;
+pub mod outbox;
+pub mod auth;
+pub mod export;
+pub mod audit;
+pub mod events;
+pub mod webhooks;
 
 pub use repositories::*;
 pub use external_services::*;
-pub use config::*;
\ No newline at end of file
+pub use config::*;
+pub use outbox::*;
+pub use auth::*;
+pub use export::*;
+pub use audit::*;
+pub use events::*;
+pub use webhooks::*;
\ No newline at end of file
@@ -0,0 +1,5 @@
+pub mod outbox_sink;
+pub mod outbox_dispatcher;
+
+pub use outbox_sink::*;
+pub use outbox_dispatcher::*;
\ No newline at end of file
@@ -0,0 +1,273 @@
+use std::time::Duration;
+use chrono::Utc;
+use crate::domain::{OutboxRepository, DomainError};
+use crate::infrastructure::outbox::OutboxSink;
+
+#[derive(Debug, Clone)]
+pub struct OutboxDispatcherConfig {
+    pub batch_size: usize,
+    pub poll_interval: Duration,
+    pub lease: Duration,
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub delivered_retention: Duration,
+}
+
+impl Default for OutboxDispatcherConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 50,
+            poll_interval: Duration::from_secs(1),
+            lease: Duration::from_secs(60),
+            max_attempts: 8,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(300),
+            delivered_retention: Duration::from_secs(3600),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DispatchReport {
+    pub delivered: usize,
+    pub retried: usize,
+    pub dead_lettered: usize,
+}
+
+impl DispatchReport {
+    pub fn processed(&self) -> usize {
+        self.delivered + self.retried + self.dead_lettered
+    }
+}
+
+pub struct OutboxDispatcher<R: OutboxRepository, S: OutboxSink> {
+    repository: R,
+    sink: S,
+    config: OutboxDispatcherConfig,
+}
+
+impl<R: OutboxRepository, S: OutboxSink> OutboxDispatcher<R, S> {
+    pub fn new(repository: R, sink: S, config: OutboxDispatcherConfig) -> Self {
+        Self { repository, sink, config }
+    }
+
+    pub fn sink(&self) -> &S {
+        &self.sink
+    }
+
+    pub async fn dispatch_once(&self) -> Result<DispatchReport, DomainError> {
+        let lease = chrono::Duration::from_std(self.config.lease)
+            .map_err(|err| DomainError::InvalidOperation(err.to_string()))?;
+        let retention = chrono::Duration::from_std(self.config.delivered_retention)
+            .map_err(|err| DomainError::InvalidOperation(err.to_string()))?;
+        // Доставленные сообщения хранятся только для просмотра в админке
+        self.repository.prune_delivered_outbox_messages(Utc::now() - retention).await?;
+
+        let messages = self.repository
+            .claim_due(Utc::now(), lease, self.config.batch_size)
+            .await?;
+
+        let mut report = DispatchReport::default();
+        for mut message in messages {
+            if !self.sink.handles(message.topic()) {
+                message.dead_letter(format!("No sink registered for topic {}", message.topic()));
+                report.dead_lettered += 1;
+            } else {
+                match self.sink.deliver(&message).await {
+                    Ok(()) => {
+                        message.mark_delivered(Utc::now());
+                        report.delivered += 1;
+                    }
+                    Err(error) if message.attempts() + 1 >= self.config.max_attempts => {
+                        message.dead_letter(error.to_string());
+                        report.dead_lettered += 1;
+                    }
+                    Err(error) => {
+                        let delay = self.backoff_delay(message.attempts());
+                        message.schedule_retry(error.to_string(), Utc::now() + delay);
+                        report.retried += 1;
+                    }
+                }
+            }
+
+            self.repository.update_outbox_message(&message).await?;
+        }
+
+        Ok(report)
+    }
+
+    pub async fn run(self) {
+        loop {
+            match self.dispatch_once().await {
+                // Полная пачка - скорее всего есть еще сообщения, забираем сразу
+                Ok(report) if report.processed() >= self.config.batch_size => continue,
+                Ok(report) => {
+                    if report.dead_lettered > 0 {
+                        tracing::warn!(count = report.dead_lettered, "Outbox messages moved to dead letter");
+                    }
+                }
+                Err(error) => tracing::error!(error = %error, "Outbox dispatch failed"),
+            }
+
+            tokio::time::sleep(self.config.poll_interval).await;
+        }
+    }
+
+    fn backoff_delay(&self, attempts: u32) -> chrono::Duration {
+        let delay = self.config.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempts))
+            .min(self.config.max_backoff);
+        chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Email, User, UserDomainService, OutboxStatus, OutboxMessage, OutboxPayload, UserRepository};
+    use crate::infrastructure::external_services::MockEmailService;
+    use crate::infrastructure::outbox::{EmailOutboxSink, LoggingEventSink};
+    use crate::infrastructure::repositories::InMemoryUserRepository;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct FailingSink {
+        calls: AtomicUsize,
+    }
+
+    impl OutboxSink for FailingSink {
+        fn handles(&self, _topic: &str) -> bool {
+            true
+        }
+
+        async fn deliver(&self, _message: &OutboxMessage) -> Result<(), DomainError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Err(DomainError::ExternalServiceError("provider is down".to_string()))
+        }
+    }
+
+    fn immediate_retries(max_attempts: u32) -> OutboxDispatcherConfig {
+        OutboxDispatcherConfig {
+            max_attempts,
+            initial_backoff: Duration::ZERO,
+            max_backoff: Duration::ZERO,
+            lease: Duration::ZERO,
+            ..OutboxDispatcherConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_created_user_gets_welcome_email_from_outbox() {
+        let repository = InMemoryUserRepository::new();
+        let service = UserDomainService::new(repository.clone());
+        let email = Email::new("test@example.com".to_string()).unwrap();
+        let user = service.create_user(email, "Test User".to_string()).await.unwrap();
+        assert!(repository.find_by_id(user.id()).await.unwrap().is_some());
+
+        let sink = (EmailOutboxSink::new(MockEmailService::new(), repository.clone()), LoggingEventSink);
+        let dispatcher = OutboxDispatcher::new(repository.clone(), sink, OutboxDispatcherConfig::default());
+
+        let report = dispatcher.dispatch_once().await.unwrap();
+
+        assert_eq!(report.delivered, 2);
+        let sent = dispatcher.sink().0.email_service().get_sent_emails();
+        assert_eq!(sent, vec!["WELCOME: Test User - test@example.com".to_string()]);
+        let keys = dispatcher.sink().0.email_service().get_idempotency_keys();
+        assert_eq!(keys, vec![format!("email.welcome:{}", user.id())]);
+        let delivered = repository.list_outbox_messages(Some(OutboxStatus::Delivered), 10).await.unwrap();
+        assert_eq!(delivered.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_failed_delivery_is_retried_then_dead_lettered() {
+        let repository = InMemoryUserRepository::new();
+        let email = Email::new("test@example.com".to_string()).unwrap();
+        let user = User::new(email, "Test User".to_string()).unwrap();
+        let messages = vec![OutboxMessage::new(OutboxPayload::WelcomeEmail { user_id: user.id().clone() })];
+        repository.save_with_outbox(&user, messages).await.unwrap();
+
+        let sink = FailingSink { calls: AtomicUsize::new(0) };
+        let dispatcher = OutboxDispatcher::new(repository.clone(), sink, immediate_retries(3));
+
+        assert_eq!(dispatcher.dispatch_once().await.unwrap().retried, 1);
+        assert_eq!(dispatcher.dispatch_once().await.unwrap().retried, 1);
+        assert_eq!(dispatcher.dispatch_once().await.unwrap().dead_lettered, 1);
+        assert_eq!(dispatcher.dispatch_once().await.unwrap().processed(), 0);
+
+        let dead = repository.list_outbox_messages(Some(OutboxStatus::DeadLettered), 10).await.unwrap();
+        assert_eq!(dead.len(), 1);
+        assert_eq!(dead[0].attempts(), 3);
+        assert_eq!(dead[0].last_error(), Some("External service error: provider is down"));
+        assert_eq!(dispatcher.sink().calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_retry_is_scheduled_with_backoff() {
+        let repository = InMemoryUserRepository::new();
+        let user_id = crate::domain::UserId::new();
+        let messages = vec![OutboxMessage::new(OutboxPayload::UserDeleted { user_id: user_id.clone() })];
+        repository.delete_with_outbox(&user_id, messages).await.unwrap();
+
+        let config = OutboxDispatcherConfig {
+            initial_backoff: Duration::from_secs(60),
+            ..OutboxDispatcherConfig::default()
+        };
+        let dispatcher = OutboxDispatcher::new(repository.clone(), FailingSink { calls: AtomicUsize::new(0) }, config);
+
+        assert_eq!(dispatcher.dispatch_once().await.unwrap().retried, 1);
+        assert_eq!(dispatcher.dispatch_once().await.unwrap().processed(), 0);
+
+        let pending = repository.list_outbox_messages(Some(OutboxStatus::Pending), 10).await.unwrap();
+        assert!(*pending[0].next_attempt_at() > Utc::now() + chrono::Duration::seconds(50));
+    }
+
+    #[tokio::test]
+    async fn test_message_without_sink_is_dead_lettered() {
+        let repository = InMemoryUserRepository::new();
+        let user_id = crate::domain::UserId::new();
+        let messages = vec![OutboxMessage::new(OutboxPayload::UserDeleted { user_id: user_id.clone() })];
+        repository.delete_with_outbox(&user_id, messages).await.unwrap();
+
+        let sink = EmailOutboxSink::new(MockEmailService::new(), repository.clone());
+        let dispatcher = OutboxDispatcher::new(repository.clone(), sink, OutboxDispatcherConfig::default());
+
+        let report = dispatcher.dispatch_once().await.unwrap();
+
+        assert_eq!(report.dead_lettered, 1);
+        assert!(dispatcher.sink().email_service().get_sent_emails().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_welcome_email_for_deleted_user_is_skipped() {
+        let repository = InMemoryUserRepository::new();
+        let user_id = crate::domain::UserId::new();
+        let messages = vec![OutboxMessage::new(OutboxPayload::WelcomeEmail { user_id: user_id.clone() })];
+        repository.delete_with_outbox(&user_id, messages).await.unwrap();
+
+        let sink = EmailOutboxSink::new(MockEmailService::new(), repository.clone());
+        let dispatcher = OutboxDispatcher::new(repository.clone(), sink, OutboxDispatcherConfig::default());
+
+        assert_eq!(dispatcher.dispatch_once().await.unwrap().delivered, 1);
+        assert!(dispatcher.sink().email_service().get_sent_emails().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delivered_messages_are_pruned_after_retention() {
+        let repository = InMemoryUserRepository::new();
+        let user_id = crate::domain::UserId::new();
+        let messages = vec![OutboxMessage::new(OutboxPayload::UserDeleted { user_id: user_id.clone() })];
+        repository.delete_with_outbox(&user_id, messages).await.unwrap();
+
+        let config = OutboxDispatcherConfig {
+            delivered_retention: Duration::ZERO,
+            ..OutboxDispatcherConfig::default()
+        };
+        let dispatcher = OutboxDispatcher::new(repository.clone(), LoggingEventSink, config);
+
+        assert_eq!(dispatcher.dispatch_once().await.unwrap().delivered, 1);
+        assert_eq!(repository.list_outbox_messages(None, 10).await.unwrap().len(), 1);
+
+        dispatcher.dispatch_once().await.unwrap();
+        assert!(repository.list_outbox_messages(None, 10).await.unwrap().is_empty());
+    }
+}
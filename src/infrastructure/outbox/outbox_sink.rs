@@ -0,0 +1,84 @@
+use crate::domain::{OutboxMessage, OutboxPayload, UserRepository, DomainError};
+use crate::infrastructure::external_services::EmailService;
+
+pub trait OutboxSink: Send + Sync {
+    fn handles(&self, topic: &str) -> bool;
+    async fn deliver(&self, message: &OutboxMessage) -> Result<(), DomainError>;
+}
+
+// Получателя письма читает из репозитория при доставке: в сообщении
+// только идентификатор пользователя
+pub struct EmailOutboxSink<E: EmailService, R: UserRepository> {
+    email_service: E,
+    user_repository: R,
+}
+
+impl<E: EmailService, R: UserRepository> EmailOutboxSink<E, R> {
+    pub fn new(email_service: E, user_repository: R) -> Self {
+        Self { email_service, user_repository }
+    }
+
+    pub fn email_service(&self) -> &E {
+        &self.email_service
+    }
+}
+
+impl<E: EmailService, R: UserRepository> OutboxSink for EmailOutboxSink<E, R> {
+    fn handles(&self, topic: &str) -> bool {
+        topic.starts_with("email.")
+    }
+
+    async fn deliver(&self, message: &OutboxMessage) -> Result<(), DomainError> {
+        let user_id = message.payload().user_id();
+        let Some(user) = self.user_repository.find_by_id(user_id).await?.filter(|user| !user.is_erased()) else {
+            // Пользователь удален или стерт до доставки - писать некому
+            tracing::info!(topic = message.topic(), user_id = %user_id, "Email recipient no longer exists, skipping");
+            return Ok(());
+        };
+
+        match message.payload() {
+            OutboxPayload::WelcomeEmail { .. } => {
+                self.email_service.send_welcome_email(&user, message.dedup_key()).await
+            }
+            OutboxPayload::AccountLockedEmail { locked_until, .. } => {
+                self.email_service.send_account_locked_email(&user, *locked_until, message.dedup_key()).await
+            }
+            _ => Err(DomainError::InvalidOperation(format!(
+                "Email sink cannot deliver topic {}",
+                message.topic()
+            ))),
+        }
+    }
+}
+
+pub struct LoggingEventSink;
+
+impl OutboxSink for LoggingEventSink {
+    fn handles(&self, topic: &str) -> bool {
+        topic.starts_with("user.")
+    }
+
+    async fn deliver(&self, message: &OutboxMessage) -> Result<(), DomainError> {
+        tracing::info!(
+            topic = message.topic(),
+            dedup_key = message.dedup_key(),
+            "Domain event published"
+        );
+        Ok(())
+    }
+}
+
+// Пара приемников: сообщение уходит первому, кто обрабатывает его тему
+impl<A: OutboxSink, B: OutboxSink> OutboxSink for (A, B) {
+    fn handles(&self, topic: &str) -> bool {
+        self.0.handles(topic) || self.1.handles(topic)
+    }
+
+    async fn deliver(&self, message: &OutboxMessage) -> Result<(), DomainError> {
+        if self.0.handles(message.topic()) {
+            self.0.deliver(message).await
+        } else {
+            self.1.deliver(message).await
+        }
+    }
+}
\ No newline at end of file
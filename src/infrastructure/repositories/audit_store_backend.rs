@@ -0,0 +1,50 @@
+use crate::domain::{AuditChainStatus, AuditEvent, AuditPage, AuditRecord, AuditStore, UserId, DomainError};
+use crate::infrastructure::auth::EnvelopePiiCipher;
+use crate::infrastructure::config::AppConfig;
+use crate::infrastructure::repositories::{FileAuditStore, InMemoryAuditStore};
+
+// Журнал аудита пишется в файл, если задан `AUDIT_LOG_PATH`, иначе хранится в памяти процесса
+#[derive(Clone)]
+pub enum AuditStoreBackend {
+    InMemory(InMemoryAuditStore),
+    File(FileAuditStore),
+}
+
+impl AuditStoreBackend {
+    pub fn from_app_config(config: &AppConfig, cipher: EnvelopePiiCipher) -> Result<Self, DomainError> {
+        match &config.audit_log_path {
+            Some(path) => FileAuditStore::open(path, cipher).map(AuditStoreBackend::File),
+            None => Ok(AuditStoreBackend::InMemory(InMemoryAuditStore::with_cipher(cipher))),
+        }
+    }
+}
+
+impl AuditStore for AuditStoreBackend {
+    async fn append_audit_record(&self, record: AuditRecord) -> Result<AuditEvent, DomainError> {
+        match self {
+            AuditStoreBackend::InMemory(store) => store.append_audit_record(record).await,
+            AuditStoreBackend::File(store) => store.append_audit_record(record).await,
+        }
+    }
+
+    async fn list_user_audit_events(&self, user_id: &UserId, offset: usize, limit: usize) -> Result<AuditPage, DomainError> {
+        match self {
+            AuditStoreBackend::InMemory(store) => store.list_user_audit_events(user_id, offset, limit).await,
+            AuditStoreBackend::File(store) => store.list_user_audit_events(user_id, offset, limit).await,
+        }
+    }
+
+    async fn verify_audit_chain(&self) -> Result<AuditChainStatus, DomainError> {
+        match self {
+            AuditStoreBackend::InMemory(store) => store.verify_audit_chain().await,
+            AuditStoreBackend::File(store) => store.verify_audit_chain().await,
+        }
+    }
+
+    async fn erase_user_audit_data(&self, user_id: &UserId) -> Result<usize, DomainError> {
+        match self {
+            AuditStoreBackend::InMemory(store) => store.erase_user_audit_data(user_id).await,
+            AuditStoreBackend::File(store) => store.erase_user_audit_data(user_id).await,
+        }
+    }
+}
\ No newline at end of file
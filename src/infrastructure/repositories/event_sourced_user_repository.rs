@@ -0,0 +1,606 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+use crate::domain::{
+    User, UserRepository, UserId, Email, Role, DomainError, OutboxMessage, OutboxRepository, OutboxStatus,
+    EncryptedUser, PiiCipher, PiiKeyRotation, AuditAction, UserHistory, UserEvent, RecordedUserEvent,
+    UserCreated, UserEmailChanged, UserRenamed, UserRolesChanged, UserErased, UserDeleted, ERASED_USER_NAME,
+};
+use crate::infrastructure::audit::AuditTrail;
+use crate::infrastructure::auth::EnvelopePiiCipher;
+use crate::infrastructure::repositories::InMemoryOutbox;
+
+const DEFAULT_SNAPSHOT_INTERVAL: u64 = 50;
+
+// Событие в том виде, в котором оно лежит в потоке. Email и имя хранятся
+// только в зашифрованном виде: события с персональными данными несут
+// зашифрованное состояние пользователя сразу после события
+#[derive(Clone)]
+enum StoredUserEvent {
+    Created { state: EncryptedUser },
+    EmailChanged { state: EncryptedUser },
+    Renamed { state: EncryptedUser },
+    RolesChanged { roles: Vec<Role>, occurred_at: DateTime<Utc> },
+    Erased { occurred_at: DateTime<Utc> },
+    Deleted { occurred_at: DateTime<Utc> },
+}
+
+impl StoredUserEvent {
+    fn state_mut(&mut self) -> Option<&mut EncryptedUser> {
+        match self {
+            StoredUserEvent::Created { state }
+            | StoredUserEvent::EmailChanged { state }
+            | StoredUserEvent::Renamed { state } => Some(state),
+            _ => None,
+        }
+    }
+}
+
+// Снимок состояния после события `version`; `None` - пользователь удален
+struct UserSnapshot {
+    version: u64,
+    state: Option<EncryptedUser>,
+}
+
+#[derive(Default)]
+struct UserStream {
+    events: Vec<StoredUserEvent>,
+    snapshot: Option<UserSnapshot>,
+}
+
+impl UserStream {
+    fn version(&self) -> u64 {
+        self.events.len() as u64
+    }
+
+    fn records_mut(&mut self) -> impl Iterator<Item = &mut EncryptedUser> {
+        self.events
+            .iter_mut()
+            .filter_map(StoredUserEvent::state_mut)
+            .chain(self.snapshot.iter_mut().filter_map(|snapshot| snapshot.state.as_mut()))
+    }
+}
+
+// Проекция для поиска по email: слепой индекс -> пользователь
+#[derive(Default)]
+struct EmailLookupProjection {
+    user_ids: HashMap<String, UserId>,
+    indexes: HashMap<String, String>,
+}
+
+impl EmailLookupProjection {
+    fn project(&mut self, user_id: &UserId, state: Option<&EncryptedUser>) {
+        if let Some(index) = self.indexes.remove(&user_id.to_string()) {
+            self.user_ids.remove(&index);
+        }
+        if let Some(state) = state {
+            self.user_ids.insert(state.email_index.clone(), user_id.clone());
+            self.indexes.insert(user_id.to_string(), state.email_index.clone());
+        }
+    }
+}
+
+// Модель чтения для списка пользователей: последнее состояние и версия потока
+#[derive(Default)]
+struct UserListProjection {
+    users: HashMap<String, (u64, EncryptedUser)>,
+}
+
+impl UserListProjection {
+    fn project(&mut self, user_id: &UserId, version: u64, state: Option<&EncryptedUser>) {
+        match state {
+            Some(state) => self.users.insert(user_id.to_string(), (version, state.clone())),
+            None => self.users.remove(&user_id.to_string()),
+        };
+    }
+}
+
+// Потоки, проекции и outbox лежат под одной блокировкой: проекции всегда
+// согласованы с последним записанным событием
+#[derive(Default)]
+struct EventStoreState {
+    streams: HashMap<String, UserStream>,
+    email_lookup: EmailLookupProjection,
+    user_list: UserListProjection,
+    outbox: InMemoryOutbox,
+}
+
+/// Хранилище пользователей в виде потоков доменных событий. Пользователь
+/// восстанавливается сверткой событий от последнего снимка; снимок
+/// делается каждые `snapshot_interval` событий. Запись проверяет, что
+/// поток не изменился с момента загрузки агрегата (`User::version`).
+///
+/// Репозиторий получает агрегат без накопленных событий, поэтому
+/// записывает разницу с текущим состоянием потока через `User::events_since`.
+#[derive(Clone)]
+pub struct EventSourcedUserRepository {
+    state: Arc<RwLock<EventStoreState>>,
+    cipher: EnvelopePiiCipher,
+    audit_trail: Option<AuditTrail>,
+    snapshot_interval: u64,
+}
+
+impl EventSourcedUserRepository {
+    pub fn new() -> Self {
+        Self::with_cipher(EnvelopePiiCipher::ephemeral())
+    }
+
+    pub fn with_cipher(cipher: EnvelopePiiCipher) -> Self {
+        Self {
+            state: Arc::new(RwLock::new(EventStoreState::default())),
+            cipher,
+            audit_trail: None,
+            snapshot_interval: DEFAULT_SNAPSHOT_INTERVAL,
+        }
+    }
+
+    pub fn with_audit_trail(mut self, audit_trail: AuditTrail) -> Self {
+        self.audit_trail = Some(audit_trail);
+        self
+    }
+
+    pub fn with_snapshot_interval(mut self, snapshot_interval: u64) -> Self {
+        self.snapshot_interval = snapshot_interval.max(1);
+        self
+    }
+
+    // Пересобирает проекции из потоков, например после изменения их формата
+    pub async fn rebuild_projections(&self) -> Result<(), DomainError> {
+        let mut state = self.state.write().await;
+        let mut email_lookup = EmailLookupProjection::default();
+        let mut user_list = UserListProjection::default();
+        for stream in state.streams.values() {
+            if let Some(user) = self.load(stream, stream.version())? {
+                let record = self.cipher.seal(&user)?;
+                email_lookup.project(user.id(), Some(&record));
+                user_list.project(user.id(), stream.version(), Some(&record));
+            }
+        }
+
+        state.email_lookup = email_lookup;
+        state.user_list = user_list;
+        Ok(())
+    }
+
+    fn decode(&self, user_id: &UserId, current: Option<&User>, stored: &StoredUserEvent) -> Result<UserEvent, DomainError> {
+        let current = || current.ok_or_else(|| DomainError::DatabaseError(format!("Event stream of user {} is corrupted", user_id)));
+        Ok(match stored {
+            StoredUserEvent::Created { state } => {
+                let user = self.cipher.open(state)?;
+                UserEvent::Created(UserCreated {
+                    user_id: user_id.clone(),
+                    email: user.email().clone(),
+                    name: user.name().to_string(),
+                    roles: user.roles().to_vec(),
+                    occurred_at: *user.created_at(),
+                })
+            }
+            StoredUserEvent::EmailChanged { state } => {
+                let user = self.cipher.open(state)?;
+                UserEvent::EmailChanged(UserEmailChanged {
+                    user_id: user_id.clone(),
+                    previous_email: current()?.email().clone(),
+                    email: user.email().clone(),
+                    occurred_at: *user.updated_at(),
+                })
+            }
+            StoredUserEvent::Renamed { state } => {
+                let user = self.cipher.open(state)?;
+                UserEvent::Renamed(UserRenamed {
+                    user_id: user_id.clone(),
+                    previous_name: current()?.name().to_string(),
+                    name: user.name().to_string(),
+                    occurred_at: *user.updated_at(),
+                })
+            }
+            StoredUserEvent::RolesChanged { roles, occurred_at } => UserEvent::RolesChanged(UserRolesChanged {
+                user_id: user_id.clone(),
+                previous_roles: current()?.roles().to_vec(),
+                roles: roles.clone(),
+                occurred_at: *occurred_at,
+            }),
+            StoredUserEvent::Erased { occurred_at } => UserEvent::Erased(UserErased { user_id: user_id.clone(), occurred_at: *occurred_at }),
+            StoredUserEvent::Deleted { occurred_at } => UserEvent::Deleted(UserDeleted { user_id: user_id.clone(), occurred_at: *occurred_at }),
+        })
+    }
+
+    fn encode(event: &UserEvent, state: Option<&EncryptedUser>) -> Result<StoredUserEvent, DomainError> {
+        let state = || state.cloned().ok_or_else(|| DomainError::InvalidOperation(format!("Event {} leaves no user state", event.name())));
+        Ok(match event {
+            UserEvent::Created(_) => StoredUserEvent::Created { state: state()? },
+            UserEvent::EmailChanged(_) => StoredUserEvent::EmailChanged { state: state()? },
+            UserEvent::Renamed(_) => StoredUserEvent::Renamed { state: state()? },
+            UserEvent::RolesChanged(event) => StoredUserEvent::RolesChanged { roles: event.roles.clone(), occurred_at: event.occurred_at },
+            UserEvent::Erased(event) => StoredUserEvent::Erased { occurred_at: event.occurred_at },
+            UserEvent::Deleted(event) => StoredUserEvent::Deleted { occurred_at: event.occurred_at },
+        })
+    }
+
+    // Свертка потока до события `version` включительно; снимок используется,
+    // если он сделан не позже этого события
+    fn load(&self, stream: &UserStream, version: u64) -> Result<Option<User>, DomainError> {
+        let (start, mut user) = match &stream.snapshot {
+            Some(snapshot) if snapshot.version <= version => {
+                (snapshot.version, snapshot.state.as_ref().map(|state| self.cipher.open(state)).transpose()?)
+            }
+            _ => (0, None),
+        };
+
+        let end = version.min(stream.version());
+        for stored in &stream.events[start as usize..end as usize] {
+            let user_id = match (&user, stored) {
+                (Some(user), _) => user.id().clone(),
+                (None, StoredUserEvent::Created { state }) => state.id.clone(),
+                (None, _) => return Err(DomainError::DatabaseError("Event stream does not start with user creation".to_string())),
+            };
+            let event = self.decode(&user_id, user.as_ref(), stored)?;
+            user = User::apply_event(user, &event)?;
+        }
+        Ok(user.map(|user| user.with_version(end)))
+    }
+
+    fn history(&self, user_id: &UserId, stream: &UserStream) -> Result<Vec<RecordedUserEvent>, DomainError> {
+        let mut user = None;
+        let mut history = Vec::with_capacity(stream.events.len());
+        for (index, stored) in stream.events.iter().enumerate() {
+            let event = self.decode(user_id, user.as_ref(), stored)?;
+            user = User::apply_event(user, &event)?;
+            history.push(RecordedUserEvent { version: index as u64 + 1, event });
+        }
+        Ok(history)
+    }
+
+    // Стирание убирает email и имя из всей истории: в ранних событиях и
+    // снимке остаются надгробные значения, остальные поля сохраняются
+    fn redact_stream(&self, stream: &mut UserStream, erased_user: &User) -> Result<(), DomainError> {
+        for record in stream.records_mut() {
+            let user = self.cipher.open(record)?;
+            let redacted = User::from_existing(
+                user.id().clone(),
+                erased_user.email().clone(),
+                ERASED_USER_NAME.to_string(),
+                user.roles().to_vec(),
+                *user.created_at(),
+                *user.updated_at(),
+                user.erased_at().copied(),
+            )?;
+            *record = self.cipher.seal(&redacted)?;
+        }
+        Ok(())
+    }
+
+    // Событие аудита пишется до изменения состояния, под той же блокировкой
+    async fn append(
+        &self,
+        state: &mut EventStoreState,
+        id: &UserId,
+        user: Option<&User>,
+        expected_version: Option<u64>,
+    ) -> Result<(), DomainError> {
+        let key = id.to_string();
+        let version = state.streams.get(&key).map(UserStream::version).unwrap_or(0);
+        let current = match state.streams.get(&key) {
+            Some(stream) => self.load(stream, version)?,
+            None => None,
+        };
+
+        let events = match (user, &current) {
+            (Some(user), current) => user.events_since(current.as_ref()),
+            (None, Some(_)) => vec![UserEvent::Deleted(UserDeleted { user_id: id.clone(), occurred_at: Utc::now() })],
+            (None, None) => Vec::new(),
+        };
+        if events.is_empty() {
+            return Ok(());
+        }
+        if let Some(expected_version) = expected_version.filter(|expected_version| *expected_version != version) {
+            return Err(DomainError::ConcurrentModification(expected_version, version));
+        }
+
+        if let Some(audit_trail) = &self.audit_trail {
+            audit_trail.record(id, AuditAction::for_user(current.as_ref(), user), current.as_ref(), user).await?;
+        }
+
+        let mut folded = current;
+        let mut record = None;
+        let mut stored = Vec::with_capacity(events.len());
+        for event in &events {
+            folded = User::apply_event(folded, event)?;
+            record = folded.as_ref().map(|user| self.cipher.seal(user)).transpose()?;
+            stored.push(Self::encode(event, record.as_ref())?);
+        }
+
+        let stream = state.streams.entry(key).or_default();
+        if let Some(erased_user) = folded.as_ref().filter(|_| events.iter().any(|event| matches!(event, UserEvent::Erased(_)))) {
+            self.redact_stream(stream, erased_user)?;
+        }
+        stream.events.extend(stored);
+
+        let version = stream.version();
+        let snapshot_version = stream.snapshot.as_ref().map(|snapshot| snapshot.version).unwrap_or(0);
+        if version - snapshot_version >= self.snapshot_interval {
+            stream.snapshot = Some(UserSnapshot { version, state: record.clone() });
+        }
+
+        state.email_lookup.project(id, record.as_ref());
+        state.user_list.project(id, version, record.as_ref());
+        Ok(())
+    }
+}
+
+impl Default for EventSourcedUserRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UserRepository for EventSourcedUserRepository {
+    async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, DomainError> {
+        let state = self.state.read().await;
+        match state.streams.get(&id.to_string()) {
+            Some(stream) => self.load(stream, stream.version()),
+            None => Ok(None),
+        }
+    }
+
+    async fn find_by_email(&self, email: &Email) -> Result<Option<User>, DomainError> {
+        let user_id = {
+            let state = self.state.read().await;
+            state.email_lookup.user_ids.get(&self.cipher.blind_index(email)).cloned()
+        };
+        match user_id {
+            Some(user_id) => self.find_by_id(&user_id).await,
+            None => Ok(None),
+        }
+    }
+
+    async fn list_users(&self) -> Result<Vec<User>, DomainError> {
+        let state = self.state.read().await;
+        let mut users = state.user_list.users
+            .values()
+            .map(|(version, record)| Ok(self.cipher.open(record)?.with_version(*version)))
+            .collect::<Result<Vec<User>, DomainError>>()?;
+        users.sort_by_key(|user| *user.created_at());
+        Ok(users)
+    }
+
+    async fn save(&self, user: &User) -> Result<(), DomainError> {
+        let mut state = self.state.write().await;
+        self.append(&mut state, user.id(), Some(user), Some(user.version())).await
+    }
+
+    async fn delete(&self, id: &UserId) -> Result<(), DomainError> {
+        let mut state = self.state.write().await;
+        self.append(&mut state, id, None, None).await
+    }
+
+    async fn save_with_outbox(&self, user: &User, messages: Vec<OutboxMessage>) -> Result<(), DomainError> {
+        let mut state = self.state.write().await;
+        self.append(&mut state, user.id(), Some(user), Some(user.version())).await?;
+        state.outbox.enqueue(messages);
+        Ok(())
+    }
+
+    async fn delete_with_outbox(&self, id: &UserId, messages: Vec<OutboxMessage>) -> Result<(), DomainError> {
+        let mut state = self.state.write().await;
+        self.append(&mut state, id, None, None).await?;
+        state.outbox.enqueue(messages);
+        Ok(())
+    }
+}
+
+impl UserHistory for EventSourcedUserRepository {
+    async fn user_history(&self, id: &UserId) -> Result<Vec<RecordedUserEvent>, DomainError> {
+        let state = self.state.read().await;
+        match state.streams.get(&id.to_string()) {
+            Some(stream) => self.history(id, stream),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    async fn user_at_version(&self, id: &UserId, version: u64) -> Result<Option<User>, DomainError> {
+        let state = self.state.read().await;
+        match state.streams.get(&id.to_string()) {
+            Some(stream) => self.load(stream, version),
+            None => Ok(None),
+        }
+    }
+}
+
+impl PiiKeyRotation for EventSourcedUserRepository {
+    async fn rotate_pii_keys(&self, limit: usize) -> Result<usize, DomainError> {
+        let mut state = self.state.write().await;
+        let EventStoreState { streams, user_list, .. } = &mut *state;
+        let records = streams
+            .values_mut()
+            .flat_map(UserStream::records_mut)
+            .chain(user_list.users.values_mut().map(|(_, record)| record));
+
+        let mut rotated = 0;
+        for record in records.filter(|record| self.cipher.needs_rotation(record)).take(limit) {
+            *record = self.cipher.rotate(record)?;
+            rotated += 1;
+        }
+        Ok(rotated)
+    }
+}
+
+impl OutboxRepository for EventSourcedUserRepository {
+    async fn claim_due(&self, now: DateTime<Utc>, lease: Duration, limit: usize) -> Result<Vec<OutboxMessage>, DomainError> {
+        Ok(self.state.write().await.outbox.claim_due(now, lease, limit))
+    }
+
+    async fn find_outbox_message(&self, id: &Uuid) -> Result<Option<OutboxMessage>, DomainError> {
+        Ok(self.state.read().await.outbox.find(id))
+    }
+
+    async fn list_outbox_messages(&self, status: Option<OutboxStatus>, limit: usize) -> Result<Vec<OutboxMessage>, DomainError> {
+        Ok(self.state.read().await.outbox.list(status, limit))
+    }
+
+    async fn update_outbox_message(&self, message: &OutboxMessage) -> Result<(), DomainError> {
+        self.state.write().await.outbox.update(message)
+    }
+
+    async fn prune_delivered_outbox_messages(&self, before: DateTime<Utc>) -> Result<usize, DomainError> {
+        Ok(self.state.write().await.outbox.prune_delivered(before))
+    }
+
+    async fn list_user_outbox_messages(&self, user_id: &UserId) -> Result<Vec<OutboxMessage>, DomainError> {
+        Ok(self.state.read().await.outbox.list_for_user(user_id))
+    }
+
+    async fn cancel_user_emails(&self, user_id: &UserId) -> Result<usize, DomainError> {
+        Ok(self.state.write().await.outbox.cancel_emails_to(user_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{AuditStore, UserDomainService};
+    use crate::infrastructure::repositories::{AuditStoreBackend, InMemoryAuditStore};
+
+    fn user(email: &str) -> User {
+        User::new(Email::new(email.to_string()).unwrap(), "Test User".to_string()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_user_is_rebuilt_from_events() {
+        let repository = EventSourcedUserRepository::new();
+        let user = user("test@example.com");
+        repository.save(&user).await.unwrap();
+
+        let mut loaded = repository.find_by_id(user.id()).await.unwrap().unwrap();
+        assert_eq!(loaded.version(), 1);
+        loaded.update_name("Renamed User".to_string()).unwrap();
+        loaded.update_email(Email::new("renamed@example.com".to_string()).unwrap()).unwrap();
+        loaded.change_roles(vec![Role::Support]).unwrap();
+        repository.save(&loaded).await.unwrap();
+
+        let rebuilt = repository.find_by_id(user.id()).await.unwrap().unwrap();
+        assert_eq!(rebuilt.version(), 4);
+        assert_eq!(rebuilt.name(), "Renamed User");
+        assert_eq!(rebuilt.email().as_str(), "renamed@example.com");
+        assert_eq!(rebuilt.roles(), &[Role::Support]);
+
+        let history = repository.user_history(user.id()).await.unwrap();
+        let names: Vec<&str> = history.iter().map(|recorded| recorded.event.name()).collect();
+        assert_eq!(names, vec!["user.created", "user.email_changed", "user.renamed", "user.roles_changed"]);
+        assert!(matches!(&history[2].event, UserEvent::Renamed(event) if event.previous_name == "Test User"));
+
+        let original = repository.user_at_version(user.id(), 1).await.unwrap().unwrap();
+        assert_eq!(original.email().as_str(), "test@example.com");
+        assert_eq!(original.name(), "Test User");
+    }
+
+    #[tokio::test]
+    async fn test_stale_version_is_rejected() {
+        let repository = EventSourcedUserRepository::new();
+        let user = user("test@example.com");
+        repository.save(&user).await.unwrap();
+
+        let mut first = repository.find_by_id(user.id()).await.unwrap().unwrap();
+        let mut second = first.clone();
+        first.update_name("First Writer".to_string()).unwrap();
+        second.update_name("Second Writer".to_string()).unwrap();
+
+        repository.save(&first).await.unwrap();
+        let result = repository.save(&second).await;
+        assert!(matches!(result, Err(DomainError::ConcurrentModification(1, 2))));
+        assert_eq!(repository.find_by_id(user.id()).await.unwrap().unwrap().name(), "First Writer");
+    }
+
+    #[tokio::test]
+    async fn test_snapshots_do_not_change_loaded_state() {
+        let repository = EventSourcedUserRepository::new().with_snapshot_interval(3);
+        let user = user("test@example.com");
+        repository.save(&user).await.unwrap();
+        for index in 0..7 {
+            let mut loaded = repository.find_by_id(user.id()).await.unwrap().unwrap();
+            loaded.update_name(format!("Name {}", index)).unwrap();
+            repository.save(&loaded).await.unwrap();
+        }
+
+        {
+            let state = repository.state.read().await;
+            assert_eq!(state.streams[&user.id().to_string()].snapshot.as_ref().unwrap().version, 6);
+        }
+        let loaded = repository.find_by_id(user.id()).await.unwrap().unwrap();
+        assert_eq!((loaded.version(), loaded.name()), (8, "Name 6"));
+        let before_snapshot = repository.user_at_version(user.id(), 5).await.unwrap().unwrap();
+        assert_eq!(before_snapshot.name(), "Name 3");
+    }
+
+    #[tokio::test]
+    async fn test_projections_follow_the_streams() {
+        let repository = EventSourcedUserRepository::new();
+        let alice = user("alice@example.com");
+        let bob = user("bob@example.com");
+        repository.save(&alice).await.unwrap();
+        repository.save(&bob).await.unwrap();
+
+        let mut loaded = repository.find_by_id(alice.id()).await.unwrap().unwrap();
+        loaded.update_email(Email::new("alice@example.org".to_string()).unwrap()).unwrap();
+        repository.save(&loaded).await.unwrap();
+        repository.delete(bob.id()).await.unwrap();
+
+        let old_email = Email::new("alice@example.com".to_string()).unwrap();
+        let new_email = Email::new("alice@example.org".to_string()).unwrap();
+        assert!(repository.find_by_email(&old_email).await.unwrap().is_none());
+        assert_eq!(repository.find_by_email(&new_email).await.unwrap().unwrap().id(), alice.id());
+        assert!(repository.find_by_email(bob.email()).await.unwrap().is_none());
+
+        let listed = repository.list_users().await.unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].email(), &new_email);
+        assert_eq!(repository.user_history(bob.id()).await.unwrap().len(), 2);
+
+        repository.rebuild_projections().await.unwrap();
+        assert_eq!(repository.find_by_email(&new_email).await.unwrap().unwrap().id(), alice.id());
+        assert_eq!(repository.list_users().await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_erasure_removes_pii_from_history() {
+        let repository = EventSourcedUserRepository::new().with_snapshot_interval(1);
+        let user = user("alice@example.com");
+        repository.save(&user).await.unwrap();
+
+        let mut loaded = repository.find_by_id(user.id()).await.unwrap().unwrap();
+        loaded.change_roles(vec![Role::Admin]).unwrap();
+        repository.save(&loaded).await.unwrap();
+        let mut loaded = repository.find_by_id(user.id()).await.unwrap().unwrap();
+        loaded.erase(Utc::now()).unwrap();
+        repository.save(&loaded).await.unwrap();
+
+        let original = repository.user_at_version(user.id(), 1).await.unwrap().unwrap();
+        assert_eq!(original.name(), ERASED_USER_NAME);
+        assert_eq!(original.email(), loaded.email());
+        let promoted = repository.user_at_version(user.id(), 2).await.unwrap().unwrap();
+        assert_eq!(promoted.roles(), &[Role::Admin]);
+        assert!(repository.find_by_email(user.email()).await.unwrap().is_none());
+
+        let history = repository.user_history(user.id()).await.unwrap();
+        assert!(history.iter().all(|recorded| !format!("{:?}", recorded.event).contains("alice")));
+        assert!(repository.find_by_id(user.id()).await.unwrap().unwrap().is_erased());
+    }
+
+    #[tokio::test]
+    async fn test_domain_service_and_audit_work_with_event_store() {
+        let audit_store = AuditStoreBackend::InMemory(InMemoryAuditStore::new());
+        let repository = EventSourcedUserRepository::new().with_audit_trail(AuditTrail::new(audit_store.clone()));
+        let service = UserDomainService::new(repository.clone());
+
+        let created = service.create_user(Email::new("test@example.com".to_string()).unwrap(), "Test User".to_string()).await.unwrap();
+        service.update_user(created.id().clone(), None, Some("Renamed User".to_string())).await.unwrap();
+        service.delete_user(created.id().clone()).await.unwrap();
+
+        assert!(repository.find_by_id(created.id()).await.unwrap().is_none());
+        assert_eq!(repository.list_outbox_messages(None, 10).await.unwrap().len(), 4);
+        let page = audit_store.list_user_audit_events(created.id(), 0, 10).await.unwrap();
+        let actions: Vec<AuditAction> = page.events.iter().map(|event| event.record.action).collect();
+        assert_eq!(actions, vec![AuditAction::UserDeleted, AuditAction::UserUpdated, AuditAction::UserCreated]);
+    }
+}
@@ -0,0 +1,296 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::RwLock;
+use crate::domain::{
+    AuditAction, AuditChainStatus, AuditEvent, AuditPage, AuditRecord, AuditStore, FieldChange, PiiCipher, UserId,
+    DomainError, ERASED_VALUE,
+};
+use crate::infrastructure::audit::AuditChain;
+use crate::infrastructure::auth::EnvelopePiiCipher;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct FieldChangeRecord {
+    field: String,
+    before: Option<String>,
+    after: Option<String>,
+    personal: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    before_digest: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    after_digest: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AuditEventRecord {
+    sequence: u64,
+    user_id: String,
+    resource: String,
+    action: String,
+    actor: String,
+    request_id: Option<String>,
+    client_ip: Option<String>,
+    occurred_at: DateTime<Utc>,
+    changes: Vec<FieldChangeRecord>,
+    previous_hash: String,
+    hash: String,
+}
+
+// Персональные значения хранятся зашифрованными; `[ERASED]` остается как есть
+fn seal_personal(cipher: &EnvelopePiiCipher, context: &str, value: &Option<String>) -> Result<Option<String>, DomainError> {
+    match value.as_deref() {
+        Some(ERASED_VALUE) | None => Ok(value.clone()),
+        Some(value) => cipher.seal_value(context, value).map(Some),
+    }
+}
+
+fn open_personal(cipher: &EnvelopePiiCipher, context: &str, value: Option<String>) -> Result<Option<String>, DomainError> {
+    match value.as_deref() {
+        Some(ERASED_VALUE) | None => Ok(value),
+        Some(sealed) => cipher.open_value(context, sealed).map(Some),
+    }
+}
+
+impl AuditEventRecord {
+    fn seal(event: &AuditEvent, cipher: &EnvelopePiiCipher) -> Result<Self, DomainError> {
+        Ok(Self {
+            sequence: event.sequence,
+            user_id: event.record.user_id.to_string(),
+            resource: event.record.resource.clone(),
+            action: event.record.action.as_str().to_string(),
+            actor: event.record.actor.clone(),
+            request_id: event.record.request_id.clone(),
+            client_ip: event.record.client_ip.clone(),
+            occurred_at: event.record.occurred_at,
+            changes: event.record.changes
+                .iter()
+                .map(|change| {
+                    let (before, after) = match change.personal {
+                        true => {
+                            let context = event.record.personal_data_context(&change.field);
+                            (seal_personal(cipher, &context, &change.before)?, seal_personal(cipher, &context, &change.after)?)
+                        }
+                        false => (change.before.clone(), change.after.clone()),
+                    };
+                    Ok(FieldChangeRecord {
+                        field: change.field.clone(),
+                        before,
+                        after,
+                        personal: change.personal,
+                        before_digest: change.before_digest.clone(),
+                        after_digest: change.after_digest.clone(),
+                    })
+                })
+                .collect::<Result<Vec<_>, DomainError>>()?,
+            previous_hash: event.previous_hash.clone(),
+            hash: event.hash.clone(),
+        })
+    }
+
+    fn open(self, cipher: &EnvelopePiiCipher) -> Result<AuditEvent, DomainError> {
+        let mut record = AuditRecord {
+            user_id: UserId::from_string(self.user_id).map_err(DomainError::DatabaseError)?,
+            resource: self.resource,
+            action: AuditAction::parse(&self.action)?,
+            actor: self.actor,
+            request_id: self.request_id,
+            client_ip: self.client_ip,
+            occurred_at: self.occurred_at,
+            changes: Vec::with_capacity(self.changes.len()),
+        };
+        for change in self.changes {
+            let (before, after) = match change.personal {
+                true => {
+                    let context = record.personal_data_context(&change.field);
+                    (open_personal(cipher, &context, change.before)?, open_personal(cipher, &context, change.after)?)
+                }
+                false => (change.before, change.after),
+            };
+            record.changes.push(FieldChange {
+                field: change.field,
+                before,
+                after,
+                personal: change.personal,
+                before_digest: change.before_digest,
+                after_digest: change.after_digest,
+            });
+        }
+
+        Ok(AuditEvent {
+            sequence: self.sequence,
+            record,
+            previous_hash: self.previous_hash,
+            hash: self.hash,
+        })
+    }
+}
+
+/// Журнал в файле JSON Lines: каждое событие дописывается отдельной строкой,
+/// существующие строки не переписываются. Исключение - стирание персональных
+/// данных, после которого файл записывается заново целиком. Email и имена
+/// в файле зашифрованы PII-шифратором, поэтому без `PII_KEYFILE` файл не прочитать.
+#[derive(Clone)]
+pub struct FileAuditStore {
+    path: PathBuf,
+    chain: Arc<RwLock<AuditChain>>,
+    cipher: EnvelopePiiCipher,
+}
+
+impl FileAuditStore {
+    pub fn open(path: impl AsRef<Path>, cipher: EnvelopePiiCipher) -> Result<Self, DomainError> {
+        let path = path.as_ref().to_path_buf();
+        let events = match std::fs::read_to_string(&path) {
+            Ok(contents) => Self::decode(&contents, &cipher)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(DomainError::DatabaseError(err.to_string())),
+        };
+
+        let chain = AuditChain::from_events(events, cipher.clone());
+        if let Some(sequence) = chain.verify().broken_at {
+            tracing::error!(sequence, path = %path.display(), "Audit log hash chain is broken");
+        }
+
+        Ok(Self {
+            path,
+            chain: Arc::new(RwLock::new(chain)),
+            cipher,
+        })
+    }
+
+    fn decode(contents: &str, cipher: &EnvelopePiiCipher) -> Result<Vec<AuditEvent>, DomainError> {
+        contents
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| {
+                serde_json::from_str::<AuditEventRecord>(line)
+                    .map_err(|err| DomainError::DatabaseError(err.to_string()))?
+                    .open(cipher)
+            })
+            .collect()
+    }
+
+    fn encode(&self, event: &AuditEvent) -> Result<String, DomainError> {
+        serde_json::to_string(&AuditEventRecord::seal(event, &self.cipher)?)
+            .map(|line| line + "\n")
+            .map_err(|err| DomainError::DatabaseError(err.to_string()))
+    }
+
+    async fn append_line(&self, line: &str) -> Result<(), DomainError> {
+        if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|err| DomainError::DatabaseError(err.to_string()))?;
+        }
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(|err| DomainError::DatabaseError(err.to_string()))?;
+        file.write_all(line.as_bytes())
+            .await
+            .map_err(|err| DomainError::DatabaseError(err.to_string()))?;
+        file.sync_data().await.map_err(|err| DomainError::DatabaseError(err.to_string()))
+    }
+
+    // Запись во временный файл и переименование: при сбое файл не останется обрезанным
+    async fn rewrite(&self, chain: &AuditChain) -> Result<(), DomainError> {
+        let contents = chain.events()
+            .iter()
+            .map(|event| self.encode(event))
+            .collect::<Result<String, DomainError>>()?;
+
+        let tmp_path = self.path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, contents)
+            .await
+            .map_err(|err| DomainError::DatabaseError(err.to_string()))?;
+        tokio::fs::rename(&tmp_path, &self.path)
+            .await
+            .map_err(|err| DomainError::DatabaseError(err.to_string()))
+    }
+}
+
+impl AuditStore for FileAuditStore {
+    async fn append_audit_record(&self, record: AuditRecord) -> Result<AuditEvent, DomainError> {
+        let mut chain = self.chain.write().await;
+        let mut appended = chain.clone();
+        let event = appended.append(record);
+        // В памяти событие появляется только после записи в файл
+        self.append_line(&self.encode(&event)?).await?;
+        *chain = appended;
+        Ok(event)
+    }
+
+    async fn list_user_audit_events(&self, user_id: &UserId, offset: usize, limit: usize) -> Result<AuditPage, DomainError> {
+        let chain = self.chain.read().await;
+        Ok(chain.user_page(user_id, offset, limit))
+    }
+
+    async fn verify_audit_chain(&self) -> Result<AuditChainStatus, DomainError> {
+        let chain = self.chain.read().await;
+        Ok(chain.verify())
+    }
+
+    async fn erase_user_audit_data(&self, user_id: &UserId) -> Result<usize, DomainError> {
+        let mut chain = self.chain.write().await;
+        let erased = chain.erase_user(user_id);
+        if erased > 0 {
+            self.rewrite(&chain).await?;
+        }
+        Ok(erased)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Email, User, ERASED_VALUE};
+
+    fn record(user: &User) -> AuditRecord {
+        AuditRecord {
+            user_id: user.id().clone(),
+            resource: "user".to_string(),
+            action: AuditAction::UserCreated,
+            actor: "system".to_string(),
+            request_id: Some("req-1".to_string()),
+            client_ip: None,
+            occurred_at: Utc::now(),
+            changes: FieldChange::diff(None, Some(user)),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_survive_reopen() {
+        let path = std::env::temp_dir().join(format!("audit-{}.jsonl", uuid::Uuid::new_v4()));
+        let user = User::new(Email::new("alice@example.com".to_string()).unwrap(), "Alice".to_string()).unwrap();
+
+        let cipher = EnvelopePiiCipher::ephemeral();
+        let store = FileAuditStore::open(&path, cipher.clone()).unwrap();
+        store.append_audit_record(record(&user)).await.unwrap();
+        store.append_audit_record(record(&user)).await.unwrap();
+        // Email и имя в файле только в зашифрованном виде
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert!(!contents.contains("alice@example.com"));
+        assert!(!contents.contains("\"Alice\""));
+        assert!(FileAuditStore::open(&path, EnvelopePiiCipher::ephemeral()).is_err());
+
+        let reopened = FileAuditStore::open(&path, cipher.clone()).unwrap();
+        let page = reopened.list_user_audit_events(user.id(), 0, 10).await.unwrap();
+        assert_eq!(page.total, 2);
+        let email = page.events[0].record.changes.iter().find(|change| change.field == "email").unwrap();
+        assert_eq!(email.after.as_deref(), Some("alice@example.com"));
+        assert!(reopened.verify_audit_chain().await.unwrap().is_valid());
+        let event = reopened.append_audit_record(record(&user)).await.unwrap();
+        assert_eq!(event.sequence, 3);
+
+        // Стирание переписывает файл без персональных значений, цепочка остается целой
+        assert_eq!(reopened.erase_user_audit_data(user.id()).await.unwrap(), 3);
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(!contents.contains("alice@example.com"));
+        assert!(contents.contains(ERASED_VALUE));
+        assert!(FileAuditStore::decode(&contents, &cipher).map(|events| AuditChain::from_events(events, cipher)).unwrap().verify().is_valid());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+use crate::domain::{AttemptSubject, LoginAttemptStore, LoginAttempts, DomainError};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct LoginAttemptsRecord {
+    subject: String,
+    failures: u32,
+    last_failure_at: Option<DateTime<Utc>>,
+    locked_until: Option<DateTime<Utc>>,
+}
+
+impl From<&LoginAttempts> for LoginAttemptsRecord {
+    fn from(attempts: &LoginAttempts) -> Self {
+        Self {
+            subject: attempts.subject().key(),
+            failures: attempts.failures(),
+            last_failure_at: attempts.last_failure_at().copied(),
+            locked_until: attempts.locked_until().copied(),
+        }
+    }
+}
+
+/// Счетчики в памяти с записью в JSON-файл после каждого изменения:
+/// блокировки переживают перезапуск без отдельной базы данных.
+#[derive(Clone)]
+pub struct FileLoginAttemptStore {
+    path: PathBuf,
+    attempts: Arc<RwLock<HashMap<AttemptSubject, LoginAttempts>>>,
+}
+
+impl FileLoginAttemptStore {
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, DomainError> {
+        let path = path.as_ref().to_path_buf();
+        let attempts = match std::fs::read(&path) {
+            Ok(bytes) => Self::decode(&bytes)?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => HashMap::new(),
+            Err(err) => return Err(DomainError::DatabaseError(err.to_string())),
+        };
+
+        Ok(Self {
+            path,
+            attempts: Arc::new(RwLock::new(attempts)),
+        })
+    }
+
+    fn decode(bytes: &[u8]) -> Result<HashMap<AttemptSubject, LoginAttempts>, DomainError> {
+        let records: Vec<LoginAttemptsRecord> = serde_json::from_slice(bytes)
+            .map_err(|err| DomainError::DatabaseError(err.to_string()))?;
+
+        records
+            .into_iter()
+            .map(|record| {
+                let subject = AttemptSubject::parse(&record.subject)?;
+                let attempts = LoginAttempts::from_existing(
+                    subject.clone(),
+                    record.failures,
+                    record.last_failure_at,
+                    record.locked_until,
+                );
+                Ok((subject, attempts))
+            })
+            .collect()
+    }
+
+    // Запись во временный файл и переименование: при сбое файл не останется обрезанным
+    async fn persist(&self, attempts: &HashMap<AttemptSubject, LoginAttempts>) -> Result<(), DomainError> {
+        let records: Vec<LoginAttemptsRecord> = attempts.values().map(LoginAttemptsRecord::from).collect();
+        let bytes = serde_json::to_vec_pretty(&records).map_err(|err| DomainError::DatabaseError(err.to_string()))?;
+
+        if let Some(parent) = self.path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .map_err(|err| DomainError::DatabaseError(err.to_string()))?;
+        }
+        let tmp_path = self.path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, bytes)
+            .await
+            .map_err(|err| DomainError::DatabaseError(err.to_string()))?;
+        tokio::fs::rename(&tmp_path, &self.path)
+            .await
+            .map_err(|err| DomainError::DatabaseError(err.to_string()))
+    }
+}
+
+impl LoginAttemptStore for FileLoginAttemptStore {
+    async fn find_attempts(&self, subject: &AttemptSubject) -> Result<Option<LoginAttempts>, DomainError> {
+        let attempts = self.attempts.read().await;
+        Ok(attempts.get(subject).cloned())
+    }
+
+    async fn save_attempts(&self, attempts: &LoginAttempts) -> Result<(), DomainError> {
+        let mut stored = self.attempts.write().await;
+        stored.insert(attempts.subject().clone(), attempts.clone());
+        self.persist(&stored).await
+    }
+
+    async fn delete_attempts(&self, subject: &AttemptSubject) -> Result<(), DomainError> {
+        let mut attempts = self.attempts.write().await;
+        if attempts.remove(subject).is_some() {
+            self.persist(&attempts).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::domain::{LockoutPolicy, UserId};
+
+    #[tokio::test]
+    async fn test_lockout_survives_reopen() {
+        let path = std::env::temp_dir().join(format!("login-attempts-{}.json", uuid::Uuid::new_v4()));
+        let subject = AttemptSubject::Account(UserId::new());
+        let policy = LockoutPolicy {
+            account_threshold: 1,
+            ..LockoutPolicy::default()
+        };
+
+        let store = FileLoginAttemptStore::open(&path).unwrap();
+        let mut attempts = LoginAttempts::new(subject.clone());
+        let until = attempts.record_failure(Utc::now(), &policy).unwrap();
+        store.save_attempts(&attempts).await.unwrap();
+
+        let reopened = FileLoginAttemptStore::open(&path).unwrap();
+        let restored = reopened.find_attempts(&subject).await.unwrap().unwrap();
+        assert_eq!(restored.locked_until(), Some(&until));
+        assert!(restored.is_locked(until - Duration::seconds(1)));
+
+        reopened.delete_attempts(&subject).await.unwrap();
+        assert!(FileLoginAttemptStore::open(&path).unwrap().find_attempts(&subject).await.unwrap().is_none());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
\ No newline at end of file
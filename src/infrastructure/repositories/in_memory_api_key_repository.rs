@@ -0,0 +1,67 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+use crate::domain::{ApiKey, ApiKeyRepository, AuditAction, UserId, DomainError};
+use crate::infrastructure::audit::AuditTrail;
+
+#[derive(Clone)]
+pub struct InMemoryApiKeyRepository {
+    api_keys: Arc<RwLock<HashMap<Uuid, ApiKey>>>,
+    audit_trail: Option<AuditTrail>,
+}
+
+impl InMemoryApiKeyRepository {
+    pub fn new() -> Self {
+        Self {
+            api_keys: Arc::new(RwLock::new(HashMap::new())),
+            audit_trail: None,
+        }
+    }
+
+    pub fn with_audit_trail(mut self, audit_trail: AuditTrail) -> Self {
+        self.audit_trail = Some(audit_trail);
+        self
+    }
+}
+
+impl Default for InMemoryApiKeyRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ApiKeyRepository for InMemoryApiKeyRepository {
+    async fn find_api_key(&self, id: &Uuid) -> Result<Option<ApiKey>, DomainError> {
+        let api_keys = self.api_keys.read().await;
+        Ok(api_keys.get(id).cloned())
+    }
+
+    async fn find_api_key_by_prefix(&self, prefix: &str) -> Result<Option<ApiKey>, DomainError> {
+        let api_keys = self.api_keys.read().await;
+        Ok(api_keys.values()
+            .find(|api_key| api_key.prefix() == prefix)
+            .cloned())
+    }
+
+    async fn list_api_keys(&self, user_id: &UserId) -> Result<Vec<ApiKey>, DomainError> {
+        let api_keys = self.api_keys.read().await;
+        let mut keys: Vec<ApiKey> = api_keys.values()
+            .filter(|api_key| api_key.user_id() == user_id)
+            .cloned()
+            .collect();
+        keys.sort_by_key(|api_key| *api_key.created_at());
+        Ok(keys)
+    }
+
+    async fn save_api_key(&self, api_key: &ApiKey) -> Result<(), DomainError> {
+        let mut api_keys = self.api_keys.write().await;
+        if let Some(audit_trail) = &self.audit_trail {
+            let before = api_keys.get(api_key.id());
+            let action = AuditAction::for_api_key(before, Some(api_key));
+            audit_trail.record(api_key.user_id(), action, before, Some(api_key)).await?;
+        }
+        api_keys.insert(*api_key.id(), api_key.clone());
+        Ok(())
+    }
+}
\ No newline at end of file
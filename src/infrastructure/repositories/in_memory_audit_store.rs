@@ -0,0 +1,50 @@
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use crate::domain::{AuditChainStatus, AuditEvent, AuditPage, AuditRecord, AuditStore, UserId, DomainError};
+use crate::infrastructure::audit::AuditChain;
+use crate::infrastructure::auth::EnvelopePiiCipher;
+
+#[derive(Clone)]
+pub struct InMemoryAuditStore {
+    chain: Arc<RwLock<AuditChain>>,
+}
+
+impl InMemoryAuditStore {
+    pub fn new() -> Self {
+        Self::with_cipher(EnvelopePiiCipher::ephemeral())
+    }
+
+    pub fn with_cipher(cipher: EnvelopePiiCipher) -> Self {
+        Self {
+            chain: Arc::new(RwLock::new(AuditChain::new(cipher))),
+        }
+    }
+}
+
+impl Default for InMemoryAuditStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AuditStore for InMemoryAuditStore {
+    async fn append_audit_record(&self, record: AuditRecord) -> Result<AuditEvent, DomainError> {
+        let mut chain = self.chain.write().await;
+        Ok(chain.append(record))
+    }
+
+    async fn list_user_audit_events(&self, user_id: &UserId, offset: usize, limit: usize) -> Result<AuditPage, DomainError> {
+        let chain = self.chain.read().await;
+        Ok(chain.user_page(user_id, offset, limit))
+    }
+
+    async fn verify_audit_chain(&self) -> Result<AuditChainStatus, DomainError> {
+        let chain = self.chain.read().await;
+        Ok(chain.verify())
+    }
+
+    async fn erase_user_audit_data(&self, user_id: &UserId) -> Result<usize, DomainError> {
+        let mut chain = self.chain.write().await;
+        Ok(chain.erase_user(user_id))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use crate::domain::{ErasureReceipt, ErasureReceiptRepository, UserId, DomainError};
+
+#[derive(Clone)]
+pub struct InMemoryErasureReceiptRepository {
+    receipts: Arc<RwLock<HashMap<String, ErasureReceipt>>>,
+}
+
+impl InMemoryErasureReceiptRepository {
+    pub fn new() -> Self {
+        Self {
+            receipts: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl Default for InMemoryErasureReceiptRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ErasureReceiptRepository for InMemoryErasureReceiptRepository {
+    async fn save_erasure_receipt(&self, receipt: &ErasureReceipt) -> Result<(), DomainError> {
+        let mut receipts = self.receipts.write().await;
+        receipts.insert(receipt.user_id.to_string(), receipt.clone());
+        Ok(())
+    }
+
+    async fn find_erasure_receipt(&self, user_id: &UserId) -> Result<Option<ErasureReceipt>, DomainError> {
+        let receipts = self.receipts.read().await;
+        Ok(receipts.get(&user_id.to_string()).cloned())
+    }
+}
\ No newline at end of file
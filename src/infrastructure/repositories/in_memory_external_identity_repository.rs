@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::Utc;
+use tokio::sync::RwLock;
+use crate::domain::{
+    ExternalIdentity, ExternalIdentityRepository, OidcLoginRequest, OidcLoginStore, UserId, DomainError,
+};
+
+#[derive(Clone)]
+pub struct InMemoryExternalIdentityRepository {
+    identities: Arc<RwLock<HashMap<(String, String), ExternalIdentity>>>,
+}
+
+impl InMemoryExternalIdentityRepository {
+    pub fn new() -> Self {
+        Self {
+            identities: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl Default for InMemoryExternalIdentityRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ExternalIdentityRepository for InMemoryExternalIdentityRepository {
+    async fn find_identity(&self, provider: &str, subject: &str) -> Result<Option<ExternalIdentity>, DomainError> {
+        let identities = self.identities.read().await;
+        Ok(identities.get(&(provider.to_string(), subject.to_string())).cloned())
+    }
+
+    async fn list_identities(&self, user_id: &UserId) -> Result<Vec<ExternalIdentity>, DomainError> {
+        let identities = self.identities.read().await;
+        let mut user_identities: Vec<ExternalIdentity> = identities.values()
+            .filter(|identity| identity.user_id() == user_id)
+            .cloned()
+            .collect();
+        user_identities.sort_by_key(|identity| *identity.linked_at());
+        Ok(user_identities)
+    }
+
+    async fn save_identity(&self, identity: &ExternalIdentity) -> Result<(), DomainError> {
+        let mut identities = self.identities.write().await;
+        identities.insert(
+            (identity.provider().to_string(), identity.subject().to_string()),
+            identity.clone(),
+        );
+        Ok(())
+    }
+
+    async fn delete_user_identities(&self, user_id: &UserId) -> Result<(), DomainError> {
+        let mut identities = self.identities.write().await;
+        identities.retain(|_, identity| identity.user_id() != user_id);
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct InMemoryOidcLoginStore {
+    requests: Arc<RwLock<HashMap<String, OidcLoginRequest>>>,
+}
+
+impl InMemoryOidcLoginStore {
+    pub fn new() -> Self {
+        Self {
+            requests: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl Default for InMemoryOidcLoginStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl OidcLoginStore for InMemoryOidcLoginStore {
+    async fn save_login_request(&self, request: &OidcLoginRequest) -> Result<(), DomainError> {
+        let mut requests = self.requests.write().await;
+        // Брошенные входы не копятся: просроченные запросы удаляются при записи
+        let now = Utc::now();
+        requests.retain(|_, pending| !pending.is_expired(now));
+        requests.insert(request.state().to_string(), request.clone());
+        Ok(())
+    }
+
+    async fn take_login_request(&self, state: &str) -> Result<Option<OidcLoginRequest>, DomainError> {
+        let mut requests = self.requests.write().await;
+        Ok(requests.remove(state))
+    }
+}
\ No newline at end of file
@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use crate::domain::{AttemptSubject, LoginAttemptStore, LoginAttempts, DomainError};
+
+#[derive(Clone)]
+pub struct InMemoryLoginAttemptStore {
+    attempts: Arc<RwLock<HashMap<AttemptSubject, LoginAttempts>>>,
+}
+
+impl InMemoryLoginAttemptStore {
+    pub fn new() -> Self {
+        Self {
+            attempts: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl Default for InMemoryLoginAttemptStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LoginAttemptStore for InMemoryLoginAttemptStore {
+    async fn find_attempts(&self, subject: &AttemptSubject) -> Result<Option<LoginAttempts>, DomainError> {
+        let attempts = self.attempts.read().await;
+        Ok(attempts.get(subject).cloned())
+    }
+
+    async fn save_attempts(&self, attempts: &LoginAttempts) -> Result<(), DomainError> {
+        let mut stored = self.attempts.write().await;
+        stored.insert(attempts.subject().clone(), attempts.clone());
+        Ok(())
+    }
+
+    async fn delete_attempts(&self, subject: &AttemptSubject) -> Result<(), DomainError> {
+        let mut attempts = self.attempts.write().await;
+        attempts.remove(subject);
+        Ok(())
+    }
+}
\ No newline at end of file
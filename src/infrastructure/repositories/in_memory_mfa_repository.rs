@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use crate::domain::{AuditAction, MfaEnrollment, MfaRepository, UserId, DomainError};
+use crate::infrastructure::audit::AuditTrail;
+
+#[derive(Clone)]
+pub struct InMemoryMfaRepository {
+    enrollments: Arc<RwLock<HashMap<String, MfaEnrollment>>>,
+    audit_trail: Option<AuditTrail>,
+}
+
+impl InMemoryMfaRepository {
+    pub fn new() -> Self {
+        Self {
+            enrollments: Arc::new(RwLock::new(HashMap::new())),
+            audit_trail: None,
+        }
+    }
+
+    pub fn with_audit_trail(mut self, audit_trail: AuditTrail) -> Self {
+        self.audit_trail = Some(audit_trail);
+        self
+    }
+
+    async fn audit(
+        &self,
+        user_id: &UserId,
+        before: Option<&MfaEnrollment>,
+        after: Option<&MfaEnrollment>,
+    ) -> Result<(), DomainError> {
+        match &self.audit_trail {
+            Some(audit_trail) => audit_trail.record(user_id, AuditAction::for_mfa(before, after), before, after).await,
+            None => Ok(()),
+        }
+    }
+}
+
+impl Default for InMemoryMfaRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MfaRepository for InMemoryMfaRepository {
+    async fn find_mfa_enrollment(&self, user_id: &UserId) -> Result<Option<MfaEnrollment>, DomainError> {
+        let enrollments = self.enrollments.read().await;
+        Ok(enrollments.get(&user_id.to_string()).cloned())
+    }
+
+    async fn save_mfa_enrollment(&self, enrollment: &MfaEnrollment) -> Result<(), DomainError> {
+        let mut enrollments = self.enrollments.write().await;
+        let before = enrollments.get(&enrollment.user_id().to_string());
+        self.audit(enrollment.user_id(), before, Some(enrollment)).await?;
+        enrollments.insert(enrollment.user_id().to_string(), enrollment.clone());
+        Ok(())
+    }
+
+    async fn delete_mfa_enrollment(&self, user_id: &UserId) -> Result<(), DomainError> {
+        let mut enrollments = self.enrollments.write().await;
+        self.audit(user_id, enrollments.get(&user_id.to_string()), None).await?;
+        enrollments.remove(&user_id.to_string());
+        Ok(())
+    }
+}
\ No newline at end of file
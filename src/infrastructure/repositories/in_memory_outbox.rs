@@ -0,0 +1,91 @@
+use std::collections::HashSet;
+use chrono::{DateTime, Duration, Utc};
+use uuid::Uuid;
+use crate::domain::{DomainError, OutboxMessage, OutboxStatus, UserId};
+
+// Сообщения outbox пользовательского хранилища в памяти. Хранилище держит
+// их под той же блокировкой, что и пользователей, поэтому запись
+// пользователя и его сообщений атомарна, как в одной транзакции БД
+#[derive(Default)]
+pub struct InMemoryOutbox {
+    messages: Vec<OutboxMessage>,
+    dedup_keys: HashSet<String>,
+}
+
+impl InMemoryOutbox {
+    pub fn enqueue(&mut self, messages: Vec<OutboxMessage>) {
+        for message in messages {
+            if self.dedup_keys.insert(message.dedup_key().to_string()) {
+                self.messages.push(message);
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.messages.clear();
+        self.dedup_keys.clear();
+    }
+
+    pub fn claim_due(&mut self, now: DateTime<Utc>, lease: Duration, limit: usize) -> Vec<OutboxMessage> {
+        let mut claimed = Vec::new();
+        for message in self.messages.iter_mut().filter(|message| message.is_due(now)).take(limit) {
+            message.lease(now + lease);
+            claimed.push(message.clone());
+        }
+        claimed
+    }
+
+    pub fn find(&self, id: &Uuid) -> Option<OutboxMessage> {
+        self.messages.iter().find(|message| message.id() == id).cloned()
+    }
+
+    pub fn list(&self, status: Option<OutboxStatus>, limit: usize) -> Vec<OutboxMessage> {
+        self.messages
+            .iter()
+            .filter(|message| status.is_none_or(|status| message.status() == status))
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+
+    pub fn list_for_user(&self, user_id: &UserId) -> Vec<OutboxMessage> {
+        self.messages
+            .iter()
+            .filter(|message| message.payload().user_id() == user_id)
+            .cloned()
+            .collect()
+    }
+
+    pub fn cancel_emails_to(&mut self, user_id: &UserId) -> usize {
+        self.messages
+            .iter_mut()
+            .map(|message| message.cancel_email_to(user_id))
+            .filter(|cancelled| *cancelled)
+            .count()
+    }
+
+    pub fn update(&mut self, message: &OutboxMessage) -> Result<(), DomainError> {
+        let existing = self.messages
+            .iter_mut()
+            .find(|existing| existing.id() == message.id())
+            .ok_or(DomainError::OutboxMessageNotFound)?;
+        *existing = message.clone();
+        Ok(())
+    }
+
+    // Удаляет доставленные до `before` сообщения вместе с их ключами
+    // дедупликации: повтор того же изменения отсекается только пока
+    // доставленное сообщение хранится
+    pub fn prune_delivered(&mut self, before: DateTime<Utc>) -> usize {
+        let dedup_keys = &mut self.dedup_keys;
+        let initial = self.messages.len();
+        self.messages.retain(|message| {
+            let expired = message.delivered_at().is_some_and(|delivered_at| *delivered_at < before);
+            if expired {
+                dedup_keys.remove(message.dedup_key());
+            }
+            !expired
+        });
+        initial - self.messages.len()
+    }
+}
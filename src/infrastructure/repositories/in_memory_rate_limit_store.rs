@@ -0,0 +1,46 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use tokio::sync::Mutex;
+use crate::domain::{RateLimitDecision, RateLimitQuota, RateLimitStore, DomainError};
+
+// Выше этого числа ключей из таблицы удаляются полностью восстановленные бюджеты
+const PRUNE_THRESHOLD: usize = 10_000;
+
+/// Бюджеты в памяти процесса: каждый экземпляр сервиса считает запросы отдельно.
+#[derive(Clone)]
+pub struct InMemoryRateLimitStore {
+    arrivals: Arc<Mutex<HashMap<String, DateTime<Utc>>>>,
+}
+
+impl InMemoryRateLimitStore {
+    pub fn new() -> Self {
+        Self {
+            arrivals: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl Default for InMemoryRateLimitStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RateLimitStore for InMemoryRateLimitStore {
+    async fn acquire(&self, key: &str, quota: &RateLimitQuota, now: DateTime<Utc>) -> Result<RateLimitDecision, DomainError> {
+        let mut arrivals = self.arrivals.lock().await;
+        let arrival = arrivals.get(key).copied();
+
+        match quota.next_arrival(arrival, now) {
+            Some(next) => {
+                if arrivals.len() >= PRUNE_THRESHOLD {
+                    arrivals.retain(|_, arrival| *arrival > now);
+                }
+                arrivals.insert(key.to_string(), next);
+                Ok(quota.decision(next, true, now))
+            }
+            None => Ok(quota.decision(arrival.unwrap_or(now), false, now)),
+        }
+    }
+}
\ No newline at end of file
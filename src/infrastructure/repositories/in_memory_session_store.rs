@@ -0,0 +1,66 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use uuid::Uuid;
+use crate::domain::{Session, SessionStore, UserId, DomainError};
+
+#[derive(Clone)]
+pub struct InMemorySessionStore {
+    sessions: Arc<RwLock<HashMap<Uuid, Session>>>,
+}
+
+impl InMemorySessionStore {
+    pub fn new() -> Self {
+        Self {
+            sessions: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl Default for InMemorySessionStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionStore for InMemorySessionStore {
+    async fn find_session(&self, id: &Uuid) -> Result<Option<Session>, DomainError> {
+        let sessions = self.sessions.read().await;
+        Ok(sessions.get(id).cloned())
+    }
+
+    async fn find_session_by_token_hash(&self, token_hash: &str) -> Result<Option<Session>, DomainError> {
+        let sessions = self.sessions.read().await;
+        Ok(sessions.values()
+            .find(|session| session.token_hash() == token_hash)
+            .cloned())
+    }
+
+    async fn list_sessions(&self, user_id: &UserId) -> Result<Vec<Session>, DomainError> {
+        let sessions = self.sessions.read().await;
+        let mut user_sessions: Vec<Session> = sessions.values()
+            .filter(|session| session.user_id() == user_id)
+            .cloned()
+            .collect();
+        user_sessions.sort_by_key(|session| *session.created_at());
+        Ok(user_sessions)
+    }
+
+    async fn save_session(&self, session: &Session) -> Result<(), DomainError> {
+        let mut sessions = self.sessions.write().await;
+        sessions.insert(*session.id(), session.clone());
+        Ok(())
+    }
+
+    async fn delete_session(&self, id: &Uuid) -> Result<(), DomainError> {
+        let mut sessions = self.sessions.write().await;
+        sessions.remove(id);
+        Ok(())
+    }
+
+    async fn delete_user_sessions(&self, user_id: &UserId) -> Result<(), DomainError> {
+        let mut sessions = self.sessions.write().await;
+        sessions.retain(|_, session| session.user_id() != user_id);
+        Ok(())
+    }
+}
\ No newline at end of file
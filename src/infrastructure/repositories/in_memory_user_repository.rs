@@ -1,30 +1,84 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use chrono::{DateTime, Duration, Utc};
 use tokio::sync::RwLock;
-use crate::domain::{User, UserRepository, UserId, Email, DomainError};
+use uuid::Uuid;
+use crate::domain::{
+    User, UserRepository, UserId, Email, DomainError, OutboxMessage, OutboxRepository, OutboxStatus,
+    EncryptedUser, PiiCipher, PiiKeyRotation, AuditAction,
+};
+use crate::infrastructure::audit::AuditTrail;
+use crate::infrastructure::auth::EnvelopePiiCipher;
+use crate::infrastructure::repositories::InMemoryOutbox;
 
+// Пользователи и outbox лежат под одной блокировкой.
+// Email и имя хранятся только в зашифрованном виде
+#[derive(Default)]
+struct InMemoryState {
+    users: HashMap<String, EncryptedUser>,
+    outbox: InMemoryOutbox,
+}
+
+#[derive(Clone)]
 pub struct InMemoryUserRepository {
-    users: Arc<RwLock<HashMap<String, User>>>,
+    state: Arc<RwLock<InMemoryState>>,
+    cipher: EnvelopePiiCipher,
+    audit_trail: Option<AuditTrail>,
 }
 
 impl InMemoryUserRepository {
     pub fn new() -> Self {
+        Self::with_cipher(EnvelopePiiCipher::ephemeral())
+    }
+
+    pub fn with_cipher(cipher: EnvelopePiiCipher) -> Self {
         Self {
-            users: Arc::new(RwLock::new(HashMap::new())),
+            state: Arc::new(RwLock::new(InMemoryState::default())),
+            cipher,
+            audit_trail: None,
         }
     }
 
+    pub fn with_audit_trail(mut self, audit_trail: AuditTrail) -> Self {
+        self.audit_trail = Some(audit_trail);
+        self
+    }
+
     pub async fn clear(&self) {
-        let mut users = self.users.write().await;
-        users.clear();
+        let mut state = self.state.write().await;
+        state.users.clear();
+        state.outbox.clear();
     }
 
     pub async fn seed(&self, users: Vec<User>) {
-        let mut users_map = self.users.write().await;
+        let mut state = self.state.write().await;
         for user in users {
-            users_map.insert(user.id().to_string(), user);
+            let record = self.cipher.seal(&user).expect("Failed to encrypt seeded user");
+            state.users.insert(user.id().to_string(), record);
         }
     }
+
+    // Событие аудита пишется до изменения состояния, под той же блокировкой
+    async fn write_user(&self, state: &mut InMemoryState, id: &UserId, user: Option<&User>) -> Result<(), DomainError> {
+        let record = user.map(|user| self.cipher.seal(user)).transpose()?;
+        if let Some(audit_trail) = &self.audit_trail {
+            let before = state.users
+                .get(&id.to_string())
+                .map(|record| self.cipher.open(record))
+                .transpose()?;
+            audit_trail.record(id, AuditAction::for_user(before.as_ref(), user), before.as_ref(), user).await?;
+        }
+
+        match record {
+            Some(record) => state.users.insert(id.to_string(), record),
+            None => state.users.remove(&id.to_string()),
+        };
+        Ok(())
+    }
+
+    fn open_all<'a>(&self, records: impl Iterator<Item = &'a EncryptedUser>) -> Result<Vec<User>, DomainError> {
+        records.map(|record| self.cipher.open(record)).collect()
+    }
 }
 
 impl Default for InMemoryUserRepository {
@@ -35,34 +89,102 @@ impl Default for InMemoryUserRepository {
 
 impl UserRepository for InMemoryUserRepository {
     async fn find_by_id(&self, id: &UserId) -> Result<Option<User>, DomainError> {
-        let users = self.users.read().await;
-        Ok(users.get(&id.to_string()).cloned())
+        let state = self.state.read().await;
+        state.users
+            .get(&id.to_string())
+            .map(|record| self.cipher.open(record))
+            .transpose()
     }
 
+    // Поиск по слепому индексу, без расшифровки остальных записей
     async fn find_by_email(&self, email: &Email) -> Result<Option<User>, DomainError> {
-        let users = self.users.read().await;
-        Ok(users.values()
-            .find(|user| user.email() == email)
-            .cloned())
+        let email_index = self.cipher.blind_index(email);
+        let state = self.state.read().await;
+        state.users.values()
+            .find(|record| record.email_index == email_index)
+            .map(|record| self.cipher.open(record))
+            .transpose()
+    }
+
+    async fn list_users(&self) -> Result<Vec<User>, DomainError> {
+        let state = self.state.read().await;
+        let mut users = self.open_all(state.users.values())?;
+        users.sort_by_key(|user| *user.created_at());
+        Ok(users)
     }
 
     async fn save(&self, user: &User) -> Result<(), DomainError> {
-        let mut users = self.users.write().await;
-        users.insert(user.id().to_string(), user.clone());
-        Ok(())
+        let mut state = self.state.write().await;
+        self.write_user(&mut state, user.id(), Some(user)).await
     }
 
     async fn delete(&self, id: &UserId) -> Result<(), DomainError> {
-        let mut users = self.users.write().await;
-        users.remove(&id.to_string());
+        let mut state = self.state.write().await;
+        self.write_user(&mut state, id, None).await
+    }
+
+    async fn save_with_outbox(&self, user: &User, messages: Vec<OutboxMessage>) -> Result<(), DomainError> {
+        let mut state = self.state.write().await;
+        self.write_user(&mut state, user.id(), Some(user)).await?;
+        state.outbox.enqueue(messages);
+        Ok(())
+    }
+
+    async fn delete_with_outbox(&self, id: &UserId, messages: Vec<OutboxMessage>) -> Result<(), DomainError> {
+        let mut state = self.state.write().await;
+        self.write_user(&mut state, id, None).await?;
+        state.outbox.enqueue(messages);
         Ok(())
     }
 }
 
+impl PiiKeyRotation for InMemoryUserRepository {
+    async fn rotate_pii_keys(&self, limit: usize) -> Result<usize, DomainError> {
+        let mut state = self.state.write().await;
+        let mut rotated = 0;
+        for record in state.users.values_mut().filter(|record| self.cipher.needs_rotation(record)).take(limit) {
+            *record = self.cipher.rotate(record)?;
+            rotated += 1;
+        }
+        Ok(rotated)
+    }
+}
+
+impl OutboxRepository for InMemoryUserRepository {
+    async fn claim_due(&self, now: DateTime<Utc>, lease: Duration, limit: usize) -> Result<Vec<OutboxMessage>, DomainError> {
+        Ok(self.state.write().await.outbox.claim_due(now, lease, limit))
+    }
+
+    async fn find_outbox_message(&self, id: &Uuid) -> Result<Option<OutboxMessage>, DomainError> {
+        Ok(self.state.read().await.outbox.find(id))
+    }
+
+    async fn list_outbox_messages(&self, status: Option<OutboxStatus>, limit: usize) -> Result<Vec<OutboxMessage>, DomainError> {
+        Ok(self.state.read().await.outbox.list(status, limit))
+    }
+
+    async fn update_outbox_message(&self, message: &OutboxMessage) -> Result<(), DomainError> {
+        self.state.write().await.outbox.update(message)
+    }
+
+    async fn prune_delivered_outbox_messages(&self, before: DateTime<Utc>) -> Result<usize, DomainError> {
+        Ok(self.state.write().await.outbox.prune_delivered(before))
+    }
+
+    async fn list_user_outbox_messages(&self, user_id: &UserId) -> Result<Vec<OutboxMessage>, DomainError> {
+        Ok(self.state.read().await.outbox.list_for_user(user_id))
+    }
+
+    async fn cancel_user_emails(&self, user_id: &UserId) -> Result<usize, DomainError> {
+        Ok(self.state.write().await.outbox.cancel_emails_to(user_id))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::domain::{Email, User};
+    use crate::domain::{AuditStore, Email, User, OutboxPayload, Role};
+    use crate::infrastructure::repositories::{AuditStoreBackend, InMemoryAuditStore};
 
     #[tokio::test]
     async fn test_save_and_find_user() {
@@ -105,4 +227,98 @@ mod tests {
         repository.delete(user.id()).await.unwrap();
         assert!(repository.find_by_id(user.id()).await.unwrap().is_none());
     }
+
+    #[tokio::test]
+    async fn test_save_with_outbox_is_deduplicated() {
+        let repository = InMemoryUserRepository::new();
+        
+        let email = Email::new("test@example.com".to_string()).unwrap();
+        let user = User::new(email, "Test User".to_string()).unwrap();
+        let messages = || vec![OutboxMessage::new(OutboxPayload::UserCreated { user_id: user.id().clone() })];
+        
+        repository.save_with_outbox(&user, messages()).await.unwrap();
+        repository.save_with_outbox(&user, messages()).await.unwrap();
+        
+        assert!(repository.find_by_id(user.id()).await.unwrap().is_some());
+        let outbox = repository.list_outbox_messages(None, 10).await.unwrap();
+        assert_eq!(outbox.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_claim_due_leases_messages() {
+        let repository = InMemoryUserRepository::new();
+        
+        let user_id = UserId::new();
+        let messages = vec![OutboxMessage::new(OutboxPayload::UserDeleted { user_id: user_id.clone() })];
+        repository.delete_with_outbox(&user_id, messages).await.unwrap();
+        
+        let now = Utc::now();
+        let claimed = repository.claim_due(now, Duration::seconds(30), 10).await.unwrap();
+        assert_eq!(claimed.len(), 1);
+        
+        let claimed_again = repository.claim_due(now, Duration::seconds(30), 10).await.unwrap();
+        assert!(claimed_again.is_empty());
+        
+        let after_lease = repository.claim_due(now + Duration::seconds(31), Duration::seconds(30), 10).await.unwrap();
+        assert_eq!(after_lease.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_mutations_are_audited() {
+        let audit_store = AuditStoreBackend::InMemory(InMemoryAuditStore::new());
+        let repository = InMemoryUserRepository::new().with_audit_trail(AuditTrail::new(audit_store.clone()));
+        
+        let email = Email::new("test@example.com".to_string()).unwrap();
+        let mut user = User::new(email, "Test User".to_string()).unwrap();
+        repository.save(&user).await.unwrap();
+        repository.save(&user).await.unwrap();
+        user.change_roles(vec![Role::Support]).unwrap();
+        repository.save(&user).await.unwrap();
+        repository.delete(user.id()).await.unwrap();
+        
+        let page = audit_store.list_user_audit_events(user.id(), 0, 10).await.unwrap();
+        let actions: Vec<AuditAction> = page.events.iter().map(|event| event.record.action).collect();
+        assert_eq!(actions, vec![AuditAction::UserDeleted, AuditAction::UserRolesChanged, AuditAction::UserCreated]);
+        assert!(audit_store.verify_audit_chain().await.unwrap().is_valid());
+    }
+
+    #[tokio::test]
+    async fn test_pii_is_encrypted_at_rest() {
+        let repository = InMemoryUserRepository::new();
+        
+        let email = Email::new("alice@example.com".to_string()).unwrap();
+        let user = User::new(email, "Alice Smith".to_string()).unwrap();
+        repository.save(&user).await.unwrap();
+        
+        let state = repository.state.read().await;
+        let record = &state.users[&user.id().to_string()];
+        assert!(!record.email.contains("alice"));
+        assert!(!record.name.contains("Alice"));
+        assert!(!record.email_index.contains("alice"));
+    }
+
+    #[tokio::test]
+    async fn test_rotate_pii_keys_rewraps_old_records() {
+        let keys = [("k1".to_string(), [1u8; 32]), ("k2".to_string(), [2u8; 32])];
+        let repository = InMemoryUserRepository::with_cipher(EnvelopePiiCipher::new("k1", keys.clone(), vec![9u8; 32]).unwrap());
+        for index in 0..3 {
+            let email = Email::new(format!("user{}@example.com", index)).unwrap();
+            repository.save(&User::new(email, "Test User".to_string()).unwrap()).await.unwrap();
+        }
+        
+        // Тот же набор записей после перезапуска с новым активным ключом
+        let rotated = InMemoryUserRepository {
+            state: repository.state.clone(),
+            cipher: EnvelopePiiCipher::new("k2", keys, vec![9u8; 32]).unwrap(),
+            audit_trail: None,
+        };
+        assert_eq!(rotated.rotate_pii_keys(2).await.unwrap(), 2);
+        assert_eq!(rotated.rotate_pii_keys(2).await.unwrap(), 1);
+        assert_eq!(rotated.rotate_pii_keys(2).await.unwrap(), 0);
+        
+        let email = Email::new("user1@example.com".to_string()).unwrap();
+        assert!(rotated.find_by_email(&email).await.unwrap().is_some());
+        let state = rotated.state.read().await;
+        assert!(state.users.values().all(|record| record.data_key.key_id == "k2"));
+    }
 }
\ No newline at end of file
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use tokio::sync::RwLock;
+use uuid::Uuid;
+use crate::domain::{WebhookDelivery, WebhookRepository, WebhookSubscription, DomainError};
+
+#[derive(Default)]
+struct WebhookState {
+    webhooks: HashMap<Uuid, WebhookSubscription>,
+    deliveries: HashMap<Uuid, WebhookDelivery>,
+}
+
+#[derive(Clone, Default)]
+pub struct InMemoryWebhookRepository {
+    state: Arc<RwLock<WebhookState>>,
+}
+
+impl InMemoryWebhookRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl WebhookRepository for InMemoryWebhookRepository {
+    async fn find_webhook(&self, id: &Uuid) -> Result<Option<WebhookSubscription>, DomainError> {
+        Ok(self.state.read().await.webhooks.get(id).cloned())
+    }
+
+    async fn list_webhooks(&self) -> Result<Vec<WebhookSubscription>, DomainError> {
+        let state = self.state.read().await;
+        let mut webhooks: Vec<WebhookSubscription> = state.webhooks.values().cloned().collect();
+        webhooks.sort_by_key(|webhook| *webhook.created_at());
+        Ok(webhooks)
+    }
+
+    async fn save_webhook(&self, webhook: &WebhookSubscription) -> Result<(), DomainError> {
+        self.state.write().await.webhooks.insert(*webhook.id(), webhook.clone());
+        Ok(())
+    }
+
+    async fn delete_webhook(&self, id: &Uuid) -> Result<(), DomainError> {
+        let mut state = self.state.write().await;
+        state.webhooks.remove(id).ok_or(DomainError::WebhookNotFound)?;
+        state.deliveries.retain(|_, delivery| delivery.webhook_id() != id);
+        Ok(())
+    }
+
+    async fn find_delivery(&self, id: &Uuid) -> Result<Option<WebhookDelivery>, DomainError> {
+        Ok(self.state.read().await.deliveries.get(id).cloned())
+    }
+
+    async fn list_deliveries(&self, webhook_id: &Uuid, limit: usize) -> Result<Vec<WebhookDelivery>, DomainError> {
+        let state = self.state.read().await;
+        let mut deliveries: Vec<WebhookDelivery> = state.deliveries
+            .values()
+            .filter(|delivery| delivery.webhook_id() == webhook_id)
+            .cloned()
+            .collect();
+        deliveries.sort_by(|a, b| b.created_at().cmp(a.created_at()));
+        deliveries.truncate(limit);
+        Ok(deliveries)
+    }
+
+    async fn due_deliveries(&self, now: DateTime<Utc>, limit: usize) -> Result<Vec<WebhookDelivery>, DomainError> {
+        let state = self.state.read().await;
+        let mut deliveries: Vec<WebhookDelivery> = state.deliveries
+            .values()
+            .filter(|delivery| delivery.is_due(now))
+            .cloned()
+            .collect();
+        deliveries.sort_by_key(|delivery| *delivery.next_attempt_at());
+        deliveries.truncate(limit);
+        Ok(deliveries)
+    }
+
+    async fn save_delivery(&self, delivery: &WebhookDelivery) -> Result<(), DomainError> {
+        let mut state = self.state.write().await;
+        if !state.webhooks.contains_key(delivery.webhook_id()) {
+            return Err(DomainError::WebhookNotFound);
+        }
+        state.deliveries.insert(*delivery.id(), delivery.clone());
+        Ok(())
+    }
+}
\ No newline at end of file
@@ -0,0 +1,43 @@
+use crate::domain::{AttemptSubject, LoginAttemptStore, LoginAttempts, DomainError};
+use crate::infrastructure::config::AppConfig;
+use crate::infrastructure::repositories::{FileLoginAttemptStore, InMemoryLoginAttemptStore};
+
+// Хранилище счетчиков выбирается конфигурацией: файл, если задан
+// `LOGIN_ATTEMPTS_PATH`, иначе память процесса
+#[derive(Clone)]
+pub enum LoginAttemptStoreBackend {
+    InMemory(InMemoryLoginAttemptStore),
+    File(FileLoginAttemptStore),
+}
+
+impl LoginAttemptStoreBackend {
+    pub fn from_app_config(config: &AppConfig) -> Result<Self, DomainError> {
+        match &config.login_attempts_path {
+            Some(path) => FileLoginAttemptStore::open(path).map(LoginAttemptStoreBackend::File),
+            None => Ok(LoginAttemptStoreBackend::InMemory(InMemoryLoginAttemptStore::new())),
+        }
+    }
+}
+
+impl LoginAttemptStore for LoginAttemptStoreBackend {
+    async fn find_attempts(&self, subject: &AttemptSubject) -> Result<Option<LoginAttempts>, DomainError> {
+        match self {
+            LoginAttemptStoreBackend::InMemory(store) => store.find_attempts(subject).await,
+            LoginAttemptStoreBackend::File(store) => store.find_attempts(subject).await,
+        }
+    }
+
+    async fn save_attempts(&self, attempts: &LoginAttempts) -> Result<(), DomainError> {
+        match self {
+            LoginAttemptStoreBackend::InMemory(store) => store.save_attempts(attempts).await,
+            LoginAttemptStoreBackend::File(store) => store.save_attempts(attempts).await,
+        }
+    }
+
+    async fn delete_attempts(&self, subject: &AttemptSubject) -> Result<(), DomainError> {
+        match self {
+            LoginAttemptStoreBackend::InMemory(store) => store.delete_attempts(subject).await,
+            LoginAttemptStoreBackend::File(store) => store.delete_attempts(subject).await,
+        }
+    }
+}
\ No newline at end of file
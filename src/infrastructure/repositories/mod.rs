@@ -1,3 +1,41 @@
 pub mod in_memory_user_repository;
+pub mod in_memory_outbox;
+pub mod in_memory_api_key_repository;
+pub mod in_memory_mfa_repository;
+pub mod in_memory_login_attempt_store;
+pub mod file_login_attempt_store;
+pub mod login_attempt_store_backend;
+pub mod in_memory_session_store;
+pub mod sql_session_store;
+pub mod session_store_backend;
+pub mod in_memory_external_identity_repository;
+pub mod in_memory_rate_limit_store;
+pub mod sql_rate_limit_store;
+pub mod rate_limit_store_backend;
+pub mod in_memory_erasure_receipt_repository;
+pub mod in_memory_audit_store;
+pub mod file_audit_store;
+pub mod audit_store_backend;
+pub mod event_sourced_user_repository;
+pub mod in_memory_webhook_repository;
 
-pub use in_memory_user_repository::*;
\ No newline at end of file
+pub use in_memory_user_repository::*;
+pub use in_memory_outbox::*;
+pub use in_memory_api_key_repository::*;
+pub use in_memory_mfa_repository::*;
+pub use in_memory_login_attempt_store::*;
+pub use file_login_attempt_store::*;
+pub use login_attempt_store_backend::*;
+pub use in_memory_session_store::*;
+pub use sql_session_store::*;
+pub use session_store_backend::*;
+pub use in_memory_external_identity_repository::*;
+pub use in_memory_rate_limit_store::*;
+pub use sql_rate_limit_store::*;
+pub use rate_limit_store_backend::*;
+pub use in_memory_erasure_receipt_repository::*;
+pub use in_memory_audit_store::*;
+pub use file_audit_store::*;
+pub use audit_store_backend::*;
+pub use event_sourced_user_repository::*;
+pub use in_memory_webhook_repository::*;
\ No newline at end of file
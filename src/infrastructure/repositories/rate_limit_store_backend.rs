@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use crate::domain::{RateLimitDecision, RateLimitQuota, RateLimitStore, DomainError};
+use crate::infrastructure::config::AppConfig;
+use crate::infrastructure::repositories::{InMemoryRateLimitStore, SqlRateLimitStore};
+
+// Без `RATE_LIMIT_STORE_URL` бюджеты живут в памяти процесса,
+// `sqlite:...` - общая база для нескольких экземпляров
+#[derive(Clone)]
+pub enum RateLimitStoreBackend {
+    InMemory(InMemoryRateLimitStore),
+    Sql(SqlRateLimitStore),
+}
+
+impl RateLimitStoreBackend {
+    pub fn from_app_config(config: &AppConfig) -> Result<Self, DomainError> {
+        match config.rate_limits.store_url.as_deref() {
+            None => Ok(RateLimitStoreBackend::InMemory(InMemoryRateLimitStore::new())),
+            Some(url) if url.starts_with("sqlite:") => SqlRateLimitStore::connect_lazy(url).map(RateLimitStoreBackend::Sql),
+            Some(url) => Err(DomainError::DatabaseError(format!("Unsupported rate limit store URL: {}", url))),
+        }
+    }
+}
+
+impl RateLimitStore for RateLimitStoreBackend {
+    async fn acquire(&self, key: &str, quota: &RateLimitQuota, now: DateTime<Utc>) -> Result<RateLimitDecision, DomainError> {
+        match self {
+            RateLimitStoreBackend::InMemory(store) => store.acquire(key, quota, now).await,
+            RateLimitStoreBackend::Sql(store) => store.acquire(key, quota, now).await,
+        }
+    }
+}
\ No newline at end of file
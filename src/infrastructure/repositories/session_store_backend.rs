@@ -0,0 +1,66 @@
+use uuid::Uuid;
+use crate::domain::{Session, SessionStore, UserId, DomainError};
+use crate::infrastructure::config::AppConfig;
+use crate::infrastructure::repositories::{InMemorySessionStore, SqlSessionStore};
+
+// Хранилище сессий выбирается по `DATABASE_URL`: `sqlite:...` - SQL-база,
+// `in-memory` - память процесса
+#[derive(Clone)]
+pub enum SessionStoreBackend {
+    InMemory(InMemorySessionStore),
+    Sql(SqlSessionStore),
+}
+
+impl SessionStoreBackend {
+    pub fn from_app_config(config: &AppConfig) -> Result<Self, DomainError> {
+        match config.database_url.as_str() {
+            "in-memory" => Ok(SessionStoreBackend::InMemory(InMemorySessionStore::new())),
+            url if url.starts_with("sqlite:") => SqlSessionStore::connect_lazy(url).map(SessionStoreBackend::Sql),
+            url => Err(DomainError::DatabaseError(format!("Unsupported database URL for sessions: {}", url))),
+        }
+    }
+}
+
+impl SessionStore for SessionStoreBackend {
+    async fn find_session(&self, id: &Uuid) -> Result<Option<Session>, DomainError> {
+        match self {
+            SessionStoreBackend::InMemory(store) => store.find_session(id).await,
+            SessionStoreBackend::Sql(store) => store.find_session(id).await,
+        }
+    }
+
+    async fn find_session_by_token_hash(&self, token_hash: &str) -> Result<Option<Session>, DomainError> {
+        match self {
+            SessionStoreBackend::InMemory(store) => store.find_session_by_token_hash(token_hash).await,
+            SessionStoreBackend::Sql(store) => store.find_session_by_token_hash(token_hash).await,
+        }
+    }
+
+    async fn list_sessions(&self, user_id: &UserId) -> Result<Vec<Session>, DomainError> {
+        match self {
+            SessionStoreBackend::InMemory(store) => store.list_sessions(user_id).await,
+            SessionStoreBackend::Sql(store) => store.list_sessions(user_id).await,
+        }
+    }
+
+    async fn save_session(&self, session: &Session) -> Result<(), DomainError> {
+        match self {
+            SessionStoreBackend::InMemory(store) => store.save_session(session).await,
+            SessionStoreBackend::Sql(store) => store.save_session(session).await,
+        }
+    }
+
+    async fn delete_session(&self, id: &Uuid) -> Result<(), DomainError> {
+        match self {
+            SessionStoreBackend::InMemory(store) => store.delete_session(id).await,
+            SessionStoreBackend::Sql(store) => store.delete_session(id).await,
+        }
+    }
+
+    async fn delete_user_sessions(&self, user_id: &UserId) -> Result<(), DomainError> {
+        match self {
+            SessionStoreBackend::InMemory(store) => store.delete_user_sessions(user_id).await,
+            SessionStoreBackend::Sql(store) => store.delete_user_sessions(user_id).await,
+        }
+    }
+}
\ No newline at end of file
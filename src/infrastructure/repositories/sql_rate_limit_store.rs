@@ -0,0 +1,115 @@
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions};
+use tokio::sync::OnceCell;
+use crate::domain::{RateLimitDecision, RateLimitQuota, RateLimitStore, DomainError};
+
+const CREATE_RATE_LIMITS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS rate_limits (
+        key TEXT PRIMARY KEY,
+        arrival_ms INTEGER NOT NULL
+    )";
+
+// Проверка и расход бюджета одним запросом: при превышении квоты строка
+// не обновляется и RETURNING ничего не возвращает
+const ACQUIRE: &str = "
+    INSERT INTO rate_limits (key, arrival_ms) VALUES (?1, ?2 + ?3)
+    ON CONFLICT (key) DO UPDATE SET arrival_ms = MAX(rate_limits.arrival_ms, ?2) + ?3
+    WHERE MAX(rate_limits.arrival_ms, ?2) + ?3 - ?2 <= ?4
+    RETURNING arrival_ms";
+
+fn database_error(err: sqlx::Error) -> DomainError {
+    DomainError::DatabaseError(err.to_string())
+}
+
+fn from_millis(millis: i64) -> Result<DateTime<Utc>, DomainError> {
+    DateTime::from_timestamp_millis(millis)
+        .ok_or_else(|| DomainError::DatabaseError("Invalid rate limit arrival time in database".to_string()))
+}
+
+/// Общие бюджеты в SQL-базе: экземпляры сервиса, подключенные к одной базе,
+/// расходуют одну квоту на ключ.
+#[derive(Clone)]
+pub struct SqlRateLimitStore {
+    pool: SqlitePool,
+    schema: Arc<OnceCell<()>>,
+}
+
+impl SqlRateLimitStore {
+    pub fn connect_lazy(database_url: &str) -> Result<Self, DomainError> {
+        let pool = SqlitePoolOptions::new()
+            .connect_lazy(database_url)
+            .map_err(database_error)?;
+
+        Ok(Self {
+            pool,
+            schema: Arc::new(OnceCell::new()),
+        })
+    }
+
+    async fn pool(&self) -> Result<&SqlitePool, DomainError> {
+        self.schema
+            .get_or_try_init(|| async {
+                sqlx::query(CREATE_RATE_LIMITS_TABLE).execute(&self.pool).await?;
+                Ok::<(), sqlx::Error>(())
+            })
+            .await
+            .map_err(database_error)?;
+        Ok(&self.pool)
+    }
+}
+
+impl RateLimitStore for SqlRateLimitStore {
+    async fn acquire(&self, key: &str, quota: &RateLimitQuota, now: DateTime<Utc>) -> Result<RateLimitDecision, DomainError> {
+        let pool = self.pool().await?;
+        let acquired: Option<i64> = sqlx::query_scalar(ACQUIRE)
+            .bind(key)
+            .bind(now.timestamp_millis())
+            .bind(quota.emission_interval().num_milliseconds())
+            .bind(quota.period().num_milliseconds())
+            .fetch_optional(pool)
+            .await
+            .map_err(database_error)?;
+
+        if let Some(arrival) = acquired {
+            return Ok(quota.decision(from_millis(arrival)?, true, now));
+        }
+
+        // Текущее состояние нужно только для заголовков ответа
+        let arrival: Option<i64> = sqlx::query_scalar("SELECT arrival_ms FROM rate_limits WHERE key = ?")
+            .bind(key)
+            .fetch_optional(pool)
+            .await
+            .map_err(database_error)?;
+        let arrival = arrival.map(from_millis).transpose()?.unwrap_or(now);
+        Ok(quota.decision(arrival, false, now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use uuid::Uuid;
+
+    #[tokio::test]
+    async fn test_instances_share_budget() {
+        let path = std::env::temp_dir().join(format!("rate-limits-{}.db", Uuid::new_v4()));
+        let url = format!("sqlite://{}?mode=rwc", path.display());
+        let first = SqlRateLimitStore::connect_lazy(&url).unwrap();
+        let second = SqlRateLimitStore::connect_lazy(&url).unwrap();
+        let quota = RateLimitQuota::new(2, Duration::seconds(60)).unwrap();
+        // База хранит время с точностью до миллисекунд
+        let now = from_millis(Utc::now().timestamp_millis()).unwrap();
+
+        assert_eq!(first.acquire("ip:203.0.113.7", &quota, now).await.unwrap().remaining, 1);
+        assert!(second.acquire("ip:203.0.113.7", &quota, now).await.unwrap().allowed);
+
+        let denied = first.acquire("ip:203.0.113.7", &quota, now).await.unwrap();
+        assert!(!denied.allowed);
+        assert_eq!(denied.retry_after, Some(Duration::seconds(30)));
+        assert!(second.acquire("ip:198.51.100.1", &quota, now).await.unwrap().allowed);
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
\ No newline at end of file
@@ -0,0 +1,206 @@
+use std::sync::Arc;
+use chrono::{DateTime, Utc};
+use sqlx::sqlite::{SqlitePool, SqlitePoolOptions, SqliteRow};
+use sqlx::Row;
+use tokio::sync::OnceCell;
+use uuid::Uuid;
+use crate::domain::{AuthLevel, Session, SessionMetadata, SessionStore, UserId, DomainError};
+
+const CREATE_SESSIONS_TABLE: &str = "
+    CREATE TABLE IF NOT EXISTS sessions (
+        id TEXT PRIMARY KEY,
+        user_id TEXT NOT NULL,
+        token_hash TEXT NOT NULL UNIQUE,
+        csrf_token_hash TEXT NOT NULL,
+        auth_level TEXT NOT NULL,
+        user_agent TEXT,
+        ip_address TEXT,
+        created_at TEXT NOT NULL,
+        last_seen_at TEXT NOT NULL,
+        expires_at TEXT NOT NULL
+    )";
+
+const CREATE_USER_ID_INDEX: &str = "CREATE INDEX IF NOT EXISTS sessions_user_id ON sessions (user_id)";
+
+const SELECT_SESSION: &str = "
+    SELECT id, user_id, token_hash, csrf_token_hash, auth_level, user_agent, ip_address,
+           created_at, last_seen_at, expires_at
+    FROM sessions";
+
+fn database_error(err: sqlx::Error) -> DomainError {
+    DomainError::DatabaseError(err.to_string())
+}
+
+/// Хранилище сессий в SQL-базе (SQLite через sqlx). Таблица создается
+/// при первом обращении, поэтому подключение можно открыть лениво.
+#[derive(Clone)]
+pub struct SqlSessionStore {
+    pool: SqlitePool,
+    schema: Arc<OnceCell<()>>,
+}
+
+impl SqlSessionStore {
+    pub fn connect_lazy(database_url: &str) -> Result<Self, DomainError> {
+        let pool = SqlitePoolOptions::new()
+            .connect_lazy(database_url)
+            .map_err(database_error)?;
+
+        Ok(Self {
+            pool,
+            schema: Arc::new(OnceCell::new()),
+        })
+    }
+
+    async fn pool(&self) -> Result<&SqlitePool, DomainError> {
+        self.schema
+            .get_or_try_init(|| async {
+                sqlx::query(CREATE_SESSIONS_TABLE).execute(&self.pool).await?;
+                sqlx::query(CREATE_USER_ID_INDEX).execute(&self.pool).await?;
+                Ok::<(), sqlx::Error>(())
+            })
+            .await
+            .map_err(database_error)?;
+        Ok(&self.pool)
+    }
+
+    fn decode(row: &SqliteRow) -> Result<Session, DomainError> {
+        let invalid = |field: &str| DomainError::DatabaseError(format!("Invalid session {} in database", field));
+
+        let id: String = row.try_get("id").map_err(database_error)?;
+        let user_id: String = row.try_get("user_id").map_err(database_error)?;
+        let auth_level: String = row.try_get("auth_level").map_err(database_error)?;
+        let ip_address: Option<String> = row.try_get("ip_address").map_err(database_error)?;
+
+        Ok(Session::from_existing(
+            Uuid::parse_str(&id).map_err(|_| invalid("id"))?,
+            UserId::from_string(user_id).map_err(|_| invalid("user_id"))?,
+            row.try_get("token_hash").map_err(database_error)?,
+            row.try_get("csrf_token_hash").map_err(database_error)?,
+            AuthLevel::parse(&auth_level)?,
+            SessionMetadata {
+                user_agent: row.try_get("user_agent").map_err(database_error)?,
+                ip_address: ip_address
+                    .map(|ip| ip.parse().map_err(|_| invalid("ip_address")))
+                    .transpose()?,
+            },
+            row.try_get::<DateTime<Utc>, _>("created_at").map_err(database_error)?,
+            row.try_get::<DateTime<Utc>, _>("last_seen_at").map_err(database_error)?,
+            row.try_get::<DateTime<Utc>, _>("expires_at").map_err(database_error)?,
+        ))
+    }
+}
+
+impl SessionStore for SqlSessionStore {
+    async fn find_session(&self, id: &Uuid) -> Result<Option<Session>, DomainError> {
+        let row = sqlx::query(&format!("{} WHERE id = ?", SELECT_SESSION))
+            .bind(id.to_string())
+            .fetch_optional(self.pool().await?)
+            .await
+            .map_err(database_error)?;
+        row.as_ref().map(Self::decode).transpose()
+    }
+
+    async fn find_session_by_token_hash(&self, token_hash: &str) -> Result<Option<Session>, DomainError> {
+        let row = sqlx::query(&format!("{} WHERE token_hash = ?", SELECT_SESSION))
+            .bind(token_hash)
+            .fetch_optional(self.pool().await?)
+            .await
+            .map_err(database_error)?;
+        row.as_ref().map(Self::decode).transpose()
+    }
+
+    async fn list_sessions(&self, user_id: &UserId) -> Result<Vec<Session>, DomainError> {
+        let rows = sqlx::query(&format!("{} WHERE user_id = ? ORDER BY created_at", SELECT_SESSION))
+            .bind(user_id.to_string())
+            .fetch_all(self.pool().await?)
+            .await
+            .map_err(database_error)?;
+        rows.iter().map(Self::decode).collect()
+    }
+
+    async fn save_session(&self, session: &Session) -> Result<(), DomainError> {
+        // Из изменяемых полей у сессии только время последнего запроса
+        sqlx::query(
+            "INSERT INTO sessions (
+                id, user_id, token_hash, csrf_token_hash, auth_level, user_agent, ip_address,
+                created_at, last_seen_at, expires_at
+            ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            ON CONFLICT (id) DO UPDATE SET last_seen_at = excluded.last_seen_at",
+        )
+        .bind(session.id().to_string())
+        .bind(session.user_id().to_string())
+        .bind(session.token_hash())
+        .bind(session.csrf_token_hash())
+        .bind(session.auth_level().as_str())
+        .bind(session.metadata().user_agent.as_deref())
+        .bind(session.metadata().ip_address.map(|ip| ip.to_string()))
+        .bind(session.created_at())
+        .bind(session.last_seen_at())
+        .bind(session.expires_at())
+        .execute(self.pool().await?)
+        .await
+        .map_err(database_error)?;
+        Ok(())
+    }
+
+    async fn delete_session(&self, id: &Uuid) -> Result<(), DomainError> {
+        sqlx::query("DELETE FROM sessions WHERE id = ?")
+            .bind(id.to_string())
+            .execute(self.pool().await?)
+            .await
+            .map_err(database_error)?;
+        Ok(())
+    }
+
+    async fn delete_user_sessions(&self, user_id: &UserId) -> Result<(), DomainError> {
+        sqlx::query("DELETE FROM sessions WHERE user_id = ?")
+            .bind(user_id.to_string())
+            .execute(self.pool().await?)
+            .await
+            .map_err(database_error)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::SessionPolicy;
+
+    #[tokio::test]
+    async fn test_sessions_round_trip_through_database() {
+        let path = std::env::temp_dir().join(format!("sessions-{}.db", Uuid::new_v4()));
+        let store = SqlSessionStore::connect_lazy(&format!("sqlite://{}?mode=rwc", path.display())).unwrap();
+        let user_id = UserId::new();
+        let now = Utc::now();
+
+        let mut session = Session::new(
+            user_id.clone(),
+            "token-hash".to_string(),
+            "csrf-hash".to_string(),
+            AuthLevel::MultiFactor,
+            SessionMetadata {
+                user_agent: Some("curl/8.0".to_string()),
+                ip_address: Some("203.0.113.7".parse().unwrap()),
+            },
+            now,
+            &SessionPolicy::default(),
+        );
+        store.save_session(&session).await.unwrap();
+        session.touch(now + chrono::Duration::minutes(5));
+        store.save_session(&session).await.unwrap();
+
+        let found = store.find_session_by_token_hash("token-hash").await.unwrap().unwrap();
+        assert_eq!(found.id(), session.id());
+        assert_eq!(found.auth_level(), AuthLevel::MultiFactor);
+        assert_eq!(found.last_seen_at(), session.last_seen_at());
+        assert_eq!(found.metadata().ip_address, session.metadata().ip_address);
+        assert_eq!(store.list_sessions(&user_id).await.unwrap().len(), 1);
+
+        store.delete_user_sessions(&user_id).await.unwrap();
+        assert!(store.find_session(session.id()).await.unwrap().is_none());
+
+        store.pool.close().await;
+        std::fs::remove_file(&path).unwrap();
+    }
+}
\ No newline at end of file
@@ -0,0 +1,7 @@
+pub mod webhook_signature;
+pub mod webhook_dispatcher;
+pub mod webhook_event_subscriber;
+
+pub use webhook_signature::*;
+pub use webhook_dispatcher::*;
+pub use webhook_event_subscriber::*;
\ No newline at end of file
@@ -0,0 +1,312 @@
+use std::time::Duration;
+use chrono::Utc;
+use crate::domain::{
+    SecretCipher, WebhookAttempt, WebhookDelivery, WebhookFailurePolicy, WebhookRepository, DomainError,
+};
+use crate::infrastructure::webhooks::{
+    sign_webhook_payload, WEBHOOK_EVENT_HEADER, WEBHOOK_ID_HEADER, WEBHOOK_SIGNATURE_HEADER, WEBHOOK_TIMESTAMP_HEADER,
+};
+
+#[derive(Debug, Clone)]
+pub struct WebhookDispatcherConfig {
+    pub batch_size: usize,
+    pub poll_interval: Duration,
+    pub request_timeout: Duration,
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub failure_policy: WebhookFailurePolicy,
+}
+
+impl Default for WebhookDispatcherConfig {
+    fn default() -> Self {
+        Self {
+            batch_size: 50,
+            poll_interval: Duration::from_secs(1),
+            request_timeout: Duration::from_secs(10),
+            max_attempts: 10,
+            initial_backoff: Duration::from_secs(5),
+            max_backoff: Duration::from_secs(3600),
+            failure_policy: WebhookFailurePolicy {
+                failures: 20,
+                window: chrono::Duration::hours(24),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WebhookDispatchReport {
+    pub delivered: usize,
+    pub retried: usize,
+    pub failed: usize,
+    pub disabled: usize,
+}
+
+impl WebhookDispatchReport {
+    pub fn processed(&self) -> usize {
+        self.delivered + self.retried + self.failed
+    }
+}
+
+/// Фоновая отправка webhook-доставок. Каждый запрос подписывается
+/// секретом подписки; при ошибке доставка повторяется с экспоненциальной
+/// паузой, а подписка, которая долго не отвечает, отключается.
+pub struct WebhookDispatcher<R: WebhookRepository, C: SecretCipher> {
+    repository: R,
+    secret_cipher: C,
+    http_client: reqwest::Client,
+    config: WebhookDispatcherConfig,
+}
+
+impl<R: WebhookRepository, C: SecretCipher> WebhookDispatcher<R, C> {
+    pub fn new(repository: R, secret_cipher: C, config: WebhookDispatcherConfig) -> Self {
+        // Перенаправления не выполняются: доставка уходит только на адрес подписки
+        let http_client = reqwest::Client::builder()
+            .timeout(config.request_timeout)
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("Failed to build webhook HTTP client");
+
+        Self { repository, secret_cipher, http_client, config }
+    }
+
+    pub async fn dispatch_once(&self) -> Result<WebhookDispatchReport, DomainError> {
+        let deliveries = self.repository
+            .due_deliveries(Utc::now(), self.config.batch_size)
+            .await?;
+
+        let mut report = WebhookDispatchReport::default();
+        for mut delivery in deliveries {
+            // Подписку перечитываем для каждой доставки: предыдущая могла ее отключить
+            let Some(mut webhook) = self.repository.find_webhook(delivery.webhook_id()).await? else {
+                continue;
+            };
+            if !webhook.is_active() {
+                delivery.fail(Utc::now(), "Webhook is disabled".to_string());
+                self.repository.save_delivery(&delivery).await?;
+                report.failed += 1;
+                continue;
+            }
+
+            let secret = self.secret_cipher.decrypt(webhook.secret(), &webhook.id().to_string())?;
+            let attempt = self.send(webhook.url(), &delivery, &secret).await;
+            let now = attempt.attempted_at;
+            match attempt.status_code.filter(|code| (200..300).contains(code)) {
+                Some(status_code) => {
+                    delivery.mark_delivered(now, status_code);
+                    webhook.record_success();
+                    report.delivered += 1;
+                }
+                None => {
+                    let disabled = webhook.record_failure(now, self.config.failure_policy);
+                    if disabled {
+                        tracing::warn!(webhook_id = %webhook.id(), url = webhook.url(), "Webhook disabled after sustained delivery failures");
+                        report.disabled += 1;
+                    }
+
+                    let retry_at = match disabled || delivery.failures() + 1 >= self.config.max_attempts {
+                        true => None,
+                        false => Some(now + self.backoff_delay(delivery.failures())),
+                    };
+                    match retry_at {
+                        Some(_) => report.retried += 1,
+                        None => report.failed += 1,
+                    }
+                    delivery.record_failure(attempt, retry_at);
+                }
+            }
+
+            self.repository.save_delivery(&delivery).await?;
+            self.repository.save_webhook(&webhook).await?;
+        }
+
+        Ok(report)
+    }
+
+    pub async fn run(self) {
+        loop {
+            match self.dispatch_once().await {
+                // Полная пачка - скорее всего есть еще доставки, забираем сразу
+                Ok(report) if report.processed() >= self.config.batch_size => continue,
+                Ok(_) => {}
+                Err(error) => tracing::error!(error = %error, "Webhook dispatch failed"),
+            }
+
+            tokio::time::sleep(self.config.poll_interval).await;
+        }
+    }
+
+    async fn send(&self, url: &str, delivery: &WebhookDelivery, secret: &[u8]) -> WebhookAttempt {
+        let attempted_at = Utc::now();
+        let timestamp = attempted_at.timestamp();
+        let response = self.http_client
+            .post(url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(WEBHOOK_ID_HEADER, delivery.id().to_string())
+            .header(WEBHOOK_EVENT_HEADER, delivery.event())
+            .header(WEBHOOK_TIMESTAMP_HEADER, timestamp.to_string())
+            .header(WEBHOOK_SIGNATURE_HEADER, sign_webhook_payload(secret, timestamp, delivery.payload()))
+            .body(delivery.payload().to_string())
+            .send()
+            .await;
+
+        match response {
+            Ok(response) if response.status().is_success() => {
+                WebhookAttempt { attempted_at, status_code: Some(response.status().as_u16()), error: None }
+            }
+            Ok(response) => WebhookAttempt {
+                attempted_at,
+                status_code: Some(response.status().as_u16()),
+                error: Some(format!("Receiver responded with {}", response.status())),
+            },
+            Err(error) => WebhookAttempt { attempted_at, status_code: None, error: Some(error.to_string()) },
+        }
+    }
+
+    fn backoff_delay(&self, failures: u32) -> chrono::Duration {
+        let delay = self.config.initial_backoff
+            .saturating_mul(2u32.saturating_pow(failures))
+            .min(self.config.max_backoff);
+        chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use axum::{extract::State, http::{HeaderMap, StatusCode}, routing::post, Router};
+    use uuid::Uuid;
+    use crate::domain::{WebhookDeliveryStatus, WebhookStatus, WebhookSubscription};
+    use crate::infrastructure::auth::AesGcmSecretCipher;
+    use crate::infrastructure::repositories::InMemoryWebhookRepository;
+    use crate::infrastructure::webhooks::verify_webhook_signature;
+
+    const SECRET: &str = "0123456789abcdef-secret";
+
+    // Локальный получатель: запоминает запросы и отвечает заданным кодом
+    #[derive(Clone)]
+    struct Receiver {
+        status: Arc<Mutex<StatusCode>>,
+        requests: Arc<Mutex<Vec<(HeaderMap, String)>>>,
+    }
+
+    async fn receive(State(receiver): State<Receiver>, headers: HeaderMap, body: String) -> StatusCode {
+        receiver.requests.lock().unwrap().push((headers, body));
+        *receiver.status.lock().unwrap()
+    }
+
+    async fn start_receiver(status: StatusCode) -> (String, Receiver) {
+        let receiver = Receiver {
+            status: Arc::new(Mutex::new(status)),
+            requests: Arc::new(Mutex::new(Vec::new())),
+        };
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hooks", listener.local_addr().unwrap());
+        let app = Router::new().route("/hooks", post(receive)).with_state(receiver.clone());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (url, receiver)
+    }
+
+    async fn setup(url: String, config: WebhookDispatcherConfig) -> (WebhookDispatcher<InMemoryWebhookRepository, AesGcmSecretCipher>, InMemoryWebhookRepository, Uuid) {
+        let repository = InMemoryWebhookRepository::new();
+        let cipher = AesGcmSecretCipher::new(&[3u8; 32]);
+        let id = Uuid::new_v4();
+        let secret = cipher.encrypt(SECRET.as_bytes(), &id.to_string()).unwrap();
+        repository.save_webhook(&WebhookSubscription::new(id, url, secret, Vec::new()).unwrap()).await.unwrap();
+        (WebhookDispatcher::new(repository.clone(), cipher, config), repository, id)
+    }
+
+    fn test_config() -> WebhookDispatcherConfig {
+        WebhookDispatcherConfig {
+            initial_backoff: Duration::ZERO,
+            failure_policy: WebhookFailurePolicy { failures: 3, window: chrono::Duration::zero() },
+            ..WebhookDispatcherConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delivery_is_signed() {
+        let (url, receiver) = start_receiver(StatusCode::NO_CONTENT).await;
+        let (dispatcher, repository, webhook_id) = setup(url, test_config()).await;
+        let delivery = WebhookDelivery::new(webhook_id, "user.created".to_string(), "{\"type\":\"user.created\"}".to_string());
+        repository.save_delivery(&delivery).await.unwrap();
+
+        let report = dispatcher.dispatch_once().await.unwrap();
+        assert_eq!(report.delivered, 1);
+
+        let (headers, body) = receiver.requests.lock().unwrap()[0].clone();
+        let timestamp: i64 = headers[WEBHOOK_TIMESTAMP_HEADER].to_str().unwrap().parse().unwrap();
+        let signature = headers[WEBHOOK_SIGNATURE_HEADER].to_str().unwrap();
+        assert!(verify_webhook_signature(SECRET.as_bytes(), timestamp, &body, signature, Utc::now().timestamp(), 300));
+        assert_eq!(headers[WEBHOOK_ID_HEADER].to_str().unwrap(), delivery.id().to_string());
+        assert_eq!(headers[WEBHOOK_EVENT_HEADER].to_str().unwrap(), "user.created");
+
+        let stored = repository.find_delivery(delivery.id()).await.unwrap().unwrap();
+        assert_eq!(stored.status(), WebhookDeliveryStatus::Delivered);
+        assert_eq!(stored.attempts()[0].status_code, Some(204));
+    }
+
+    #[tokio::test]
+    async fn test_failed_delivery_is_retried_with_backoff() {
+        let (url, receiver) = start_receiver(StatusCode::INTERNAL_SERVER_ERROR).await;
+        let config = WebhookDispatcherConfig { initial_backoff: Duration::from_secs(60), ..test_config() };
+        let (dispatcher, repository, webhook_id) = setup(url, config).await;
+        let delivery = WebhookDelivery::new(webhook_id, "user.created".to_string(), "{}".to_string());
+        repository.save_delivery(&delivery).await.unwrap();
+
+        let report = dispatcher.dispatch_once().await.unwrap();
+        assert_eq!(report.retried, 1);
+        let stored = repository.find_delivery(delivery.id()).await.unwrap().unwrap();
+        assert_eq!(stored.attempts()[0].status_code, Some(500));
+        assert!(*stored.next_attempt_at() >= stored.attempts()[0].attempted_at + chrono::Duration::seconds(60));
+        // Пауза еще не прошла - повторной отправки нет
+        assert_eq!(dispatcher.dispatch_once().await.unwrap(), WebhookDispatchReport::default());
+
+        *receiver.status.lock().unwrap() = StatusCode::OK;
+        let mut stored = stored;
+        stored.redeliver(Utc::now());
+        repository.save_delivery(&stored).await.unwrap();
+        assert_eq!(dispatcher.dispatch_once().await.unwrap().delivered, 1);
+        assert_eq!(repository.find_webhook(&webhook_id).await.unwrap().unwrap().consecutive_failures(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_webhook_is_disabled_after_sustained_failure() {
+        let (url, _receiver) = start_receiver(StatusCode::SERVICE_UNAVAILABLE).await;
+        let (dispatcher, repository, webhook_id) = setup(url, test_config()).await;
+        for _ in 0..4 {
+            let delivery = WebhookDelivery::new(webhook_id, "user.renamed".to_string(), "{}".to_string());
+            repository.save_delivery(&delivery).await.unwrap();
+        }
+
+        let mut disabled = 0;
+        for _ in 0..3 {
+            disabled += dispatcher.dispatch_once().await.unwrap().disabled;
+        }
+
+        assert_eq!(disabled, 1);
+        let webhook = repository.find_webhook(&webhook_id).await.unwrap().unwrap();
+        assert_eq!(webhook.status(), WebhookStatus::Disabled);
+        let deliveries = repository.list_deliveries(&webhook_id, 10).await.unwrap();
+        assert!(deliveries.iter().all(|delivery| delivery.status() == WebhookDeliveryStatus::Failed));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_receiver_is_recorded() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/hooks", listener.local_addr().unwrap());
+        drop(listener);
+        let (dispatcher, repository, webhook_id) = setup(url, test_config()).await;
+        let delivery = WebhookDelivery::new(webhook_id, "user.deleted".to_string(), "{}".to_string());
+        repository.save_delivery(&delivery).await.unwrap();
+
+        assert_eq!(dispatcher.dispatch_once().await.unwrap().retried, 1);
+        let stored = repository.find_delivery(delivery.id()).await.unwrap().unwrap();
+        assert_eq!(stored.attempts()[0].status_code, None);
+        assert!(stored.attempts()[0].error.is_some());
+    }
+}
\ No newline at end of file
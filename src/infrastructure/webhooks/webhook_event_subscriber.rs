@@ -0,0 +1,75 @@
+use serde_json::json;
+use uuid::Uuid;
+use crate::domain::{UserEvent, UserEventBus, WebhookDelivery, WebhookRepository, DomainError};
+use crate::infrastructure::events::user_event_data;
+use crate::infrastructure::repositories::InMemoryWebhookRepository;
+
+/// Ставит событие в очередь доставки каждой активной подписке, фильтр
+/// которой его принимает. Отправляет доставки `WebhookDispatcher`.
+pub fn subscribe_webhooks(event_bus: &UserEventBus, repository: InMemoryWebhookRepository) {
+    event_bus.subscribe(move |event: UserEvent| {
+        let repository = repository.clone();
+        async move {
+            if let Err(error) = enqueue_webhook_deliveries(&repository, &event).await {
+                tracing::error!(event = event.name(), error = %error, "Failed to enqueue webhook deliveries");
+            }
+        }
+    });
+}
+
+pub async fn enqueue_webhook_deliveries<R: WebhookRepository>(repository: &R, event: &UserEvent) -> Result<usize, DomainError> {
+    let webhooks = repository.list_webhooks().await?;
+    let payload = webhook_payload(event);
+
+    let mut enqueued = 0;
+    for webhook in webhooks.iter().filter(|webhook| webhook.accepts(event.name())) {
+        repository.save_delivery(&WebhookDelivery::new(*webhook.id(), event.name().to_string(), payload.clone())).await?;
+        enqueued += 1;
+    }
+    Ok(enqueued)
+}
+
+// Событие без персональных данных, см. `user_event_data`.
+// `id` общий для всех подписок и нужен получателю для дедупликации
+fn webhook_payload(event: &UserEvent) -> String {
+    json!({
+        "id": Uuid::new_v4().to_string(),
+        "type": event.name(),
+        "occurred_at": event.occurred_at().to_rfc3339(),
+        "data": user_event_data(event),
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{Email, User, WebhookSubscription};
+
+    #[tokio::test]
+    async fn test_deliveries_follow_filters_and_status() {
+        let repository = InMemoryWebhookRepository::new();
+        let all = WebhookSubscription::new(Uuid::new_v4(), "https://a.example.com".to_string(), String::new(), Vec::new()).unwrap();
+        let deletions = WebhookSubscription::new(
+            Uuid::new_v4(),
+            "https://b.example.com".to_string(),
+            String::new(),
+            vec!["user.deleted".to_string()],
+        )
+        .unwrap();
+        repository.save_webhook(&all).await.unwrap();
+        repository.save_webhook(&deletions).await.unwrap();
+
+        let mut user = User::new(Email::new("alice@example.com".to_string()).unwrap(), "Alice".to_string()).unwrap();
+        let created = user.take_events().remove(0);
+        assert_eq!(enqueue_webhook_deliveries(&repository, &created).await.unwrap(), 1);
+
+        let deliveries = repository.list_deliveries(all.id(), 10).await.unwrap();
+        let payload: serde_json::Value = serde_json::from_str(deliveries[0].payload()).unwrap();
+        assert_eq!(payload["type"], "user.created");
+        assert_eq!(payload["data"]["user_id"], user.id().to_string());
+        assert_eq!(payload["data"]["roles"], json!(["member"]));
+        assert!(!deliveries[0].payload().contains("alice"));
+        assert!(repository.list_deliveries(deletions.id(), 10).await.unwrap().is_empty());
+    }
+}
\ No newline at end of file
@@ -0,0 +1,49 @@
+use data_encoding::HEXLOWER;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+pub const WEBHOOK_ID_HEADER: &str = "x-webhook-id";
+pub const WEBHOOK_EVENT_HEADER: &str = "x-webhook-event";
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "x-webhook-timestamp";
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "x-webhook-signature";
+
+const SIGNATURE_PREFIX: &str = "sha256=";
+
+/// Подпись тела запроса: `sha256=` и HMAC-SHA256 от `"{timestamp}.{body}"`
+/// в hex. Метка времени входит в подпись, поэтому получатель может
+/// отбрасывать старые запросы, не опасаясь подмены заголовка.
+pub fn sign_webhook_payload(secret: &[u8], timestamp: i64, body: &str) -> String {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+    format!("{}{}", SIGNATURE_PREFIX, HEXLOWER.encode(&mac.finalize().into_bytes()))
+}
+
+// Проверка на стороне получателя: подпись и отклонение метки времени от `now`
+pub fn verify_webhook_signature(secret: &[u8], timestamp: i64, body: &str, signature: &str, now: i64, tolerance_secs: i64) -> bool {
+    let Some(signature) = signature.strip_prefix(SIGNATURE_PREFIX).and_then(|hex| HEXLOWER.decode(hex.as_bytes()).ok()) else {
+        return false;
+    };
+    if (now - timestamp).abs() > tolerance_secs {
+        return false;
+    }
+
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+    mac.verify_slice(&signature).is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_round_trip() {
+        let signature = sign_webhook_payload(b"secret", 1_700_000_000, "{\"type\":\"user.created\"}");
+
+        assert!(signature.starts_with("sha256="));
+        assert!(verify_webhook_signature(b"secret", 1_700_000_000, "{\"type\":\"user.created\"}", &signature, 1_700_000_100, 300));
+        assert!(!verify_webhook_signature(b"other", 1_700_000_000, "{\"type\":\"user.created\"}", &signature, 1_700_000_100, 300));
+        assert!(!verify_webhook_signature(b"secret", 1_700_000_000, "{\"type\":\"user.deleted\"}", &signature, 1_700_000_100, 300));
+        assert!(!verify_webhook_signature(b"secret", 1_700_000_000, "{\"type\":\"user.created\"}", &signature, 1_700_001_000, 300));
+    }
+}
\ No newline at end of file
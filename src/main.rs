@@ -1,22 +1,37 @@
-use server::create_app_router;
+use server::infrastructure::ConfigCommand;
+use server::presentation::routers::create_app_router;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // Инициализация логирования
     tracing_subscriber::fmt::init();
 
-    println!("🚀 Запуск сервера на адресе: 0.0.0.0:3000");
+    // Конфигурация из файлов, окружения и аргументов; с ошибками сервер не запускается
+    let config = match ConfigCommand::from_args(std::env::args().skip(1)) {
+        Ok(ConfigCommand::Serve(config)) => *config,
+        Ok(ConfigCommand::PrintConfig(rendered)) => {
+            print!("{}", rendered);
+            return Ok(());
+        }
+        Err(error) => {
+            eprint!("{}", error);
+            std::process::exit(2);
+        }
+    };
+    let address = config.server_address();
+
+    println!("🚀 Запуск сервера на адресе: {}", address);
 
     // Создание приложения
-    let app = create_app_router();
+    let app = create_app_router(config);
 
     // Создание TCP listener
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await?;
+    let listener = tokio::net::TcpListener::bind(&address).await?;
 
     println!("✅ Сервер запущен и ожидает подключений...");
 
-    // Запуск сервера
-    axum::serve(listener, app).await?;
+    // Запуск сервера (адрес клиента нужен для защиты от перебора)
+    axum::serve(listener, app.into_make_service_with_connect_info::<std::net::SocketAddr>()).await?;
 
     Ok(())
 }
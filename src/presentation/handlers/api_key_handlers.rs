@@ -0,0 +1,75 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension,
+};
+use crate::application::{ApiKeyApplicationService, ApiKeyResponse, ApiResponse, CreateApiKeyRequest, CreatedApiKeyResponse};
+use crate::domain::Actor;
+use crate::infrastructure::{InMemoryApiKeyRepository, InMemoryUserRepository, Sha256SecretHasher};
+use super::content_negotiation::{Encoded, Payload, ResponseCodec};
+
+pub type ApiKeyService = ApiKeyApplicationService<InMemoryUserRepository, InMemoryApiKeyRepository, Sha256SecretHasher>;
+
+#[utoipa::path(post, path = "/api/users/{id}/api-keys", tag = "api-keys", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID пользователя (UUID)")),
+    request_body = CreateApiKeyRequest,
+    responses(
+        (status = 201, description = "Ключ выпущен, полный ключ показывается только здесь", body = ApiResponse<CreatedApiKeyResponse>),
+        (status = 400, description = "Неверные scopes или срок действия", body = ApiResponse<CreatedApiKeyResponse>),
+    ))]
+pub async fn create_api_key_handler(
+    State(api_key_service): State<ApiKeyService>,
+    ResponseCodec(codec): ResponseCodec,
+    Extension(actor): Extension<Actor>,
+    Path(user_id): Path<String>,
+    Payload(request): Payload<CreateApiKeyRequest>,
+) -> impl IntoResponse {
+    let response = api_key_service.create_api_key(&actor, user_id, request).await;
+    
+    match response.success {
+        true => (StatusCode::CREATED, Encoded(codec, response)).into_response(),
+        false => (StatusCode::BAD_REQUEST, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(get, path = "/api/users/{id}/api-keys", tag = "api-keys", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID пользователя (UUID)")),
+    responses(
+        (status = 200, description = "Ключи пользователя без секретов", body = ApiResponse<Vec<ApiKeyResponse>>),
+        (status = 400, description = "Неверный ID пользователя", body = ApiResponse<Vec<ApiKeyResponse>>),
+    ))]
+pub async fn list_api_keys_handler(
+    State(api_key_service): State<ApiKeyService>,
+    ResponseCodec(codec): ResponseCodec,
+    Path(user_id): Path<String>,
+) -> impl IntoResponse {
+    let response = api_key_service.list_api_keys(user_id).await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::BAD_REQUEST, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(delete, path = "/api/users/{id}/api-keys/{key_id}", tag = "api-keys", security(("bearer" = [])),
+    params(
+        ("id" = String, Path, description = "ID пользователя (UUID)"),
+        ("key_id" = String, Path, description = "ID ключа (UUID)"),
+    ),
+    responses(
+        (status = 200, description = "Ключ отозван", body = ApiResponse<ApiKeyResponse>),
+        (status = 404, description = "Ключ не найден", body = ApiResponse<ApiKeyResponse>),
+    ))]
+pub async fn revoke_api_key_handler(
+    State(api_key_service): State<ApiKeyService>,
+    ResponseCodec(codec): ResponseCodec,
+    Path((user_id, key_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+    let response = api_key_service.revoke_api_key(user_id, key_id).await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::NOT_FOUND, Encoded(codec, response)).into_response(),
+    }
+}
\ No newline at end of file
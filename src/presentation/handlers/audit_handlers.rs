@@ -0,0 +1,47 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use crate::application::{ApiResponse, AuditApplicationService, AuditChainResponse, AuditListQuery, AuditPageResponse};
+use crate::infrastructure::AuditStoreBackend;
+use super::content_negotiation::{Encoded, ResponseCodec};
+
+pub type AuditService = AuditApplicationService<AuditStoreBackend>;
+
+#[utoipa::path(get, path = "/api/users/{id}/audit", tag = "audit", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID пользователя (UUID)"), AuditListQuery),
+    responses(
+        (status = 200, description = "События пользователя, новые первыми", body = ApiResponse<AuditPageResponse>),
+        (status = 400, description = "Неверный ID или параметры страницы", body = ApiResponse<AuditPageResponse>),
+    ))]
+pub async fn list_user_audit_events_handler(
+    State(audit_service): State<AuditService>,
+    ResponseCodec(codec): ResponseCodec,
+    Path(user_id): Path<String>,
+    Query(query): Query<AuditListQuery>,
+) -> impl IntoResponse {
+    let response = audit_service.list_user_events(user_id, query).await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::BAD_REQUEST, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(get, path = "/api/admin/audit/verify", tag = "audit", security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Результат проверки хеш-цепочки", body = ApiResponse<AuditChainResponse>),
+        (status = 500, description = "Журнал не удалось прочитать", body = ApiResponse<AuditChainResponse>),
+    ))]
+pub async fn verify_audit_chain_handler(
+    State(audit_service): State<AuditService>,
+    ResponseCodec(codec): ResponseCodec,
+) -> impl IntoResponse {
+    let response = audit_service.verify_chain().await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::INTERNAL_SERVER_ERROR, Encoded(codec, response)).into_response(),
+    }
+}
\ No newline at end of file
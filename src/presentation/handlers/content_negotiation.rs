@@ -0,0 +1,311 @@
+use std::convert::Infallible;
+use axum::{
+    body::Bytes,
+    extract::{FromRequest, FromRequestParts, OptionalFromRequestParts, Request},
+    http::{header, request::Parts, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use serde::{de::DeserializeOwned, Serialize};
+use crate::application::dto::ApiResponse;
+use crate::presentation::middleware::EncodedEnvelope;
+
+/// Формат тела запроса и ответа. Встроенным клиентам нужен компактный
+/// двоичный формат, поэтому кроме JSON поддерживаются MessagePack и CBOR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    Json,
+    MessagePack,
+    Cbor,
+    Xml,
+}
+
+impl Codec {
+    pub const ALL: [Codec; 4] = [Codec::Json, Codec::MessagePack, Codec::Cbor, Codec::Xml];
+
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            Codec::Json => "application/json",
+            Codec::MessagePack => "application/msgpack",
+            Codec::Cbor => "application/cbor",
+            Codec::Xml => "application/xml",
+        }
+    }
+
+    // Тип без параметров (`; charset=utf-8`), с распространенными синонимами
+    pub fn from_media_type(media_type: &str) -> Option<Self> {
+        let essence = media_type.split(';').next().unwrap_or_default().trim().to_ascii_lowercase();
+        match essence.as_str() {
+            "application/json" => Some(Codec::Json),
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => Some(Codec::MessagePack),
+            "application/cbor" => Some(Codec::Cbor),
+            "application/xml" | "text/xml" => Some(Codec::Xml),
+            essence if essence.ends_with("+json") => Some(Codec::Json),
+            _ => None,
+        }
+    }
+
+    // Формат с наибольшим `q` из Accept; `*/*` и `application/*` - JSON
+    pub fn from_accept(accept: &str) -> Option<Self> {
+        let mut best: Option<(Codec, f32)> = None;
+        for item in accept.split(',').map(str::trim).filter(|item| !item.is_empty()) {
+            let mut params = item.split(';');
+            let media_type = params.next().unwrap_or_default().trim().to_ascii_lowercase();
+            let quality = params
+                .filter_map(|param| param.trim().strip_prefix("q="))
+                .find_map(|quality| quality.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            let codec = match media_type.as_str() {
+                "*/*" | "application/*" => Some(Codec::Json),
+                media_type => Codec::from_media_type(media_type),
+            };
+            if let Some(codec) = codec
+                && quality > 0.0
+                && best.is_none_or(|(_, best_quality)| quality > best_quality)
+            {
+                best = Some((codec, quality));
+            }
+        }
+        best.map(|(codec, _)| codec)
+    }
+
+    pub fn encode<T: Serialize>(&self, value: &T) -> Result<Vec<u8>, String> {
+        match self {
+            Codec::Json => serde_json::to_vec(value).map_err(|error| error.to_string()),
+            // С именами полей: клиенту не нужно знать порядок полей структуры
+            Codec::MessagePack => rmp_serde::to_vec_named(value).map_err(|error| error.to_string()),
+            Codec::Cbor => {
+                let mut bytes = Vec::new();
+                ciborium::into_writer(value, &mut bytes).map_err(|error| error.to_string())?;
+                Ok(bytes)
+            }
+            Codec::Xml => quick_xml::se::to_string(value).map(String::into_bytes).map_err(|error| error.to_string()),
+        }
+    }
+
+    pub fn decode<T: DeserializeOwned>(&self, bytes: &[u8]) -> Result<T, String> {
+        match self {
+            Codec::Json => serde_json::from_slice(bytes).map_err(|error| error.to_string()),
+            Codec::MessagePack => rmp_serde::from_slice(bytes).map_err(|error| error.to_string()),
+            Codec::Cbor => ciborium::from_reader(bytes).map_err(|error| error.to_string()),
+            Codec::Xml => {
+                let text = std::str::from_utf8(bytes).map_err(|error| error.to_string())?;
+                quick_xml::de::from_str(text).map_err(|error| error.to_string())
+            }
+        }
+    }
+}
+
+pub struct NegotiationRejection {
+    status: StatusCode,
+    message: String,
+}
+
+impl IntoResponse for NegotiationRejection {
+    // Формат клиента неизвестен или не поддерживается, поэтому ошибка в JSON
+    fn into_response(self) -> Response {
+        (self.status, ApiResponse::<()>::error(self.message)).into_response()
+    }
+}
+
+fn supported_media_types() -> String {
+    Codec::ALL.iter().map(Codec::media_type).collect::<Vec<_>>().join(", ")
+}
+
+/// Формат ответа по заголовку Accept (без заголовка - JSON), иначе 406.
+#[derive(Debug, Clone, Copy)]
+pub struct ResponseCodec(pub Codec);
+
+impl<S: Send + Sync> FromRequestParts<S> for ResponseCodec {
+    type Rejection = NegotiationRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let Some(accept) = parts.headers.get(header::ACCEPT) else {
+            return Ok(ResponseCodec(Codec::Json));
+        };
+        accept
+            .to_str()
+            .ok()
+            .and_then(Codec::from_accept)
+            .map(ResponseCodec)
+            .ok_or_else(|| NegotiationRejection {
+                status: StatusCode::NOT_ACCEPTABLE,
+                message: format!("Acceptable response types: {}", supported_media_types()),
+            })
+    }
+}
+
+// Для обработчиков, отдающих файл (ZIP, QR-код): Accept описывает файл, поэтому
+// неподходящий Accept не дает 406, а ошибку обработчик отдает в JSON
+impl<S: Send + Sync> OptionalFromRequestParts<S> for ResponseCodec {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Option<Self>, Self::Rejection> {
+        Ok(<Self as FromRequestParts<S>>::from_request_parts(parts, state).await.ok())
+    }
+}
+
+/// Тело запроса в формате из Content-Type, иначе 415.
+pub struct Payload<T>(pub T);
+
+impl<T: DeserializeOwned, S: Send + Sync> FromRequest<S> for Payload<T> {
+    type Rejection = NegotiationRejection;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let codec = request
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(Codec::from_media_type)
+            .ok_or_else(|| NegotiationRejection {
+                status: StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                message: format!("Supported request types: {}", supported_media_types()),
+            })?;
+
+        let bytes = Bytes::from_request(request, state).await.map_err(|rejection| NegotiationRejection {
+            status: rejection.status(),
+            message: rejection.body_text(),
+        })?;
+        codec.decode(&bytes).map(Payload).map_err(|error| NegotiationRejection {
+            status: StatusCode::BAD_REQUEST,
+            message: format!("Invalid request body: {}", error),
+        })
+    }
+}
+
+/// `ApiResponse<T>` в формате, выбранном клиентом.
+pub struct Encoded<T>(pub Codec, pub ApiResponse<T>);
+
+impl<T: Serialize + Send + Sync + 'static> IntoResponse for Encoded<T> {
+    fn into_response(self) -> Response {
+        let Encoded(codec, response) = self;
+        let body = match codec.encode(&response) {
+            Ok(body) => body,
+            Err(error) => {
+                tracing::error!(error = %error, media_type = codec.media_type(), "Failed to encode response");
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+        };
+
+        // XML и двоичные форматы не разобрать обратно, поэтому ресурс для v2
+        // кодирует метка, и только если ее запросила v2
+        let ApiResponse { success, data, error, .. } = response;
+        let envelope = EncodedEnvelope::new(success, error, move || {
+            data.as_ref().map(|data| codec.encode(data).map(Bytes::from)).transpose()
+        });
+        let mut http_response = ([(header::CONTENT_TYPE, HeaderValue::from_static(codec.media_type()))], body).into_response();
+        http_response.extensions_mut().insert(envelope);
+        http_response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{atomic::{AtomicUsize, Ordering}, Arc};
+    use axum::{body::{to_bytes, Body}, routing::post, Router};
+    use tower::ServiceExt;
+    use crate::application::dto::{CreateUserRequest, UserResponse};
+
+    async fn echo(ResponseCodec(codec): ResponseCodec, Payload(request): Payload<CreateUserRequest>) -> Encoded<UserResponse> {
+        let now = chrono::Utc::now();
+        Encoded(codec, ApiResponse::success(UserResponse {
+            id: "42".to_string(),
+            email: request.email,
+            name: request.name,
+            roles: vec!["member".to_string()],
+            created_at: now,
+            updated_at: now,
+            erased_at: None,
+        }))
+    }
+
+    async fn call(content_type: &str, accept: &str, body: Vec<u8>) -> (StatusCode, String, Vec<u8>) {
+        let app = Router::new().route("/users", post(echo));
+        let request = Request::builder()
+            .method("POST")
+            .uri("/users")
+            .header(header::CONTENT_TYPE, content_type)
+            .header(header::ACCEPT, accept)
+            .body(Body::from(body))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        let status = response.status();
+        let content_type = response.headers().get(header::CONTENT_TYPE).map(|value| value.to_str().unwrap().to_string()).unwrap_or_default();
+        (status, content_type, to_bytes(response.into_body(), usize::MAX).await.unwrap().to_vec())
+    }
+
+    fn request() -> CreateUserRequest {
+        CreateUserRequest { email: "alice@example.com".to_string(), name: "Alice".to_string() }
+    }
+
+    #[tokio::test]
+    async fn test_every_codec_round_trips() {
+        for codec in Codec::ALL {
+            let (status, content_type, body) = call(codec.media_type(), codec.media_type(), codec.encode(&request()).unwrap()).await;
+            assert_eq!((status, content_type.as_str()), (StatusCode::OK, codec.media_type()), "{:?}", codec);
+
+            let response: ApiResponse<UserResponse> = codec.decode(&body).unwrap();
+            assert_eq!(response.data.unwrap().email, "alice@example.com", "{:?}", codec);
+        }
+
+        // Тело в MessagePack, ответ в CBOR по наибольшему q
+        let (_, content_type, _) = call(
+            "application/x-msgpack",
+            "application/json;q=0.5, application/cbor",
+            Codec::MessagePack.encode(&request()).unwrap(),
+        ).await;
+        assert_eq!(content_type, "application/cbor");
+    }
+
+    struct Counted(Arc<AtomicUsize>);
+
+    impl Serialize for Counted {
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            serializer.serialize_str("counted")
+        }
+    }
+
+    #[test]
+    fn test_bare_resource_is_encoded_only_on_demand() {
+        let encodings = Arc::new(AtomicUsize::new(0));
+        let mut response = Encoded(Codec::Cbor, ApiResponse::success(Counted(encodings.clone()))).into_response();
+        assert_eq!(encodings.load(Ordering::SeqCst), 1);
+
+        let envelope = response.extensions_mut().remove::<EncodedEnvelope>().unwrap();
+        let resource = envelope.resource().unwrap().unwrap();
+        assert_eq!(encodings.load(Ordering::SeqCst), 2);
+        assert_eq!(Codec::Cbor.decode::<String>(&resource).unwrap(), "counted");
+    }
+
+    #[tokio::test]
+    async fn test_file_handlers_accept_any_media_type() {
+        async fn file(codec: Option<ResponseCodec>) -> &'static str {
+            match codec {
+                Some(ResponseCodec(codec)) => codec.media_type(),
+                None => "fallback",
+            }
+        }
+        let app = Router::new().route("/file", axum::routing::get(file));
+        let request = Request::builder().uri("/file").header(header::ACCEPT, "application/zip").body(Body::empty()).unwrap();
+        let body = to_bytes(app.oneshot(request).await.unwrap().into_body(), usize::MAX).await.unwrap();
+        assert_eq!(&body[..], b"fallback");
+    }
+
+    #[tokio::test]
+    async fn test_unsupported_types_are_rejected() {
+        let json = serde_json::to_vec(&request()).unwrap();
+
+        let (status, _, _) = call("text/plain", "application/json", json.clone()).await;
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+        let (status, content_type, body) = call("application/json", "text/html, application/cbor;q=0", json.clone()).await;
+        assert_eq!((status, content_type.as_str()), (StatusCode::NOT_ACCEPTABLE, "application/json"));
+        assert!(String::from_utf8(body).unwrap().contains("application/msgpack"));
+
+        let (status, _, _) = call("application/json", "*/*", json).await;
+        assert_eq!(status, StatusCode::OK);
+        let (status, _, _) = call("application/cbor", "*/*", b"not cbor".to_vec()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+    }
+}
@@ -0,0 +1,76 @@
+use std::future::ready;
+use async_graphql::http::{GraphiQLSource, WebSocketProtocols, WsMessage, ALL_WEBSOCKET_PROTOCOLS};
+use async_graphql::Data;
+use axum::{
+    extract::{ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade}, State},
+    response::{Html, Response},
+    Extension, Json,
+};
+use futures_util::{SinkExt, StreamExt};
+use crate::domain::Actor;
+use super::graphql_schema::UserSchema;
+
+const MAX_CLIENT_MESSAGE_SIZE: usize = 64 * 1024;
+
+pub async fn graphql_handler(
+    State(schema): State<UserSchema>,
+    Extension(actor): Extension<Actor>,
+    Json(request): Json<async_graphql::Request>,
+) -> Json<async_graphql::Response> {
+    Json(schema.execute(request.data(actor)).await)
+}
+
+// Подписки по `graphql-transport-ws` (и устаревшему `graphql-ws`),
+// протокол выбирается по заголовку Sec-WebSocket-Protocol
+pub async fn graphql_subscription_handler(
+    State(schema): State<UserSchema>,
+    Extension(actor): Extension<Actor>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    upgrade
+        .max_message_size(MAX_CLIENT_MESSAGE_SIZE)
+        .protocols(ALL_WEBSOCKET_PROTOCOLS)
+        .on_upgrade(move |socket| handle_graphql_socket(socket, schema, actor))
+}
+
+pub async fn graphiql_handler() -> Html<String> {
+    Html(
+        GraphiQLSource::build()
+            .endpoint("/graphql")
+            .subscription_endpoint("/graphql/ws")
+            .finish(),
+    )
+}
+
+async fn handle_graphql_socket(socket: WebSocket, schema: UserSchema, actor: Actor) {
+    let protocol = socket
+        .protocol()
+        .and_then(|protocol| protocol.to_str().ok())
+        .and_then(|protocol| protocol.parse::<WebSocketProtocols>().ok())
+        .unwrap_or(WebSocketProtocols::GraphQLWS);
+    let (mut sink, stream) = socket.split();
+
+    let incoming = stream
+        .take_while(|message| ready(matches!(message, Ok(message) if !matches!(message, Message::Close(_)))))
+        .filter_map(|message| {
+            ready(match message {
+                Ok(Message::Text(text)) => Some(text.to_string()),
+                _ => None,
+            })
+        });
+
+    let mut data = Data::default();
+    data.insert(actor);
+    let outgoing = async_graphql::http::WebSocket::new(schema, incoming, protocol).connection_data(data);
+    let mut outgoing = std::pin::pin!(outgoing);
+
+    while let Some(message) = outgoing.next().await {
+        let message = match message {
+            WsMessage::Text(text) => Message::Text(text.into()),
+            WsMessage::Close(code, reason) => Message::Close(Some(CloseFrame { code, reason: reason.into() })),
+        };
+        if sink.send(message).await.is_err() {
+            return;
+        }
+    }
+}
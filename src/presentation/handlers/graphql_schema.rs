@@ -0,0 +1,419 @@
+use std::future::ready;
+use async_graphql::{
+    Context, Error, ErrorExtensions, InputObject, Json, Object, Result, Schema, SimpleObject, Subscription, ID,
+};
+use chrono::{DateTime, Utc};
+use futures_util::{stream, Stream, StreamExt};
+use crate::application::use_cases::{create_user, delete_user, get_user, list_users, update_user};
+use crate::application::{
+    CreateUserUseCase, DeleteUserUseCase, GetUserUseCase, ListUsersQuery, ListUsersUseCase, UpdateUserUseCase, UserResponse,
+};
+use crate::domain::{Actor, AuthorizationService, Permission, UserEventBus, UserId, DomainError, USER_EVENT_NAMES};
+use crate::infrastructure::{AppConfig, InMemoryUserRepository, UserChangeFeed};
+
+pub type UserSchema = Schema<QueryRoot, MutationRoot, SubscriptionRoot>;
+
+const DEFAULT_PAGE_SIZE: usize = 20;
+
+#[derive(SimpleObject)]
+#[graphql(name = "User")]
+pub struct GraphQlUser {
+    id: ID,
+    email: String,
+    name: String,
+    roles: Vec<String>,
+    created_at: DateTime<Utc>,
+    updated_at: DateTime<Utc>,
+    erased_at: Option<DateTime<Utc>>,
+}
+
+impl From<UserResponse> for GraphQlUser {
+    fn from(user: UserResponse) -> Self {
+        Self {
+            id: ID(user.id),
+            email: user.email,
+            name: user.name,
+            roles: user.roles,
+            created_at: user.created_at,
+            updated_at: user.updated_at,
+            erased_at: user.erased_at,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "UserPage")]
+pub struct GraphQlUserPage {
+    items: Vec<GraphQlUser>,
+    total_count: usize,
+    offset: usize,
+    limit: usize,
+}
+
+#[derive(SimpleObject)]
+#[graphql(name = "UserChange")]
+pub struct GraphQlUserChange {
+    // Номер изменения в ленте, общий с SSE и `/ws`
+    id: ID,
+    event: String,
+    user_id: ID,
+    data: Json<serde_json::Value>,
+}
+
+#[derive(InputObject, Default)]
+pub struct UserFilter {
+    role: Option<String>,
+    search: Option<String>,
+    #[graphql(default)]
+    include_erased: bool,
+}
+
+#[derive(InputObject)]
+pub struct CreateUserInput {
+    email: String,
+    name: String,
+}
+
+#[derive(InputObject)]
+pub struct UpdateUserInput {
+    email: Option<String>,
+    name: Option<String>,
+}
+
+// Резолверы вызывают те же use case, что и REST-обработчики
+pub struct GraphQlUseCases {
+    create_user: CreateUserUseCase<InMemoryUserRepository>,
+    get_user: GetUserUseCase<InMemoryUserRepository>,
+    list_users: ListUsersUseCase<InMemoryUserRepository>,
+    update_user: UpdateUserUseCase<InMemoryUserRepository>,
+    delete_user: DeleteUserUseCase<InMemoryUserRepository>,
+}
+
+impl GraphQlUseCases {
+    pub fn new(user_repository: InMemoryUserRepository, event_bus: UserEventBus) -> Self {
+        Self {
+            create_user: CreateUserUseCase::new(user_repository.clone()).with_event_bus(event_bus.clone()),
+            get_user: GetUserUseCase::new(user_repository.clone()),
+            list_users: ListUsersUseCase::new(user_repository.clone()),
+            update_user: UpdateUserUseCase::new(user_repository.clone()).with_event_bus(event_bus.clone()),
+            delete_user: DeleteUserUseCase::new(user_repository).with_event_bus(event_bus),
+        }
+    }
+}
+
+pub fn build_user_schema(use_cases: GraphQlUseCases, feed: UserChangeFeed, config: &AppConfig) -> UserSchema {
+    Schema::build(QueryRoot, MutationRoot, SubscriptionRoot)
+        .data(use_cases)
+        .data(feed)
+        .limit_depth(config.graphql_max_depth)
+        .limit_complexity(config.graphql_max_complexity)
+        .finish()
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn user(&self, ctx: &Context<'_>, id: ID) -> Result<GraphQlUser> {
+        authorize(ctx, Permission::ReadUser, Some(&id))?;
+        let user = use_cases(ctx).get_user.execute(id.0).await.map_err(get_user_error)?;
+        Ok(GraphQlUser::from(user))
+    }
+
+    async fn user_by_email(&self, ctx: &Context<'_>, email: String) -> Result<GraphQlUser> {
+        authorize(ctx, Permission::FindUserByEmail, None)?;
+        let user = use_cases(ctx).get_user.get_by_email(email).await.map_err(get_user_error)?;
+        Ok(GraphQlUser::from(user))
+    }
+
+    // Сложность растет с размером страницы
+    #[graphql(complexity = "limit * child_complexity")]
+    async fn users(
+        &self,
+        ctx: &Context<'_>,
+        filter: Option<UserFilter>,
+        #[graphql(default)] offset: usize,
+        #[graphql(default_with = "DEFAULT_PAGE_SIZE", validator(minimum = 1, maximum = 100))] limit: usize,
+    ) -> Result<GraphQlUserPage> {
+        authorize(ctx, Permission::ListUsers, None)?;
+        let filter = filter.unwrap_or_default();
+        let page = use_cases(ctx)
+            .list_users
+            .execute(ListUsersQuery {
+                offset: Some(offset),
+                limit: Some(limit),
+                role: filter.role,
+                search: filter.search,
+                include_erased: filter.include_erased,
+            })
+            .await
+            .map_err(list_users_error)?;
+
+        Ok(GraphQlUserPage {
+            items: page.users.into_iter().map(GraphQlUser::from).collect(),
+            total_count: page.total,
+            offset: page.offset,
+            limit: page.limit,
+        })
+    }
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    // Как и `POST /api/users`, создание доступно любому клиенту
+    async fn create_user(&self, ctx: &Context<'_>, input: CreateUserInput) -> Result<GraphQlUser> {
+        actor(ctx)?;
+        let user = use_cases(ctx)
+            .create_user
+            .execute(input.email, input.name)
+            .await
+            .map_err(create_user_error)?;
+        Ok(GraphQlUser::from(UserResponse::from(user)))
+    }
+
+    async fn update_user(&self, ctx: &Context<'_>, id: ID, input: UpdateUserInput) -> Result<GraphQlUser> {
+        authorize(ctx, Permission::UpdateUser, Some(&id))?;
+        let user = use_cases(ctx)
+            .update_user
+            .execute(id.0, input.email, input.name)
+            .await
+            .map_err(update_user_error)?;
+        Ok(GraphQlUser::from(user))
+    }
+
+    async fn delete_user(&self, ctx: &Context<'_>, id: ID) -> Result<bool> {
+        authorize(ctx, Permission::DeleteUser, Some(&id))?;
+        use_cases(ctx).delete_user.execute(id.0).await.map_err(delete_user_error)?;
+        Ok(true)
+    }
+}
+
+pub struct SubscriptionRoot;
+
+#[Subscription]
+impl SubscriptionRoot {
+    // Без `user_ids` - все изменения (поддержка и администраторы)
+    async fn user_changes(
+        &self,
+        ctx: &Context<'_>,
+        #[graphql(default)] events: Vec<String>,
+        #[graphql(default)] user_ids: Vec<ID>,
+    ) -> Result<impl Stream<Item = GraphQlUserChange>> {
+        if let Some(unknown) = events.iter().find(|event| !USER_EVENT_NAMES.contains(&event.as_str())) {
+            return Err(graphql_error(format!("Unknown event type: {}", unknown), "BAD_USER_INPUT"));
+        }
+        if user_ids.is_empty() {
+            authorize(ctx, Permission::WatchUserChanges, None)?;
+        }
+        for user_id in &user_ids {
+            authorize(ctx, Permission::ReadUser, Some(user_id))?;
+        }
+
+        let receiver = ctx.data_unchecked::<UserChangeFeed>().subscribe(None).receiver;
+        // Отставший подписчик получает завершение подписки, как и в `/ws`
+        let changes = stream::unfold(receiver, |mut receiver| async move {
+            receiver.recv().await.ok().map(|change| (change, receiver))
+        });
+
+        Ok(changes
+            .filter(move |change| {
+                ready(
+                    (events.is_empty() || events.iter().any(|event| event == change.event))
+                        && (user_ids.is_empty() || user_ids.iter().any(|id| id.as_str() == change.user_id.to_string())),
+                )
+            })
+            .map(|change| GraphQlUserChange {
+                id: ID(change.id.to_string()),
+                event: change.event.to_string(),
+                user_id: ID(change.user_id.to_string()),
+                data: Json(serde_json::from_str(&change.data).unwrap_or_default()),
+            }))
+    }
+}
+
+fn use_cases<'a>(ctx: &Context<'a>) -> &'a GraphQlUseCases {
+    ctx.data_unchecked::<GraphQlUseCases>()
+}
+
+fn actor<'a>(ctx: &Context<'a>) -> Result<&'a Actor> {
+    ctx.data::<Actor>()
+        .map_err(|_| graphql_error("Authentication required", "UNAUTHENTICATED"))
+}
+
+fn authorize(ctx: &Context<'_>, permission: Permission, target: Option<&ID>) -> Result<()> {
+    let actor = actor(ctx)?;
+    let target = target
+        .map(|id| UserId::from_string(id.to_string()))
+        .transpose()
+        .map_err(|error| graphql_error(format!("Invalid user ID: {}", error), "BAD_USER_INPUT"))?;
+
+    AuthorizationService::authorize(actor, permission, target.as_ref()).map_err(domain_error)
+}
+
+// Код ошибки в `extensions.code`, по нему клиенты отличают 404 от 403
+fn graphql_error(message: impl Into<String>, code: &'static str) -> Error {
+    Error::new(message).extend_with(|_, extensions| extensions.set("code", code))
+}
+
+fn domain_error(error: DomainError) -> Error {
+    let code = match &error {
+        DomainError::UserNotFound => "NOT_FOUND",
+        DomainError::UserAlreadyExists | DomainError::ConcurrentModification(_, _) => "CONFLICT",
+        DomainError::InvalidEmail(_) | DomainError::InvalidUserData(_) | DomainError::InvalidOperation(_) => "BAD_USER_INPUT",
+        DomainError::Unauthorized(_) => "UNAUTHENTICATED",
+        DomainError::Forbidden(_) => "FORBIDDEN",
+        _ => "INTERNAL_SERVER_ERROR",
+    };
+    graphql_error(error.to_string(), code)
+}
+
+fn get_user_error(error: get_user::ApplicationError) -> Error {
+    match error {
+        get_user::ApplicationError::DomainError(error) => domain_error(error),
+        get_user::ApplicationError::UserNotFound => graphql_error(error.to_string(), "NOT_FOUND"),
+        error => graphql_error(error.to_string(), "BAD_USER_INPUT"),
+    }
+}
+
+fn list_users_error(error: list_users::ApplicationError) -> Error {
+    match error {
+        list_users::ApplicationError::DomainError(error) => domain_error(error),
+        error => graphql_error(error.to_string(), "BAD_USER_INPUT"),
+    }
+}
+
+fn create_user_error(error: create_user::ApplicationError) -> Error {
+    match error {
+        create_user::ApplicationError::DomainError(error) => domain_error(error),
+        create_user::ApplicationError::Unexpected(_) => graphql_error(error.to_string(), "INTERNAL_SERVER_ERROR"),
+        error => graphql_error(error.to_string(), "BAD_USER_INPUT"),
+    }
+}
+
+fn update_user_error(error: update_user::ApplicationError) -> Error {
+    match error {
+        update_user::ApplicationError::DomainError(error) => domain_error(error),
+        update_user::ApplicationError::UserNotFound => graphql_error(error.to_string(), "NOT_FOUND"),
+        error => graphql_error(error.to_string(), "BAD_USER_INPUT"),
+    }
+}
+
+fn delete_user_error(error: delete_user::ApplicationError) -> Error {
+    match error {
+        delete_user::ApplicationError::DomainError(error) => domain_error(error),
+        delete_user::ApplicationError::UserNotFound => graphql_error(error.to_string(), "NOT_FOUND"),
+        error => graphql_error(error.to_string(), "BAD_USER_INPUT"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use async_graphql::Request;
+    use crate::domain::{AuthLevel, Role};
+
+    fn schema() -> UserSchema {
+        let feed = UserChangeFeed::new();
+        let event_bus = UserEventBus::new();
+        crate::infrastructure::subscribe_user_change_feed(&event_bus, feed.clone());
+        build_user_schema(GraphQlUseCases::new(InMemoryUserRepository::new(), event_bus), feed, &AppConfig::default())
+    }
+
+    fn admin() -> Actor {
+        Actor::new(UserId::new(), Role::Admin).with_auth_level(AuthLevel::MultiFactor)
+    }
+
+    async fn execute(schema: &UserSchema, actor: &Actor, query: &str) -> async_graphql::Response {
+        schema.execute(Request::new(query).data(actor.clone())).await
+    }
+
+    fn error_code(response: &async_graphql::Response) -> String {
+        let extensions = response.errors[0].extensions.as_ref().unwrap();
+        extensions.get("code").unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_queries_and_mutations_use_the_use_cases() {
+        let schema = schema();
+        let admin = admin();
+
+        let created = execute(&schema, &admin, r#"mutation { createUser(input: {email: "alice@example.com", name: "Alice"}) { id roles } }"#).await;
+        assert!(created.errors.is_empty(), "{:?}", created.errors);
+        let created = created.data.into_json().unwrap();
+        let id = created["createUser"]["id"].as_str().unwrap().to_string();
+        assert_eq!(created["createUser"]["roles"], serde_json::json!(["member"]));
+
+        let updated = execute(&schema, &admin, &format!(r#"mutation {{ updateUser(id: "{}", input: {{name: "Alice Smith"}}) {{ name }} }}"#, id)).await;
+        assert_eq!(updated.data.into_json().unwrap()["updateUser"]["name"], "Alice Smith");
+
+        let by_email = execute(&schema, &admin, r#"{ userByEmail(email: "alice@example.com") { id } }"#).await;
+        assert_eq!(by_email.data.into_json().unwrap()["userByEmail"]["id"], id.as_str());
+
+        let page = execute(&schema, &admin, r#"{ users(filter: {search: "smith"}, limit: 10) { totalCount items { email } } }"#).await;
+        let page = page.data.into_json().unwrap();
+        assert_eq!(page["users"]["totalCount"], 1);
+        assert_eq!(page["users"]["items"][0]["email"], "alice@example.com");
+
+        let deleted = execute(&schema, &admin, &format!(r#"mutation {{ deleteUser(id: "{}") }}"#, id)).await;
+        assert_eq!(deleted.data.into_json().unwrap()["deleteUser"], true);
+        let missing = execute(&schema, &admin, &format!(r#"{{ user(id: "{}") {{ id }} }}"#, id)).await;
+        assert_eq!(error_code(&missing), "\"NOT_FOUND\"");
+    }
+
+    #[tokio::test]
+    async fn test_resolvers_check_permissions() {
+        let schema = schema();
+        let member = Actor::new(UserId::new(), Role::Member);
+
+        let other = execute(&schema, &member, &format!(r#"{{ user(id: "{}") {{ id }} }}"#, UserId::new())).await;
+        assert_eq!(error_code(&other), "\"FORBIDDEN\"");
+        let list = execute(&schema, &member, "{ users { totalCount } }").await;
+        assert_eq!(error_code(&list), "\"FORBIDDEN\"");
+
+        let anonymous = schema.execute(Request::new("{ users { totalCount } }")).await;
+        assert_eq!(error_code(&anonymous), "\"UNAUTHENTICATED\"");
+    }
+
+    #[tokio::test]
+    async fn test_depth_and_complexity_limits() {
+        let schema = schema();
+        let admin = admin();
+
+        let deep = execute(&schema, &admin, "{ __schema { types { fields { type { ofType { ofType { ofType { ofType { name } } } } } } } } }").await;
+        assert!(deep.errors[0].message.contains("nested too deep"), "{:?}", deep.errors);
+
+        let complex = execute(&schema, &admin, "{ users(limit: 100) { items { id email name roles createdAt updatedAt } } }").await;
+        assert!(complex.errors[0].message.contains("too complex"), "{:?}", complex.errors);
+    }
+
+    #[tokio::test]
+    async fn test_subscription_streams_user_changes() {
+        let schema = schema();
+        let admin = admin();
+        let mut changes = schema.execute_stream(
+            Request::new(r#"subscription { userChanges(events: ["user.created"]) { event userId data } }"#).data(admin.clone()),
+        );
+        // Резолвер подписки запускается при первом опросе потока
+        let change = tokio::spawn(async move { changes.next().await });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let created = execute(&schema, &admin, r#"mutation { createUser(input: {email: "bob@example.com", name: "Bob"}) { id } }"#).await;
+        let id = created.data.into_json().unwrap()["createUser"]["id"].clone();
+
+        let change = tokio::time::timeout(Duration::from_secs(1), change).await.unwrap().unwrap().unwrap();
+        let change = change.data.into_json().unwrap();
+        assert_eq!(change["userChanges"]["event"], "user.created");
+        assert_eq!(change["userChanges"]["userId"], id);
+        assert_eq!(change["userChanges"]["data"]["data"]["roles"], serde_json::json!(["member"]));
+
+        let forbidden = schema
+            .execute_stream(Request::new("subscription { userChanges { event } }").data(Actor::new(UserId::new(), Role::Member)))
+            .next()
+            .await
+            .unwrap();
+        assert!(!forbidden.errors.is_empty());
+    }
+}
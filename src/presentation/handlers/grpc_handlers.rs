@@ -0,0 +1,339 @@
+use std::pin::Pin;
+use chrono::{DateTime, Utc};
+use futures_util::{stream, Stream, StreamExt};
+use tokio::sync::broadcast::error::RecvError;
+use tonic::{Code, Request, Response, Status};
+use crate::application::dto::{ApiResponse, CreateUserRequest, ListUsersQuery, UpdateUserRequest, UserResponse};
+use crate::application::UserApplicationService;
+use crate::domain::{Actor, AuthorizationService, DomainError, Permission, UserId, USER_EVENT_NAMES};
+use crate::infrastructure::{InMemoryUserRepository, UserChangeFeed};
+use self::proto::user_service_server::UserService;
+
+pub mod proto {
+    tonic::include_proto!("user.v1");
+
+    pub const FILE_DESCRIPTOR_SET: &[u8] = tonic::include_file_descriptor_set!("user_service_descriptor");
+}
+
+pub type GrpcUserServer = proto::user_service_server::UserServiceServer<GrpcUserService>;
+
+/// gRPC-адаптер над `UserApplicationService` для внутренних сервисов.
+/// Права проверяются так же, как в REST, по `Actor` из auth_middleware.
+pub struct GrpcUserService {
+    user_service: UserApplicationService<InMemoryUserRepository>,
+    feed: UserChangeFeed,
+}
+
+impl GrpcUserService {
+    pub fn new(user_service: UserApplicationService<InMemoryUserRepository>, feed: UserChangeFeed) -> Self {
+        Self { user_service, feed }
+    }
+}
+
+type UserChangeStream = Pin<Box<dyn Stream<Item = Result<proto::UserChange, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl UserService for GrpcUserService {
+    // Как и `POST /api/users`, создание доступно любому клиенту
+    async fn create_user(&self, request: Request<proto::CreateUserRequest>) -> Result<Response<proto::User>, Status> {
+        actor(&request)?;
+        let request = request.into_inner();
+        let response = self.user_service
+            .create_user(CreateUserRequest { email: request.email, name: request.name })
+            .await;
+        user_reply(response)
+    }
+
+    async fn get_user(&self, request: Request<proto::GetUserRequest>) -> Result<Response<proto::User>, Status> {
+        authorize(&request, Permission::ReadUser, Some(&request.get_ref().id))?;
+        user_reply(self.user_service.get_user(request.into_inner().id).await)
+    }
+
+    async fn get_user_by_email(&self, request: Request<proto::GetUserByEmailRequest>) -> Result<Response<proto::User>, Status> {
+        authorize(&request, Permission::FindUserByEmail, None)?;
+        user_reply(self.user_service.get_user_by_email(request.into_inner().email).await)
+    }
+
+    async fn update_user(&self, request: Request<proto::UpdateUserRequest>) -> Result<Response<proto::User>, Status> {
+        authorize(&request, Permission::UpdateUser, Some(&request.get_ref().id))?;
+        let request = request.into_inner();
+        let response = self.user_service
+            .update_user(request.id, UpdateUserRequest { email: request.email, name: request.name })
+            .await;
+        user_reply(response)
+    }
+
+    async fn delete_user(&self, request: Request<proto::DeleteUserRequest>) -> Result<Response<proto::DeleteUserResponse>, Status> {
+        authorize(&request, Permission::DeleteUser, Some(&request.get_ref().id))?;
+        let response = self.user_service.delete_user(request.into_inner().id).await;
+
+        match response.success {
+            true => Ok(Response::new(proto::DeleteUserResponse {})),
+            false => Err(error_status(response)),
+        }
+    }
+
+    async fn list_users(&self, request: Request<proto::ListUsersRequest>) -> Result<Response<proto::ListUsersResponse>, Status> {
+        authorize(&request, Permission::ListUsers, None)?;
+        let request = request.into_inner();
+        let response = self.user_service
+            .list_users(ListUsersQuery {
+                offset: Some(request.offset as usize),
+                // 0 - значение по умолчанию в proto3, то есть лимит не задан
+                limit: (request.limit > 0).then_some(request.limit as usize),
+                role: request.role,
+                search: request.search,
+                include_erased: request.include_erased,
+            })
+            .await;
+
+        match response.data {
+            Some(page) if response.success => Ok(Response::new(proto::ListUsersResponse {
+                users: page.users.into_iter().map(proto::User::from).collect(),
+                total: page.total as u32,
+                offset: page.offset as u32,
+                limit: page.limit as u32,
+            })),
+            _ => Err(error_status(response)),
+        }
+    }
+
+    type WatchUsersStream = UserChangeStream;
+
+    // Без `user_ids` - все изменения (поддержка и администраторы)
+    async fn watch_users(&self, request: Request<proto::WatchUsersRequest>) -> Result<Response<Self::WatchUsersStream>, Status> {
+        let filter = request.get_ref();
+        if let Some(unknown) = filter.events.iter().find(|event| !USER_EVENT_NAMES.contains(&event.as_str())) {
+            return Err(Status::invalid_argument(format!("Unknown event type: {}", unknown)));
+        }
+        if filter.user_ids.is_empty() {
+            authorize(&request, Permission::WatchUserChanges, None)?;
+        }
+        for user_id in &filter.user_ids {
+            authorize(&request, Permission::ReadUser, Some(user_id))?;
+        }
+
+        let proto::WatchUsersRequest { events, user_ids } = request.into_inner();
+        let receiver = self.feed.subscribe(None).receiver;
+        let changes = stream::unfold(Some(receiver), |receiver| async move {
+            let mut receiver = receiver?;
+            match receiver.recv().await {
+                Ok(change) => Some((Ok(change), Some(receiver))),
+                // Отставший клиент получает ошибку и переподключается
+                Err(RecvError::Lagged(skipped)) => Some((
+                    Err(Status::resource_exhausted(format!("Slow consumer: {} changes skipped", skipped))),
+                    None,
+                )),
+                Err(RecvError::Closed) => None,
+            }
+        });
+
+        let changes = changes
+            .filter(move |change| {
+                let accepted = match change {
+                    Ok(change) => {
+                        (events.is_empty() || events.iter().any(|event| event == change.event))
+                            && (user_ids.is_empty() || user_ids.contains(&change.user_id.to_string()))
+                    }
+                    Err(_) => true,
+                };
+                std::future::ready(accepted)
+            })
+            .map(|change| {
+                change.map(|change| proto::UserChange {
+                    id: change.id,
+                    event: change.event.to_string(),
+                    user_id: change.user_id.to_string(),
+                    data: change.data,
+                })
+            });
+
+        Ok(Response::new(Box::pin(changes)))
+    }
+}
+
+impl From<UserResponse> for proto::User {
+    fn from(user: UserResponse) -> Self {
+        Self {
+            id: user.id,
+            email: user.email,
+            name: user.name,
+            roles: user.roles,
+            created_at: Some(timestamp(user.created_at)),
+            updated_at: Some(timestamp(user.updated_at)),
+            erased_at: user.erased_at.map(timestamp),
+        }
+    }
+}
+
+fn timestamp(time: DateTime<Utc>) -> prost_types::Timestamp {
+    prost_types::Timestamp {
+        seconds: time.timestamp(),
+        nanos: time.timestamp_subsec_nanos() as i32,
+    }
+}
+
+fn actor<T>(request: &Request<T>) -> Result<Actor, Status> {
+    request
+        .extensions()
+        .get::<Actor>()
+        .cloned()
+        .ok_or_else(|| Status::unauthenticated("Authentication required"))
+}
+
+fn authorize<T>(request: &Request<T>, permission: Permission, target: Option<&str>) -> Result<(), Status> {
+    let actor = actor(request)?;
+    let target = target
+        .map(|id| UserId::from_string(id.to_string()))
+        .transpose()
+        .map_err(|error| Status::invalid_argument(format!("Invalid user ID: {}", error)))?;
+
+    AuthorizationService::authorize(&actor, permission, target.as_ref()).map_err(status_from_domain_error)
+}
+
+fn user_reply(response: ApiResponse<UserResponse>) -> Result<Response<proto::User>, Status> {
+    match response.data {
+        Some(user) if response.success => Ok(Response::new(proto::User::from(user))),
+        _ => Err(error_status(response)),
+    }
+}
+
+fn error_status<T>(response: ApiResponse<T>) -> Status {
+    match response.cause {
+        Some(error) => status_from_domain_error(error),
+        None => Status::internal(response.error.unwrap_or_else(|| "Unexpected error".to_string())),
+    }
+}
+
+pub fn status_from_domain_error(error: DomainError) -> Status {
+    let code = match &error {
+        DomainError::UserNotFound
+        | DomainError::OutboxMessageNotFound
+        | DomainError::WebhookNotFound
+        | DomainError::WebhookDeliveryNotFound
+        | DomainError::ApiKeyNotFound
+        | DomainError::SessionNotFound => Code::NotFound,
+        DomainError::InvalidEmail(_) | DomainError::InvalidUserData(_) | DomainError::InvalidMfaCode => Code::InvalidArgument,
+        DomainError::UserAlreadyExists => Code::AlreadyExists,
+        // Клиент может перечитать пользователя и повторить запрос
+        DomainError::ConcurrentModification(_, _) => Code::Aborted,
+        DomainError::InvalidOperation(_) | DomainError::MfaNotEnrolled => Code::FailedPrecondition,
+        DomainError::AccountLocked(_) | DomainError::TooManyAttempts(_) => Code::ResourceExhausted,
+        DomainError::Unauthorized(_) => Code::Unauthenticated,
+        DomainError::Forbidden(_) => Code::PermissionDenied,
+        DomainError::ExternalServiceError(_) => Code::Unavailable,
+        DomainError::DatabaseError(_) => Code::Internal,
+    };
+    Status::new(code, error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use axum::{Extension, Router};
+    use tonic::transport::Channel;
+    use tonic::server::NamedService;
+    use crate::domain::{AuthLevel, Role, UserEventBus};
+    use crate::infrastructure::subscribe_user_change_feed;
+    use super::proto::user_service_client::UserServiceClient;
+
+    async fn connect(actor: Actor) -> UserServiceClient<Channel> {
+        let repository = InMemoryUserRepository::new();
+        let feed = UserChangeFeed::new();
+        let event_bus = UserEventBus::new();
+        subscribe_user_change_feed(&event_bus, feed.clone());
+        let service = GrpcUserService::new(UserApplicationService::new(repository).with_event_bus(event_bus), feed);
+
+        let app = Router::new()
+            .route_service(&format!("/{}/{{*rest}}", GrpcUserServer::NAME), GrpcUserServer::new(service))
+            .layer(Extension(actor));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        UserServiceClient::connect(format!("http://{}", address)).await.unwrap()
+    }
+
+    fn admin() -> Actor {
+        Actor::new(UserId::new(), Role::Admin).with_auth_level(AuthLevel::MultiFactor)
+    }
+
+    #[tokio::test]
+    async fn test_crud_and_list_over_grpc() {
+        let mut client = connect(admin()).await;
+
+        let created = client
+            .create_user(proto::CreateUserRequest { email: "alice@example.com".to_string(), name: "Alice".to_string() })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(created.roles, vec!["member"]);
+        assert!(created.created_at.is_some() && created.erased_at.is_none());
+
+        let updated = client
+            .update_user(proto::UpdateUserRequest { id: created.id.clone(), email: None, name: Some("Alice Smith".to_string()) })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(updated.name, "Alice Smith");
+
+        let page = client
+            .list_users(proto::ListUsersRequest { search: Some("SMITH".to_string()), ..Default::default() })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!((page.total, page.limit), (1, 20));
+
+        client.delete_user(proto::DeleteUserRequest { id: created.id.clone() }).await.unwrap();
+        let missing = client.get_user(proto::GetUserRequest { id: created.id }).await.unwrap_err();
+        assert_eq!(missing.code(), Code::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_domain_errors_map_to_status_codes() {
+        let mut client = connect(admin()).await;
+        let request = proto::CreateUserRequest { email: "bob@example.com".to_string(), name: "Bob".to_string() };
+        client.create_user(request.clone()).await.unwrap();
+
+        let duplicate = client.create_user(request).await.unwrap_err();
+        assert_eq!(duplicate.code(), Code::AlreadyExists);
+        let invalid = client.get_user(proto::GetUserRequest { id: "not-a-uuid".to_string() }).await.unwrap_err();
+        assert_eq!(invalid.code(), Code::InvalidArgument);
+
+        let mut member = connect(Actor::new(UserId::new(), Role::Member)).await;
+        let forbidden = member.list_users(proto::ListUsersRequest::default()).await.unwrap_err();
+        assert_eq!(forbidden.code(), Code::PermissionDenied);
+    }
+
+    #[tokio::test]
+    async fn test_watch_streams_filtered_changes() {
+        let mut client = connect(admin()).await;
+        let mut changes = client
+            .watch_users(proto::WatchUsersRequest { events: vec!["user.renamed".to_string()], user_ids: Vec::new() })
+            .await
+            .unwrap()
+            .into_inner();
+
+        let created = client
+            .create_user(proto::CreateUserRequest { email: "carol@example.com".to_string(), name: "Carol".to_string() })
+            .await
+            .unwrap()
+            .into_inner();
+        client
+            .update_user(proto::UpdateUserRequest { id: created.id.clone(), email: None, name: Some("Caroline".to_string()) })
+            .await
+            .unwrap();
+
+        let change = tokio::time::timeout(Duration::from_secs(1), changes.message()).await.unwrap().unwrap().unwrap();
+        assert_eq!((change.event.as_str(), change.user_id.as_str()), ("user.renamed", created.id.as_str()));
+
+        let unknown = client
+            .watch_users(proto::WatchUsersRequest { events: vec!["user.deleted_forever".to_string()], user_ids: Vec::new() })
+            .await
+            .unwrap_err();
+        assert_eq!(unknown.code(), Code::InvalidArgument);
+    }
+}
@@ -0,0 +1,29 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use crate::application::{AccountUnlockResponse, ApiResponse, LoginProtectionApplicationService};
+use crate::infrastructure::{InMemoryUserRepository, LoginAttemptStoreBackend};
+use super::content_negotiation::{Encoded, ResponseCodec};
+
+pub type LoginProtectionService = LoginProtectionApplicationService<InMemoryUserRepository, LoginAttemptStoreBackend>;
+
+#[utoipa::path(post, path = "/api/users/{id}/unlock", tag = "users", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID пользователя (UUID)")),
+    responses(
+        (status = 200, description = "Блокировка входа снята", body = ApiResponse<AccountUnlockResponse>),
+        (status = 404, description = "Пользователь не найден", body = ApiResponse<AccountUnlockResponse>),
+    ))]
+pub async fn unlock_user_handler(
+    State(login_protection_service): State<LoginProtectionService>,
+    ResponseCodec(codec): ResponseCodec,
+    Path(user_id): Path<String>,
+) -> impl IntoResponse {
+    let response = login_protection_service.unlock_account(user_id).await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::NOT_FOUND, Encoded(codec, response)).into_response(),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,10 @@
+use axum::{
+    extract::State,
+    http::header,
+    response::IntoResponse,
+};
+use crate::presentation::middleware::ApiVersionMetrics;
+
+pub async fn metrics_handler(State(metrics): State<ApiVersionMetrics>) -> impl IntoResponse {
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], metrics.render())
+}
\ No newline at end of file
@@ -0,0 +1,163 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::IntoResponse,
+};
+use crate::application::{
+    MfaApplicationService, MfaChallengeResponse, MfaConfirmRequest, MfaEnrollmentResponse, MfaQrQuery, MfaRecoveryCodesResponse,
+    MfaStatusResponse, MfaVerifyRequest, ApiResponse,
+};
+use crate::infrastructure::{
+    render_qr_png, render_qr_svg, AesGcmSecretCipher, InMemoryMfaRepository, InMemoryUserRepository, JwtTokenService,
+    Rfc6238TotpService, Sha256SecretHasher,
+};
+use super::content_negotiation::{Codec, Encoded, Payload, ResponseCodec};
+
+pub type MfaService = MfaApplicationService<
+    InMemoryUserRepository,
+    InMemoryMfaRepository,
+    AesGcmSecretCipher,
+    Rfc6238TotpService,
+    Sha256SecretHasher,
+    JwtTokenService,
+>;
+
+#[utoipa::path(get, path = "/api/users/{id}/mfa", tag = "mfa", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID пользователя (UUID)")),
+    responses(
+        (status = 200, description = "Состояние MFA", body = ApiResponse<MfaStatusResponse>),
+        (status = 400, description = "Неверный ID пользователя", body = ApiResponse<MfaStatusResponse>),
+    ))]
+pub async fn get_mfa_status_handler(
+    State(mfa_service): State<MfaService>,
+    ResponseCodec(codec): ResponseCodec,
+    Path(user_id): Path<String>,
+) -> impl IntoResponse {
+    let response = mfa_service.get_status(user_id).await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::BAD_REQUEST, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(post, path = "/api/users/{id}/mfa/enroll", tag = "mfa", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID пользователя (UUID)")),
+    responses(
+        (status = 201, description = "Секрет TOTP выпущен, MFA ждет подтверждения", body = ApiResponse<MfaEnrollmentResponse>),
+        (status = 400, description = "MFA уже подключена", body = ApiResponse<MfaEnrollmentResponse>),
+    ))]
+pub async fn enroll_mfa_handler(
+    State(mfa_service): State<MfaService>,
+    ResponseCodec(codec): ResponseCodec,
+    Path(user_id): Path<String>,
+) -> impl IntoResponse {
+    let response = mfa_service.enroll(user_id).await;
+    
+    match response.success {
+        true => (StatusCode::CREATED, Encoded(codec, response)).into_response(),
+        false => (StatusCode::BAD_REQUEST, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(get, path = "/api/users/{id}/mfa/qr", tag = "mfa", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID пользователя (UUID)"), MfaQrQuery),
+    responses(
+        (status = 200, description = "QR-код otpauth URI", content(
+            (Vec<u8> = "image/png"),
+            (String = "image/svg+xml"),
+        )),
+        (status = 400, description = "Нет ожидающей подтверждения MFA или неизвестный формат", body = ApiResponse<String>),
+    ))]
+pub async fn get_mfa_qr_code_handler(
+    State(mfa_service): State<MfaService>,
+    codec: Option<ResponseCodec>,
+    Path(user_id): Path<String>,
+    Query(query): Query<MfaQrQuery>,
+) -> impl IntoResponse {
+    let codec = codec.map_or(Codec::Json, |ResponseCodec(codec)| codec);
+    let response = mfa_service.get_provisioning_uri(user_id).await;
+    let Some(uri) = response.data else {
+        return (StatusCode::BAD_REQUEST, Encoded(codec, response)).into_response();
+    };
+    
+    let rendered = match query.format.as_deref().unwrap_or("png") {
+        "png" => render_qr_png(&uri).map(|png| ("image/png", png)),
+        "svg" => render_qr_svg(&uri).map(|svg| ("image/svg+xml", svg.into_bytes())),
+        other => {
+            let error = ApiResponse::<()>::error(format!("Unsupported QR code format: {}", other));
+            return (StatusCode::BAD_REQUEST, Encoded(codec, error)).into_response();
+        }
+    };
+    
+    match rendered {
+        Ok((content_type, body)) => (StatusCode::OK, [(CONTENT_TYPE, content_type)], body).into_response(),
+        Err(error) => {
+            let error = ApiResponse::<()>::error(error.to_string());
+            (StatusCode::INTERNAL_SERVER_ERROR, Encoded(codec, error)).into_response()
+        }
+    }
+}
+
+#[utoipa::path(post, path = "/api/users/{id}/mfa/confirm", tag = "mfa", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID пользователя (UUID)")),
+    request_body = MfaConfirmRequest,
+    responses(
+        (status = 200, description = "MFA подключена, коды восстановления показываются один раз", body = ApiResponse<MfaRecoveryCodesResponse>),
+        (status = 400, description = "Неверный код", body = ApiResponse<MfaRecoveryCodesResponse>),
+    ))]
+pub async fn confirm_mfa_handler(
+    State(mfa_service): State<MfaService>,
+    ResponseCodec(codec): ResponseCodec,
+    Path(user_id): Path<String>,
+    Payload(request): Payload<MfaConfirmRequest>,
+) -> impl IntoResponse {
+    let response = mfa_service.confirm(user_id, request).await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::BAD_REQUEST, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(post, path = "/api/users/{id}/mfa/challenge", tag = "mfa", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID пользователя (UUID)")),
+    request_body = MfaVerifyRequest,
+    responses(
+        (status = 200, description = "Токен с подтвержденным вторым фактором", body = ApiResponse<MfaChallengeResponse>),
+        (status = 429, description = "Слишком много неверных кодов, см. `Retry-After`"),
+    ))]
+pub async fn mfa_challenge_handler(
+    State(mfa_service): State<MfaService>,
+    ResponseCodec(codec): ResponseCodec,
+    Path(user_id): Path<String>,
+    Payload(request): Payload<MfaVerifyRequest>,
+) -> impl IntoResponse {
+    let response = mfa_service.challenge(user_id, request).await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::UNAUTHORIZED, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(delete, path = "/api/users/{id}/mfa", tag = "mfa", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID пользователя (UUID)")),
+    request_body = MfaVerifyRequest,
+    responses(
+        (status = 200, description = "MFA отключена", body = ApiResponse<String>),
+        (status = 429, description = "Слишком много неверных кодов, см. `Retry-After`"),
+    ))]
+pub async fn disable_mfa_handler(
+    State(mfa_service): State<MfaService>,
+    ResponseCodec(codec): ResponseCodec,
+    Path(user_id): Path<String>,
+    Payload(request): Payload<MfaVerifyRequest>,
+) -> impl IntoResponse {
+    let response = mfa_service.disable(user_id, request).await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::UNAUTHORIZED, Encoded(codec, response)).into_response(),
+    }
+}
\ No newline at end of file
@@ -1,3 +1,39 @@
 pub mod user_handlers;
+pub mod outbox_handlers;
+pub mod api_key_handlers;
+pub mod mfa_handlers;
+pub mod login_protection_handlers;
+pub mod session_handlers;
+pub mod oidc_handlers;
+pub mod scim_handlers;
+pub mod user_data_handlers;
+pub mod audit_handlers;
+pub mod webhook_handlers;
+pub mod user_change_handlers;
+pub mod websocket_handlers;
+pub mod graphql_schema;
+pub mod graphql_handlers;
+pub mod grpc_handlers;
+pub mod openapi_handlers;
+pub mod metrics_handlers;
+pub mod content_negotiation;
 
-pub use user_handlers::*;
\ No newline at end of file
+pub use user_handlers::*;
+pub use outbox_handlers::*;
+pub use api_key_handlers::*;
+pub use mfa_handlers::*;
+pub use login_protection_handlers::*;
+pub use session_handlers::*;
+pub use oidc_handlers::*;
+pub use scim_handlers::*;
+pub use user_data_handlers::*;
+pub use audit_handlers::*;
+pub use webhook_handlers::*;
+pub use user_change_handlers::*;
+pub use websocket_handlers::*;
+pub use graphql_schema::*;
+pub use graphql_handlers::*;
+pub use grpc_handlers::*;
+pub use openapi_handlers::*;
+pub use metrics_handlers::*;
+pub use content_negotiation::*;
\ No newline at end of file
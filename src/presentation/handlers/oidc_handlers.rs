@@ -0,0 +1,42 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect},
+};
+use crate::application::{OidcApplicationService, OidcCallbackQuery};
+use crate::infrastructure::{
+    InMemoryExternalIdentityRepository, InMemoryOidcLoginStore, InMemoryUserRepository, JwtTokenService, OidcClient,
+};
+
+pub type OidcService = OidcApplicationService<
+    InMemoryUserRepository,
+    InMemoryExternalIdentityRepository,
+    InMemoryOidcLoginStore,
+    JwtTokenService,
+    OidcClient,
+>;
+
+pub async fn oidc_login_handler(
+    State(oidc_service): State<OidcService>,
+    Path(provider): Path<String>,
+) -> impl IntoResponse {
+    let response = oidc_service.begin_login(provider).await;
+    
+    match &response.data {
+        Some(authorization) => Redirect::to(&authorization.authorization_url).into_response(),
+        None => (StatusCode::NOT_FOUND, response).into_response(),
+    }
+}
+
+pub async fn oidc_callback_handler(
+    State(oidc_service): State<OidcService>,
+    Path(provider): Path<String>,
+    Query(query): Query<OidcCallbackQuery>,
+) -> impl IntoResponse {
+    let response = oidc_service.complete_login(provider, query).await;
+    
+    match response.success {
+        true => (StatusCode::OK, response).into_response(),
+        false => (StatusCode::UNAUTHORIZED, response).into_response(),
+    }
+}
\ No newline at end of file
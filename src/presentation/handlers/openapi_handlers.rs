@@ -0,0 +1,354 @@
+use axum::{
+    http::{header::{CACHE_CONTROL, CONTENT_TYPE}, StatusCode},
+    response::{Html, IntoResponse},
+    Json,
+};
+use std::collections::BTreeMap;
+use utoipa::openapi::path::{Operation, ParameterBuilder, ParameterIn, PathItem};
+use utoipa::openapi::schema::{ObjectBuilder, Schema, Type};
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::openapi::{Content, Ref, RefOr, Required, Response};
+use utoipa::{Modify, OpenApi};
+use crate::presentation::middleware::{ProblemDetails, ACCEPT_VERSION_HEADER};
+use super::content_negotiation::Codec;
+use super::{
+    api_key_handlers, audit_handlers, login_protection_handlers, mfa_handlers, session_handlers, user_data_handlers,
+    user_handlers, webhook_handlers,
+};
+
+// Описание REST API пользователей. Копия лежит в openapi.json в корне
+// репозитория, тест ниже не дает им разойтись.
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Rust Clean Architecture API",
+        description = "REST API пользователей. Защищенные маршруты принимают `Authorization: Bearer <JWT или API-ключ>` или cookie сессии.\n\n\
+            Каждый маршрут доступен в двух версиях: `/api/v1/...` отвечает в конверте `ApiResponse`, \
+            `/api/v2/...` - ресурсом без конверта, ошибки v2 - `application/problem+json` (RFC 9457). \
+            Для `/api/...` версию выбирает заголовок `Accept-Version: 1` или `2`, без заголовка - v1.",
+        license(name = "MIT"),
+    ),
+    paths(
+        user_handlers::health_handler,
+        user_handlers::create_user_handler,
+        user_handlers::get_user_handler,
+        user_handlers::get_user_by_email_handler,
+        user_handlers::list_users_handler,
+        user_handlers::update_user_handler,
+        user_handlers::change_user_roles_handler,
+        user_handlers::delete_user_handler,
+        login_protection_handlers::unlock_user_handler,
+        api_key_handlers::create_api_key_handler,
+        api_key_handlers::list_api_keys_handler,
+        api_key_handlers::revoke_api_key_handler,
+        mfa_handlers::get_mfa_status_handler,
+        mfa_handlers::enroll_mfa_handler,
+        mfa_handlers::get_mfa_qr_code_handler,
+        mfa_handlers::confirm_mfa_handler,
+        mfa_handlers::mfa_challenge_handler,
+        mfa_handlers::disable_mfa_handler,
+        session_handlers::create_session_handler,
+        session_handlers::end_current_session_handler,
+        session_handlers::list_sessions_handler,
+        session_handlers::revoke_all_sessions_handler,
+        session_handlers::revoke_session_handler,
+        audit_handlers::list_user_audit_events_handler,
+        audit_handlers::verify_audit_chain_handler,
+        user_data_handlers::export_user_data_handler,
+        user_data_handlers::erase_user_handler,
+        webhook_handlers::create_webhook_handler,
+        webhook_handlers::list_webhooks_handler,
+        webhook_handlers::get_webhook_handler,
+        webhook_handlers::delete_webhook_handler,
+        webhook_handlers::enable_webhook_handler,
+        webhook_handlers::list_webhook_deliveries_handler,
+        webhook_handlers::redeliver_webhook_handler,
+    ),
+    components(schemas(ProblemDetails)),
+    modifiers(&BearerSecurity, &ContentNegotiation, &ApiVersions),
+    tags(
+        (name = "users", description = "Пользователи"),
+        (name = "api-keys", description = "API-ключи пользователей"),
+        (name = "mfa", description = "Двухфакторная аутентификация (TOTP)"),
+        (name = "sessions", description = "Cookie-сессии"),
+        (name = "audit", description = "Журнал аудита"),
+        (name = "user-data", description = "Выгрузка и стирание персональных данных"),
+        (name = "webhooks", description = "Исходящие webhooks"),
+        (name = "health", description = "Проверка состояния"),
+    )
+)]
+pub struct ApiDoc;
+
+struct BearerSecurity;
+
+impl Modify for BearerSecurity {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).bearer_format("JWT").build()),
+        );
+
+        // Ответы auth_middleware и authorization_middleware общие для всех защищенных маршрутов
+        let operations = openapi.paths.paths.values_mut().flat_map(operations);
+        for operation in operations.filter(|operation| operation.security.is_some()) {
+            let responses = &mut operation.responses.responses;
+            responses.insert("401".to_string(), RefOr::T(Response::new("Требуется аутентификация")));
+            responses.insert("403".to_string(), RefOr::T(Response::new("Недостаточно прав")));
+        }
+    }
+}
+
+struct ContentNegotiation;
+
+impl Modify for ContentNegotiation {
+    // Схемы одинаковы для всех форматов, меняется только кодирование
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let json = Codec::Json.media_type();
+        let operations = openapi.paths.paths.values_mut().flat_map(operations);
+        for operation in operations {
+            if let Some(body) = operation.request_body.as_mut()
+                && let Some(content) = body.content.get(json).cloned()
+            {
+                for codec in &Codec::ALL[1..] {
+                    body.content.insert(codec.media_type().to_string(), content.clone());
+                }
+                let response = Response::new("Content-Type не поддерживается");
+                operation.responses.responses.insert("415".to_string(), RefOr::T(response));
+            }
+
+            // Обработчики файлов (ZIP, QR-код) отдают ошибки в формате из Accept, но не отвечают 406
+            let mut negotiated = false;
+            for (status, response) in operation.responses.responses.iter_mut() {
+                if let RefOr::T(response) = response
+                    && let Some(content) = response.content.get(json).cloned()
+                {
+                    for codec in &Codec::ALL[1..] {
+                        response.content.insert(codec.media_type().to_string(), content.clone());
+                    }
+                    negotiated |= status.starts_with('2');
+                }
+            }
+            if negotiated {
+                let response = Response::new("Ни один формат из Accept не поддерживается");
+                operation.responses.responses.insert("406".to_string(), RefOr::T(response));
+            }
+        }
+    }
+}
+
+// Маршрут /api/... описывается трижды: /api/v1 и /api/v2 с форматом своей версии
+// и /api с заголовком Accept-Version и ответами v1 (версия по умолчанию)
+struct ApiVersions;
+
+impl Modify for ApiVersions {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let schemas = openapi.components.as_ref().map(|components| components.schemas.clone()).unwrap_or_default();
+
+        for (path, mut item) in std::mem::take(&mut openapi.paths.paths) {
+            let Some(route) = path.strip_prefix("/api/") else {
+                openapi.paths.paths.insert(path, item);
+                continue;
+            };
+
+            let mut v1 = item.clone();
+            operations(&mut v1).for_each(|operation| suffix_operation_id(operation, "v1"));
+            let mut v2 = item.clone();
+            for operation in operations(&mut v2) {
+                suffix_operation_id(operation, "v2");
+                unwrap_responses(operation, &schemas);
+            }
+            for operation in operations(&mut item) {
+                operation.parameters.get_or_insert_with(Vec::new).push(accept_version_parameter());
+            }
+
+            openapi.paths.paths.insert(format!("/api/v1/{}", route), v1);
+            openapi.paths.paths.insert(format!("/api/v2/{}", route), v2);
+            openapi.paths.paths.insert(path, item);
+        }
+    }
+}
+
+fn operations(item: &mut PathItem) -> impl Iterator<Item = &mut Operation> {
+    [&mut item.get, &mut item.post, &mut item.put, &mut item.delete].into_iter().flatten()
+}
+
+fn suffix_operation_id(operation: &mut Operation, version: &str) {
+    if let Some(operation_id) = &mut operation.operation_id {
+        operation_id.push('_');
+        operation_id.push_str(version);
+    }
+}
+
+fn accept_version_parameter() -> utoipa::openapi::path::Parameter {
+    ParameterBuilder::new()
+        .name(ACCEPT_VERSION_HEADER.as_str())
+        .parameter_in(ParameterIn::Header)
+        .required(Required::False)
+        .description(Some("Версия API, без заголовка - 1. Ответы v2 такие же, как у /api/v2"))
+        .schema(Some(ObjectBuilder::new().schema_type(Type::String).enum_values(Some(["1", "2"]))))
+        .build()
+}
+
+// v2: успешный ответ - поле `data` конверта, ошибка - problem+json
+fn unwrap_responses(operation: &mut Operation, schemas: &BTreeMap<String, RefOr<Schema>>) {
+    for (status, response) in operation.responses.responses.iter_mut() {
+        let RefOr::T(response) = response else {
+            continue;
+        };
+        if !status.starts_with('2') {
+            response.content.clear();
+            response.content.insert(
+                "application/problem+json".to_string(),
+                Content::new(Some(Ref::from_schema_name("ProblemDetails"))),
+            );
+            continue;
+        }
+
+        response.content.retain(|_, content| {
+            let Some(RefOr::Ref(reference)) = &content.schema else {
+                return true;
+            };
+            let Some(name) = reference.ref_location.strip_prefix("#/components/schemas/") else {
+                return true;
+            };
+            if !name.starts_with("ApiResponse_") {
+                return true;
+            }
+            let data = match schemas.get(name) {
+                Some(RefOr::T(Schema::Object(envelope))) => envelope.properties.get("data").cloned(),
+                _ => None,
+            };
+            content.schema = data;
+            content.schema.is_some()
+        });
+    }
+}
+
+pub async fn openapi_handler() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+pub async fn api_docs_handler() -> Html<&'static str> {
+    Html(REDOC_PAGE)
+}
+
+// Redoc отдается с того же origin, страница не загружает скрипты со стороннего CDN.
+// Файл ставит scripts/fetch-redoc.sh: версия закреплена, архив сверяется
+// с хешем целостности из реестра npm
+const REDOC_BUNDLE_PATH: &str = "assets/redoc.standalone.js";
+
+pub async fn redoc_bundle_handler() -> impl IntoResponse {
+    match tokio::fs::read(REDOC_BUNDLE_PATH).await {
+        Ok(bundle) => (
+            [(CONTENT_TYPE, "text/javascript; charset=utf-8"), (CACHE_CONTROL, "public, max-age=86400")],
+            bundle,
+        ).into_response(),
+        Err(error) => {
+            tracing::warn!(error = %error, path = REDOC_BUNDLE_PATH, "Redoc bundle is not installed, run scripts/fetch-redoc.sh");
+            (StatusCode::NOT_FOUND, "Redoc bundle is not installed").into_response()
+        }
+    }
+}
+
+const REDOC_PAGE: &str = r#"<!DOCTYPE html>
+<html>
+  <head>
+    <title>Rust Clean Architecture API</title>
+    <meta charset="utf-8"/>
+    <meta name="viewport" content="width=device-width, initial-scale=1">
+  </head>
+  <body>
+    <redoc spec-url="/openapi.json"></redoc>
+    <script src="/docs/redoc.standalone.js"></script>
+  </body>
+</html>
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPEC_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/openapi.json");
+
+    // После изменения DTO или маршрутов: `UPDATE_OPENAPI=1 cargo test` и закоммитить openapi.json
+    #[test]
+    fn test_committed_spec_matches_code() {
+        let generated = ApiDoc::openapi().to_pretty_json().unwrap();
+        if std::env::var_os("UPDATE_OPENAPI").is_some() {
+            std::fs::write(SPEC_PATH, generated + "\n").unwrap();
+            return;
+        }
+
+        let committed = std::fs::read_to_string(SPEC_PATH).expect("openapi.json is missing");
+        let committed: serde_json::Value = serde_json::from_str(&committed).unwrap();
+        let generated: serde_json::Value = serde_json::from_str(&generated).unwrap();
+        assert!(
+            committed == generated,
+            "openapi.json is out of date: run `UPDATE_OPENAPI=1 cargo test` and commit the result"
+        );
+    }
+
+    #[test]
+    fn test_spec_documents_user_routes() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+
+        assert_eq!(spec["openapi"], "3.1.0");
+        assert!(spec["paths"]["/api/users/email"]["post"].is_object());
+        assert_eq!(spec["paths"]["/api/users/{id}"]["get"]["responses"]["401"]["description"], "Требуется аутентификация");
+        assert!(spec["paths"]["/api/users"]["post"]["security"].is_null());
+        assert!(spec["components"]["schemas"]["ApiResponse_UserResponse"]["properties"].get("cause").is_none());
+
+        let create = &spec["paths"]["/api/users"]["post"];
+        assert!(create["requestBody"]["content"]["application/msgpack"]["schema"].is_object());
+        assert!(create["responses"]["201"]["content"]["application/cbor"]["schema"].is_object());
+        assert!(create["responses"]["415"].is_object() && create["responses"]["406"].is_object());
+        assert!(spec["paths"]["/health"]["get"]["responses"].get("406").is_none());
+    }
+
+    #[test]
+    fn test_spec_documents_account_routes() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let paths = &spec["paths"];
+
+        assert!(paths["/api/users/{id}/api-keys"]["post"].is_object());
+        assert!(paths["/api/users/{id}/mfa/challenge"]["post"].is_object());
+        assert!(paths["/api/users/{id}/sessions/{session_id}"]["delete"].is_object());
+        assert!(paths["/api/users/{id}/audit"]["get"].is_object());
+        assert!(paths["/api/users/{id}/data-export"]["get"].is_object());
+        assert!(paths["/api/users/{id}/erase"]["post"].is_object());
+        assert!(paths["/api/webhooks/{id}/deliveries/{delivery_id}/redeliver"]["post"].is_object());
+        assert!(paths["/api/users/{id}/api-keys"]["post"]["requestBody"]["content"]["application/cbor"].is_object());
+        assert!(paths["/api/users/{id}/data-export"]["get"]["responses"].get("406").is_none());
+        // Секрет сессии уходит только в cookie
+        assert!(spec["components"]["schemas"]["CreatedSessionResponse"]["properties"].get("session_token").is_none());
+    }
+
+    #[test]
+    fn test_spec_documents_api_versions() {
+        let spec = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let paths = &spec["paths"];
+
+        let v1 = &paths["/api/v1/users/{id}"]["get"]["responses"];
+        assert_eq!(v1["200"]["content"]["application/json"]["schema"]["$ref"], "#/components/schemas/ApiResponse_UserResponse");
+
+        let v2 = &paths["/api/v2/users/{id}"]["get"];
+        let bare = &v2["responses"]["200"]["content"]["application/json"]["schema"];
+        assert!(bare["properties"]["email"].is_object() && bare["properties"].get("success").is_none());
+        assert_eq!(v2["responses"]["404"]["content"]["application/problem+json"]["schema"]["$ref"], "#/components/schemas/ProblemDetails");
+        assert!(v2["responses"]["401"]["content"]["application/problem+json"].is_object());
+        assert_eq!(v2["operationId"], "get_user_handler_v2");
+        assert!(spec["components"]["schemas"]["ProblemDetails"]["properties"]["type"].is_object());
+
+        let parameters = paths["/api/users/{id}"]["get"]["parameters"].as_array().unwrap();
+        assert!(parameters.iter().any(|parameter| parameter["name"] == "accept-version" && parameter["in"] == "header"));
+        assert!(paths["/api/v2/webhooks"]["post"].is_object());
+        assert!(paths.get("/api/v1/health").is_none());
+    }
+
+    #[test]
+    fn test_docs_page_loads_no_third_party_scripts() {
+        assert!(REDOC_PAGE.contains(r#"<script src="/docs/redoc.standalone.js">"#));
+        assert!(!REDOC_PAGE.contains("http"));
+    }
+}
@@ -0,0 +1,43 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use crate::application::{OutboxAdminService, OutboxListQuery};
+use crate::infrastructure::InMemoryUserRepository;
+
+pub async fn list_outbox_messages_handler(
+    State(outbox_service): State<OutboxAdminService<InMemoryUserRepository>>,
+    Query(query): Query<OutboxListQuery>,
+) -> impl IntoResponse {
+    let response = outbox_service.list_messages(query).await;
+    
+    match response.success {
+        true => (StatusCode::OK, response).into_response(),
+        false => (StatusCode::BAD_REQUEST, response).into_response(),
+    }
+}
+
+pub async fn get_outbox_message_handler(
+    State(outbox_service): State<OutboxAdminService<InMemoryUserRepository>>,
+    Path(message_id): Path<String>,
+) -> impl IntoResponse {
+    let response = outbox_service.get_message(message_id).await;
+    
+    match response.success {
+        true => (StatusCode::OK, response).into_response(),
+        false => (StatusCode::NOT_FOUND, response).into_response(),
+    }
+}
+
+pub async fn requeue_outbox_message_handler(
+    State(outbox_service): State<OutboxAdminService<InMemoryUserRepository>>,
+    Path(message_id): Path<String>,
+) -> impl IntoResponse {
+    let response = outbox_service.requeue_message(message_id).await;
+    
+    match response.success {
+        true => (StatusCode::OK, response).into_response(),
+        false => (StatusCode::BAD_REQUEST, response).into_response(),
+    }
+}
\ No newline at end of file
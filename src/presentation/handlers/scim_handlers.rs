@@ -0,0 +1,129 @@
+use axum::{
+    extract::{rejection::{JsonRejection, QueryRejection}, Path, Query, State, Json},
+    http::{header::LOCATION, HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
+use crate::application::{ScimApplicationService, ScimError, ScimListQuery, ScimPatchRequest, ScimUser};
+use crate::infrastructure::InMemoryUserRepository;
+use crate::presentation::middleware::{scim_error_response, scim_response};
+
+pub type ScimService = ScimApplicationService<InMemoryUserRepository>;
+
+// Ошибки разбора тела и параметров тоже возвращаются в формате SCIM
+fn invalid_syntax(detail: String) -> Response {
+    scim_error_response(ScimError::new(400, Some("invalidSyntax"), detail))
+}
+
+fn not_found(detail: String) -> Response {
+    scim_error_response(ScimError::new(404, None, detail))
+}
+
+fn scim_result<T: serde::Serialize>(status: StatusCode, result: Result<T, ScimError>) -> Response {
+    match result {
+        Ok(body) => scim_response(status, body),
+        Err(error) => scim_error_response(error),
+    }
+}
+
+pub async fn create_scim_user_handler(
+    State(scim_service): State<ScimService>,
+    body: Result<Json<ScimUser>, JsonRejection>,
+) -> impl IntoResponse {
+    let Json(resource) = match body {
+        Ok(body) => body,
+        Err(rejection) => return invalid_syntax(rejection.body_text()),
+    };
+    
+    match scim_service.create_user(resource).await {
+        Ok(user) => {
+            let location = user.meta.as_ref().and_then(|meta| HeaderValue::from_str(&meta.location).ok());
+            let mut response = scim_response(StatusCode::CREATED, user);
+            if let Some(location) = location {
+                response.headers_mut().insert(LOCATION, location);
+            }
+            response
+        }
+        Err(error) => scim_error_response(error),
+    }
+}
+
+pub async fn get_scim_user_handler(
+    State(scim_service): State<ScimService>,
+    Path(user_id): Path<String>,
+) -> impl IntoResponse {
+    scim_result(StatusCode::OK, scim_service.get_user(user_id).await)
+}
+
+pub async fn replace_scim_user_handler(
+    State(scim_service): State<ScimService>,
+    Path(user_id): Path<String>,
+    body: Result<Json<ScimUser>, JsonRejection>,
+) -> impl IntoResponse {
+    match body {
+        Ok(Json(resource)) => scim_result(StatusCode::OK, scim_service.replace_user(user_id, resource).await),
+        Err(rejection) => invalid_syntax(rejection.body_text()),
+    }
+}
+
+pub async fn patch_scim_user_handler(
+    State(scim_service): State<ScimService>,
+    Path(user_id): Path<String>,
+    body: Result<Json<ScimPatchRequest>, JsonRejection>,
+) -> impl IntoResponse {
+    match body {
+        Ok(Json(request)) => scim_result(StatusCode::OK, scim_service.patch_user(user_id, request).await),
+        Err(rejection) => invalid_syntax(rejection.body_text()),
+    }
+}
+
+pub async fn delete_scim_user_handler(
+    State(scim_service): State<ScimService>,
+    Path(user_id): Path<String>,
+) -> impl IntoResponse {
+    match scim_service.delete_user(user_id).await {
+        Ok(()) => StatusCode::NO_CONTENT.into_response(),
+        Err(error) => scim_error_response(error),
+    }
+}
+
+pub async fn list_scim_users_handler(
+    State(scim_service): State<ScimService>,
+    query: Result<Query<ScimListQuery>, QueryRejection>,
+) -> impl IntoResponse {
+    match query {
+        Ok(Query(query)) => scim_result(StatusCode::OK, scim_service.list_users(query).await),
+        Err(rejection) => invalid_syntax(rejection.body_text()),
+    }
+}
+
+pub async fn service_provider_config_handler(State(scim_service): State<ScimService>) -> impl IntoResponse {
+    scim_response(StatusCode::OK, scim_service.service_provider_config())
+}
+
+pub async fn resource_types_handler(State(scim_service): State<ScimService>) -> impl IntoResponse {
+    scim_response(StatusCode::OK, scim_service.resource_types())
+}
+
+pub async fn resource_type_handler(
+    State(scim_service): State<ScimService>,
+    Path(name): Path<String>,
+) -> impl IntoResponse {
+    match scim_service.resource_type(&name) {
+        Some(resource_type) => scim_response(StatusCode::OK, resource_type),
+        None => not_found(format!("Resource type {} not found", name)),
+    }
+}
+
+pub async fn schemas_handler(State(scim_service): State<ScimService>) -> impl IntoResponse {
+    scim_response(StatusCode::OK, scim_service.schemas())
+}
+
+pub async fn schema_handler(
+    State(scim_service): State<ScimService>,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    match scim_service.schema(&id) {
+        Some(schema) => scim_response(StatusCode::OK, schema),
+        None => not_found(format!("Schema {} not found", id)),
+    }
+}
\ No newline at end of file
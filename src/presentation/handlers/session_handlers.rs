@@ -0,0 +1,130 @@
+use std::net::SocketAddr;
+use axum::{
+    extract::{ConnectInfo, Path, State},
+    http::{header::{SET_COOKIE, USER_AGENT}, HeaderMap, StatusCode},
+    response::{AppendHeaders, IntoResponse},
+    Extension,
+};
+use crate::application::{ApiResponse, CreatedSessionResponse, SessionApplicationService, SessionResponse, SessionsRevokedResponse};
+use crate::domain::{Actor, SessionMetadata};
+use crate::infrastructure::{InMemoryUserRepository, SessionStoreBackend, Sha256SecretHasher};
+use crate::presentation::middleware::SessionCookieConfig;
+use super::content_negotiation::{Encoded, ResponseCodec};
+
+pub type SessionService = SessionApplicationService<InMemoryUserRepository, SessionStoreBackend, Sha256SecretHasher>;
+
+#[derive(Clone)]
+pub struct SessionState {
+    pub session_service: SessionService,
+    pub cookie_config: SessionCookieConfig,
+}
+
+#[utoipa::path(post, path = "/api/sessions", tag = "sessions", security(("bearer" = [])),
+    responses(
+        (status = 201, description = "Сессия создана, ее cookie и CSRF-cookie выставлены в `Set-Cookie`", body = ApiResponse<CreatedSessionResponse>),
+        (status = 403, description = "Сессию нельзя создать по API-ключу", body = ApiResponse<CreatedSessionResponse>),
+    ))]
+pub async fn create_session_handler(
+    State(state): State<SessionState>,
+    ResponseCodec(codec): ResponseCodec,
+    Extension(actor): Extension<Actor>,
+    connect_info: Option<Extension<ConnectInfo<SocketAddr>>>,
+    headers: HeaderMap,
+) -> impl IntoResponse {
+    let metadata = SessionMetadata {
+        user_agent: headers.get(USER_AGENT).and_then(|value| value.to_str().ok()).map(str::to_string),
+        ip_address: connect_info.map(|Extension(ConnectInfo(address))| address.ip()),
+    };
+    let response = state.session_service.create_session(&actor, metadata).await;
+    
+    match &response.data {
+        Some(created) => {
+            let [session_cookie, csrf_cookie] = state.cookie_config
+                .session_cookies(&created.session_token, &created.csrf_token);
+            let cookies = AppendHeaders([(SET_COOKIE, session_cookie), (SET_COOKIE, csrf_cookie)]);
+            (StatusCode::CREATED, cookies, Encoded(codec, response)).into_response()
+        }
+        None => (StatusCode::FORBIDDEN, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(delete, path = "/api/sessions/current", tag = "sessions", security(("bearer" = [])),
+    responses(
+        (status = 200, description = "Сессия завершена, cookie очищены", body = ApiResponse<SessionsRevokedResponse>),
+        (status = 400, description = "Запрос сделан не из сессии", body = ApiResponse<SessionsRevokedResponse>),
+    ))]
+pub async fn end_current_session_handler(
+    State(state): State<SessionState>,
+    ResponseCodec(codec): ResponseCodec,
+    Extension(actor): Extension<Actor>,
+) -> impl IntoResponse {
+    let response = state.session_service.end_current_session(&actor).await;
+    let [session_cookie, csrf_cookie] = state.cookie_config.cleared_cookies();
+    let cookies = AppendHeaders([(SET_COOKIE, session_cookie), (SET_COOKIE, csrf_cookie)]);
+    
+    match response.success {
+        true => (StatusCode::OK, cookies, Encoded(codec, response)).into_response(),
+        false => (StatusCode::BAD_REQUEST, cookies, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(get, path = "/api/users/{id}/sessions", tag = "sessions", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID пользователя (UUID)")),
+    responses(
+        (status = 200, description = "Активные сессии пользователя", body = ApiResponse<Vec<SessionResponse>>),
+        (status = 400, description = "Неверный ID пользователя", body = ApiResponse<Vec<SessionResponse>>),
+    ))]
+pub async fn list_sessions_handler(
+    State(state): State<SessionState>,
+    ResponseCodec(codec): ResponseCodec,
+    Extension(actor): Extension<Actor>,
+    Path(user_id): Path<String>,
+) -> impl IntoResponse {
+    let response = state.session_service.list_sessions(&actor, user_id).await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::BAD_REQUEST, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(delete, path = "/api/users/{id}/sessions", tag = "sessions", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID пользователя (UUID)")),
+    responses(
+        (status = 200, description = "Все сессии пользователя завершены", body = ApiResponse<SessionsRevokedResponse>),
+        (status = 404, description = "Пользователь не найден", body = ApiResponse<SessionsRevokedResponse>),
+    ))]
+pub async fn revoke_all_sessions_handler(
+    State(state): State<SessionState>,
+    ResponseCodec(codec): ResponseCodec,
+    Path(user_id): Path<String>,
+) -> impl IntoResponse {
+    let response = state.session_service.revoke_all_sessions(user_id).await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::NOT_FOUND, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(delete, path = "/api/users/{id}/sessions/{session_id}", tag = "sessions", security(("bearer" = [])),
+    params(
+        ("id" = String, Path, description = "ID пользователя (UUID)"),
+        ("session_id" = String, Path, description = "ID сессии (UUID)"),
+    ),
+    responses(
+        (status = 200, description = "Сессия завершена", body = ApiResponse<SessionsRevokedResponse>),
+        (status = 404, description = "Сессия не найдена", body = ApiResponse<SessionsRevokedResponse>),
+    ))]
+pub async fn revoke_session_handler(
+    State(state): State<SessionState>,
+    ResponseCodec(codec): ResponseCodec,
+    Path((user_id, session_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+    let response = state.session_service.revoke_session(user_id, session_id).await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::NOT_FOUND, Encoded(codec, response)).into_response(),
+    }
+}
\ No newline at end of file
@@ -0,0 +1,150 @@
+use std::convert::Infallible;
+use std::future::ready;
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, StatusCode},
+    response::{sse::{Event, KeepAlive, Sse}, IntoResponse, Response},
+};
+use futures_util::{stream, StreamExt};
+use tokio::sync::broadcast::error::RecvError;
+use crate::application::{ApiResponse, UserChangesQuery};
+use crate::domain::USER_EVENT_NAMES;
+use crate::infrastructure::UserChangeFeed;
+
+const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+pub async fn user_changes_handler(
+    State(feed): State<UserChangeFeed>,
+    headers: HeaderMap,
+    Query(query): Query<UserChangesQuery>,
+) -> Response {
+    let filter = match parse_event_filter(query.events.as_deref()) {
+        Ok(filter) => filter,
+        Err(error) => return (StatusCode::BAD_REQUEST, ApiResponse::<()>::error(error)).into_response(),
+    };
+    
+    // Нечитаемый Last-Event-ID считаем неизвестным: клиент получит reset
+    let last_event_id = headers
+        .get(LAST_EVENT_ID_HEADER)
+        .map(|value| value.to_str().ok().and_then(|value| value.trim().parse().ok()).unwrap_or(u64::MAX));
+    let subscription = feed.subscribe(last_event_id);
+    
+    // Пропущенное уже вытеснено из буфера - клиенту нужно перечитать состояние
+    let reset = subscription.truncated.then(|| Event::default().event("reset").data("{}"));
+    let live = stream::unfold(subscription.receiver, |mut receiver| async move {
+        match receiver.recv().await {
+            Ok(change) => Some((change, receiver)),
+            // Отставший клиент отключается и догоняет по Last-Event-ID
+            Err(RecvError::Lagged(_)) | Err(RecvError::Closed) => None,
+        }
+    });
+    let changes = stream::iter(subscription.replay)
+        .chain(live)
+        .filter(move |change| ready(filter.is_empty() || filter.contains(&change.event)))
+        .map(|change| Event::default().id(change.id.to_string()).event(change.event).data(change.data));
+    let events = stream::iter(reset).chain(changes).map(Ok::<_, Infallible>);
+    
+    Sse::new(events).keep_alive(KeepAlive::default()).into_response()
+}
+
+fn parse_event_filter(events: Option<&str>) -> Result<Vec<&'static str>, String> {
+    let Some(events) = events else {
+        return Ok(Vec::new());
+    };
+    
+    events
+        .split(',')
+        .map(str::trim)
+        .filter(|event| !event.is_empty())
+        .map(|event| {
+            USER_EVENT_NAMES
+                .iter()
+                .find(|name| **name == event)
+                .copied()
+                .ok_or_else(|| format!("Unknown event type: {}", event))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use axum::body::{Body, BodyDataStream};
+    use axum::extract::Request;
+    use axum::routing::get;
+    use axum::Router;
+    use tower::ServiceExt;
+    use crate::domain::{Email, User};
+
+    fn app(feed: UserChangeFeed) -> Router {
+        Router::new().route("/api/users/changes", get(user_changes_handler)).with_state(feed)
+    }
+
+    fn renamed_user(renames: usize) -> User {
+        let mut user = User::new(Email::new("alice@example.com".to_string()).unwrap(), "Alice".to_string()).unwrap();
+        for index in 0..renames {
+            user.update_name(format!("Alice {}", index)).unwrap();
+        }
+        user
+    }
+
+    async fn next_frame(body: &mut BodyDataStream) -> String {
+        let frame = tokio::time::timeout(Duration::from_secs(1), body.next()).await.unwrap().unwrap().unwrap();
+        String::from_utf8(frame.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_resume_with_event_filter() {
+        let feed = UserChangeFeed::new();
+        for event in renamed_user(2).take_events() {
+            feed.publish(&event);
+        }
+
+        let request = Request::builder()
+            .uri("/api/users/changes?events=user.renamed")
+            .header(LAST_EVENT_ID_HEADER, "1")
+            .body(Body::empty())
+            .unwrap();
+        let response = app(feed.clone()).oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "text/event-stream");
+        let mut body = response.into_body().into_data_stream();
+
+        assert!(next_frame(&mut body).await.starts_with("id: 2\nevent: user.renamed\n"));
+        assert!(next_frame(&mut body).await.starts_with("id: 3\n"));
+
+        // Создание другого пользователя не проходит фильтр
+        let mut other = renamed_user(1);
+        for event in other.take_events() {
+            feed.publish(&event);
+        }
+        assert!(next_frame(&mut body).await.starts_with("id: 5\nevent: user.renamed\n"));
+    }
+
+    #[tokio::test]
+    async fn test_reset_when_history_is_gone() {
+        let feed = UserChangeFeed::with_capacity(1);
+        for event in renamed_user(2).take_events() {
+            feed.publish(&event);
+        }
+
+        let request = Request::builder()
+            .uri("/api/users/changes")
+            .header(LAST_EVENT_ID_HEADER, "1")
+            .body(Body::empty())
+            .unwrap();
+        let mut body = app(feed).oneshot(request).await.unwrap().into_body().into_data_stream();
+
+        assert!(next_frame(&mut body).await.starts_with("event: reset\n"));
+        assert!(next_frame(&mut body).await.starts_with("id: 3\n"));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_event_type_is_rejected() {
+        let request = Request::builder().uri("/api/users/changes?events=user.updated").body(Body::empty()).unwrap();
+        let response = app(UserChangeFeed::new()).oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
\ No newline at end of file
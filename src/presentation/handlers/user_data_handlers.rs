@@ -0,0 +1,92 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::{header::{CACHE_CONTROL, CONTENT_DISPOSITION, CONTENT_TYPE}, StatusCode},
+    response::IntoResponse,
+    Extension,
+};
+use crate::application::{ApiResponse, ErasureReceiptResponse, FieldsetQuery, UserDataApplicationService, UserFieldset};
+use crate::domain::{Actor, DomainError};
+use crate::infrastructure::{
+    write_zip_archive, AuditStoreBackend, InMemoryApiKeyRepository, InMemoryErasureReceiptRepository, InMemoryExternalIdentityRepository,
+    InMemoryMfaRepository, InMemoryUserRepository, SessionStoreBackend,
+};
+use super::content_negotiation::{Codec, Encoded, ResponseCodec};
+
+pub type UserDataService = UserDataApplicationService<
+    InMemoryUserRepository,
+    InMemoryApiKeyRepository,
+    SessionStoreBackend,
+    InMemoryMfaRepository,
+    InMemoryExternalIdentityRepository,
+    InMemoryErasureReceiptRepository,
+    AuditStoreBackend,
+>;
+
+#[utoipa::path(get, path = "/api/users/{id}/data-export", tag = "user-data", security(("bearer" = [])),
+    params(
+        ("id" = String, Path, description = "ID пользователя (UUID)"),
+        ("fields" = Option<String>, Query, description = "Поля профиля через запятую, например `id,email`"),
+        ("include" = Option<String>, Query, description = "Встроить в профиль `roles` и/или `api_keys`"),
+    ),
+    responses(
+        (status = 200, description = "ZIP-архив с данными пользователя", body = Vec<u8>, content_type = "application/zip"),
+        (status = 400, description = "Неизвестное поле в `fields` или `include`", body = ApiResponse<String>),
+        (status = 404, description = "Пользователь не найден", body = ApiResponse<String>),
+    ))]
+pub async fn export_user_data_handler(
+    State(user_data_service): State<UserDataService>,
+    codec: Option<ResponseCodec>,
+    Path(user_id): Path<String>,
+    Query(fieldset): Query<FieldsetQuery>,
+) -> impl IntoResponse {
+    let codec = codec.map_or(Codec::Json, |ResponseCodec(codec)| codec);
+    let fieldset = match UserFieldset::parse(&fieldset) {
+        Ok(fieldset) => fieldset,
+        Err(error) => return (StatusCode::BAD_REQUEST, Encoded(codec, ApiResponse::<()>::error(error.to_string()))).into_response(),
+    };
+    let response = user_data_service.export_user_data(user_id.clone()).await;
+    let Some(export) = &response.data else {
+        return (StatusCode::NOT_FOUND, Encoded(codec, response)).into_response();
+    };
+    
+    let archive = export
+        .files_with(&fieldset)
+        .map_err(|err| DomainError::InvalidOperation(err.to_string()))
+        .and_then(|files| write_zip_archive(&files));
+    
+    match archive {
+        Ok(archive) => (
+            StatusCode::OK,
+            [
+                (CONTENT_TYPE, "application/zip".to_string()),
+                (CONTENT_DISPOSITION, format!("attachment; filename=\"user-{}-export.zip\"", user_id)),
+                (CACHE_CONTROL, "no-store".to_string()),
+            ],
+            archive,
+        ).into_response(),
+        Err(error) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Encoded(codec, ApiResponse::<()>::error(error.to_string())),
+        ).into_response(),
+    }
+}
+
+#[utoipa::path(post, path = "/api/users/{id}/erase", tag = "user-data", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID пользователя (UUID)")),
+    responses(
+        (status = 200, description = "Пользователь обезличен, квитанция о стирании", body = ApiResponse<ErasureReceiptResponse>),
+        (status = 400, description = "Пользователь не найден или уже стерт", body = ApiResponse<ErasureReceiptResponse>),
+    ))]
+pub async fn erase_user_handler(
+    State(user_data_service): State<UserDataService>,
+    ResponseCodec(codec): ResponseCodec,
+    Extension(actor): Extension<Actor>,
+    Path(user_id): Path<String>,
+) -> impl IntoResponse {
+    let response = user_data_service.erase_user(&actor, user_id).await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::BAD_REQUEST, Encoded(codec, response)).into_response(),
+    }
+}
\ No newline at end of file
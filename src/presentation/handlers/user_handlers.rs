@@ -1,85 +1,283 @@
 use axum::{
-    extract::{Path, State, Json},
+    extract::{FromRef, Path, Query, State},
     http::StatusCode,
-    response::IntoResponse,
+    response::{IntoResponse, Response},
+    Extension,
+};
+use serde_json::Value;
+use crate::application::{
+    UserApplicationService, CreateUserRequest, UpdateUserRequest, UpdateUserRolesRequest, UserByEmailRequest, UserResponse,
+    FieldsetQuery, ListUsersQuery, SparseUser, SparseUserList, UserFieldset,
 };
-use crate::application::{UserApplicationService, CreateUserRequest, UpdateUserRequest};
 use crate::application::dto::ApiResponse;
+use crate::domain::{Actor, AuthorizationService, Permission, UserId};
+use super::api_key_handlers::ApiKeyService;
+use super::content_negotiation::{Codec, Encoded, Payload, ResponseCodec};
+
+pub type UserService = UserApplicationService<crate::infrastructure::InMemoryUserRepository>;
+
+// API-ключи нужны маршрутам пользователей только для `?include=api_keys`
+#[derive(Clone)]
+pub struct UserRoutesState {
+    pub users: UserService,
+    pub api_keys: ApiKeyService,
+}
+
+impl FromRef<UserRoutesState> for UserService {
+    fn from_ref(state: &UserRoutesState) -> Self {
+        state.users.clone()
+    }
+}
 
+impl FromRef<UserRoutesState> for ApiKeyService {
+    fn from_ref(state: &UserRoutesState) -> Self {
+        state.api_keys.clone()
+    }
+}
+
+#[utoipa::path(get, path = "/health", tag = "health",
+    responses((status = 200, description = "Сервер работает", body = String, content_type = "text/plain")))]
 pub async fn health_handler() -> impl IntoResponse {
     (StatusCode::OK, "OK")
 }
 
+#[utoipa::path(post, path = "/api/users", tag = "users", request_body = CreateUserRequest,
+    responses(
+        (status = 201, description = "Пользователь создан", body = ApiResponse<UserResponse>),
+        (status = 400, description = "Неверные данные или email занят", body = ApiResponse<UserResponse>),
+    ))]
 pub async fn create_user_handler(
-    State(user_service): State<UserApplicationService<infrastructure::InMemoryUserRepository>>,
-    Json(request): Json<CreateUserRequest>,
+    State(user_service): State<UserService>,
+    ResponseCodec(codec): ResponseCodec,
+    Payload(request): Payload<CreateUserRequest>,
 ) -> impl IntoResponse {
     let response = user_service.create_user(request).await;
     
     match response.success {
-        true => (StatusCode::CREATED, Json(response)).into_response(),
-        false => (StatusCode::BAD_REQUEST, Json(response)).into_response(),
+        true => (StatusCode::CREATED, Encoded(codec, response)).into_response(),
+        false => (StatusCode::BAD_REQUEST, Encoded(codec, response)).into_response(),
     }
 }
 
+#[utoipa::path(get, path = "/api/users/{id}", tag = "users", security(("bearer" = [])),
+    params(
+        ("id" = String, Path, description = "ID пользователя (UUID)"),
+        ("fields" = Option<String>, Query, description = "Поля пользователя через запятую, например `id,email`"),
+        ("include" = Option<String>, Query, description = "Встроить `roles` и/или `api_keys`"),
+    ),
+    responses(
+        (status = 200, description = "Пользователь", body = ApiResponse<UserResponse>),
+        (status = 400, description = "Неизвестное поле в `fields` или `include`", body = ApiResponse<UserResponse>),
+        (status = 404, description = "Пользователь не найден", body = ApiResponse<UserResponse>),
+    ))]
 pub async fn get_user_handler(
-    State(user_service): State<UserApplicationService<infrastructure::InMemoryUserRepository>>,
+    State(user_service): State<UserService>,
+    State(api_key_service): State<ApiKeyService>,
+    Extension(actor): Extension<Actor>,
+    ResponseCodec(codec): ResponseCodec,
     Path(user_id): Path<String>,
+    Query(fieldset): Query<FieldsetQuery>,
 ) -> impl IntoResponse {
+    let fieldset = match UserFieldset::parse(&fieldset) {
+        Ok(fieldset) => fieldset,
+        Err(error) => return error_response(codec, StatusCode::BAD_REQUEST, error.to_string()),
+    };
     let response = user_service.get_user(user_id).await;
     
     match response.success {
-        true => (StatusCode::OK, Json(response)).into_response(),
-        false => (StatusCode::NOT_FOUND, Json(response)).into_response(),
+        true => sparse_user_response(codec, &api_key_service, &actor, &fieldset, response).await,
+        false => (StatusCode::NOT_FOUND, Encoded(codec, response)).into_response(),
     }
 }
 
+#[utoipa::path(post, path = "/api/users/email", tag = "users", security(("bearer" = [])),
+    params(
+        ("fields" = Option<String>, Query, description = "Поля пользователя через запятую, например `id,email`"),
+        ("include" = Option<String>, Query, description = "Встроить `roles` и/или `api_keys`"),
+    ),
+    request_body(content = UserByEmailRequest, description = "Email для поиска"),
+    responses(
+        (status = 200, description = "Пользователь", body = ApiResponse<UserResponse>),
+        (status = 400, description = "Email не указан или неизвестное поле", body = ApiResponse<UserResponse>),
+        (status = 404, description = "Пользователь не найден", body = ApiResponse<UserResponse>),
+    ))]
 pub async fn get_user_by_email_handler(
-    State(user_service): State<UserApplicationService<infrastructure::InMemoryUserRepository>>,
-    Json(request): Json<serde_json::Value>,
+    State(user_service): State<UserService>,
+    State(api_key_service): State<ApiKeyService>,
+    Extension(actor): Extension<Actor>,
+    ResponseCodec(codec): ResponseCodec,
+    Query(fieldset): Query<FieldsetQuery>,
+    Payload(request): Payload<UserByEmailRequest>,
 ) -> impl IntoResponse {
-    let email = request["email"].as_str().unwrap_or("").to_string();
+    let fieldset = match UserFieldset::parse(&fieldset) {
+        Ok(fieldset) => fieldset,
+        Err(error) => return error_response(codec, StatusCode::BAD_REQUEST, error.to_string()),
+    };
+    let email = request.email;
     
     if email.is_empty() {
-        let error_response = ApiResponse::<application::dto::UserResponse> {
+        let error_response = ApiResponse::<crate::application::dto::UserResponse> {
             success: false,
             data: None,
             error: Some("Email is required".to_string()),
+            cause: None,
         };
-        return (StatusCode::BAD_REQUEST, Json(error_response)).into_response();
+        return (StatusCode::BAD_REQUEST, Encoded(codec, error_response)).into_response();
     }
     
     let response = user_service.get_user_by_email(email).await;
     
     match response.success {
-        true => (StatusCode::OK, Json(response)).into_response(),
-        false => (StatusCode::NOT_FOUND, Json(response)).into_response(),
+        true => sparse_user_response(codec, &api_key_service, &actor, &fieldset, response).await,
+        false => (StatusCode::NOT_FOUND, Encoded(codec, response)).into_response(),
     }
 }
 
+#[utoipa::path(put, path = "/api/users/{id}", tag = "users", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID пользователя (UUID)")),
+    request_body = UpdateUserRequest,
+    responses(
+        (status = 200, description = "Пользователь обновлен", body = ApiResponse<UserResponse>),
+        (status = 400, description = "Неверные данные или пользователь не найден", body = ApiResponse<UserResponse>),
+    ))]
 pub async fn update_user_handler(
-    State(user_service): State<UserApplicationService<infrastructure::InMemoryUserRepository>>,
+    State(user_service): State<UserService>,
+    ResponseCodec(codec): ResponseCodec,
     Path(user_id): Path<String>,
-    Json(request): Json<UpdateUserRequest>,
+    Payload(request): Payload<UpdateUserRequest>,
 ) -> impl IntoResponse {
     let response = user_service.update_user(user_id, request).await;
     
     match response.success {
-        true => (StatusCode::OK, Json(response)).into_response(),
-        false => (StatusCode::BAD_REQUEST, Json(response)).into_response(),
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::BAD_REQUEST, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(put, path = "/api/users/{id}/roles", tag = "users", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID пользователя (UUID)")),
+    request_body = UpdateUserRolesRequest,
+    responses(
+        (status = 200, description = "Роли изменены", body = ApiResponse<UserResponse>),
+        (status = 400, description = "Неизвестная роль или пользователь не найден", body = ApiResponse<UserResponse>),
+    ))]
+pub async fn change_user_roles_handler(
+    State(user_service): State<UserService>,
+    ResponseCodec(codec): ResponseCodec,
+    Path(user_id): Path<String>,
+    Payload(request): Payload<UpdateUserRolesRequest>,
+) -> impl IntoResponse {
+    let response = user_service.change_user_roles(user_id, request).await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::BAD_REQUEST, Encoded(codec, response)).into_response(),
     }
 }
 
+#[utoipa::path(delete, path = "/api/users/{id}", tag = "users", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID пользователя (UUID)")),
+    responses(
+        (status = 204, description = "Пользователь удален"),
+        (status = 404, description = "Пользователь не найден", body = ApiResponse<UserResponse>),
+    ))]
 pub async fn delete_user_handler(
-    State(user_service): State<UserApplicationService<infrastructure::InMemoryUserRepository>>,
+    State(user_service): State<UserService>,
+    ResponseCodec(codec): ResponseCodec,
     Path(user_id): Path<String>,
 ) -> impl IntoResponse {
     let response = user_service.delete_user(user_id).await;
     
     match response.success {
-        true => (StatusCode::NO_CONTENT, Json(response)).into_response(),
-        false => (StatusCode::NOT_FOUND, Json(response)).into_response(),
+        true => (StatusCode::NO_CONTENT, Encoded(codec, response)).into_response(),
+        false => (StatusCode::NOT_FOUND, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(get, path = "/api/users", tag = "users", security(("bearer" = [])),
+    params(
+        ("offset" = Option<usize>, Query, description = "Сколько пользователей пропустить"),
+        ("limit" = Option<usize>, Query, description = "Размер страницы"),
+        ("role" = Option<String>, Query, description = "Только пользователи с ролью"),
+        ("search" = Option<String>, Query, description = "Подстрока email или имени"),
+        ("fields" = Option<String>, Query, description = "Поля пользователя через запятую, например `id,email`"),
+        ("include" = Option<String>, Query, description = "Встроить `roles` и/или `api_keys`"),
+    ),
+    responses(
+        (status = 200, description = "Страница пользователей", body = Object),
+        (status = 400, description = "Неверные параметры", body = ApiResponse<UserResponse>),
+    ))]
+pub async fn list_users_handler(
+    State(user_service): State<UserService>,
+    State(api_key_service): State<ApiKeyService>,
+    Extension(actor): Extension<Actor>,
+    ResponseCodec(codec): ResponseCodec,
+    Query(query): Query<ListUsersQuery>,
+    Query(fieldset): Query<FieldsetQuery>,
+) -> impl IntoResponse {
+    let fieldset = match UserFieldset::parse(&fieldset) {
+        Ok(fieldset) => fieldset,
+        Err(error) => return error_response(codec, StatusCode::BAD_REQUEST, error.to_string()),
+    };
+    let response = user_service.list_users(query).await;
+    let Some(page) = response.data else {
+        return (StatusCode::BAD_REQUEST, Encoded(codec, response)).into_response();
+    };
+    if fieldset.is_default() {
+        return (StatusCode::OK, Encoded(codec, ApiResponse::success(page))).into_response();
     }
+
+    let mut users = Vec::with_capacity(page.users.len());
+    for user in &page.users {
+        match project_user(&api_key_service, &actor, &fieldset, user).await {
+            Ok(user) => users.push(user),
+            Err((status, message)) => return error_response(codec, status, message),
+        }
+    }
+    let page = SparseUserList { users, total: page.total, offset: page.offset, limit: page.limit };
+    (StatusCode::OK, Encoded(codec, ApiResponse::success(page))).into_response()
+}
+
+// Без `fields` и `include` пользователь отдается как UserResponse
+async fn sparse_user_response(
+    codec: Codec,
+    api_key_service: &ApiKeyService,
+    actor: &Actor,
+    fieldset: &UserFieldset,
+    response: ApiResponse<UserResponse>,
+) -> Response {
+    let Some(user) = response.data.as_ref().filter(|_| !fieldset.is_default()) else {
+        return (StatusCode::OK, Encoded(codec, response)).into_response();
+    };
+    match project_user(api_key_service, actor, fieldset, user).await {
+        Ok(user) => (StatusCode::OK, Encoded(codec, ApiResponse::success(user))).into_response(),
+        Err((status, message)) => error_response(codec, status, message),
+    }
+}
+
+// Чужие API-ключи видит только тот, кому можно ими управлять
+async fn project_user(
+    api_key_service: &ApiKeyService,
+    actor: &Actor,
+    fieldset: &UserFieldset,
+    user: &UserResponse,
+) -> Result<SparseUser, (StatusCode, String)> {
+    if !fieldset.includes_api_keys() {
+        return Ok(SparseUser(fieldset.project(user, None)));
+    }
+
+    let user_id = UserId::from_string(user.id.clone()).map_err(|error| (StatusCode::INTERNAL_SERVER_ERROR, error))?;
+    AuthorizationService::authorize(actor, Permission::ManageApiKeys, Some(&user_id))
+        .map_err(|error| (StatusCode::FORBIDDEN, error.to_string()))?;
+    let api_keys = api_key_service.list_api_keys(user.id.clone()).await;
+    match api_keys.data {
+        Some(api_keys) => Ok(SparseUser(fieldset.project(user, Some(api_keys)))),
+        None => Err((StatusCode::INTERNAL_SERVER_ERROR, api_keys.error.unwrap_or_default())),
+    }
+}
+
+fn error_response(codec: Codec, status: StatusCode, message: String) -> Response {
+    (status, Encoded(codec, ApiResponse::<Value>::error(message))).into_response()
 }
 
 #[cfg(test)]
@@ -90,10 +288,121 @@ mod tests {
     use axum::extract::Request;
     use axum::routing::post;
     use axum::Router;
+    use serde_json::json;
 
     #[tokio::test]
     async fn test_health_handler() {
         let response = health_handler().await.into_response();
         assert_eq!(response.status(), StatusCode::OK);
     }
-}
\ No newline at end of file
+
+    #[tokio::test]
+    async fn test_sparse_fieldsets_on_single_and_list_responses() {
+        use axum::routing::get;
+        use tower::ServiceExt;
+        use crate::application::ApiKeyApplicationService;
+        use crate::domain::{AuthLevel, Role};
+        use crate::infrastructure::{InMemoryApiKeyRepository, InMemoryUserRepository, Sha256SecretHasher};
+
+        let user_repository = InMemoryUserRepository::new();
+        let state = UserRoutesState {
+            users: UserApplicationService::new(user_repository.clone()),
+            api_keys: ApiKeyApplicationService::new(user_repository, InMemoryApiKeyRepository::new(), Sha256SecretHasher::new()),
+        };
+        let user = state.users.create_user(CreateUserRequest {
+            email: "alice@example.com".to_string(),
+            name: "Alice".to_string(),
+        }).await.data.unwrap();
+        let app = |role: Role| {
+            Router::new()
+                .route("/users", get(list_users_handler))
+                .route("/users/{id}", get(get_user_handler))
+                .route("/users/email", post(get_user_by_email_handler))
+                .layer(Extension(Actor::new(UserId::new(), role).with_auth_level(AuthLevel::MultiFactor)))
+                .with_state(state.clone())
+        };
+        let call = |app: Router, uri: String| async move {
+            let response = app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap()).await.unwrap();
+            let status = response.status();
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            (status, serde_json::from_slice::<Value>(&body).unwrap())
+        };
+
+        let (status, body) = call(app(Role::Admin), format!("/users/{}?fields=email&include=api_keys", user.id)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["data"], json!({ "id": user.id, "email": "alice@example.com", "api_keys": [] }));
+
+        let (_, body) = call(app(Role::Admin), "/users?fields=name&include=roles".to_string()).await;
+        assert_eq!(body["data"]["users"], json!([{ "id": user.id, "name": "Alice", "roles": user.roles }]));
+        assert_eq!(body["data"]["total"], 1);
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/users/email?fields=created_at")
+            .header("content-type", "application/json")
+            .body(Body::from(r#"{"email": "alice@example.com"}"#))
+            .unwrap();
+        let response = app(Role::Admin).oneshot(request).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"].as_object().unwrap().keys().collect::<Vec<_>>(), ["created_at", "id"]);
+
+        // Без `fields` ответ не меняется
+        let (_, body) = call(app(Role::Admin), format!("/users/{}", user.id)).await;
+        assert_eq!(body["data"]["name"], "Alice");
+
+        let (status, body) = call(app(Role::Admin), "/users?fields=id,password".to_string()).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body["error"].as_str().unwrap().starts_with("Unknown `password` in `fields`"));
+
+        // Чужие API-ключи участнику недоступны
+        let (status, _) = call(app(Role::Member), format!("/users/{}?include=api_keys", user.id)).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_sparse_fieldsets_without_envelope_in_xml() {
+        use axum::middleware::from_fn_with_state;
+        use axum::routing::get;
+        use tower::ServiceExt;
+        use crate::application::ApiKeyApplicationService;
+        use crate::domain::Role;
+        use crate::infrastructure::{InMemoryApiKeyRepository, InMemoryUserRepository, Sha256SecretHasher};
+        use crate::presentation::middleware::{api_version_middleware, ApiVersion, ApiVersionMetrics, ApiVersionState};
+
+        let user_repository = InMemoryUserRepository::new();
+        let state = UserRoutesState {
+            users: UserApplicationService::new(user_repository.clone()),
+            api_keys: ApiKeyApplicationService::new(user_repository, InMemoryApiKeyRepository::new(), Sha256SecretHasher::new()),
+        };
+        let user = state.users.create_user(CreateUserRequest {
+            email: "alice@example.com".to_string(),
+            name: "Alice".to_string(),
+        }).await.data.unwrap();
+        let v2 = ApiVersionState::new(ApiVersionMetrics::new()).for_version(ApiVersion::V2);
+        let app = Router::new()
+            .route("/users", get(list_users_handler))
+            .route("/users/{id}", get(get_user_handler))
+            .layer(Extension(Actor::new(UserId::new(), Role::Admin)))
+            .with_state(state)
+            .layer(from_fn_with_state(v2, api_version_middleware));
+        let call = |uri: String| {
+            let app = app.clone();
+            async move {
+                let request = Request::builder().uri(uri).header("accept", "application/xml").body(Body::empty()).unwrap();
+                let response = app.oneshot(request).await.unwrap();
+                let status = response.status();
+                let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+                (status, String::from_utf8(body.to_vec()).unwrap())
+            }
+        };
+
+        let (status, body) = call(format!("/users/{}?fields=email", user.id)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, format!("<UserResponse><email>alice@example.com</email><id>{}</id></UserResponse>", user.id));
+
+        let (status, body) = call("/users?fields=name".to_string()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert!(body.starts_with(&format!("<UserListResponse><users><id>{}</id><name>Alice</name></users>", user.id)), "{}", body);
+    }
+}
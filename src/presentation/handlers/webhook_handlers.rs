@@ -0,0 +1,145 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use crate::application::{
+    ApiResponse, CreateWebhookRequest, WebhookApplicationService, WebhookDeliveryListQuery, WebhookDeliveryResponse, WebhookResponse,
+};
+use crate::infrastructure::{AesGcmSecretCipher, InMemoryWebhookRepository};
+use super::content_negotiation::{Encoded, Payload, ResponseCodec};
+
+pub type WebhookService = WebhookApplicationService<InMemoryWebhookRepository, AesGcmSecretCipher>;
+
+#[utoipa::path(post, path = "/api/webhooks", tag = "webhooks", security(("bearer" = [])),
+    request_body = CreateWebhookRequest,
+    responses(
+        (status = 201, description = "Подписка создана", body = ApiResponse<WebhookResponse>),
+        (status = 400, description = "Неверный URL, секрет или тип события", body = ApiResponse<WebhookResponse>),
+    ))]
+pub async fn create_webhook_handler(
+    State(webhook_service): State<WebhookService>,
+    ResponseCodec(codec): ResponseCodec,
+    Payload(request): Payload<CreateWebhookRequest>,
+) -> impl IntoResponse {
+    let response = webhook_service.create_webhook(request).await;
+    
+    match response.success {
+        true => (StatusCode::CREATED, Encoded(codec, response)).into_response(),
+        false => (StatusCode::BAD_REQUEST, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(get, path = "/api/webhooks", tag = "webhooks", security(("bearer" = [])),
+    responses((status = 200, description = "Подписки", body = ApiResponse<Vec<WebhookResponse>>)))]
+pub async fn list_webhooks_handler(
+    State(webhook_service): State<WebhookService>,
+    ResponseCodec(codec): ResponseCodec,
+) -> impl IntoResponse {
+    let response = webhook_service.list_webhooks().await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::INTERNAL_SERVER_ERROR, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(get, path = "/api/webhooks/{id}", tag = "webhooks", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID подписки (UUID)")),
+    responses(
+        (status = 200, description = "Подписка", body = ApiResponse<WebhookResponse>),
+        (status = 404, description = "Подписка не найдена", body = ApiResponse<WebhookResponse>),
+    ))]
+pub async fn get_webhook_handler(
+    State(webhook_service): State<WebhookService>,
+    ResponseCodec(codec): ResponseCodec,
+    Path(webhook_id): Path<String>,
+) -> impl IntoResponse {
+    let response = webhook_service.get_webhook(webhook_id).await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::NOT_FOUND, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(delete, path = "/api/webhooks/{id}", tag = "webhooks", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID подписки (UUID)")),
+    responses(
+        (status = 200, description = "Подписка удалена", body = ApiResponse<String>),
+        (status = 404, description = "Подписка не найдена", body = ApiResponse<String>),
+    ))]
+pub async fn delete_webhook_handler(
+    State(webhook_service): State<WebhookService>,
+    ResponseCodec(codec): ResponseCodec,
+    Path(webhook_id): Path<String>,
+) -> impl IntoResponse {
+    let response = webhook_service.delete_webhook(webhook_id).await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::NOT_FOUND, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(post, path = "/api/webhooks/{id}/enable", tag = "webhooks", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID подписки (UUID)")),
+    responses(
+        (status = 200, description = "Подписка снова включена", body = ApiResponse<WebhookResponse>),
+        (status = 404, description = "Подписка не найдена", body = ApiResponse<WebhookResponse>),
+    ))]
+pub async fn enable_webhook_handler(
+    State(webhook_service): State<WebhookService>,
+    ResponseCodec(codec): ResponseCodec,
+    Path(webhook_id): Path<String>,
+) -> impl IntoResponse {
+    let response = webhook_service.enable_webhook(webhook_id).await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::NOT_FOUND, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(get, path = "/api/webhooks/{id}/deliveries", tag = "webhooks", security(("bearer" = [])),
+    params(("id" = String, Path, description = "ID подписки (UUID)"), WebhookDeliveryListQuery),
+    responses(
+        (status = 200, description = "Журнал доставок, новые первыми", body = ApiResponse<Vec<WebhookDeliveryResponse>>),
+        (status = 404, description = "Подписка не найдена", body = ApiResponse<Vec<WebhookDeliveryResponse>>),
+    ))]
+pub async fn list_webhook_deliveries_handler(
+    State(webhook_service): State<WebhookService>,
+    ResponseCodec(codec): ResponseCodec,
+    Path(webhook_id): Path<String>,
+    Query(query): Query<WebhookDeliveryListQuery>,
+) -> impl IntoResponse {
+    let response = webhook_service.list_deliveries(webhook_id, query).await;
+    
+    match response.success {
+        true => (StatusCode::OK, Encoded(codec, response)).into_response(),
+        false => (StatusCode::NOT_FOUND, Encoded(codec, response)).into_response(),
+    }
+}
+
+#[utoipa::path(post, path = "/api/webhooks/{id}/deliveries/{delivery_id}/redeliver", tag = "webhooks", security(("bearer" = [])),
+    params(
+        ("id" = String, Path, description = "ID подписки (UUID)"),
+        ("delivery_id" = String, Path, description = "ID доставки (UUID)"),
+    ),
+    responses(
+        (status = 202, description = "Доставка поставлена в очередь", body = ApiResponse<WebhookDeliveryResponse>),
+        (status = 400, description = "Доставка не найдена или подписка отключена", body = ApiResponse<WebhookDeliveryResponse>),
+    ))]
+pub async fn redeliver_webhook_handler(
+    State(webhook_service): State<WebhookService>,
+    ResponseCodec(codec): ResponseCodec,
+    Path((webhook_id, delivery_id)): Path<(String, String)>,
+) -> impl IntoResponse {
+    let response = webhook_service.redeliver(webhook_id, delivery_id).await;
+    
+    // Доставка только поставлена в очередь, отправит ее диспетчер
+    match response.success {
+        true => (StatusCode::ACCEPTED, Encoded(codec, response)).into_response(),
+        false => (StatusCode::BAD_REQUEST, Encoded(codec, response)).into_response(),
+    }
+}
\ No newline at end of file
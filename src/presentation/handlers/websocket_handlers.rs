@@ -0,0 +1,355 @@
+use std::collections::BTreeSet;
+use std::time::Duration;
+use axum::{
+    extract::{ws::{CloseFrame, Message, WebSocket, WebSocketUpgrade}, State},
+    response::Response,
+    Extension,
+};
+use tokio::sync::broadcast::error::RecvError;
+use tokio::time::Instant;
+use crate::application::{WebSocketClientMessage, WebSocketServerMessage};
+use crate::domain::{Actor, AuthorizationService, Permission, UserId};
+use crate::infrastructure::{UserChange, UserChangeFeed};
+
+// Коды закрытия (RFC 6455): отставший клиент, смена прав владельца
+// соединения, клиент перестал отвечать на ping
+pub const SLOW_CONSUMER_CLOSE_CODE: u16 = 1013;
+pub const POLICY_VIOLATION_CLOSE_CODE: u16 = 1008;
+pub const HEARTBEAT_TIMEOUT_CLOSE_CODE: u16 = 1001;
+
+const MAX_CLIENT_MESSAGE_SIZE: usize = 64 * 1024;
+const MAX_USER_SUBSCRIPTIONS: usize = 100;
+
+#[derive(Clone)]
+pub struct WebSocketState {
+    feed: UserChangeFeed,
+    heartbeat_interval: Duration,
+    client_timeout: Duration,
+    send_timeout: Duration,
+}
+
+impl WebSocketState {
+    pub fn new(feed: UserChangeFeed) -> Self {
+        Self {
+            feed,
+            heartbeat_interval: Duration::from_secs(30),
+            client_timeout: Duration::from_secs(75),
+            send_timeout: Duration::from_secs(10),
+        }
+    }
+
+    pub fn with_heartbeat(mut self, interval: Duration, client_timeout: Duration) -> Self {
+        self.heartbeat_interval = interval;
+        self.client_timeout = client_timeout;
+        self
+    }
+
+    pub fn with_send_timeout(mut self, send_timeout: Duration) -> Self {
+        self.send_timeout = send_timeout;
+        self
+    }
+}
+
+// Подписки одного соединения
+#[derive(Default)]
+struct Subscriptions {
+    all: bool,
+    user_ids: BTreeSet<String>,
+}
+
+impl Subscriptions {
+    fn accepts(&self, change: &UserChange) -> bool {
+        self.all || self.user_ids.contains(&change.user_id.to_string())
+    }
+
+    fn to_message(&self) -> WebSocketServerMessage {
+        WebSocketServerMessage::Subscriptions {
+            all: self.all,
+            user_ids: self.user_ids.iter().cloned().collect(),
+        }
+    }
+}
+
+enum SendFailure {
+    Disconnected,
+    TimedOut,
+}
+
+pub async fn websocket_handler(
+    State(state): State<WebSocketState>,
+    Extension(actor): Extension<Actor>,
+    upgrade: WebSocketUpgrade,
+) -> Response {
+    upgrade
+        .max_message_size(MAX_CLIENT_MESSAGE_SIZE)
+        .on_upgrade(move |socket| handle_socket(socket, state, actor))
+}
+
+async fn handle_socket(mut socket: WebSocket, state: WebSocketState, actor: Actor) {
+    let mut changes = state.feed.subscribe(None).receiver;
+    let mut subscriptions = Subscriptions::default();
+    let mut heartbeat = tokio::time::interval_at(Instant::now() + state.heartbeat_interval, state.heartbeat_interval);
+    let mut last_seen = Instant::now();
+
+    let close = loop {
+        let outgoing = tokio::select! {
+            message = socket.recv() => {
+                let Some(Ok(message)) = message else {
+                    return;
+                };
+                last_seen = Instant::now();
+                match message {
+                    Message::Text(text) => Some(handle_client_message(&text, &actor, &mut subscriptions)),
+                    Message::Binary(_) => Some(WebSocketServerMessage::Error {
+                        message: "Binary messages are not supported".to_string(),
+                    }),
+                    // На ping axum отвечает сам
+                    Message::Ping(_) | Message::Pong(_) => None,
+                    Message::Close(_) => return,
+                }
+            }
+            change = changes.recv() => match change {
+                // Роли или сам владелец соединения изменились - выданные
+                // подписки могли стать недоступны, клиент переподключится
+                Ok(change) if revokes_access(&change, &actor) => {
+                    break close_frame(POLICY_VIOLATION_CLOSE_CODE, "Authorization changed");
+                }
+                Ok(change) if subscriptions.accepts(&change) => Some(WebSocketServerMessage::Event {
+                    id: change.id,
+                    event: change.event.to_string(),
+                    user_id: change.user_id.to_string(),
+                    data: serde_json::from_str(&change.data).unwrap_or_default(),
+                }),
+                Ok(_) => None,
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::warn!(user_id = %actor.user_id(), skipped, "Dropping slow WebSocket consumer");
+                    break close_frame(SLOW_CONSUMER_CLOSE_CODE, "Slow consumer");
+                }
+                Err(RecvError::Closed) => return,
+            },
+            _ = heartbeat.tick() => {
+                if last_seen.elapsed() >= state.client_timeout {
+                    break close_frame(HEARTBEAT_TIMEOUT_CLOSE_CODE, "Heartbeat timeout");
+                }
+                match send(&mut socket, Message::Ping(Default::default()), state.send_timeout).await {
+                    Ok(()) => None,
+                    Err(SendFailure::Disconnected) => return,
+                    Err(SendFailure::TimedOut) => break close_frame(SLOW_CONSUMER_CLOSE_CODE, "Slow consumer"),
+                }
+            }
+        };
+
+        let Some(outgoing) = outgoing else {
+            continue;
+        };
+        let text = serde_json::to_string(&outgoing).expect("WebSocket message is serializable");
+        match send(&mut socket, Message::Text(text.into()), state.send_timeout).await {
+            Ok(()) => {}
+            Err(SendFailure::Disconnected) => return,
+            // Клиент не читает сокет: буфер отправки заполнен
+            Err(SendFailure::TimedOut) => break close_frame(SLOW_CONSUMER_CLOSE_CODE, "Slow consumer"),
+        }
+    };
+
+    if send(&mut socket, Message::Close(Some(close)), state.send_timeout).await.is_ok() {
+        // Ждем ответный Close, чтобы клиент успел прочитать код закрытия
+        let _ = tokio::time::timeout(state.send_timeout, async {
+            while let Some(Ok(message)) = socket.recv().await {
+                if matches!(message, Message::Close(_)) {
+                    break;
+                }
+            }
+        })
+        .await;
+    }
+}
+
+fn handle_client_message(text: &str, actor: &Actor, subscriptions: &mut Subscriptions) -> WebSocketServerMessage {
+    let message = match serde_json::from_str::<WebSocketClientMessage>(text) {
+        Ok(message) => message,
+        Err(error) => return WebSocketServerMessage::Error { message: format!("Invalid message: {}", error) },
+    };
+
+    match message {
+        WebSocketClientMessage::Subscribe { user_ids, all } => {
+            // Права проверяются на каждую подписку: участник может следить
+            // только за собой, поддержка - за любым пользователем
+            if all && let Err(error) = AuthorizationService::authorize(actor, Permission::WatchUserChanges, None) {
+                return WebSocketServerMessage::Error { message: error.to_string() };
+            }
+            let mut accepted = Vec::with_capacity(user_ids.len());
+            for user_id in user_ids {
+                let user_id = match UserId::from_string(user_id) {
+                    Ok(user_id) => user_id,
+                    Err(error) => return WebSocketServerMessage::Error { message: error },
+                };
+                if let Err(error) = AuthorizationService::authorize(actor, Permission::ReadUser, Some(&user_id)) {
+                    return WebSocketServerMessage::Error { message: error.to_string() };
+                }
+                accepted.push(user_id.to_string());
+            }
+            if subscriptions.user_ids.iter().chain(&accepted).collect::<BTreeSet<_>>().len() > MAX_USER_SUBSCRIPTIONS {
+                return WebSocketServerMessage::Error {
+                    message: format!("At most {} user subscriptions per connection", MAX_USER_SUBSCRIPTIONS),
+                };
+            }
+
+            subscriptions.all |= all;
+            subscriptions.user_ids.extend(accepted);
+            subscriptions.to_message()
+        }
+        WebSocketClientMessage::Unsubscribe { user_ids, all } => {
+            subscriptions.all &= !all;
+            for user_id in user_ids {
+                subscriptions.user_ids.remove(&user_id);
+            }
+            subscriptions.to_message()
+        }
+        WebSocketClientMessage::Ping => WebSocketServerMessage::Pong,
+    }
+}
+
+fn revokes_access(change: &UserChange, actor: &Actor) -> bool {
+    &change.user_id == actor.user_id() && matches!(change.event, "user.roles_changed" | "user.erased" | "user.deleted")
+}
+
+fn close_frame(code: u16, reason: &str) -> CloseFrame {
+    CloseFrame { code, reason: reason.into() }
+}
+
+async fn send(socket: &mut WebSocket, message: Message, send_timeout: Duration) -> Result<(), SendFailure> {
+    match tokio::time::timeout(send_timeout, socket.send(message)).await {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(_)) => Err(SendFailure::Disconnected),
+        Err(_) => Err(SendFailure::TimedOut),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::routing::get;
+    use axum::Router;
+    use futures_util::{SinkExt, StreamExt};
+    use tokio::net::TcpStream;
+    use tokio_tungstenite::{connect_async, tungstenite, MaybeTlsStream, WebSocketStream};
+    use crate::domain::{Email, Role, User};
+
+    type Client = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+    async fn connect(state: WebSocketState, actor: Actor) -> Client {
+        let app = Router::new()
+            .route("/ws", get(websocket_handler))
+            .layer(Extension(actor))
+            .with_state(state);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        connect_async(format!("ws://{}/ws", address)).await.unwrap().0
+    }
+
+    async fn request(client: &mut Client, message: serde_json::Value) -> serde_json::Value {
+        client.send(tungstenite::Message::Text(message.to_string().into())).await.unwrap();
+        next_json(client).await
+    }
+
+    async fn next_json(client: &mut Client) -> serde_json::Value {
+        loop {
+            let message = tokio::time::timeout(Duration::from_secs(1), client.next()).await.unwrap().unwrap().unwrap();
+            if let tungstenite::Message::Text(text) = message {
+                return serde_json::from_str(&text).unwrap();
+            }
+        }
+    }
+
+    async fn close_code(client: &mut Client) -> u16 {
+        loop {
+            match tokio::time::timeout(Duration::from_secs(1), client.next()).await.unwrap() {
+                Some(Ok(tungstenite::Message::Close(Some(frame)))) => return frame.code.into(),
+                Some(Ok(_)) => continue,
+                other => panic!("Expected close frame, got {:?}", other),
+            }
+        }
+    }
+
+    fn user(email: &str) -> User {
+        User::new(Email::new(email.to_string()).unwrap(), "Alice".to_string()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_member_subscribes_to_own_changes_only() {
+        let feed = UserChangeFeed::new();
+        let mut alice = user("alice@example.com");
+        let mut bob = user("bob@example.com");
+        let mut client = connect(WebSocketState::new(feed.clone()), Actor::new(alice.id().clone(), Role::Member)).await;
+
+        let forbidden = request(&mut client, serde_json::json!({ "type": "subscribe", "all": true })).await;
+        assert_eq!(forbidden["type"], "error");
+        let forbidden = request(&mut client, serde_json::json!({ "type": "subscribe", "user_ids": [bob.id().to_string()] })).await;
+        assert_eq!(forbidden["type"], "error");
+        let subscribed = request(&mut client, serde_json::json!({ "type": "subscribe", "user_ids": [alice.id().to_string()] })).await;
+        assert_eq!(subscribed, serde_json::json!({ "type": "subscriptions", "all": false, "user_ids": [alice.id().to_string()] }));
+        assert_eq!(request(&mut client, serde_json::json!({ "type": "ping" })).await["type"], "pong");
+
+        bob.update_name("Bob".to_string()).unwrap();
+        alice.update_name("Alice Smith".to_string()).unwrap();
+        for event in bob.take_events().iter().chain(&alice.take_events()) {
+            feed.publish(event);
+        }
+
+        let event = next_json(&mut client).await;
+        assert_eq!(event["type"], "event");
+        assert_eq!(event["event"], "user.created");
+        assert_eq!(event["user_id"], alice.id().to_string());
+        assert_eq!(next_json(&mut client).await["event"], "user.renamed");
+
+        let unsubscribed = request(&mut client, serde_json::json!({ "type": "unsubscribe", "user_ids": [alice.id().to_string()] })).await;
+        assert_eq!(unsubscribed["user_ids"], serde_json::json!([]));
+    }
+
+    #[tokio::test]
+    async fn test_slow_consumer_is_dropped() {
+        let feed = UserChangeFeed::new();
+        let mut alice = user("alice@example.com");
+        let actor = Actor::new(UserId::new(), Role::Support);
+        let mut client = connect(WebSocketState::new(feed.clone()), actor).await;
+        assert_eq!(request(&mut client, serde_json::json!({ "type": "subscribe", "all": true })).await["all"], true);
+
+        // Без await серверная задача не успевает читать ленту
+        for index in 0..300 {
+            alice.update_name(format!("Alice {}", index)).unwrap();
+            feed.publish(&alice.take_events().remove(0));
+        }
+
+        assert_eq!(close_code(&mut client).await, SLOW_CONSUMER_CLOSE_CODE);
+    }
+
+    #[tokio::test]
+    async fn test_role_change_closes_connection() {
+        let feed = UserChangeFeed::new();
+        let mut alice = user("alice@example.com");
+        alice.take_events();
+        let mut client = connect(WebSocketState::new(feed.clone()), Actor::new(alice.id().clone(), Role::Support)).await;
+        assert_eq!(request(&mut client, serde_json::json!({ "type": "ping" })).await["type"], "pong");
+
+        alice.change_roles(vec![Role::Support]).unwrap();
+        feed.publish(&alice.take_events().remove(0));
+
+        assert_eq!(close_code(&mut client).await, POLICY_VIOLATION_CLOSE_CODE);
+    }
+
+    #[tokio::test]
+    async fn test_silent_client_times_out() {
+        let state = WebSocketState::new(UserChangeFeed::new())
+            .with_heartbeat(Duration::from_millis(20), Duration::from_millis(60));
+        let mut client = connect(state, Actor::new(UserId::new(), Role::Member)).await;
+
+        // Клиент не читает сокет и не отвечает на ping
+        tokio::time::sleep(Duration::from_millis(150)).await;
+
+        assert_eq!(close_code(&mut client).await, HEARTBEAT_TIMEOUT_CLOSE_CODE);
+    }
+}
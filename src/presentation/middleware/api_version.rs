@@ -0,0 +1,420 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+use axum::{
+    body::{Body, Bytes},
+    extract::{Request, State},
+    http::{header, HeaderName, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use utoipa::ToSchema;
+use crate::application::ApiResponse;
+
+pub const ACCEPT_VERSION_HEADER: HeaderName = HeaderName::from_static("accept-version");
+pub const API_VERSION_HEADER: HeaderName = HeaderName::from_static("api-version");
+// RFC 9745 и RFC 8594
+const DEPRECATION_HEADER: HeaderName = HeaderName::from_static("deprecation");
+const SUNSET_HEADER: HeaderName = HeaderName::from_static("sunset");
+
+const PROBLEM_JSON: &str = "application/problem+json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ApiVersion {
+    // Ответы в конверте ApiResponse { success, data, error }
+    V1,
+    // Ресурс без конверта, ошибки - application/problem+json (RFC 9457)
+    V2,
+}
+
+impl ApiVersion {
+    pub const ALL: [ApiVersion; 2] = [ApiVersion::V1, ApiVersion::V2];
+
+    // Принимает `2` и `v2`
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        let number = value.strip_prefix(['v', 'V']).unwrap_or(value);
+        Self::ALL.into_iter().find(|version| version.as_str() == number)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "1",
+            ApiVersion::V2 => "2",
+        }
+    }
+
+    fn successor(&self) -> Option<Self> {
+        Self::ALL.into_iter().find(|version| version > self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct VersionDeprecation {
+    pub deprecated_at: Option<DateTime<Utc>>,
+    pub sunset_at: Option<DateTime<Utc>>,
+}
+
+/// Счетчики запросов по версии API и коду ответа.
+#[derive(Clone, Default)]
+pub struct ApiVersionMetrics {
+    counters: Arc<Mutex<BTreeMap<(ApiVersion, u16), u64>>>,
+}
+
+impl ApiVersionMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn record(&self, version: ApiVersion, status: StatusCode) {
+        let mut counters = self.counters.lock().expect("Metrics lock poisoned");
+        *counters.entry((version, status.as_u16())).or_default() += 1;
+    }
+
+    pub fn requests(&self, version: ApiVersion) -> u64 {
+        let counters = self.counters.lock().expect("Metrics lock poisoned");
+        counters.iter().filter(|((counted, _), _)| *counted == version).map(|(_, count)| count).sum()
+    }
+
+    // Текстовый формат Prometheus
+    pub fn render(&self) -> String {
+        let counters = self.counters.lock().expect("Metrics lock poisoned");
+        let mut output = String::from(
+            "# HELP api_requests_total API requests by version and status code\n# TYPE api_requests_total counter\n",
+        );
+        for ((version, status), count) in counters.iter() {
+            output.push_str(&format!(
+                "api_requests_total{{version=\"{}\",status=\"{}\"}} {}\n",
+                version.as_str(),
+                status,
+                count
+            ));
+        }
+        output
+    }
+}
+
+type EncodeResource = dyn Fn() -> Result<Option<Bytes>, String> + Send + Sync;
+
+/// Метка ответа в конверте ApiResponse. По ней v2 снимает конверт, не разбирая
+/// тело; ресурс без конверта кодируется, только если его запросила v2.
+#[derive(Clone)]
+pub struct EncodedEnvelope {
+    pub success: bool,
+    pub error: Option<String>,
+    resource: Arc<EncodeResource>,
+}
+
+impl EncodedEnvelope {
+    pub fn new(
+        success: bool,
+        error: Option<String>,
+        resource: impl Fn() -> Result<Option<Bytes>, String> + Send + Sync + 'static,
+    ) -> Self {
+        Self { success, error, resource: Arc::new(resource) }
+    }
+
+    pub fn resource(&self) -> Result<Option<Bytes>, String> {
+        (self.resource)()
+    }
+}
+
+// Конверт v1 в JSON; v2 получает ресурс из метки
+impl<T: Serialize + Send + Sync + 'static> IntoResponse for ApiResponse<T> {
+    fn into_response(self) -> Response {
+        let body = match serde_json::to_vec(&self) {
+            Ok(body) => body,
+            Err(error) => {
+                tracing::error!(error = %error, "Failed to encode response");
+                return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to encode response").into_response();
+            }
+        };
+        let ApiResponse { success, data, error, .. } = self;
+        let envelope = EncodedEnvelope::new(success, error, move || {
+            data.as_ref()
+                .map(|data| serde_json::to_vec(data).map(Bytes::from))
+                .transpose()
+                .map_err(|error| error.to_string())
+        });
+
+        let mut response = ([(header::CONTENT_TYPE, "application/json")], body).into_response();
+        response.extensions_mut().insert(envelope);
+        response
+    }
+}
+
+#[derive(Clone)]
+pub struct ApiVersionState {
+    // Версия из пути (`/api/v2/...`); без нее - из Accept-Version, по умолчанию v1
+    version: Option<ApiVersion>,
+    deprecations: Arc<BTreeMap<ApiVersion, VersionDeprecation>>,
+    metrics: ApiVersionMetrics,
+}
+
+impl ApiVersionState {
+    pub fn new(metrics: ApiVersionMetrics) -> Self {
+        Self { version: None, deprecations: Arc::new(BTreeMap::new()), metrics }
+    }
+
+    pub fn with_deprecation(mut self, version: ApiVersion, deprecation: VersionDeprecation) -> Self {
+        if deprecation.deprecated_at.is_some() || deprecation.sunset_at.is_some() {
+            Arc::make_mut(&mut self.deprecations).insert(version, deprecation);
+        }
+        self
+    }
+
+    pub fn for_version(&self, version: ApiVersion) -> Self {
+        Self { version: Some(version), ..self.clone() }
+    }
+}
+
+/// Выбирает версию API и приводит ответ к ее формату. Подключается снаружи
+/// аутентификации, чтобы ошибки auth_middleware тоже были в формате версии.
+pub async fn api_version_middleware(State(state): State<ApiVersionState>, request: Request, next: Next) -> Response {
+    let version = match (state.version, request.headers().get(&ACCEPT_VERSION_HEADER)) {
+        (Some(version), _) => version,
+        (None, None) => ApiVersion::V1,
+        (None, Some(value)) => match value.to_str().ok().and_then(ApiVersion::parse) {
+            Some(version) => version,
+            None => {
+                let supported: Vec<_> = ApiVersion::ALL.iter().map(ApiVersion::as_str).collect();
+                return problem_response(
+                    StatusCode::BAD_REQUEST,
+                    format!("Unsupported API version, supported versions: {}", supported.join(", ")),
+                );
+            }
+        },
+    };
+
+    let response = next.run(request).await;
+    let mut response = match version {
+        ApiVersion::V1 => response,
+        ApiVersion::V2 => unwrap_envelope(response),
+    };
+    state.metrics.record(version, response.status());
+
+    let headers = response.headers_mut();
+    headers.insert(API_VERSION_HEADER, HeaderValue::from_static(version.as_str()));
+    if let Some(deprecation) = state.deprecations.get(&version) {
+        if let Some(deprecated_at) = deprecation.deprecated_at
+            && let Ok(value) = HeaderValue::from_str(&format!("@{}", deprecated_at.timestamp()))
+        {
+            headers.insert(DEPRECATION_HEADER, value);
+        }
+        if let Some(sunset_at) = deprecation.sunset_at
+            && let Ok(value) = HeaderValue::from_str(&sunset_at.format("%a, %d %b %Y %H:%M:%S GMT").to_string())
+        {
+            headers.insert(SUNSET_HEADER, value);
+        }
+        if let Some(successor) = version.successor()
+            && let Ok(value) = HeaderValue::from_str(&format!("</api/v{}>; rel=\"successor-version\"", successor.as_str()))
+        {
+            headers.append(header::LINK, value);
+        }
+    }
+    response
+}
+
+// Конверт ApiResponse -> ресурс или problem+json; ответы без метки EncodedEnvelope
+// (файлы, потоки, SCIM) не меняются и не буферизуются
+fn unwrap_envelope(mut response: Response) -> Response {
+    let Some(envelope) = response.extensions_mut().remove::<EncodedEnvelope>() else {
+        return response;
+    };
+    let (mut parts, _) = response.into_parts();
+    parts.headers.remove(header::CONTENT_LENGTH);
+    if !envelope.success {
+        return problem_with_headers(parts, envelope.error.unwrap_or_default());
+    }
+
+    let body = match parts.status {
+        StatusCode::NO_CONTENT => Body::empty(),
+        _ => match envelope.resource() {
+            Ok(Some(data)) => Body::from(data),
+            Ok(None) => Body::empty(),
+            Err(error) => {
+                tracing::error!(error = %error, "Failed to encode response without envelope");
+                return problem_response(StatusCode::INTERNAL_SERVER_ERROR, "Failed to encode response".to_string());
+            }
+        },
+    };
+    Response::from_parts(parts, body)
+}
+
+fn problem_with_headers(parts: axum::http::response::Parts, detail: String) -> Response {
+    let mut problem = problem_response(parts.status, detail);
+    // Retry-After, RateLimit-* и прочие заголовки исходного ответа сохраняются,
+    // многозначные (WWW-Authenticate, Set-Cookie) - со всеми значениями
+    for (name, value) in parts.headers.iter().filter(|(name, _)| *name != header::CONTENT_TYPE) {
+        problem.headers_mut().append(name.clone(), value.clone());
+    }
+    problem
+}
+
+/// Ошибка v2 в формате RFC 9457.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ProblemDetails {
+    #[serde(rename = "type")]
+    pub problem_type: String,
+    pub title: String,
+    pub status: u16,
+    pub detail: String,
+}
+
+pub fn problem_response(status: StatusCode, detail: String) -> Response {
+    let problem = ProblemDetails {
+        problem_type: "about:blank".to_string(),
+        title: status.canonical_reason().unwrap_or("Error").to_string(),
+        status: status.as_u16(),
+        detail,
+    };
+    (status, [(header::CONTENT_TYPE, PROBLEM_JSON)], Json(problem)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::to_bytes, middleware::from_fn_with_state, response::AppendHeaders, routing::{delete, get}, Router};
+    use serde_json::{json, Value};
+    use tower::ServiceExt;
+
+    async fn found() -> (StatusCode, ApiResponse<Value>) {
+        (StatusCode::OK, ApiResponse::success(json!({ "id": "42" })))
+    }
+
+    async fn missing() -> (StatusCode, [(HeaderName, &'static str); 1], ApiResponse<Value>) {
+        (StatusCode::NOT_FOUND, [(HeaderName::from_static("retry-after"), "5")], ApiResponse::error("User not found".to_string()))
+    }
+
+    async fn challenged() -> (StatusCode, AppendHeaders<[(HeaderName, &'static str); 2]>, ApiResponse<Value>) {
+        let challenge = HeaderName::from_static("www-authenticate");
+        (
+            StatusCode::UNAUTHORIZED,
+            AppendHeaders([(challenge.clone(), "Bearer"), (challenge, "ApiKey")]),
+            ApiResponse::error("Authentication required".to_string()),
+        )
+    }
+
+    async fn deleted() -> (StatusCode, ApiResponse<()>) {
+        (StatusCode::NO_CONTENT, ApiResponse::success(()))
+    }
+
+    // JSON без метки, похожий на конверт
+    async fn lookalike() -> Json<Value> {
+        Json(json!({ "success": false, "error": "not an envelope" }))
+    }
+
+    async fn large() -> (StatusCode, ApiResponse<String>) {
+        (StatusCode::OK, ApiResponse::success("x".repeat(8 * 1024 * 1024)))
+    }
+
+    fn app(state: ApiVersionState) -> Router {
+        let routes = Router::new()
+            .route("/users/found", get(found))
+            .route("/users/missing", get(missing))
+            .route("/users/challenged", get(challenged))
+            .route("/users/deleted", delete(deleted))
+            .route("/users/lookalike", get(lookalike))
+            .route("/users/large", get(large));
+        Router::new()
+            .nest("/api/v1", routes.clone().layer(from_fn_with_state(state.for_version(ApiVersion::V1), api_version_middleware)))
+            .nest("/api/v2", routes.clone().layer(from_fn_with_state(state.for_version(ApiVersion::V2), api_version_middleware)))
+            .nest("/api", routes.layer(from_fn_with_state(state, api_version_middleware)))
+    }
+
+    async fn call(app: &Router, method: &str, uri: &str, accept_version: Option<&str>) -> (StatusCode, axum::http::HeaderMap, String) {
+        let mut request = Request::builder().method(method).uri(uri);
+        if let Some(version) = accept_version {
+            request = request.header(ACCEPT_VERSION_HEADER, version);
+        }
+        let response = app.clone().oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+        let (parts, body) = response.into_parts();
+        let body = String::from_utf8(to_bytes(body, usize::MAX).await.unwrap().to_vec()).unwrap();
+        (parts.status, parts.headers, body)
+    }
+
+    #[tokio::test]
+    async fn test_v1_keeps_envelope_and_v2_returns_bare_resources() {
+        let app = app(ApiVersionState::new(ApiVersionMetrics::new()));
+
+        let (_, headers, body) = call(&app, "GET", "/api/v1/users/found", None).await;
+        assert_eq!(serde_json::from_str::<Value>(&body).unwrap(), json!({ "success": true, "data": { "id": "42" }, "error": null }));
+        assert_eq!(headers[API_VERSION_HEADER], "1");
+
+        let (status, _, body) = call(&app, "GET", "/api/v2/users/found", None).await;
+        assert_eq!((status, serde_json::from_str::<Value>(&body).unwrap()), (StatusCode::OK, json!({ "id": "42" })));
+
+        let (status, headers, body) = call(&app, "GET", "/api/v2/users/missing", None).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(headers[header::CONTENT_TYPE], PROBLEM_JSON);
+        assert_eq!(headers["retry-after"], "5");
+        let problem: Value = serde_json::from_str(&body).unwrap();
+        assert_eq!((problem["status"].clone(), problem["detail"].clone()), (json!(404), json!("User not found")));
+
+        let (status, _, body) = call(&app, "DELETE", "/api/v2/users/deleted", None).await;
+        assert_eq!((status, body.as_str()), (StatusCode::NO_CONTENT, ""));
+    }
+
+    #[tokio::test]
+    async fn test_v2_unwraps_only_marked_envelopes() {
+        let app = app(ApiVersionState::new(ApiVersionMetrics::new()));
+
+        let (status, headers, body) = call(&app, "GET", "/api/v2/users/lookalike", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(headers[header::CONTENT_TYPE], "application/json");
+        assert_eq!(serde_json::from_str::<Value>(&body).unwrap()["error"], "not an envelope");
+
+        let (status, _, body) = call(&app, "GET", "/api/v2/users/large", None).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body.len(), 8 * 1024 * 1024 + 2);
+    }
+
+    #[tokio::test]
+    async fn test_problem_keeps_every_value_of_repeated_headers() {
+        let app = app(ApiVersionState::new(ApiVersionMetrics::new()));
+
+        let (status, headers, _) = call(&app, "GET", "/api/v2/users/challenged", None).await;
+
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(headers[header::CONTENT_TYPE], PROBLEM_JSON);
+        let challenges: Vec<&str> = headers.get_all("www-authenticate").iter().map(|value| value.to_str().unwrap()).collect();
+        assert_eq!(challenges, vec!["Bearer", "ApiKey"]);
+    }
+
+    #[tokio::test]
+    async fn test_accept_version_header_selects_unversioned_routes() {
+        let metrics = ApiVersionMetrics::new();
+        let app = app(ApiVersionState::new(metrics.clone()));
+
+        let (_, _, body) = call(&app, "GET", "/api/users/found", None).await;
+        assert_eq!(serde_json::from_str::<Value>(&body).unwrap()["success"], true);
+        let (_, headers, body) = call(&app, "GET", "/api/users/found", Some("v2")).await;
+        assert_eq!((headers[API_VERSION_HEADER].to_str().unwrap(), body.as_str()), ("2", r#"{"id":"42"}"#));
+
+        let (status, headers, _) = call(&app, "GET", "/api/users/found", Some("7")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(headers[header::CONTENT_TYPE], PROBLEM_JSON);
+
+        assert_eq!((metrics.requests(ApiVersion::V1), metrics.requests(ApiVersion::V2)), (1, 1));
+        assert!(metrics.render().contains("api_requests_total{version=\"2\",status=\"200\"} 1\n"));
+    }
+
+    #[tokio::test]
+    async fn test_deprecated_version_gets_deprecation_headers() {
+        let deprecation = VersionDeprecation {
+            deprecated_at: Some("2026-01-01T00:00:00Z".parse().unwrap()),
+            sunset_at: Some("2026-12-31T23:59:59Z".parse().unwrap()),
+        };
+        let app = app(ApiVersionState::new(ApiVersionMetrics::new()).with_deprecation(ApiVersion::V1, deprecation));
+
+        let (_, headers, _) = call(&app, "GET", "/api/v1/users/found", None).await;
+        assert_eq!(headers["deprecation"], "@1767225600");
+        assert_eq!(headers["sunset"], "Thu, 31 Dec 2026 23:59:59 GMT");
+        assert_eq!(headers[header::LINK], "</api/v2>; rel=\"successor-version\"");
+
+        let (_, headers, _) = call(&app, "GET", "/api/v2/users/found", None).await;
+        assert!(headers.get("deprecation").is_none() && headers.get("sunset").is_none());
+    }
+}
@@ -0,0 +1,371 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use axum::{
+    extract::{Path, Request, State},
+    http::{header::AUTHORIZATION, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Extension,
+};
+use crate::application::{AuthenticateUseCase, Credentials};
+use crate::application::dto::ApiResponse;
+use crate::application::use_cases::authenticate::ApplicationError as AuthenticateError;
+use crate::domain::{Actor, AuthorizationService, Permission, SessionPolicy, UserId};
+use crate::infrastructure::{
+    AuditContext, InMemoryApiKeyRepository, InMemoryUserRepository, JwtTokenService, SessionStoreBackend, Sha256SecretHasher,
+};
+use crate::presentation::middleware::{
+    client_ip, read_cookie, rejection_response, tokens_match, LoginProtectionState, CSRF_COOKIE, CSRF_HEADER,
+    SESSION_COOKIE,
+};
+
+type AuthenticateService = AuthenticateUseCase<
+    InMemoryUserRepository,
+    JwtTokenService,
+    InMemoryApiKeyRepository,
+    SessionStoreBackend,
+    Sha256SecretHasher,
+>;
+
+#[derive(Clone)]
+pub struct AuthState {
+    authenticate_use_case: Arc<AuthenticateService>,
+    login_protection: LoginProtectionState,
+}
+
+impl AuthState {
+    pub fn new(
+        user_repository: InMemoryUserRepository,
+        token_service: JwtTokenService,
+        api_key_repository: InMemoryApiKeyRepository,
+        session_store: SessionStoreBackend,
+        session_policy: SessionPolicy,
+        login_protection: LoginProtectionState,
+    ) -> Self {
+        Self {
+            authenticate_use_case: Arc::new(AuthenticateUseCase::new(
+                user_repository,
+                token_service,
+                api_key_repository,
+                session_store,
+                Sha256SecretHasher::new(),
+                session_policy,
+            )),
+            login_protection,
+        }
+    }
+}
+
+fn error_response(status: StatusCode, error: String) -> Response {
+    (status, ApiResponse::<()>::error(error)).into_response()
+}
+
+// Поддерживаются схемы `Bearer <jwt>` и `ApiKey <prefix>.<secret>`
+fn parse_credentials(header: &str) -> Option<Credentials> {
+    if let Some(token) = header.strip_prefix("Bearer ") {
+        Some(Credentials::Bearer(token.trim().to_string()))
+    } else {
+        header
+            .strip_prefix("ApiKey ")
+            .map(|key| Credentials::ApiKey(key.trim().to_string()))
+    }
+}
+
+// Cookie браузер отправляет сам, поэтому изменяющие запросы должны повторить
+// CSRF-cookie в заголовке (double-submit); сам токен еще сверяется с сессией
+fn session_credentials(request: &Request, token: String) -> Result<Credentials, AuthenticateError> {
+    if request.method().is_safe() {
+        return Ok(Credentials::Session { token, csrf_token: None });
+    }
+
+    let header = request.headers().get(CSRF_HEADER).and_then(|value| value.to_str().ok());
+    match (header, read_cookie(request.headers(), CSRF_COOKIE)) {
+        (Some(header), Some(cookie)) if tokens_match(header, &cookie) => Ok(Credentials::Session {
+            token,
+            csrf_token: Some(cookie),
+        }),
+        _ => Err(AuthenticateError::InvalidCsrfToken),
+    }
+}
+
+pub async fn auth_middleware(State(auth_state): State<AuthState>, mut request: Request, next: Next) -> Response {
+    // Подбор токенов и ключей ограничивается по адресу клиента
+    let ip = client_ip(&request);
+    if let Err(error) = auth_state.login_protection.use_case().check(None, ip).await {
+        return rejection_response(error.retry_after(), error.to_string());
+    }
+
+    // Заголовок Authorization имеет приоритет над cookie сессии
+    let credentials = match request.headers().get(AUTHORIZATION) {
+        Some(value) => value.to_str().ok().and_then(parse_credentials),
+        None => match read_cookie(request.headers(), SESSION_COOKIE) {
+            Some(token) => match session_credentials(&request, token) {
+                Ok(credentials) => Some(credentials),
+                Err(error) => return error_response(StatusCode::FORBIDDEN, error.to_string()),
+            },
+            None => None,
+        },
+    };
+
+    let Some(credentials) = credentials else {
+        return error_response(
+            StatusCode::UNAUTHORIZED,
+            "Missing bearer token, API key or session cookie".to_string(),
+        );
+    };
+
+    match auth_state.authenticate_use_case.execute(&credentials).await {
+        Ok(actor) => {
+            AuditContext::set_actor(format!("user:{}", actor.user_id()));
+            // Токены и ключи, выданные до блокировки, тоже не работают, пока аккаунт заблокирован
+            if let Err(error) = auth_state.login_protection.use_case().check_account(actor.user_id()).await {
+                return rejection_response(error.retry_after(), error.to_string());
+            }
+            request.extensions_mut().insert(actor);
+            next.run(request).await
+        }
+        Err(error @ AuthenticateError::InvalidCsrfToken) => error_response(StatusCode::FORBIDDEN, error.to_string()),
+        Err(error) => {
+            if let Err(record_error) = auth_state.login_protection.use_case().record_failure(None, ip).await {
+                tracing::error!(error = %record_error, "Failed to record login attempt");
+            }
+            error_response(StatusCode::UNAUTHORIZED, error.to_string())
+        }
+    }
+}
+
+// Подключается к конкретному маршруту после `auth_middleware`; целевой
+// пользователь берется из параметра пути `{id}`, если он есть
+pub async fn authorization_middleware(
+    State(permission): State<Permission>,
+    actor: Option<Extension<Actor>>,
+    path: Option<Path<HashMap<String, String>>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some(Extension(actor)) = actor else {
+        return error_response(StatusCode::UNAUTHORIZED, "Authentication required".to_string());
+    };
+
+    let target = path
+        .and_then(|Path(params)| params.get("id").cloned())
+        .and_then(|id| UserId::from_string(id).ok());
+
+    match AuthorizationService::authorize(&actor, permission, target.as_ref()) {
+        Ok(()) => next.run(request).await,
+        Err(error) => error_response(StatusCode::FORBIDDEN, error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::middleware::from_fn_with_state;
+    use axum::routing::{delete, get};
+    use axum::Router;
+    use chrono::{Duration, Utc};
+    use tower::ServiceExt;
+    use axum::http::header::COOKIE;
+    use crate::domain::{
+        ApiKeyDomainService, AuthLevel, Email, LockoutPolicy, Role, Scope, SessionDomainService, SessionMetadata,
+        TokenService, User, UserRepository,
+    };
+    use crate::infrastructure::{InMemoryLoginAttemptStore, InMemorySessionStore, LoginAttemptStoreBackend};
+
+    async fn app_with_user(role: Role) -> (Router, User, JwtTokenService) {
+        let (app, user, token_service, _, _, _) = app_with_credentials(role).await;
+        (app, user, token_service)
+    }
+
+    async fn app_with_credentials(
+        role: Role,
+    ) -> (Router, User, JwtTokenService, InMemoryApiKeyRepository, InMemorySessionStore, LoginProtectionState) {
+        let repository = InMemoryUserRepository::new();
+        let api_key_repository = InMemoryApiKeyRepository::new();
+        let session_store = InMemorySessionStore::new();
+        let token_service = JwtTokenService::new("secret", Duration::minutes(5));
+        let email = Email::new("actor@example.com".to_string()).unwrap();
+        let mut user = User::new(email, "Actor".to_string()).unwrap();
+        user.change_roles(vec![role]).unwrap();
+        repository.save(&user).await.unwrap();
+        let login_protection = LoginProtectionState::new(
+            repository.clone(),
+            LoginAttemptStoreBackend::InMemory(InMemoryLoginAttemptStore::new()),
+            LockoutPolicy::default(),
+        );
+
+        let app = Router::new()
+            .route(
+                "/api/users/{id}",
+                get(|| async { "ok" }).layer(from_fn_with_state(Permission::ReadUser, authorization_middleware)),
+            )
+            .route(
+                "/api/users/{id}",
+                delete(|| async { "deleted" }).layer(from_fn_with_state(Permission::DeleteUser, authorization_middleware)),
+            )
+            .route_layer(from_fn_with_state(
+                AuthState::new(
+                    repository.clone(),
+                    token_service.clone(),
+                    api_key_repository.clone(),
+                    SessionStoreBackend::InMemory(session_store.clone()),
+                    SessionPolicy::default(),
+                    login_protection.clone(),
+                ),
+                auth_middleware,
+            ));
+
+        (app, user, token_service, api_key_repository, session_store, login_protection)
+    }
+
+    fn request(method: &str, uri: String, token: Option<String>) -> Request {
+        authorized_request(method, uri, token.map(|token| format!("Bearer {}", token)))
+    }
+
+    fn authorized_request(method: &str, uri: String, authorization: Option<String>) -> Request {
+        let mut builder = Request::builder().method(method).uri(uri);
+        if let Some(authorization) = authorization {
+            builder = builder.header(AUTHORIZATION, authorization);
+        }
+        builder.body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_missing_token_is_unauthorized() {
+        let (app, user, _) = app_with_user(Role::Member).await;
+
+        let response = app.oneshot(request("GET", format!("/api/users/{}", user.id()), None)).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_member_reads_self_but_not_others() {
+        let (app, user, tokens) = app_with_user(Role::Member).await;
+        let token = tokens.issue(user.id(), AuthLevel::SingleFactor).unwrap();
+
+        let own = app.clone()
+            .oneshot(request("GET", format!("/api/users/{}", user.id()), Some(token.clone())))
+            .await
+            .unwrap();
+        let other = app
+            .oneshot(request("GET", format!("/api/users/{}", UserId::new()), Some(token)))
+            .await
+            .unwrap();
+
+        assert_eq!(own.status(), StatusCode::OK);
+        assert_eq!(other.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_only_admin_can_delete() {
+        let (support_app, support, support_tokens) = app_with_user(Role::Support).await;
+        let token = support_tokens.issue(support.id(), AuthLevel::SingleFactor).unwrap();
+        let response = support_app
+            .oneshot(request("DELETE", format!("/api/users/{}", UserId::new()), Some(token)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let (admin_app, admin, admin_tokens) = app_with_user(Role::Admin).await;
+        let token = admin_tokens.issue(admin.id(), AuthLevel::MultiFactor).unwrap();
+        let response = admin_app
+            .oneshot(request("DELETE", format!("/api/users/{}", UserId::new()), Some(token)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_admin_needs_mfa_token_to_delete() {
+        let (app, admin, tokens) = app_with_user(Role::Admin).await;
+        let token = tokens.issue(admin.id(), AuthLevel::SingleFactor).unwrap();
+
+        let response = app
+            .oneshot(request("DELETE", format!("/api/users/{}", UserId::new()), Some(token)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_api_key_authenticates_within_its_scopes() {
+        let (app, admin, _, api_key_repository, _, _) = app_with_credentials(Role::Admin).await;
+        let api_keys = ApiKeyDomainService::new(api_key_repository, Sha256SecretHasher::new());
+        let (_, key) = api_keys
+            .issue_key(admin.id().clone(), "reporting".to_string(), vec![Scope::UsersRead], Utc::now() + Duration::days(1))
+            .await
+            .unwrap();
+        let authorization = Some(format!("ApiKey {}", key));
+
+        let read = app.clone()
+            .oneshot(authorized_request("GET", format!("/api/users/{}", UserId::new()), authorization.clone()))
+            .await
+            .unwrap();
+        let delete = app.clone()
+            .oneshot(authorized_request("DELETE", format!("/api/users/{}", UserId::new()), authorization))
+            .await
+            .unwrap();
+        let forged = app
+            .oneshot(authorized_request("GET", format!("/api/users/{}", admin.id()), Some(format!("ApiKey {}x", key))))
+            .await
+            .unwrap();
+
+        assert_eq!(read.status(), StatusCode::OK);
+        assert_eq!(delete.status(), StatusCode::FORBIDDEN);
+        assert_eq!(forged.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_locked_account_cannot_use_valid_token() {
+        let (app, user, tokens, _, _, login_protection) = app_with_credentials(Role::Member).await;
+        let token = tokens.issue(user.id(), AuthLevel::SingleFactor).unwrap();
+        let user_id = user.id().to_string();
+        for _ in 0..LockoutPolicy::default().account_threshold {
+            login_protection.use_case().record_failure(Some(&user_id), None).await.unwrap();
+        }
+
+        let response = app
+            .oneshot(request("GET", format!("/api/users/{}", user.id()), Some(token)))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn test_session_cookie_requires_csrf_for_changes() {
+        let (app, admin, _, _, session_store, _) = app_with_credentials(Role::Admin).await;
+        let sessions = SessionDomainService::new(session_store, Sha256SecretHasher::new(), SessionPolicy::default());
+        let (_, tokens) = sessions
+            .start(admin.id().clone(), AuthLevel::MultiFactor, SessionMetadata::default(), Utc::now())
+            .await
+            .unwrap();
+        let (_, other_tokens) = sessions
+            .start(admin.id().clone(), AuthLevel::MultiFactor, SessionMetadata::default(), Utc::now())
+            .await
+            .unwrap();
+        let cookie_request = |method: &str, csrf_token: Option<&str>| {
+            let mut builder = Request::builder()
+                .method(method)
+                .uri(format!("/api/users/{}", UserId::new()))
+                .header(COOKIE, format!("session={}; csrf_token={}", tokens.session_token, csrf_token.unwrap_or("")));
+            if let Some(csrf_token) = csrf_token {
+                builder = builder.header(CSRF_HEADER, csrf_token);
+            }
+            builder.body(Body::empty()).unwrap()
+        };
+
+        let read = app.clone().oneshot(cookie_request("GET", None)).await.unwrap();
+        let without_csrf = app.clone().oneshot(cookie_request("DELETE", None)).await.unwrap();
+        let foreign_csrf = app.clone().oneshot(cookie_request("DELETE", Some(&other_tokens.csrf_token))).await.unwrap();
+        let with_csrf = app.oneshot(cookie_request("DELETE", Some(&tokens.csrf_token))).await.unwrap();
+
+        assert_eq!(read.status(), StatusCode::OK);
+        assert_eq!(without_csrf.status(), StatusCode::FORBIDDEN);
+        assert_eq!(foreign_csrf.status(), StatusCode::FORBIDDEN);
+        assert_eq!(with_csrf.status(), StatusCode::OK);
+    }
+}
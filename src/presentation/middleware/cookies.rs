@@ -0,0 +1,91 @@
+use axum::http::{header::COOKIE, HeaderMap};
+use chrono::Duration;
+use crate::infrastructure::AppConfig;
+
+pub const SESSION_COOKIE: &str = "session";
+// Читается скриптом панели и повторяется в заголовке `X-CSRF-Token`
+pub const CSRF_COOKIE: &str = "csrf_token";
+pub const CSRF_HEADER: &str = "x-csrf-token";
+
+#[derive(Debug, Clone)]
+pub struct SessionCookieConfig {
+    pub secure: bool,
+    pub max_age: Duration,
+}
+
+impl SessionCookieConfig {
+    pub fn from_app_config(config: &AppConfig) -> Self {
+        Self {
+            secure: config.session_cookie_secure,
+            max_age: config.session_policy().absolute_timeout,
+        }
+    }
+
+    pub fn session_cookies(&self, session_token: &str, csrf_token: &str) -> [String; 2] {
+        let max_age = self.max_age.num_seconds();
+        [
+            self.cookie(SESSION_COOKIE, session_token, true, max_age),
+            self.cookie(CSRF_COOKIE, csrf_token, false, max_age),
+        ]
+    }
+
+    pub fn cleared_cookies(&self) -> [String; 2] {
+        [
+            self.cookie(SESSION_COOKIE, "", true, 0),
+            self.cookie(CSRF_COOKIE, "", false, 0),
+        ]
+    }
+
+    fn cookie(&self, name: &str, value: &str, http_only: bool, max_age: i64) -> String {
+        let mut cookie = format!("{}={}; Path=/; Max-Age={}; SameSite=Strict", name, value, max_age);
+        if http_only {
+            cookie.push_str("; HttpOnly");
+        }
+        if self.secure {
+            cookie.push_str("; Secure");
+        }
+        cookie
+    }
+}
+
+pub(crate) fn read_cookie(headers: &HeaderMap, name: &str) -> Option<String> {
+    headers
+        .get_all(COOKIE)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(';'))
+        .filter_map(|pair| pair.trim().split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value.to_string())
+}
+
+// Сравнение за постоянное время
+pub(crate) fn tokens_match(left: &str, right: &str) -> bool {
+    left.len() == right.len()
+        && left.bytes().zip(right.bytes()).fold(0u8, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_cookie_attributes() {
+        let config = SessionCookieConfig { secure: true, max_age: Duration::hours(12) };
+
+        let [session, csrf] = config.session_cookies("abc", "xyz");
+
+        assert_eq!(session, "session=abc; Path=/; Max-Age=43200; SameSite=Strict; HttpOnly; Secure");
+        assert_eq!(csrf, "csrf_token=xyz; Path=/; Max-Age=43200; SameSite=Strict; Secure");
+    }
+
+    #[test]
+    fn test_read_cookie() {
+        let mut headers = HeaderMap::new();
+        headers.insert(COOKIE, "theme=dark; session=abc; csrf_token=xyz".parse().unwrap());
+
+        assert_eq!(read_cookie(&headers, SESSION_COOKIE).as_deref(), Some("abc"));
+        assert_eq!(read_cookie(&headers, CSRF_COOKIE).as_deref(), Some("xyz"));
+        assert!(read_cookie(&headers, "missing").is_none());
+    }
+}
\ No newline at end of file
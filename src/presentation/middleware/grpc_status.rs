@@ -0,0 +1,107 @@
+use axum::{
+    body::{to_bytes, Body},
+    extract::Request,
+    http::{header, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use tonic::{Code, Status};
+
+// Тело отказа middleware - короткий JSON, больше не читаем
+const MAX_REJECTION_BODY: usize = 64 * 1024;
+
+fn is_grpc(response: &Response) -> bool {
+    response
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/grpc"))
+}
+
+fn grpc_code(status: StatusCode) -> Code {
+    match status {
+        StatusCode::UNAUTHORIZED => Code::Unauthenticated,
+        StatusCode::FORBIDDEN => Code::PermissionDenied,
+        StatusCode::TOO_MANY_REQUESTS => Code::ResourceExhausted,
+        StatusCode::NOT_FOUND => Code::NotFound,
+        StatusCode::SERVICE_UNAVAILABLE => Code::Unavailable,
+        _ => Code::Unknown,
+    }
+}
+
+async fn rejection_message(status: StatusCode, body: Body) -> String {
+    let error = to_bytes(body, MAX_REJECTION_BODY)
+        .await
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok())
+        .and_then(|body| body.get("error")?.as_str().map(str::to_string));
+    error.unwrap_or_else(|| status.canonical_reason().unwrap_or("Request rejected").to_string())
+}
+
+/// Отказы слоев аутентификации и лимитов (HTTP 401/429 с JSON) на gRPC-маршрутах
+/// заменяются ответом trailers-only с `grpc-status`: gRPC-клиенты читают только
+/// его. `Retry-After` и `RateLimit-*` остаются метаданными ответа.
+pub async fn grpc_status_middleware(request: Request, next: Next) -> Response {
+    let response = next.run(request).await;
+    if is_grpc(&response) {
+        return response;
+    }
+
+    let (parts, body) = response.into_parts();
+    let message = rejection_message(parts.status, body).await;
+    let mut grpc_response = Status::new(grpc_code(parts.status), message).into_http::<Body>();
+    for (name, value) in &parts.headers {
+        if name != header::CONTENT_TYPE && name != header::CONTENT_LENGTH {
+            grpc_response.headers_mut().append(name.clone(), value.clone());
+        }
+    }
+    grpc_response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware::from_fn, response::IntoResponse, routing::post, Json, Router};
+    use tower::ServiceExt;
+    use crate::application::dto::ApiResponse;
+
+    async fn send(app: Router) -> Response {
+        let request = Request::builder().method("POST").uri("/").body(Body::empty()).unwrap();
+        app.oneshot(request).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_rejection_becomes_trailers_only_status() {
+        let app = Router::new()
+            .route("/", post(|| async {
+                let mut response = (
+                    StatusCode::TOO_MANY_REQUESTS,
+                    Json(ApiResponse::<()>::error("Rate limit exceeded".to_string())),
+                )
+                    .into_response();
+                response.headers_mut().insert(header::RETRY_AFTER, "30".parse().unwrap());
+                response
+            }))
+            .route_layer(from_fn(grpc_status_middleware));
+
+        let response = send(app).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[header::CONTENT_TYPE], "application/grpc");
+        assert_eq!(response.headers()["grpc-status"], "8");
+        assert_eq!(response.headers()["grpc-message"], "Rate%20limit%20exceeded");
+        assert_eq!(response.headers()[header::RETRY_AFTER], "30");
+    }
+
+    #[tokio::test]
+    async fn test_grpc_responses_pass_through() {
+        let app = Router::new()
+            .route("/", post(|| async { Status::not_found("missing").into_http::<Body>() }))
+            .route_layer(from_fn(grpc_status_middleware));
+
+        let response = send(app).await;
+
+        assert_eq!(response.headers()["grpc-status"], "5");
+        assert_eq!(response.headers()["grpc-message"], "missing");
+    }
+}
\ No newline at end of file
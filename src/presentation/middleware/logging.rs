@@ -4,11 +4,13 @@ use axum::{
     response::Response,
 };
 use std::time::Instant;
+use crate::infrastructure::AuditContext;
 
 pub async fn logging_middleware(request: Request, next: Next) -> Response {
     let start = Instant::now();
     let method = request.method().clone();
     let uri = request.uri().clone();
+    let request_id = AuditContext::current().map(|context| context.request_id().to_string());
     
     let response = next.run(request).await;
     
@@ -18,14 +20,11 @@ pub async fn logging_middleware(request: Request, next: Next) -> Response {
     tracing::info!(
         method = %method,
         uri = %uri,
+        request_id = request_id.as_deref().unwrap_or("-"),
         status = %status,
         duration = ?duration,
         "HTTP request processed"
     );
     
     response
-}
-
-pub fn build_logging_layer() -> axum::middleware::Layer {
-    axum::middleware::from_fn(logging_middleware)
 }
\ No newline at end of file
@@ -0,0 +1,142 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use axum::{
+    extract::{ConnectInfo, Path, Request, State},
+    http::{header::RETRY_AFTER, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use crate::application::LoginProtectionUseCase;
+use crate::application::dto::ApiResponse;
+use crate::domain::LockoutPolicy;
+use crate::infrastructure::{InMemoryUserRepository, LoginAttemptStoreBackend};
+
+type LoginProtection = LoginProtectionUseCase<InMemoryUserRepository, LoginAttemptStoreBackend>;
+
+#[derive(Clone)]
+pub struct LoginProtectionState {
+    login_protection_use_case: Arc<LoginProtection>,
+}
+
+impl LoginProtectionState {
+    pub fn new(user_repository: InMemoryUserRepository, attempt_store: LoginAttemptStoreBackend, policy: LockoutPolicy) -> Self {
+        Self {
+            login_protection_use_case: Arc::new(LoginProtectionUseCase::new(user_repository, attempt_store, policy)),
+        }
+    }
+
+    pub(crate) fn use_case(&self) -> &LoginProtection {
+        &self.login_protection_use_case
+    }
+}
+
+// Адрес берется из соединения (`into_make_service_with_connect_info`);
+// заголовкам вроде X-Forwarded-For без доверенного прокси верить нельзя
+pub(crate) fn client_ip(request: &Request) -> Option<IpAddr> {
+    request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(address)| address.ip())
+}
+
+pub(crate) fn rejection_response(retry_after: Option<u64>, error: String) -> Response {
+    let body = ApiResponse::<()>::error(error);
+    match retry_after {
+        Some(seconds) => (StatusCode::TOO_MANY_REQUESTS, [(RETRY_AFTER, seconds.to_string())], body).into_response(),
+        None => (StatusCode::INTERNAL_SERVER_ERROR, body).into_response(),
+    }
+}
+
+/// Защита шагов входа от перебора: проверяет блокировки до обработчика,
+/// а ответ 401 засчитывает как неудачную попытку для аккаунта `{id}` и адреса.
+pub async fn login_protection_middleware(
+    State(state): State<LoginProtectionState>,
+    path: Option<Path<HashMap<String, String>>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let ip = client_ip(&request);
+    let user_id = path.and_then(|Path(params)| params.get("id").cloned());
+
+    if let Err(error) = state.use_case().check(user_id.as_deref(), ip).await {
+        return rejection_response(error.retry_after(), error.to_string());
+    }
+
+    let response = next.run(request).await;
+
+    let recorded = match (response.status(), &user_id) {
+        (StatusCode::UNAUTHORIZED, _) => state.use_case().record_failure(user_id.as_deref(), ip).await,
+        (status, Some(user_id)) if status.is_success() => state.use_case().record_success(user_id).await,
+        _ => Ok(()),
+    };
+    if let Err(error) = recorded {
+        tracing::error!(error = %error, "Failed to record login attempt");
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::middleware::from_fn_with_state;
+    use axum::routing::post;
+    use axum::Router;
+    use tower::ServiceExt;
+    use crate::domain::UserId;
+    use crate::infrastructure::InMemoryLoginAttemptStore;
+
+    fn app() -> Router {
+        let state = LoginProtectionState::new(
+            InMemoryUserRepository::new(),
+            LoginAttemptStoreBackend::InMemory(InMemoryLoginAttemptStore::new()),
+            LockoutPolicy::default(),
+        );
+
+        Router::new()
+            .route(
+                "/api/users/{id}/mfa/challenge",
+                post(|body: String| async move {
+                    if body == "good" { StatusCode::OK } else { StatusCode::UNAUTHORIZED }
+                }),
+            )
+            .route_layer(from_fn_with_state(state, login_protection_middleware))
+    }
+
+    fn request(user_id: &UserId, body: &str) -> Request {
+        let mut request = Request::builder()
+            .method("POST")
+            .uri(format!("/api/users/{}/mfa/challenge", user_id))
+            .body(Body::from(body.to_string()))
+            .unwrap();
+        request.extensions_mut().insert(ConnectInfo(SocketAddr::from(([198, 51, 100, 4], 50000))));
+        request
+    }
+
+    #[tokio::test]
+    async fn test_failed_attempt_delays_next_one() {
+        let app = app();
+        let user_id = UserId::new();
+
+        let first = app.clone().oneshot(request(&user_id, "bad")).await.unwrap();
+        let second = app.oneshot(request(&user_id, "good")).await.unwrap();
+
+        assert_eq!(first.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(second.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(second.headers()[RETRY_AFTER], "1");
+    }
+
+    #[tokio::test]
+    async fn test_successful_attempt_is_not_throttled() {
+        let app = app();
+        let user_id = UserId::new();
+
+        let first = app.clone().oneshot(request(&user_id, "good")).await.unwrap();
+        let second = app.oneshot(request(&user_id, "good")).await.unwrap();
+
+        assert_eq!(first.status(), StatusCode::OK);
+        assert_eq!(second.status(), StatusCode::OK);
+    }
+}
\ No newline at end of file
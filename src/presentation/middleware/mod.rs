@@ -1,3 +1,19 @@
 pub mod logging;
+pub mod auth;
+pub mod login_protection;
+pub mod cookies;
+pub mod scim_auth;
+pub mod rate_limit;
+pub mod request_context;
+pub mod grpc_status;
+pub mod api_version;
 
-pub use logging::*;
\ No newline at end of file
+pub use logging::*;
+pub use auth::*;
+pub use login_protection::*;
+pub use cookies::*;
+pub use scim_auth::*;
+pub use rate_limit::*;
+pub use request_context::*;
+pub use grpc_status::*;
+pub use api_version::*;
\ No newline at end of file
@@ -0,0 +1,134 @@
+use std::sync::Arc;
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use chrono::Duration;
+use crate::application::RateLimitUseCase;
+use crate::domain::{Actor, RateLimitDecision, RateLimitKey, RateLimitQuota};
+use crate::infrastructure::RateLimitStoreBackend;
+use crate::presentation::middleware::{client_ip, rejection_response};
+
+// Заголовки из черновика IETF "RateLimit header fields for HTTP"
+const RATE_LIMIT_LIMIT: HeaderName = HeaderName::from_static("ratelimit-limit");
+const RATE_LIMIT_REMAINING: HeaderName = HeaderName::from_static("ratelimit-remaining");
+const RATE_LIMIT_RESET: HeaderName = HeaderName::from_static("ratelimit-reset");
+const RATE_LIMIT_POLICY: HeaderName = HeaderName::from_static("ratelimit-policy");
+
+/// Общее хранилище бюджетов и квота группы маршрутов, к которой применен слой.
+#[derive(Clone)]
+pub struct RateLimitState {
+    rate_limit_use_case: Arc<RateLimitUseCase<RateLimitStoreBackend>>,
+    group: &'static str,
+    quota: Option<RateLimitQuota>,
+}
+
+impl RateLimitState {
+    pub fn new(rate_limit_store: RateLimitStoreBackend) -> Self {
+        Self {
+            rate_limit_use_case: Arc::new(RateLimitUseCase::new(rate_limit_store)),
+            group: "default",
+            quota: None,
+        }
+    }
+
+    pub fn for_group(&self, group: &'static str, quota: Option<RateLimitQuota>) -> Self {
+        Self {
+            rate_limit_use_case: self.rate_limit_use_case.clone(),
+            group,
+            quota,
+        }
+    }
+}
+
+// После аутентификации бюджет принадлежит API-ключу или пользователю, иначе адресу
+fn rate_limit_key(request: &Request) -> RateLimitKey {
+    if let Some(actor) = request.extensions().get::<Actor>() {
+        return match actor.api_key_id() {
+            Some(api_key_id) => RateLimitKey::ApiKey(*api_key_id),
+            None => RateLimitKey::User(actor.user_id().clone()),
+        };
+    }
+    client_ip(request).map_or(RateLimitKey::Unknown, RateLimitKey::ClientIp)
+}
+
+fn whole_seconds(duration: Duration) -> u64 {
+    (duration.num_milliseconds().max(0) as u64).div_ceil(1000)
+}
+
+fn insert_headers(headers: &mut HeaderMap, quota: &RateLimitQuota, decision: &RateLimitDecision) {
+    let values = [
+        (RATE_LIMIT_LIMIT, decision.limit.to_string()),
+        (RATE_LIMIT_REMAINING, decision.remaining.to_string()),
+        (RATE_LIMIT_RESET, whole_seconds(decision.reset_after).to_string()),
+        (RATE_LIMIT_POLICY, format!("{};w={}", quota.limit(), quota.period().num_seconds())),
+    ];
+    for (name, value) in values {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(name, value);
+        }
+    }
+}
+
+pub async fn rate_limit_middleware(State(state): State<RateLimitState>, request: Request, next: Next) -> Response {
+    let Some(quota) = state.quota else {
+        return next.run(request).await;
+    };
+    
+    let key = rate_limit_key(&request);
+    let decision = match state.rate_limit_use_case.check(state.group, &key, &quota).await {
+        Ok(decision) => decision,
+        // Недоступность хранилища не должна останавливать сервис
+        Err(error) => {
+            tracing::error!(error = %error, group = state.group, "Rate limit check failed");
+            return next.run(request).await;
+        }
+    };
+    
+    if !decision.allowed {
+        let retry_after = whole_seconds(decision.retry_after.unwrap_or(decision.reset_after)).max(1);
+        let mut response = rejection_response(
+            Some(retry_after),
+            format!("Rate limit exceeded, retry in {} seconds", retry_after),
+        );
+        insert_headers(response.headers_mut(), &quota, &decision);
+        return response;
+    }
+    
+    let mut response = next.run(request).await;
+    insert_headers(response.headers_mut(), &quota, &decision);
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Body, http::StatusCode, middleware::from_fn_with_state, routing::get, Router};
+    use tower::ServiceExt;
+    use crate::infrastructure::InMemoryRateLimitStore;
+
+    #[tokio::test]
+    async fn test_requests_over_quota_are_rejected() {
+        let quota = RateLimitQuota::new(2, Duration::minutes(1)).unwrap();
+        let state = RateLimitState::new(RateLimitStoreBackend::InMemory(InMemoryRateLimitStore::new()))
+            .for_group("public", Some(quota));
+        let app = Router::new()
+            .route("/", get(|| async { "ok" }))
+            .route_layer(from_fn_with_state(state, rate_limit_middleware));
+        let request = || Request::builder().uri("/").body(Body::empty()).unwrap();
+
+        let response = app.clone().oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["ratelimit-limit"], "2");
+        assert_eq!(response.headers()["ratelimit-remaining"], "1");
+        assert_eq!(response.headers()["ratelimit-policy"], "2;w=60");
+
+        app.clone().oneshot(request()).await.unwrap();
+        let response = app.oneshot(request()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()["retry-after"], "30");
+        assert_eq!(response.headers()["ratelimit-remaining"], "0");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,36 @@
+use axum::{
+    extract::Request,
+    http::{HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use uuid::Uuid;
+use crate::infrastructure::AuditContext;
+use crate::presentation::middleware::client_ip;
+
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+// Идентификатор от клиента или прокси принимается, только если он короткий
+// и печатный: он попадает в журнал аудита и логи
+fn incoming_request_id(request: &Request) -> Option<String> {
+    request
+        .headers()
+        .get(&REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(str::trim)
+        .filter(|id| !id.is_empty() && id.len() <= 128 && id.chars().all(|c| c.is_ascii_graphic()))
+        .map(str::to_string)
+}
+
+/// Задает контекст запроса для журнала аудита и возвращает `X-Request-Id`
+/// в ответе. Подключается ко всему приложению, до аутентификации.
+pub async fn request_context_middleware(request: Request, next: Next) -> Response {
+    let request_id = incoming_request_id(&request).unwrap_or_else(|| Uuid::new_v4().to_string());
+    let context = AuditContext::new(request_id.clone(), client_ip(&request));
+
+    let mut response = context.scope(next.run(request)).await;
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
\ No newline at end of file
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+use axum::{
+    extract::{Request, State},
+    http::{header::{AUTHORIZATION, CONTENT_TYPE, WWW_AUTHENTICATE}, HeaderValue, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use crate::application::ScimError;
+use crate::infrastructure::AuditContext;
+use crate::presentation::middleware::tokens_match;
+
+pub const SCIM_CONTENT_TYPE: &str = "application/scim+json";
+
+// Ответы SCIM отдаются с собственным media type (RFC 7644, раздел 3.1)
+pub(crate) fn scim_response<T: Serialize>(status: StatusCode, body: T) -> Response {
+    let mut response = (status, Json(body)).into_response();
+    response.headers_mut().insert(CONTENT_TYPE, HeaderValue::from_static(SCIM_CONTENT_TYPE));
+    response
+}
+
+pub(crate) fn scim_error_response(error: ScimError) -> Response {
+    let status = StatusCode::from_u16(error.status_code()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+    scim_response(status, error)
+}
+
+/// IdP не является пользователем сервиса, поэтому SCIM защищен отдельным
+/// долгоживущим bearer-токеном из конфигурации, а не JWT пользователя.
+#[derive(Clone)]
+pub struct ScimAuthState {
+    token: Arc<str>,
+}
+
+impl ScimAuthState {
+    pub fn new(token: String) -> Self {
+        Self { token: token.into() }
+    }
+}
+
+pub async fn scim_auth_middleware(State(scim_auth): State<ScimAuthState>, request: Request, next: Next) -> Response {
+    let authorized = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| tokens_match(token.trim(), &scim_auth.token));
+    
+    if authorized {
+        AuditContext::set_actor("scim");
+        return next.run(request).await;
+    }
+    
+    let mut response = scim_error_response(ScimError::new(401, None, "Missing or invalid bearer token"));
+    response.headers_mut().insert(WWW_AUTHENTICATE, HeaderValue::from_static("Bearer realm=\"SCIM\""));
+    response
+}
\ No newline at end of file
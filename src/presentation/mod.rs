@@ -0,0 +1,7 @@
+pub mod handlers;
+pub mod middleware;
+pub mod routers;
+
+pub use handlers::*;
+pub use middleware::*;
+pub use routers::*;
\ No newline at end of file
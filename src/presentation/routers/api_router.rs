@@ -1,73 +1,1145 @@
 use axum::{
     Router,
+    middleware::{from_fn, from_fn_with_state},
     routing::{get, post, put, delete},
 };
+use tonic::server::NamedService;
 use tower::ServiceBuilder;
 use tower_http::cors::{CorsLayer, Any};
-use crate::presentation::{user_handlers, logging};
+use crate::presentation::{
+    user_handlers, outbox_handlers, api_key_handlers, mfa_handlers, login_protection_handlers, session_handlers,
+    oidc_handlers, scim_handlers, user_data_handlers, audit_handlers, webhook_handlers, user_change_handlers, websocket_handlers,
+    graphql_handlers, graphql_schema, grpc_handlers, openapi_handlers, metrics_handlers, logging,
+};
+use crate::presentation::middleware::{
+    api_version_middleware, auth_middleware, authorization_middleware, grpc_status_middleware, login_protection_middleware, rate_limit_middleware,
+    request_context_middleware, scim_auth_middleware, ApiVersion, ApiVersionMetrics, ApiVersionState, AuthState,
+    LoginProtectionState, RateLimitState, ScimAuthState, SessionCookieConfig, VersionDeprecation,
+};
+use crate::application::{
+    ApiKeyApplicationService, AuditApplicationService, LoginProtectionApplicationService, MfaApplicationService, OutboxAdminService,
+    OidcApplicationService, RotatePiiKeysUseCase, ScimApplicationService, SessionApplicationService,
+    UserApplicationService, UserDataApplicationService, WebhookApplicationService,
+};
+use crate::domain::{MfaDomainService, Permission, UserEventBus};
+use crate::infrastructure::{
+    AesGcmSecretCipher, AppConfig, AuditStoreBackend, AuditTrail, EmailOutboxSink, EmailServiceBackend, EnvelopePiiCipher, InMemoryApiKeyRepository,
+    InMemoryErasureReceiptRepository,
+    InMemoryExternalIdentityRepository, InMemoryMfaRepository, InMemoryOidcLoginStore, InMemoryUserRepository, OidcClient, JwtTokenService, LoggingEventSink, LoginAttemptStoreBackend,
+    OutboxDispatcher, OutboxDispatcherConfig, RateLimitStoreBackend, Rfc6238TotpService, SessionStoreBackend,
+    Sha256SecretHasher, subscribe_event_logging, subscribe_user_change_feed, subscribe_webhooks, InMemoryWebhookRepository,
+    UserChangeFeed, WebhookDispatcher, WebhookDispatcherConfig,
+};
+
+const MFA_ISSUER: &str = "RustCleanArchitecture";
 
-pub fn create_app_router() -> Router {
+// Конфигурация из всех слоев, включая аргументы командной строки (ConfigCommand::from_args)
+pub fn create_app_router(config: AppConfig) -> Router {
     // Настройка CORS
     let cors = CorsLayer::new()
         .allow_origin(Any)
         .allow_methods(Any)
         .allow_headers(Any);
     
+    // Email и имена в репозитории пользователей и журнале аудита защищены ключами из PII_KEYFILE
+    let pii_cipher = EnvelopePiiCipher::from_app_config(&config).expect("Invalid PII_KEYFILE");
+    
+    // Журнал аудита: изменения пользователей, ролей, API-ключей и MFA
+    let audit_store = AuditStoreBackend::from_app_config(&config, pii_cipher.clone()).expect("Invalid AUDIT_LOG_PATH");
+    let audit_trail = AuditTrail::new(audit_store.clone());
+    
     // Создаем пользовательское приложение (с in-memory репозиторием для примера)
-    let user_repository = infrastructure::InMemoryUserRepository::new();
-    let user_application_service = application::UserApplicationService::new(user_repository);
+    let user_repository = InMemoryUserRepository::with_cipher(pii_cipher).with_audit_trail(audit_trail.clone());
+    
+    // Доменные события пользователей публикуются после сохранения; сюда же
+    // подключаются внутрипроцессные подписчики (журнал, уведомления и т.д.)
+    let user_event_bus = UserEventBus::new();
+    subscribe_event_logging(&user_event_bus);
+    
+    // Исходящие webhooks: события ставятся в очередь доставок, отправляет их диспетчер
+    let webhook_repository = InMemoryWebhookRepository::new();
+    subscribe_webhooks(&user_event_bus, webhook_repository.clone());
+    let webhook_cipher = AesGcmSecretCipher::for_webhooks(&config).expect("Invalid WEBHOOK_ENCRYPTION_KEY");
+    let webhook_dispatcher = WebhookDispatcher::new(
+        webhook_repository.clone(),
+        webhook_cipher.clone(),
+        WebhookDispatcherConfig::default(),
+    );
+    tokio::spawn(webhook_dispatcher.run());
+    
+    // Лента изменений для SSE-клиентов (панель администратора)
+    let user_change_feed = UserChangeFeed::new();
+    subscribe_user_change_feed(&user_event_bus, user_change_feed.clone());
+    
+    // GraphQL использует те же use case и ту же шину событий, что и REST
+    let graphql_schema = graphql_schema::build_user_schema(
+        graphql_schema::GraphQlUseCases::new(user_repository.clone(), user_event_bus.clone()),
+        user_change_feed.clone(),
+        &config,
+    );
+    
+    // gRPC для внутренних сервисов поверх того же прикладного сервиса
+    let grpc_user_service = grpc_handlers::GrpcUserService::new(
+        UserApplicationService::new(user_repository.clone()).with_event_bus(user_event_bus.clone()),
+        user_change_feed.clone(),
+    );
+    let user_application_service = UserApplicationService::new(user_repository.clone())
+        .with_event_bus(user_event_bus);
+    
+    // Фоновая доставка сообщений outbox (письма и события пользователей);
+    // без EMAIL_SERVICE_URL письма печатаются в консоль
+    let email_service = EmailServiceBackend::from_app_config(&config).expect("Invalid EMAIL_SERVICE_URL");
+    let outbox_dispatcher = OutboxDispatcher::new(
+        user_repository.clone(),
+        (EmailOutboxSink::new(email_service, user_repository.clone()), LoggingEventSink),
+        OutboxDispatcherConfig::default(),
+    );
+    tokio::spawn(outbox_dispatcher.run());
+    
+    // После смены active_key_id записи переоборачиваются новым мастер-ключом
+    let pii_rotation = RotatePiiKeysUseCase::new(user_repository.clone());
+    tokio::spawn(async move {
+        match pii_rotation.execute().await {
+            Ok(0) => {}
+            Ok(rotated) => tracing::info!(rotated, "PII data keys rewrapped with active master key"),
+            Err(error) => tracing::error!(error = %error, "PII key rotation failed"),
+        }
+    });
+    
+    let api_key_repository = InMemoryApiKeyRepository::new().with_audit_trail(audit_trail.clone());
+    let mfa_repository = InMemoryMfaRepository::new().with_audit_trail(audit_trail);
+    let identity_repository = InMemoryExternalIdentityRepository::new();
+    
+    // Счетчики неудачных попыток входа общие для всех маршрутов
+    let login_attempt_store = LoginAttemptStoreBackend::from_app_config(&config).expect("Invalid LOGIN_ATTEMPTS_PATH");
+    let login_protection = LoginProtectionState::new(
+        user_repository.clone(),
+        login_attempt_store.clone(),
+        config.lockout_policy(),
+    );
+    
+    // Cookie-сессии браузерной панели: SQL-хранилище при `DATABASE_URL=sqlite:...`
+    let session_store = SessionStoreBackend::from_app_config(&config).expect("Invalid DATABASE_URL");
+    let auth_state = AuthState::new(
+        user_repository.clone(),
+        JwtTokenService::from_app_config(&config),
+        api_key_repository.clone(),
+        session_store.clone(),
+        config.session_policy(),
+        login_protection.clone(),
+    );
+    
+    // Бюджеты запросов общие для всех групп маршрутов, квоты у каждой группы свои
+    let rate_limit = RateLimitState::new(
+        RateLimitStoreBackend::from_app_config(&config).expect("Invalid RATE_LIMIT_STORE_URL"),
+    );
+    let public_rate_limit = rate_limit.for_group("public", config.rate_limits.public);
+    let users_rate_limit = rate_limit.for_group("users", config.rate_limits.users);
+    let admin_rate_limit = rate_limit.for_group("admin", config.rate_limits.admin);
+    
+    let public_user_routes = Router::new()
+        .route("/users", post(user_handlers::create_user_handler))
+        .route_layer(from_fn_with_state(public_rate_limit.clone(), rate_limit_middleware));
+    
+    // Защищенные маршруты: сначала аутентификация, затем проверка прав для каждого метода
+    let protected_user_routes = Router::new()
+        .route("/users", get(user_handlers::list_users_handler)
+            .layer(from_fn_with_state(Permission::ListUsers, authorization_middleware)))
+        .route("/users/{id}", get(user_handlers::get_user_handler)
+            .layer(from_fn_with_state(Permission::ReadUser, authorization_middleware)))
+        .route("/users/email", post(user_handlers::get_user_by_email_handler)
+            .layer(from_fn_with_state(Permission::FindUserByEmail, authorization_middleware)))
+        .route("/users/{id}", put(user_handlers::update_user_handler)
+            .layer(from_fn_with_state(Permission::UpdateUser, authorization_middleware)))
+        .route("/users/{id}", delete(user_handlers::delete_user_handler)
+            .layer(from_fn_with_state(Permission::DeleteUser, authorization_middleware)))
+        .route("/users/{id}/roles", put(user_handlers::change_user_roles_handler)
+            .layer(from_fn_with_state(Permission::ChangeRoles, authorization_middleware)))
+        .route_layer(from_fn_with_state(users_rate_limit.clone(), rate_limit_middleware))
+        .route_layer(from_fn_with_state(auth_state.clone(), auth_middleware));
+    
+    let user_routes = Router::new()
+        .merge(public_user_routes)
+        .merge(protected_user_routes)
+        .with_state(user_handlers::UserRoutesState {
+            users: user_application_service,
+            api_keys: ApiKeyApplicationService::new(user_repository.clone(), api_key_repository.clone(), Sha256SecretHasher::new()),
+        });
+    
+    // Все маршруты /api доступны как /api/v1, /api/v2 и /api (версия из
+    // Accept-Version, по умолчанию v1); формат ответа приводит api_version_middleware
+    let api_routes = user_routes
+        // API-ключи пользователей
+        .merge(create_api_key_router(
+            user_repository.clone(),
+            api_key_repository.clone(),
+            auth_state.clone(),
+            users_rate_limit.clone(),
+        ))
+        
+        // Двухфакторная аутентификация (TOTP)
+        .merge(create_mfa_router(
+            user_repository.clone(),
+            mfa_repository.clone(),
+            &config,
+            auth_state.clone(),
+            login_protection.clone(),
+            users_rate_limit.clone(),
+        ))
+        
+        // Вход через внешних OIDC-провайдеров
+        .merge(create_oidc_router(
+            user_repository.clone(),
+            identity_repository.clone(),
+            &config,
+            login_protection,
+            public_rate_limit,
+        ))
+        
+        // Cookie-сессии
+        .merge(create_session_router(
+            user_repository.clone(),
+            session_store.clone(),
+            &config,
+            auth_state.clone(),
+            users_rate_limit.clone(),
+        ))
+        
+        // Выгрузка и стирание персональных данных (GDPR)
+        .merge(create_user_data_router(
+            UserDataApplicationService::new(
+                user_repository.clone(),
+                api_key_repository.clone(),
+                session_store,
+                mfa_repository,
+                identity_repository,
+                InMemoryErasureReceiptRepository::new(),
+                audit_store.clone(),
+                config.session_policy(),
+            ),
+            auth_state.clone(),
+            users_rate_limit.clone(),
+            admin_rate_limit.clone(),
+        ))
+        
+        // Журнал аудита
+        .merge(create_audit_router(
+            AuditApplicationService::new(audit_store),
+            auth_state.clone(),
+            users_rate_limit.clone(),
+            admin_rate_limit.clone(),
+        ))
+        
+        // Снятие блокировки после перебора
+        .merge(create_unlock_router(
+            user_repository.clone(),
+            login_attempt_store,
+            &config,
+            auth_state.clone(),
+            admin_rate_limit.clone(),
+        ))
+        
+        // Лента изменений пользователей (SSE)
+        .merge(create_user_changes_router(user_change_feed.clone(), auth_state.clone(), users_rate_limit.clone()))
+        
+        // Подписки на исходящие webhooks
+        .merge(create_webhook_router(
+            WebhookApplicationService::new(webhook_repository, webhook_cipher),
+            auth_state.clone(),
+            admin_rate_limit.clone(),
+        ))
+        
+        // Администрирование outbox
+        .merge(create_outbox_admin_router(user_repository.clone(), auth_state.clone(), admin_rate_limit));
+    
+    let api_version_metrics = ApiVersionMetrics::new();
+    let api_versions = ApiVersionState::new(api_version_metrics.clone()).with_deprecation(
+        ApiVersion::V1,
+        VersionDeprecation {
+            deprecated_at: config.api_v1_deprecated_at,
+            sunset_at: config.api_v1_sunset_at,
+        },
+    );
     
     Router::new()
         // Health check
         .route("/health", get(user_handlers::health_handler))
         
-        // User routes
-        .route("/api/users", post(create_user_handler))
-        .route("/api/users/:id", get(get_user_handler))
-        .route("/api/users/email", post(get_user_by_email_handler))
-        .route("/api/users/:id", put(update_user_handler))
-        .route("/api/users/:id", delete(delete_user_handler))
+        // Описание API (OpenAPI 3.1) и его просмотр в Redoc
+        .route("/openapi.json", get(openapi_handlers::openapi_handler))
+        .route("/docs", get(openapi_handlers::api_docs_handler))
+        .route("/docs/redoc.standalone.js", get(openapi_handlers::redoc_bundle_handler))
+        
+        // Запросы по версиям API в формате Prometheus
+        .merge(Router::new()
+            .route("/metrics", get(metrics_handlers::metrics_handler))
+            .with_state(api_version_metrics))
+        
+        // REST API
+        .nest("/api/v1", api_routes.clone()
+            .layer(from_fn_with_state(api_versions.for_version(ApiVersion::V1), api_version_middleware)))
+        .nest("/api/v2", api_routes.clone()
+            .layer(from_fn_with_state(api_versions.for_version(ApiVersion::V2), api_version_middleware)))
+        .nest("/api", api_routes.layer(from_fn_with_state(api_versions, api_version_middleware)))
         
-        // Добавляем состояние приложения
-        .with_state(user_application_service)
+        // Провижининг пользователей из IdP (SCIM 2.0)
+        .merge(create_scim_router(user_repository.clone(), &config, rate_limit.for_group("scim", config.rate_limits.scim)))
+        
+        // Подписки на изменения пользователей по WebSocket
+        .merge(create_websocket_router(
+            websocket_handlers::WebSocketState::new(user_change_feed),
+            auth_state.clone(),
+            users_rate_limit.clone(),
+        ))
+        
+        // GraphQL API пользователей
+        .merge(create_graphql_router(graphql_schema, &config, auth_state.clone(), users_rate_limit.clone()))
+        
+        // gRPC UserService, health и reflection на том же порту
+        .merge(create_grpc_router(grpc_user_service, auth_state.clone(), users_rate_limit))
         
         // Добавляем middleware
         .layer(ServiceBuilder::new().layer(cors))
-        .layer(logging::build_logging_layer())
+        .layer(from_fn(logging::logging_middleware))
+        .layer(from_fn(request_context_middleware))
+}
+
+fn create_api_key_router(
+    user_repository: InMemoryUserRepository,
+    api_key_repository: InMemoryApiKeyRepository,
+    auth_state: AuthState,
+    rate_limit: RateLimitState,
+) -> Router {
+    Router::new()
+        .route("/users/{id}/api-keys", post(api_key_handlers::create_api_key_handler)
+            .get(api_key_handlers::list_api_keys_handler))
+        .route("/users/{id}/api-keys/{key_id}", delete(api_key_handlers::revoke_api_key_handler))
+        .route_layer(from_fn_with_state(Permission::ManageApiKeys, authorization_middleware))
+        .route_layer(from_fn_with_state(rate_limit, rate_limit_middleware))
+        .route_layer(from_fn_with_state(auth_state, auth_middleware))
+        .with_state(ApiKeyApplicationService::new(user_repository, api_key_repository, Sha256SecretHasher::new()))
+}
+
+fn create_mfa_router(
+    user_repository: InMemoryUserRepository,
+    mfa_repository: InMemoryMfaRepository,
+    config: &AppConfig,
+    auth_state: AuthState,
+    login_protection: LoginProtectionState,
+    rate_limit: RateLimitState,
+) -> Router {
+    let secret_cipher = AesGcmSecretCipher::from_app_config(config).expect("Invalid MFA_ENCRYPTION_KEY");
+    let mfa_domain_service = MfaDomainService::new(
+        mfa_repository,
+        secret_cipher,
+        Rfc6238TotpService::new(MFA_ISSUER),
+        Sha256SecretHasher::new(),
+    );
+    
+    // Проверка второго фактора защищена от перебора кодов
+    Router::new()
+        .route("/users/{id}/mfa", get(mfa_handlers::get_mfa_status_handler))
+        .route("/users/{id}/mfa", delete(mfa_handlers::disable_mfa_handler)
+            .layer(from_fn_with_state(login_protection.clone(), login_protection_middleware)))
+        .route("/users/{id}/mfa/enroll", post(mfa_handlers::enroll_mfa_handler))
+        .route("/users/{id}/mfa/qr", get(mfa_handlers::get_mfa_qr_code_handler))
+        .route("/users/{id}/mfa/confirm", post(mfa_handlers::confirm_mfa_handler))
+        .route("/users/{id}/mfa/challenge", post(mfa_handlers::mfa_challenge_handler)
+            .layer(from_fn_with_state(login_protection, login_protection_middleware)))
+        .route_layer(from_fn_with_state(Permission::ManageMfa, authorization_middleware))
+        .route_layer(from_fn_with_state(rate_limit, rate_limit_middleware))
+        .route_layer(from_fn_with_state(auth_state, auth_middleware))
+        .with_state(MfaApplicationService::new(
+            user_repository,
+            mfa_domain_service,
+            JwtTokenService::from_app_config(config),
+        ))
+}
+
+fn create_oidc_router(
+    user_repository: InMemoryUserRepository,
+    identity_repository: InMemoryExternalIdentityRepository,
+    config: &AppConfig,
+    login_protection: LoginProtectionState,
+    rate_limit: RateLimitState,
+) -> Router {
+    let providers = config
+        .oidc_providers
+        .iter()
+        .map(|provider| (provider.name.clone(), OidcClient::new(provider.clone())));
+    
+    // Обмен кода на токен защищен от перебора так же, как вход по JWT
+    Router::new()
+        .route("/auth/oidc/{provider}/login", get(oidc_handlers::oidc_login_handler))
+        .route("/auth/oidc/{provider}/callback", get(oidc_handlers::oidc_callback_handler)
+            .layer(from_fn_with_state(login_protection, login_protection_middleware)))
+        .route_layer(from_fn_with_state(rate_limit, rate_limit_middleware))
+        .with_state(OidcApplicationService::new(
+            user_repository,
+            identity_repository,
+            InMemoryOidcLoginStore::new(),
+            JwtTokenService::from_app_config(config),
+            providers,
+        ))
+}
+
+fn create_scim_router(user_repository: InMemoryUserRepository, config: &AppConfig, rate_limit: RateLimitState) -> Router {
+    // Без SCIM_BEARER_TOKEN провижининг отключен
+    let Some(token) = config.scim_bearer_token.clone() else {
+        return Router::new();
+    };
+    
+    let user_routes = Router::new()
+        .route("/scim/v2/Users", get(scim_handlers::list_scim_users_handler)
+            .post(scim_handlers::create_scim_user_handler))
+        .route("/scim/v2/Users/{id}", get(scim_handlers::get_scim_user_handler)
+            .put(scim_handlers::replace_scim_user_handler)
+            .patch(scim_handlers::patch_scim_user_handler)
+            .delete(scim_handlers::delete_scim_user_handler))
+        .route_layer(from_fn_with_state(ScimAuthState::new(token), scim_auth_middleware));
+    
+    // Discovery не раскрывает данных и доступен без токена (RFC 7644, раздел 4)
+    Router::new()
+        .route("/scim/v2/ServiceProviderConfig", get(scim_handlers::service_provider_config_handler))
+        .route("/scim/v2/ResourceTypes", get(scim_handlers::resource_types_handler))
+        .route("/scim/v2/ResourceTypes/{name}", get(scim_handlers::resource_type_handler))
+        .route("/scim/v2/Schemas", get(scim_handlers::schemas_handler))
+        .route("/scim/v2/Schemas/{id}", get(scim_handlers::schema_handler))
+        .merge(user_routes)
+        // Бюджет по адресу IdP, включая запросы с неверным токеном
+        .route_layer(from_fn_with_state(rate_limit, rate_limit_middleware))
+        .with_state(ScimApplicationService::new(user_repository))
+}
+
+fn create_session_router(
+    user_repository: InMemoryUserRepository,
+    session_store: SessionStoreBackend,
+    config: &AppConfig,
+    auth_state: AuthState,
+    rate_limit: RateLimitState,
+) -> Router {
+    let state = session_handlers::SessionState {
+        session_service: SessionApplicationService::new(
+            user_repository,
+            session_store,
+            Sha256SecretHasher::new(),
+            config.session_policy(),
+        ),
+        cookie_config: SessionCookieConfig::from_app_config(config),
+    };
+    
+    // Вход и выход относятся к самому вызывающему, отдельные права не нужны
+    let own_session_routes = Router::new()
+        .route("/sessions", post(session_handlers::create_session_handler))
+        .route("/sessions/current", delete(session_handlers::end_current_session_handler));
+    
+    let user_session_routes = Router::new()
+        .route("/users/{id}/sessions", get(session_handlers::list_sessions_handler)
+            .delete(session_handlers::revoke_all_sessions_handler))
+        .route("/users/{id}/sessions/{session_id}", delete(session_handlers::revoke_session_handler))
+        .route_layer(from_fn_with_state(Permission::ManageSessions, authorization_middleware));
+    
+    own_session_routes
+        .merge(user_session_routes)
+        .route_layer(from_fn_with_state(rate_limit, rate_limit_middleware))
+        .route_layer(from_fn_with_state(auth_state, auth_middleware))
+        .with_state(state)
+}
+
+fn create_user_data_router(
+    user_data_service: user_data_handlers::UserDataService,
+    auth_state: AuthState,
+    users_rate_limit: RateLimitState,
+    admin_rate_limit: RateLimitState,
+) -> Router {
+    // Выгрузку запрашивает сам пользователь или администратор, стирание - только администратор
+    let export_routes = Router::new()
+        .route("/users/{id}/data-export", get(user_data_handlers::export_user_data_handler))
+        .route_layer(from_fn_with_state(Permission::ExportUserData, authorization_middleware))
+        .route_layer(from_fn_with_state(users_rate_limit, rate_limit_middleware));
+    
+    let erase_routes = Router::new()
+        .route("/users/{id}/erase", post(user_data_handlers::erase_user_handler))
+        .route_layer(from_fn_with_state(Permission::EraseUser, authorization_middleware))
+        .route_layer(from_fn_with_state(admin_rate_limit, rate_limit_middleware));
+    
+    export_routes
+        .merge(erase_routes)
+        .route_layer(from_fn_with_state(auth_state, auth_middleware))
+        .with_state(user_data_service)
+}
+
+fn create_audit_router(
+    audit_service: audit_handlers::AuditService,
+    auth_state: AuthState,
+    users_rate_limit: RateLimitState,
+    admin_rate_limit: RateLimitState,
+) -> Router {
+    // Свой журнал видит пользователь, чужие - поддержка; целостность всей цепочки проверяет администратор
+    let user_routes = Router::new()
+        .route("/users/{id}/audit", get(audit_handlers::list_user_audit_events_handler))
+        .route_layer(from_fn_with_state(Permission::ReadAuditTrail, authorization_middleware))
+        .route_layer(from_fn_with_state(users_rate_limit, rate_limit_middleware));
+    
+    let verify_routes = Router::new()
+        .route("/admin/audit/verify", get(audit_handlers::verify_audit_chain_handler))
+        .route_layer(from_fn_with_state(Permission::VerifyAuditTrail, authorization_middleware))
+        .route_layer(from_fn_with_state(admin_rate_limit, rate_limit_middleware));
+    
+    user_routes
+        .merge(verify_routes)
+        .route_layer(from_fn_with_state(auth_state, auth_middleware))
+        .with_state(audit_service)
+}
+
+fn create_unlock_router(
+    user_repository: InMemoryUserRepository,
+    login_attempt_store: LoginAttemptStoreBackend,
+    config: &AppConfig,
+    auth_state: AuthState,
+    rate_limit: RateLimitState,
+) -> Router {
+    Router::new()
+        .route("/users/{id}/unlock", post(login_protection_handlers::unlock_user_handler))
+        .route_layer(from_fn_with_state(Permission::UnlockUser, authorization_middleware))
+        .route_layer(from_fn_with_state(rate_limit, rate_limit_middleware))
+        .route_layer(from_fn_with_state(auth_state, auth_middleware))
+        .with_state(LoginProtectionApplicationService::new(user_repository, login_attempt_store, config.lockout_policy()))
 }
 
-fn create_user_handler(
-    state: axum::extract::State<application::UserApplicationService<infrastructure::InMemoryUserRepository>>,
-    request: axum::extract::Json<application::dto::CreateUserRequest>,
-) -> axum::response::Response {
-    user_handlers::create_user_handler(state, request).await
+fn create_user_changes_router(
+    feed: UserChangeFeed,
+    auth_state: AuthState,
+    rate_limit: RateLimitState,
+) -> Router {
+    Router::new()
+        .route("/users/changes", get(user_change_handlers::user_changes_handler))
+        .route_layer(from_fn_with_state(Permission::WatchUserChanges, authorization_middleware))
+        .route_layer(from_fn_with_state(rate_limit, rate_limit_middleware))
+        .route_layer(from_fn_with_state(auth_state, auth_middleware))
+        .with_state(feed)
 }
 
-fn get_user_handler(
-    state: axum::extract::State<application::UserApplicationService<infrastructure::InMemoryUserRepository>>,
-    request: axum::extract::Path<String>,
-) -> axum::response::Response {
-    user_handlers::get_user_handler(state, request).await
+// Права проверяются на каждую подписку внутри соединения
+fn create_websocket_router(
+    state: websocket_handlers::WebSocketState,
+    auth_state: AuthState,
+    rate_limit: RateLimitState,
+) -> Router {
+    Router::new()
+        .route("/ws", get(websocket_handlers::websocket_handler))
+        .route_layer(from_fn_with_state(rate_limit, rate_limit_middleware))
+        .route_layer(from_fn_with_state(auth_state, auth_middleware))
+        .with_state(state)
 }
 
-fn get_user_by_email_handler(
-    state: axum::extract::State<application::UserApplicationService<infrastructure::InMemoryUserRepository>>,
-    request: axum::extract::Json<serde_json::Value>,
-) -> axum::response::Response {
-    user_handlers::get_user_by_email_handler(state, request).await
+// Права проверяются в резолверах; GraphiQL только при APP_ENV=development
+fn create_graphql_router(
+    schema: graphql_schema::UserSchema,
+    config: &AppConfig,
+    auth_state: AuthState,
+    rate_limit: RateLimitState,
+) -> Router {
+    let router = Router::new()
+        .route("/graphql", post(graphql_handlers::graphql_handler))
+        .route("/graphql/ws", get(graphql_handlers::graphql_subscription_handler))
+        .route_layer(from_fn_with_state(rate_limit, rate_limit_middleware))
+        .route_layer(from_fn_with_state(auth_state, auth_middleware))
+        .with_state(schema);
+
+    match config.is_development() {
+        true => router.route("/graphiql", get(graphql_handlers::graphiql_handler)),
+        false => router,
+    }
 }
 
-fn update_user_handler(
-    state: axum::extract::State<application::UserApplicationService<infrastructure::InMemoryUserRepository>>,
-    path: axum::extract::Path<String>,
-    request: axum::extract::Json<application::dto::UpdateUserRequest>,
-) -> axum::response::Response {
-    user_handlers::update_user_handler(state, path, request).await
+// gRPC-сервисы отличаются от REST по пути `/<пакет>.<Сервис>/<метод>`, поэтому
+// делят с ним порт; health и reflection доступны без аутентификации.
+// Отказы аутентификации и лимитов отдаются gRPC-статусом, а не JSON
+fn create_grpc_router(
+    service: grpc_handlers::GrpcUserService,
+    auth_state: AuthState,
+    rate_limit: RateLimitState,
+) -> Router {
+    let (health_reporter, health_service) = tonic_health::server::health_reporter();
+    tokio::spawn(async move {
+        health_reporter.set_serving::<grpc_handlers::GrpcUserServer>().await;
+    });
+    let user_server = grpc_handlers::GrpcUserServer::new(service);
+    let reflection_v1 = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(grpc_handlers::proto::FILE_DESCRIPTOR_SET)
+        .build_v1()
+        .expect("Invalid gRPC file descriptor set");
+    // v1alpha - для grpcurl и клиентов, не знающих v1
+    let reflection_v1alpha = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(grpc_handlers::proto::FILE_DESCRIPTOR_SET)
+        .build_v1alpha()
+        .expect("Invalid gRPC file descriptor set");
+
+    Router::new()
+        .route_service(&grpc_path(&user_server), user_server)
+        .route_layer(from_fn_with_state(rate_limit, rate_limit_middleware))
+        .route_layer(from_fn_with_state(auth_state, auth_middleware))
+        .route_layer(from_fn(grpc_status_middleware))
+        .route_service(&grpc_path(&health_service), health_service)
+        .route_service(&grpc_path(&reflection_v1), reflection_v1)
+        .route_service(&grpc_path(&reflection_v1alpha), reflection_v1alpha)
+}
+
+fn grpc_path<S: NamedService>(_: &S) -> String {
+    format!("/{}/{{*rest}}", S::NAME)
 }
 
-fn delete_user_handler(
-    state: axum::extract::State<application::UserApplicationService<infrastructure::InMemoryUserRepository>>,
-    request: axum::extract::Path<String>,
-) -> axum::response::Response {
-    user_handlers::delete_user_handler(state, request).await
+fn create_webhook_router(
+    webhook_service: webhook_handlers::WebhookService,
+    auth_state: AuthState,
+    rate_limit: RateLimitState,
+) -> Router {
+    Router::new()
+        .route("/webhooks", post(webhook_handlers::create_webhook_handler)
+            .get(webhook_handlers::list_webhooks_handler))
+        .route("/webhooks/{id}", get(webhook_handlers::get_webhook_handler)
+            .delete(webhook_handlers::delete_webhook_handler))
+        .route("/webhooks/{id}/enable", post(webhook_handlers::enable_webhook_handler))
+        .route("/webhooks/{id}/deliveries", get(webhook_handlers::list_webhook_deliveries_handler))
+        .route("/webhooks/{id}/deliveries/{delivery_id}/redeliver", post(webhook_handlers::redeliver_webhook_handler))
+        .route_layer(from_fn_with_state(Permission::ManageWebhooks, authorization_middleware))
+        .route_layer(from_fn_with_state(rate_limit, rate_limit_middleware))
+        .route_layer(from_fn_with_state(auth_state, auth_middleware))
+        .with_state(webhook_service)
+}
+
+fn create_outbox_admin_router(
+    outbox_repository: InMemoryUserRepository,
+    auth_state: AuthState,
+    rate_limit: RateLimitState,
+) -> Router {
+    Router::new()
+        .route("/admin/outbox", get(outbox_handlers::list_outbox_messages_handler))
+        .route("/admin/outbox/{id}", get(outbox_handlers::get_outbox_message_handler))
+        .route("/admin/outbox/{id}/requeue", post(outbox_handlers::requeue_outbox_message_handler))
+        .route_layer(from_fn_with_state(Permission::ManageOutbox, authorization_middleware))
+        .route_layer(from_fn_with_state(rate_limit, rate_limit_middleware))
+        .route_layer(from_fn_with_state(auth_state, auth_middleware))
+        .with_state(OutboxAdminService::new(outbox_repository))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::extract::Request;
+    use axum::http::StatusCode;
+    use tower::ServiceExt;
+
+    fn test_rate_limit() -> RateLimitState {
+        RateLimitState::new(RateLimitStoreBackend::from_app_config(&AppConfig::default()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_health_is_public() {
+        let app = create_app_router(AppConfig::default());
+
+        let request = Request::builder().uri("/health").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_grpc_health_is_public() {
+        let app = create_app_router(AppConfig::default());
+
+        // Пустой HealthCheckRequest: флаг сжатия и нулевая длина сообщения
+        let request = Request::builder()
+            .method("POST")
+            .uri("/grpc.health.v1.Health/Check")
+            .header("content-type", "application/grpc")
+            .body(Body::from(vec![0u8; 5]))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "application/grpc");
+    }
+
+    #[tokio::test]
+    async fn test_user_routes_are_versioned() {
+        let app = create_app_router(AppConfig::default());
+        let create = |uri: &str, email: &str| {
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::json!({ "email": email, "name": "Alice" }).to_string()))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(create("/api/v1/users", "v1@example.com")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(serde_json::from_slice::<serde_json::Value>(&body).unwrap()["data"]["email"], "v1@example.com");
+
+        let response = app.clone().oneshot(create("/api/v2/users", "v2@example.com")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(serde_json::from_slice::<serde_json::Value>(&body).unwrap()["email"], "v2@example.com");
+
+        // Ошибка аутентификации тоже в формате v2
+        let request = Request::builder().uri("/api/v2/users/00000000-0000-0000-0000-000000000000").body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()["content-type"], "application/problem+json");
+
+        let request = Request::builder().uri("/metrics").body(Body::empty()).unwrap();
+        let body = axum::body::to_bytes(app.oneshot(request).await.unwrap().into_body(), usize::MAX).await.unwrap();
+        assert!(String::from_utf8(body.to_vec()).unwrap().contains("api_requests_total{version=\"2\",status=\"401\"} 1"));
+    }
+
+    #[tokio::test]
+    async fn test_grpc_rejects_missing_token_with_grpc_status() {
+        let app = create_app_router(AppConfig::default());
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/user.v1.UserService/ListUsers")
+            .header("content-type", "application/grpc")
+            .body(Body::from(vec![0u8; 5]))
+            .unwrap();
+        let response = app.oneshot(request).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "application/grpc");
+        assert_eq!(response.headers()["grpc-status"], "16");
+    }
+
+    #[tokio::test]
+    async fn test_user_routes_require_authentication() {
+        let app = create_app_router(AppConfig::default());
+
+        for (method, uri) in [
+            ("GET", "/api/users/00000000-0000-0000-0000-000000000000"),
+            ("PUT", "/api/users/00000000-0000-0000-0000-000000000000/roles"),
+            ("GET", "/api/users/00000000-0000-0000-0000-000000000000/api-keys"),
+            ("POST", "/api/users/00000000-0000-0000-0000-000000000000/mfa/challenge"),
+            ("POST", "/api/users/00000000-0000-0000-0000-000000000000/unlock"),
+            ("GET", "/api/users/00000000-0000-0000-0000-000000000000/sessions"),
+            ("POST", "/api/sessions"),
+            ("GET", "/api/admin/outbox"),
+            ("GET", "/api/users/00000000-0000-0000-0000-000000000000/data-export"),
+            ("POST", "/api/users/00000000-0000-0000-0000-000000000000/erase"),
+            ("GET", "/api/users/00000000-0000-0000-0000-000000000000/audit"),
+            ("GET", "/api/admin/audit/verify"),
+            ("GET", "/api/users/changes"),
+            ("GET", "/ws"),
+            ("POST", "/graphql"),
+            ("GET", "/graphql/ws"),
+            ("POST", "/api/webhooks"),
+        ] {
+            let request = Request::builder().method(method).uri(uri).body(Body::empty()).unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{} {}", method, uri);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_user_routes_negotiate_binary_codecs() {
+        use crate::presentation::content_negotiation::Codec;
+        let app = create_app_router(AppConfig::default());
+        let create = |uri: &str, codec: Codec, email: &str| {
+            let body = codec.encode(&serde_json::json!({ "email": email, "name": "Alice" })).unwrap();
+            Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", codec.media_type())
+                .header("accept", codec.media_type())
+                .body(Body::from(body))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(create("/api/v1/users", Codec::MessagePack, "msgpack@example.com")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers()["content-type"], "application/msgpack");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let envelope: serde_json::Value = Codec::MessagePack.decode(&body).unwrap();
+        assert_eq!(envelope["data"]["email"], "msgpack@example.com");
+
+        // v2 снимает конверт и с двоичного ответа
+        let response = app.clone().oneshot(create("/api/v2/users", Codec::Cbor, "cbor@example.com")).await.unwrap();
+        assert_eq!(response.headers()["content-type"], "application/cbor");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(Codec::Cbor.decode::<serde_json::Value>(&body).unwrap()["email"], "cbor@example.com");
+
+        let response = app.clone().oneshot(create("/api/v2/users", Codec::Cbor, "cbor@example.com")).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        assert_eq!(response.headers()["content-type"], "application/problem+json");
+
+        let request = Request::builder()
+            .method("POST")
+            .uri("/api/v1/users")
+            .header("content-type", "text/csv")
+            .body(Body::from("email,name"))
+            .unwrap();
+        assert_eq!(app.clone().oneshot(request).await.unwrap().status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        let mut request = create("/api/v1/users", Codec::Json, "html@example.com");
+        request.headers_mut().insert("accept", "text/html".parse().unwrap());
+        assert_eq!(app.oneshot(request).await.unwrap().status(), StatusCode::NOT_ACCEPTABLE);
+    }
+
+    #[tokio::test]
+    async fn test_all_api_routes_are_versioned() {
+        let app = create_app_router(AppConfig::default());
+
+        for uri in [
+            "/api/v2/users/00000000-0000-0000-0000-000000000000/api-keys",
+            "/api/v2/users/00000000-0000-0000-0000-000000000000/mfa",
+            "/api/v2/users/00000000-0000-0000-0000-000000000000/sessions",
+            "/api/v2/users/00000000-0000-0000-0000-000000000000/data-export",
+            "/api/v2/users/00000000-0000-0000-0000-000000000000/audit",
+            "/api/v2/admin/audit/verify",
+            "/api/v2/admin/outbox",
+            "/api/v2/webhooks",
+        ] {
+            let request = Request::builder().uri(uri).body(Body::empty()).unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "{}", uri);
+            assert_eq!(response.headers()["content-type"], "application/problem+json", "{}", uri);
+            assert_eq!(response.headers()["api-version"], "2", "{}", uri);
+        }
+
+        let request = Request::builder().uri("/api/v1/webhooks").body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.headers()["api-version"], "1");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert_eq!(serde_json::from_slice::<serde_json::Value>(&body).unwrap()["success"], false);
+
+        let request = Request::builder().uri("/api/webhooks").header("accept-version", "2").body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.headers()["api-version"], "2");
+    }
+
+    #[tokio::test]
+    async fn test_oidc_login_flow() {
+        use crate::infrastructure::mock_oidc_issuer::{MockIdentity, MockOidcIssuer};
+
+        let issuer = MockOidcIssuer::start(MockIdentity::verified("sub-42", "sso@example.com")).await;
+        let config = AppConfig {
+            oidc_providers: vec![issuer.provider_config("mock")],
+            ..AppConfig::default()
+        };
+        let user_repository = InMemoryUserRepository::new();
+        let login_protection = LoginProtectionState::new(
+            user_repository.clone(),
+            LoginAttemptStoreBackend::from_app_config(&config).unwrap(),
+            config.lockout_policy(),
+        );
+        let app = Router::new().nest("/api", create_oidc_router(
+            user_repository,
+            InMemoryExternalIdentityRepository::new(),
+            &config,
+            login_protection,
+            test_rate_limit(),
+        ));
+
+        let request = Request::builder().uri("/api/auth/oidc/unknown/login").body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let request = Request::builder().uri("/api/auth/oidc/mock/login").body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::SEE_OTHER);
+        let location = response.headers()["location"].to_str().unwrap().to_string();
+        assert!(location.starts_with(issuer.issuer()));
+
+        // Провайдер возвращает браузер на callback с кодом и state
+        let callback = issuer.authorize_redirect(&location).await;
+        let callback_uri = format!("{}?{}", callback.path(), callback.query().unwrap());
+        let request = Request::builder().uri(&callback_uri).body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"]["created"], true);
+        assert_eq!(body["data"]["user"]["email"], "sso@example.com");
+        assert!(body["data"]["token"].as_str().is_some_and(|token| !token.is_empty()));
+
+        // Повторное использование state отклоняется
+        let request = Request::builder().uri(&callback_uri).body(Body::empty()).unwrap();
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_scim_user_provisioning() {
+        let config = AppConfig {
+            scim_bearer_token: Some("scim-token".to_string()),
+            ..AppConfig::default()
+        };
+        let app = create_scim_router(InMemoryUserRepository::new(), &config, test_rate_limit());
+        let scim_request = |method: &str, uri: &str, token: Option<&str>, body: Option<serde_json::Value>| {
+            let mut builder = Request::builder().method(method).uri(uri);
+            if let Some(token) = token {
+                builder = builder.header("authorization", format!("Bearer {}", token));
+            }
+            match body {
+                Some(body) => builder
+                    .header("content-type", "application/scim+json")
+                    .body(Body::from(body.to_string()))
+                    .unwrap(),
+                None => builder.body(Body::empty()).unwrap(),
+            }
+        };
+        let read_json = |response: axum::response::Response| async move {
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+
+        let response = app.clone().oneshot(scim_request("GET", "/scim/v2/Users", Some("wrong"), None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()["content-type"], "application/scim+json");
+
+        let response = app.clone().oneshot(scim_request("GET", "/scim/v2/ServiceProviderConfig", None, None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(read_json(response).await["patch"]["supported"], true);
+
+        let user = serde_json::json!({
+            "schemas": ["urn:ietf:params:scim:schemas:core:2.0:User"],
+            "userName": "idp.user@example.com",
+            "name": {"givenName": "Idp", "familyName": "User"},
+        });
+        let response = app.clone()
+            .oneshot(scim_request("POST", "/scim/v2/Users", Some("scim-token"), Some(user.clone())))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        let created = read_json(response).await;
+        assert_eq!(created["displayName"], "Idp User");
+        let id = created["id"].as_str().unwrap().to_string();
+
+        let response = app.clone()
+            .oneshot(scim_request("POST", "/scim/v2/Users", Some("scim-token"), Some(user)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+        let error = read_json(response).await;
+        assert_eq!(error["status"], "409");
+        assert_eq!(error["scimType"], "uniqueness");
+
+        let filter = "/scim/v2/Users?filter=userName%20eq%20%22idp.user%40example.com%22";
+        let response = app.clone().oneshot(scim_request("GET", filter, Some("scim-token"), None)).await.unwrap();
+        let list = read_json(response).await;
+        assert_eq!(list["totalResults"], 1);
+        assert_eq!(list["Resources"][0]["id"], id.as_str());
+
+        let uri = format!("/scim/v2/Users/{}", id);
+        let response = app.clone().oneshot(scim_request("DELETE", &uri, Some("scim-token"), None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+        let response = app.oneshot(scim_request("GET", &uri, Some("scim-token"), None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_user_data_export_and_erase() {
+        use std::io::Read;
+        use crate::domain::{AuthLevel, Email, Role, TokenService, User, UserRepository};
+
+        let config = AppConfig::default();
+        let user_repository = InMemoryUserRepository::new();
+        let api_key_repository = InMemoryApiKeyRepository::new();
+        let session_store = SessionStoreBackend::from_app_config(&config).unwrap();
+        let token_service = JwtTokenService::from_app_config(&config);
+        let auth_state = AuthState::new(
+            user_repository.clone(),
+            token_service.clone(),
+            api_key_repository.clone(),
+            session_store.clone(),
+            config.session_policy(),
+            LoginProtectionState::new(
+                user_repository.clone(),
+                LoginAttemptStoreBackend::from_app_config(&config).unwrap(),
+                config.lockout_policy(),
+            ),
+        );
+        let app = Router::new().nest("/api", create_user_data_router(
+            UserDataApplicationService::new(
+                user_repository.clone(),
+                api_key_repository,
+                session_store,
+                InMemoryMfaRepository::new(),
+                InMemoryExternalIdentityRepository::new(),
+                InMemoryErasureReceiptRepository::new(),
+                AuditStoreBackend::from_app_config(&config, EnvelopePiiCipher::ephemeral()).unwrap(),
+                config.session_policy(),
+            ),
+            auth_state,
+            test_rate_limit(),
+            test_rate_limit(),
+        ));
+
+        let user = User::new(Email::new("subject@example.com".to_string()).unwrap(), "Data Subject".to_string()).unwrap();
+        let mut admin = User::new(Email::new("dpo@example.com".to_string()).unwrap(), "Privacy Officer".to_string()).unwrap();
+        admin.change_roles(vec![Role::Admin]).unwrap();
+        user_repository.save(&user).await.unwrap();
+        user_repository.save(&admin).await.unwrap();
+        let user_token = token_service.issue(user.id(), AuthLevel::SingleFactor).unwrap();
+        let admin_token = token_service.issue(admin.id(), AuthLevel::MultiFactor).unwrap();
+        let request = |method: &str, uri: String, token: &str| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("authorization", format!("Bearer {}", token))
+                .body(Body::empty())
+                .unwrap()
+        };
+        let export_uri = format!("/api/users/{}/data-export", user.id());
+        let erase_uri = format!("/api/users/{}/erase", user.id());
+
+        let mut export_request = request("GET", export_uri.clone(), &user_token);
+        export_request.headers_mut().insert("accept", "application/zip".parse().unwrap());
+        let response = app.clone().oneshot(export_request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "application/zip");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(body.to_vec())).unwrap();
+        let mut profile = String::new();
+        archive.by_name("profile.json").unwrap().read_to_string(&mut profile).unwrap();
+        assert!(profile.contains("subject@example.com"));
+
+        // Выгрузка с выбранными полями профиля
+        let response = app.clone().oneshot(request("GET", format!("{}?fields=email&include=api_keys", export_uri), &user_token)).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(body.to_vec())).unwrap();
+        let profile: serde_json::Value = serde_json::from_reader(archive.by_name("profile.json").unwrap()).unwrap();
+        assert_eq!(profile, serde_json::json!({ "id": user.id().to_string(), "email": "subject@example.com", "api_keys": [] }));
+        let response = app.clone().oneshot(request("GET", format!("{}?fields=ssn", export_uri), &user_token)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // Стирать может только администратор
+        let response = app.clone().oneshot(request("POST", erase_uri.clone(), &user_token)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = app.clone().oneshot(request("POST", erase_uri.clone(), &admin_token)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let receipt: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(receipt["data"]["user_id"], user.id().to_string());
+        assert_eq!(receipt["data"]["requested_by"], admin.id().to_string());
+
+        // Стертый пользователь больше не аутентифицируется, но администратор видит квитанцию
+        let response = app.clone().oneshot(request("GET", export_uri.clone(), &user_token)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = app.clone().oneshot(request("GET", export_uri, &admin_token)).await.unwrap();
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(body.to_vec())).unwrap();
+        assert!(archive.by_name("erasure_receipt.json").is_ok());
+        let mut profile = String::new();
+        archive.by_name("profile.json").unwrap().read_to_string(&mut profile).unwrap();
+        assert!(!profile.contains("subject@example.com"));
+
+        let response = app.oneshot(request("POST", erase_uri, &admin_token)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+    #[tokio::test]
+    async fn test_audit_trail_records_request_context() {
+        use crate::domain::{AuthLevel, Email, Role, TokenService, User, UserRepository};
+
+        let config = AppConfig::default();
+        let audit_store = AuditStoreBackend::from_app_config(&config, EnvelopePiiCipher::ephemeral()).unwrap();
+        let user_repository = InMemoryUserRepository::new();
+        let api_key_repository = InMemoryApiKeyRepository::new().with_audit_trail(AuditTrail::new(audit_store.clone()));
+        let session_store = SessionStoreBackend::from_app_config(&config).unwrap();
+        let token_service = JwtTokenService::from_app_config(&config);
+        let auth_state = AuthState::new(
+            user_repository.clone(),
+            token_service.clone(),
+            api_key_repository.clone(),
+            session_store,
+            config.session_policy(),
+            LoginProtectionState::new(
+                user_repository.clone(),
+                LoginAttemptStoreBackend::from_app_config(&config).unwrap(),
+                config.lockout_policy(),
+            ),
+        );
+        let api_routes = create_api_key_router(user_repository.clone(), api_key_repository, auth_state.clone(), test_rate_limit())
+            .merge(create_audit_router(
+                AuditApplicationService::new(audit_store),
+                auth_state,
+                test_rate_limit(),
+                test_rate_limit(),
+            ));
+        let app = Router::new().nest("/api", api_routes).layer(from_fn(request_context_middleware));
+
+        let user = User::new(Email::new("owner@example.com".to_string()).unwrap(), "Key Owner".to_string()).unwrap();
+        let mut admin = User::new(Email::new("admin@example.com".to_string()).unwrap(), "Admin".to_string()).unwrap();
+        admin.change_roles(vec![Role::Admin]).unwrap();
+        user_repository.save(&user).await.unwrap();
+        user_repository.save(&admin).await.unwrap();
+        let user_token = token_service.issue(user.id(), AuthLevel::SingleFactor).unwrap();
+        let admin_token = token_service.issue(admin.id(), AuthLevel::MultiFactor).unwrap();
+        let request = |method: &str, uri: String, token: &str, body: Body| {
+            Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("authorization", format!("Bearer {}", token))
+                .header("content-type", "application/json")
+                .header("x-request-id", "req-audit-1")
+                .body(body)
+                .unwrap()
+        };
+        let read_json = |response: axum::response::Response| async move {
+            let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+            serde_json::from_slice::<serde_json::Value>(&body).unwrap()
+        };
+
+        let create_key = serde_json::json!({"name": "ci", "scopes": ["users:read"]});
+        let response = app.clone()
+            .oneshot(request("POST", format!("/api/users/{}/api-keys", user.id()), &user_token, Body::from(create_key.to_string())))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(response.headers()["x-request-id"], "req-audit-1");
+
+        let response = app.clone()
+            .oneshot(request("GET", format!("/api/users/{}/audit?limit=10", user.id()), &user_token, Body::empty()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let page = read_json(response).await;
+        assert_eq!(page["data"]["total"], 1);
+        let event = &page["data"]["events"][0];
+        assert_eq!(event["action"], "api_key.created");
+        assert_eq!(event["actor"], format!("user:{}", user.id()));
+        assert_eq!(event["request_id"], "req-audit-1");
+        let secret = event["changes"].as_array().unwrap().iter().find(|change| change["field"] == "secret_hash").unwrap();
+        assert_eq!(secret["after"], "[REDACTED]");
+
+        // Чужой журнал участнику недоступен, проверка цепочки - только администратору
+        let response = app.clone()
+            .oneshot(request("GET", format!("/api/users/{}/audit", admin.id()), &user_token, Body::empty()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = app.clone()
+            .oneshot(request("GET", "/api/admin/audit/verify".to_string(), &user_token, Body::empty()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        let response = app.clone()
+            .oneshot(request("GET", "/api/admin/audit/verify".to_string(), &admin_token, Body::empty()))
+            .await
+            .unwrap();
+        let status = read_json(response).await;
+        assert_eq!(status["data"]["valid"], true);
+        assert_eq!(status["data"]["events"], 1);
+
+        // Маршруты ключей и журнала согласуют формат так же, как маршруты пользователей
+        let mut list_keys = request("GET", format!("/api/users/{}/api-keys", user.id()), &user_token, Body::empty());
+        list_keys.headers_mut().insert("accept", "application/msgpack".parse().unwrap());
+        let response = app.oneshot(list_keys).await.unwrap();
+        assert_eq!(response.headers()["content-type"], "application/msgpack");
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let keys: serde_json::Value = crate::presentation::content_negotiation::Codec::MessagePack.decode(&body).unwrap();
+        assert_eq!(keys["data"][0]["name"], "ci");
+    }
 }
\ No newline at end of file
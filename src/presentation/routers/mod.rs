@@ -1,2 +1,3 @@
 pub mod api_router;
 
+pub use api_router::*;